@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+import contextlib
 import re
 from dataclasses import dataclass
 from pathlib import Path
@@ -7,17 +8,27 @@ from pathlib import Path
 from rich.text import Text
 
 from .build import BuildPlan, build_binaries
-from .cases import default_grpc_cases, default_http_cases, is_too_slow, run_grpc_case, run_http_case
+from .cases import (
+    default_grpc_cases,
+    default_http_cases,
+    external_cases,
+    is_too_slow,
+    run_grpc_case,
+    run_http_case,
+)
 from .config import (
     Config,
     ConfigError,
+    ExternalTarget,
     Ratios,
     RunTuning,
     ToolRequirements,
     validate_ratios,
+    validate_target,
     validate_tuning,
 )
 from .parse import Rps
+from .report import CaseResult, write_results
 from .server import TestServer
 from .tool_detection import detect_tools
 from .ui import RunUI
@@ -35,7 +46,7 @@ class OverallOutcome:
         return self.failures == 0
 
 
-def run(cfg: Config, *, color: str = "auto") -> OverallOutcome:
+def run(cfg: Config, *, color: str = "auto", results_json: Path | None = None) -> OverallOutcome:
     """
     Orchestrate a full perf comparison run.
 
@@ -46,6 +57,10 @@ def run(cfg: Config, *, color: str = "auto") -> OverallOutcome:
       - starting wrkr-testserver and acquiring targets
       - running all default HTTP and gRPC cases
       - the cross-protocol gate (wrkr gRPC vs wrk hello), when possible
+      - saving wrkr's per-case numbers to `results_json` for the `gate` command
+
+    With `cfg.target` set, the testserver and the built-in cases are replaced by a single case
+    against the external target, and the cross-protocol gate is skipped.
 
     The caller (CLI) is responsible for translating failures into exit codes.
     """
@@ -64,6 +79,7 @@ def run(cfg: Config, *, color: str = "auto") -> OverallOutcome:
         failures = 0
         failure_summary: list[str] = []
         case_summaries: list[tuple[str, ...]] = []
+        case_results: list[CaseResult] = []
 
         ui.set_status(
             {
@@ -74,8 +90,11 @@ def run(cfg: Config, *, color: str = "auto") -> OverallOutcome:
             }
         )
 
-        http_cases = default_http_cases(cfg)
-        grpc_cases = default_grpc_cases(cfg)
+        if cfg.target is None:
+            http_cases = default_http_cases(cfg)
+            grpc_cases = default_grpc_cases(cfg)
+        else:
+            http_cases, grpc_cases = external_cases(cfg.target)
 
         # Once we know which tools are present, compute a stable total step count so
         # progress bars can be meaningful even in Docker/CI logs.
@@ -83,18 +102,19 @@ def run(cfg: Config, *, color: str = "auto") -> OverallOutcome:
         if cfg.tuning.build:
             steps += 2  # cargo build wrkr-testserver + wrkr
         steps += 1  # detect tools
-        steps += 1  # wait for testserver
-        steps += 1  # cross-protocol gate
+        if cfg.target is None:
+            steps += 1  # wait for testserver
+            steps += 1  # cross-protocol gate
 
-        for _ in http_cases:
-            if tools.wrk is not None:
+        for case in http_cases:
+            if tools.wrk is not None and case.scripts.wrk is not None:
                 steps += 1
             steps += 1  # wrkr
-            if tools.k6 is not None:
+            if tools.k6 is not None and case.scripts.k6 is not None:
                 steps += 1
-        for _ in grpc_cases:
+        for case in grpc_cases:
             steps += 1  # wrkr
-            if tools.k6 is not None:
+            if tools.k6 is not None and case.scripts.k6 is not None:
                 steps += 1
 
         ui.set_total_steps(steps)
@@ -105,29 +125,38 @@ def run(cfg: Config, *, color: str = "auto") -> OverallOutcome:
         targets_http_url: str | None = None
         targets_grpc_url: str | None = None
 
-        server = TestServer.start(
-            root=cfg.root,
-            server_bin=tools.wrkr_testserver,
-            on_log=lambda m: ui.tail(m, style="dim"),
+        server = (
+            TestServer.start(
+                root=cfg.root,
+                server_bin=tools.wrkr_testserver,
+                on_log=lambda m: ui.tail(m, style="dim"),
+            )
+            if cfg.target is None
+            else None
         )
-        with server:
-            with ui.step("wait for testserver"):
-                targets = server.wait_for_targets(
-                    timeout_s=5.0,
-                    on_log=lambda m: ui.tail(m, style="dim"),
-                )
-
-            targets_http_url = targets.http_url
-            targets_grpc_url = targets.grpc_url
-
-            ui.set_status({"http_url": targets.http_url, "grpc_url": targets.grpc_url})
+        with server if server is not None else contextlib.nullcontext():
+            if server is not None:
+                with ui.step("wait for testserver"):
+                    targets = server.wait_for_targets(
+                        timeout_s=5.0,
+                        on_log=lambda m: ui.tail(m, style="dim"),
+                    )
+                http_url, grpc_url = targets.http_url, targets.grpc_url
+            else:
+                assert cfg.target is not None
+                http_url = grpc_url = cfg.target.url
+
+            targets_http_url = http_url
+            targets_grpc_url = grpc_url
+
+            ui.set_status({"http_url": http_url, "grpc_url": grpc_url})
 
             # HTTP cases
             for i, case in enumerate(http_cases):
                 outcome = run_http_case(
                     cfg=cfg,
                     tools=tools,
-                    base_url=targets.http_url,
+                    base_url=http_url,
                     case=case,
                     ui=ui,
                 )
@@ -135,6 +164,9 @@ def run(cfg: Config, *, color: str = "auto") -> OverallOutcome:
                 for msg in outcome.failure_messages:
                     failure_summary.append(f"HTTP {case.title}: {msg}")
                 case_summaries.append(outcome.summary_lines)
+                case_results.append(
+                    _case_result(f"HTTP {case.title}", outcome.wrkr_rps, outcome.wrkr_p99_seconds)
+                )
 
                 # Keep wrk RPS for hello to power the cross-protocol gate.
                 if i == 0:
@@ -145,7 +177,7 @@ def run(cfg: Config, *, color: str = "auto") -> OverallOutcome:
                 outcome = run_grpc_case(
                     cfg=cfg,
                     tools=tools,
-                    grpc_url=targets.grpc_url,
+                    grpc_url=grpc_url,
                     case=case,
                     ui=ui,
                 )
@@ -153,22 +185,30 @@ def run(cfg: Config, *, color: str = "auto") -> OverallOutcome:
                 for msg in outcome.failure_messages:
                     failure_summary.append(f"gRPC {case.title}: {msg}")
                 case_summaries.append(outcome.summary_lines)
+                case_results.append(
+                    _case_result(f"gRPC {case.title}", outcome.wrkr_rps, outcome.wrkr_p99_seconds)
+                )
 
                 # Use the first gRPC case (Echo plaintext) for cross-protocol gate.
                 if i == 0:
                     grpc_first_wrkr_rps = outcome.wrkr_rps
 
-        # Cross-protocol comparison: wrkr gRPC vs wrk GET /hello.
-        with ui.step("cross-protocol gate"):
-            gate_failure = _cross_protocol_gate(
-                cfg=cfg,
-                grpc_wrkr_rps=grpc_first_wrkr_rps,
-                wrk_hello_rps=hello_wrk_rps,
-                ui=ui,
-            )
-            if gate_failure is not None:
-                failures += 1
-                failure_summary.append(f"cross-protocol: {gate_failure}")
+        # Cross-protocol comparison: wrkr gRPC vs wrk GET /hello (built-in cases only).
+        if cfg.target is None:
+            with ui.step("cross-protocol gate"):
+                gate_failure = _cross_protocol_gate(
+                    cfg=cfg,
+                    grpc_wrkr_rps=grpc_first_wrkr_rps,
+                    wrk_hello_rps=hello_wrk_rps,
+                    ui=ui,
+                )
+                if gate_failure is not None:
+                    failures += 1
+                    failure_summary.append(f"cross-protocol: {gate_failure}")
+
+        if results_json is not None:
+            write_results(results_json, case_results)
+            ui.log(f"results written to {results_json}")
 
         if failures > 0:
             ui.log(f"OVERALL: FAIL ({failures} failing case(s))")
@@ -235,6 +275,10 @@ def run(cfg: Config, *, color: str = "auto") -> OverallOutcome:
             ui.stop()
 
 
+def _case_result(title: str, rps: Rps | None, p99_seconds: float | None) -> CaseResult:
+    return CaseResult(title=title, rps=None if rps is None else rps.value, p99_seconds=p99_seconds)
+
+
 def _validate_config(cfg: Config) -> None:
     if not isinstance(cfg.root, Path):
         raise ConfigError("Config.root must be a pathlib.Path")
@@ -244,6 +288,8 @@ def _validate_config(cfg: Config) -> None:
 
     validate_tuning(cfg.tuning)
     validate_ratios(cfg.ratios)
+    if cfg.target is not None:
+        validate_target(cfg.target)
 
 
 def _cross_protocol_gate(
@@ -358,6 +404,8 @@ def config_from_values(
     # tool requirements
     require_wrk: bool = False,
     require_k6: bool = False,
+    # external target
+    target: ExternalTarget | None = None,
 ) -> Config:
     """
     Convenience constructor used by the CLI.
@@ -385,4 +433,5 @@ def config_from_values(
             ratio_ok_grpc_wrkr_over_wrk_hello=ratio_ok_grpc_wrkr_over_wrk_hello,
         ),
         requirements=ToolRequirements(require_wrk=require_wrk, require_k6=require_k6),
+        target=target,
     )
@@ -2,16 +2,20 @@ from __future__ import annotations
 
 import json
 import os
+import urllib.error
+import urllib.request
+from collections.abc import Callable
 from dataclasses import dataclass
 from pathlib import Path
 
-from .config import Config, parse_duration_to_seconds
+from .config import Config, ExternalTarget, parse_duration_to_seconds
 from .exec import RunResult, run_with_peak_rss_sampling_streaming
 from .parse import (
     ParseError,
     Rps,
     count_k6_request_failed_warnings,
     detect_wrk_errors,
+    json_mismatches,
     parse_k6_grpc_req_failed_rate,
     parse_k6_grpc_rps,
     parse_k6_http_req_failed_rate,
@@ -127,36 +131,77 @@ class CaseError(RuntimeError):
 
 @dataclass(frozen=True, slots=True)
 class HttpCaseScripts:
-    wrk: str
+    wrk: str | None
     wrkr: str
-    k6: str
+    k6: str | None
+
+
+@dataclass(frozen=True, slots=True)
+class ResponseCheck:
+    """
+    A single request sent before the load tools run, to verify what the server returns.
+
+    `validate` receives the response body and returns mismatch messages (empty when correct).
+    """
+
+    method: str
+    path: str
+    body: bytes | None = None
+    headers: tuple[tuple[str, str], ...] = ()
+    expect_status: int = 200
+    validate: Callable[[bytes], list[str]] | None = None
+
+
+def expect_text(expected: str) -> Callable[[bytes], list[str]]:
+    def validate(body: bytes) -> list[str]:
+        actual = body.decode("utf-8", errors="replace")
+        if actual != expected:
+            return [f"expected body {expected!r}, got {actual[:200]!r}"]
+        return []
+
+    return validate
+
+
+def expect_json(expected: object) -> Callable[[bytes], list[str]]:
+    def validate(body: bytes) -> list[str]:
+        try:
+            actual = json.loads(body)
+        except ValueError as e:
+            return [f"response is not valid JSON ({e})"]
+        return json_mismatches(actual, expected)
+
+    return validate
 
 
 @dataclass(frozen=True, slots=True)
 class HttpCase:
     title: str
     scripts: HttpCaseScripts
-    ratio_ok_wrkr_over_wrk: float
-    ratio_ok_wrkr_over_k6: float
+    # `None` reports the ratio without gating on it.
+    ratio_ok_wrkr_over_wrk: float | None
+    ratio_ok_wrkr_over_k6: float | None
+    response_check: ResponseCheck | None = None
 
 
 @dataclass(frozen=True, slots=True)
 class GrpcCaseScripts:
     wrkr: str
-    k6: str
+    k6: str | None
 
 
 @dataclass(frozen=True, slots=True)
 class GrpcCase:
     title: str
     scripts: GrpcCaseScripts
-    ratio_ok_wrkr_over_k6: float
+    ratio_ok_wrkr_over_k6: float | None
 
 
 @dataclass(frozen=True, slots=True)
 class HttpCaseOutcome:
     failures: int
     wrk_rps: Rps | None
+    wrkr_rps: Rps | None
+    wrkr_p99_seconds: float | None
     failure_messages: tuple[str, ...]
     summary_lines: tuple[str, ...]
 
@@ -165,10 +210,55 @@ class HttpCaseOutcome:
 class GrpcCaseOutcome:
     failures: int
     wrkr_rps: Rps | None
+    wrkr_p99_seconds: float | None
     failure_messages: tuple[str, ...]
     summary_lines: tuple[str, ...]
 
 
+_JSON_HEADERS = (("content-type", "application/json"), ("accept", "application/json"))
+
+# Same object as `tools/perf/*_post_json.*`.
+_POST_JSON_PAYLOAD = {"a": 1, "b": 2, "arr": [1, 2, 3], "nested": {"x": "y", "z": True}}
+
+# Small hand-computed aggregate: only `status == 1` orders count, amounts are
+# `price_cents * quantity` per country, quantities are summed per category.
+_AGGREGATE_REQUEST = {
+    "client_id": "compare-perf",
+    "orders": [
+        {
+            "status": 1,
+            "country": "US",
+            "items": [
+                {"quantity": 2, "category": "Books", "price_cents": 1000},
+                {"quantity": 1, "category": "Home", "price_cents": 500},
+            ],
+        },
+        {
+            "status": 2,
+            "country": "DE",
+            "items": [{"quantity": 5, "category": "Books", "price_cents": 999}],
+        },
+        {
+            "status": 1,
+            "country": "DE",
+            "items": [{"quantity": 3, "category": "Electronics", "price_cents": 2000}],
+        },
+        {
+            "status": 1,
+            "country": "US",
+            "items": [{"quantity": 1, "category": "Books", "price_cents": 700}],
+        },
+    ],
+}
+
+_AGGREGATE_RESPONSE = {
+    "echoed_client_id": "compare-perf",
+    "processed_orders": 3,
+    "amount_by_country": {"US": 3200, "DE": 6000},
+    "quantity_by_category": {"Books": 3, "Home": 1, "Electronics": 3},
+}
+
+
 def default_http_cases(cfg: Config) -> list[HttpCase]:
     """
     Default HTTP cases.
@@ -185,6 +275,9 @@ def default_http_cases(cfg: Config) -> list[HttpCase]:
             ),
             ratio_ok_wrkr_over_wrk=cfg.ratios.ratio_ok_get_hello,
             ratio_ok_wrkr_over_k6=cfg.ratios.ratio_ok_wrkr_over_k6,
+            response_check=ResponseCheck(
+                method="GET", path="/hello", validate=expect_text("Hello World!")
+            ),
         ),
         HttpCase(
             title="POST /echo (json + checks)",
@@ -195,6 +288,13 @@ def default_http_cases(cfg: Config) -> list[HttpCase]:
             ),
             ratio_ok_wrkr_over_wrk=cfg.ratios.ratio_ok_post_json,
             ratio_ok_wrkr_over_k6=cfg.ratios.ratio_ok_wrkr_over_k6,
+            response_check=ResponseCheck(
+                method="POST",
+                path="/echo",
+                body=json.dumps(_POST_JSON_PAYLOAD).encode(),
+                headers=_JSON_HEADERS,
+                validate=expect_json(_POST_JSON_PAYLOAD),
+            ),
         ),
         HttpCase(
             title="POST /analytics/aggregate (wfb json + checks)",
@@ -205,6 +305,13 @@ def default_http_cases(cfg: Config) -> list[HttpCase]:
             ),
             ratio_ok_wrkr_over_wrk=cfg.ratios.ratio_ok_wfb_json_aggregate,
             ratio_ok_wrkr_over_k6=cfg.ratios.ratio_ok_wrkr_over_k6,
+            response_check=ResponseCheck(
+                method="POST",
+                path="/analytics/aggregate",
+                body=json.dumps(_AGGREGATE_REQUEST).encode(),
+                headers=_JSON_HEADERS,
+                validate=expect_json(_AGGREGATE_RESPONSE),
+            ),
         ),
     ]
 
@@ -235,6 +342,60 @@ def default_grpc_cases(cfg: Config) -> list[GrpcCase]:
     ]
 
 
+def external_cases(target: ExternalTarget) -> tuple[list[HttpCase], list[GrpcCase]]:
+    """
+    The single case for a user-supplied target.
+
+    Ratio gates are off: on a real service the server is usually the bottleneck, so the tools'
+    relative throughput is reported but not judged.
+    """
+    title = f"{target.url} ({Path(target.wrkr_script).name})"
+    if target.protocol == "grpc":
+        case = GrpcCase(
+            title=title,
+            scripts=GrpcCaseScripts(wrkr=target.wrkr_script, k6=target.k6_script),
+            ratio_ok_wrkr_over_k6=None,
+        )
+        return [], [case]
+
+    http_case = HttpCase(
+        title=title,
+        scripts=HttpCaseScripts(
+            wrk=target.wrk_script, wrkr=target.wrkr_script, k6=target.k6_script
+        ),
+        ratio_ok_wrkr_over_wrk=None,
+        ratio_ok_wrkr_over_k6=None,
+    )
+    return [http_case], []
+
+
+def check_response(base_url: str, check: ResponseCheck) -> list[str]:
+    """Send `check` once and return what was wrong with the response (empty when correct)."""
+    req = urllib.request.Request(
+        base_url.rstrip("/") + check.path,
+        data=check.body,
+        headers=dict(check.headers),
+        method=check.method,
+    )
+    # Never route the server under test through a developer proxy.
+    opener = urllib.request.build_opener(urllib.request.ProxyHandler({}))
+    try:
+        with opener.open(req, timeout=10) as resp:
+            status = resp.status
+            body = resp.read()
+    except urllib.error.HTTPError as e:
+        status = e.code
+        body = e.read()
+    except (urllib.error.URLError, OSError) as e:
+        return [f"request failed ({e})"]
+
+    if status != check.expect_status:
+        return [f"expected status {check.expect_status}, got {status}"]
+    if check.validate is None:
+        return []
+    return check.validate(body)
+
+
 def run_http_case(
     *, cfg: Config, tools: ToolPaths, base_url: str, case: HttpCase, ui: RunUI
 ) -> HttpCaseOutcome:
@@ -244,18 +405,36 @@ def run_http_case(
     ui.log(f"CASE: {title}")
 
     _ensure_script_exists(cfg.root, scripts.wrkr)
-    _ensure_script_exists(cfg.root, scripts.wrk)
-    _ensure_script_exists(cfg.root, scripts.k6)
+    if scripts.wrk is not None:
+        _ensure_script_exists(cfg.root, scripts.wrk)
+    if scripts.k6 is not None:
+        _ensure_script_exists(cfg.root, scripts.k6)
+
+    # A tool runs only when it is installed and the case has a script for it.
+    wrk_bin = tools.wrk if scripts.wrk is not None else None
+    k6_bin = tools.k6 if scripts.k6 is not None else None
 
     failures = 0
     failure_messages: list[str] = []
     summary_lines: list[str] = []
 
+    # A fast wrong answer must not pass as a fast right one.
+    response_ok: bool | None = None
+    if case.response_check is not None:
+        rc = case.response_check
+        mismatches = check_response(base_url, rc)
+        response_ok = not mismatches
+        for m in mismatches:
+            msg = f"FAIL: {rc.method} {rc.path} returned a wrong response: {m}"
+            ui.log(msg, style="red")
+            failure_messages.append(msg)
+            failures += 1
+
     wrk_res: RunResult | None
     wrk_ok = True
-    if tools.wrk is not None:
+    if wrk_bin is not None and scripts.wrk is not None:
         wrk_argv = [
-            str(tools.wrk),
+            str(wrk_bin),
             f"-t{cfg.tuning.wrk_threads}",
             f"-c{cfg.tuning.wrk_connections}",
             f"-d{cfg.tuning.duration}",
@@ -288,7 +467,7 @@ def run_http_case(
                     failure_messages.append(msg)
                     failures += 1
     else:
-        ui.log("wrk: skipped (not installed)")
+        ui.log(f"wrk: skipped ({_skip_reason(tools.wrk)})")
         wrk_res = None
         wrk_ok = False
 
@@ -329,9 +508,9 @@ def run_http_case(
 
     k6_res: RunResult | None
     k6_ok = True
-    if tools.k6 is not None:
+    if k6_bin is not None and scripts.k6 is not None:
         k6_argv = [
-            str(tools.k6),
+            str(k6_bin),
             "run",
             "--vus",
             str(cfg.effective_k6_vus()),
@@ -356,7 +535,7 @@ def run_http_case(
             failure_messages.append(msg)
             failures += 1
     else:
-        ui.log("k6: skipped (not installed)")
+        ui.log(f"k6: skipped ({_skip_reason(tools.k6)})")
         k6_res = None
         k6_ok = False
 
@@ -438,10 +617,12 @@ def run_http_case(
     summary_lines.append(
         f"  scripts: wrk={scripts.wrk} wrkr={scripts.wrkr} k6={scripts.k6} duration={cfg.tuning.duration}"
     )
+    if response_ok is not None:
+        summary_lines.append(f"  response: {'OK' if response_ok else 'FAIL'}")
     summary_lines.append(
-        f"  wrk : {'OK' if (tools.wrk is not None and wrk_ok) else ('SKIP' if tools.wrk is None else 'FAIL')} rps={wrk_rps.value:.3f}"
+        f"  wrk : {'OK' if (wrk_bin is not None and wrk_ok) else ('SKIP' if wrk_bin is None else 'FAIL')} rps={wrk_rps.value:.3f}"
         if wrk_rps is not None
-        else f"  wrk : {'OK' if (tools.wrk is not None and wrk_ok) else ('SKIP' if tools.wrk is None else 'FAIL')} rps=-"
+        else f"  wrk : {'OK' if (wrk_bin is not None and wrk_ok) else ('SKIP' if wrk_bin is None else 'FAIL')} rps=-"
     )
     summary_lines.append(
         f"  wrkr: {'OK' if wrkr_ok else 'FAIL'} rps={wrkr_rps.value:.3f}"
@@ -456,7 +637,7 @@ def run_http_case(
             f"mean={_fmt_ms_f(_sec_to_ms(wrkr_json.latency_mean_seconds))}ms failed_checks={wrkr_json.checks_failed_total} "
             f"rx/s={_fmt_int(wrkr_json.bytes_received_per_sec)} tx/s={_fmt_int(wrkr_json.bytes_sent_per_sec)}"
         )
-    if tools.k6 is None:
+    if k6_bin is None:
         summary_lines.append("  k6  : SKIP")
     else:
         summary_lines.append(
@@ -468,7 +649,9 @@ def run_http_case(
     # Gate: wrkr vs wrk (inclusive)
     if wrk_rps is not None and wrkr_rps is not None and wrk_ok and wrkr_ok:
         ratio_actual = wrkr_rps.value / wrk_rps.value if wrk_rps.value > 0 else float("inf")
-        if is_too_slow(
+        if case.ratio_ok_wrkr_over_wrk is None:
+            ui.log(f"INFO: wrkr/wrk ratio_actual={ratio_actual:.3f} (no gate)")
+        elif is_too_slow(
             wrkr=wrkr_rps, other=wrk_rps, ratio=case.ratio_ok_wrkr_over_wrk, inclusive=True
         ):
             msg = (
@@ -484,7 +667,7 @@ def run_http_case(
             )
             ui.log(msg)
         summary_lines.append(
-            f"  gate wrkr/wrk: ratio_ok={case.ratio_ok_wrkr_over_wrk} ratio_actual={ratio_actual:.3f}"
+            f"  gate wrkr/wrk: ratio_ok={_fmt_ratio(case.ratio_ok_wrkr_over_wrk)} ratio_actual={ratio_actual:.3f}"
         )
     else:
         summary_lines.append("  gate wrkr/wrk: SKIP (correctness failed or missing tool)")
@@ -492,7 +675,9 @@ def run_http_case(
     # Gate: wrkr vs k6 (strict)
     if k6_rps is not None and wrkr_rps is not None and k6_ok and wrkr_ok:
         ratio_actual = wrkr_rps.value / k6_rps.value if k6_rps.value > 0 else float("inf")
-        if is_too_slow(
+        if case.ratio_ok_wrkr_over_k6 is None:
+            ui.log(f"INFO: wrkr/k6 ratio_actual={ratio_actual:.3f} (no gate)")
+        elif is_too_slow(
             wrkr=wrkr_rps, other=k6_rps, ratio=case.ratio_ok_wrkr_over_k6, inclusive=False
         ):
             msg = (
@@ -506,7 +691,7 @@ def run_http_case(
             msg = f"PASS: wrkr/k6 > {case.ratio_ok_wrkr_over_k6} (ratio_actual={ratio_actual:.3f})"
             ui.log(msg)
         summary_lines.append(
-            f"  gate wrkr/k6 : ratio_ok={case.ratio_ok_wrkr_over_k6} ratio_actual={ratio_actual:.3f}"
+            f"  gate wrkr/k6 : ratio_ok={_fmt_ratio(case.ratio_ok_wrkr_over_k6)} ratio_actual={ratio_actual:.3f}"
         )
     else:
         summary_lines.append("  gate wrkr/k6 : SKIP (correctness failed or missing tool)")
@@ -514,6 +699,8 @@ def run_http_case(
     return HttpCaseOutcome(
         failures=failures,
         wrk_rps=wrk_rps,
+        wrkr_rps=wrkr_rps,
+        wrkr_p99_seconds=None if wrkr_json is None else wrkr_json.latency_p99_seconds,
         failure_messages=tuple(failure_messages),
         summary_lines=tuple(summary_lines),
     )
@@ -533,7 +720,10 @@ def run_grpc_case(
     ui.log(f"CASE: {title}")
 
     _ensure_script_exists(cfg.root, scripts.wrkr)
-    _ensure_script_exists(cfg.root, scripts.k6)
+    if scripts.k6 is not None:
+        _ensure_script_exists(cfg.root, scripts.k6)
+
+    k6_bin = tools.k6 if scripts.k6 is not None else None
 
     failures = 0
     failure_messages: list[str] = []
@@ -599,9 +789,9 @@ def run_grpc_case(
 
     k6_res: RunResult | None
     k6_ok = True
-    if tools.k6 is not None:
+    if k6_bin is not None and scripts.k6 is not None:
         k6_argv = [
-            str(tools.k6),
+            str(k6_bin),
             "run",
             "--vus",
             str(cfg.effective_k6_vus()),
@@ -626,7 +816,7 @@ def run_grpc_case(
             failure_messages.append(msg)
             failures += 1
     else:
-        ui.log("k6: skipped (not installed)")
+        ui.log(f"k6: skipped ({_skip_reason(tools.k6)})")
         k6_res = None
         k6_ok = False
 
@@ -684,7 +874,7 @@ def run_grpc_case(
             f"mean={_fmt_ms_f(_sec_to_ms(wrkr_json.latency_mean_seconds))}ms failed_checks={wrkr_json.checks_failed_total} "
             f"rx/s={_fmt_int(wrkr_json.bytes_received_per_sec)} tx/s={_fmt_int(wrkr_json.bytes_sent_per_sec)}"
         )
-    if tools.k6 is None:
+    if k6_bin is None:
         summary_lines.append("  k6  : SKIP")
     else:
         summary_lines.append(
@@ -696,7 +886,9 @@ def run_grpc_case(
     # Gate: wrkr vs k6 (strict)
     if k6_rps is not None and wrkr_rps is not None and k6_ok and wrkr_ok:
         ratio_actual = wrkr_rps.value / k6_rps.value if k6_rps.value > 0 else float("inf")
-        if is_too_slow(
+        if case.ratio_ok_wrkr_over_k6 is None:
+            ui.log(f"INFO: wrkr/k6 ratio_actual={ratio_actual:.3f} (no gate)")
+        elif is_too_slow(
             wrkr=wrkr_rps, other=k6_rps, ratio=case.ratio_ok_wrkr_over_k6, inclusive=False
         ):
             msg = (
@@ -710,7 +902,7 @@ def run_grpc_case(
             msg = f"PASS: wrkr/k6 > {case.ratio_ok_wrkr_over_k6} (ratio_actual={ratio_actual:.3f})"
             ui.log(msg)
         summary_lines.append(
-            f"  gate wrkr/k6 : ratio_ok={case.ratio_ok_wrkr_over_k6} ratio_actual={ratio_actual:.3f}"
+            f"  gate wrkr/k6 : ratio_ok={_fmt_ratio(case.ratio_ok_wrkr_over_k6)} ratio_actual={ratio_actual:.3f}"
         )
     else:
         summary_lines.append("  gate wrkr/k6 : SKIP (correctness failed or missing tool)")
@@ -718,6 +910,7 @@ def run_grpc_case(
     return GrpcCaseOutcome(
         failures=failures,
         wrkr_rps=wrkr_rps,
+        wrkr_p99_seconds=None if wrkr_json is None else wrkr_json.latency_p99_seconds,
         failure_messages=tuple(failure_messages),
         summary_lines=tuple(summary_lines),
     )
@@ -737,6 +930,14 @@ def is_too_slow(*, wrkr: Rps, other: Rps, ratio: float, inclusive: bool) -> bool
     return wrkr.value <= (other.value * ratio)
 
 
+def _skip_reason(tool: Path | None) -> str:
+    return "not installed" if tool is None else "no script for this case"
+
+
+def _fmt_ratio(ratio: float | None) -> str:
+    return "-" if ratio is None else str(ratio)
+
+
 def _ensure_script_exists(root: Path, rel_path: str) -> None:
     """
     Ensure a script file exists relative to repo root.
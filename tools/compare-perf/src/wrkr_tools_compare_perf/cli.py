@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+import json
 import os
 from pathlib import Path
 from typing import Annotated
@@ -8,7 +9,15 @@ import typer
 
 from .app import config_from_values
 from .app import run as run_suite
-from .config import ConfigError, env_path
+from .config import ConfigError, ExternalTarget, env_path
+from .report import (
+    GateLimits,
+    ReportError,
+    compare_results,
+    format_gate_line,
+    load_results,
+    verdict_document,
+)
 
 app = typer.Typer(
     add_completion=False,
@@ -214,6 +223,57 @@ def run(
             show_default=True,
         ),
     ] = "auto",
+    # External target
+    target_url: Annotated[
+        str | None,
+        typer.Option(
+            "--target-url",
+            help="Run against this service instead of the built-in testserver cases "
+            "(passed to every tool as BASE_URL).",
+            envvar="WRKR_TOOLS_COMPARE_PERF_TARGET_URL",
+        ),
+    ] = None,
+    target_protocol: Annotated[
+        str,
+        typer.Option(
+            "--target-protocol",
+            help="Protocol of --target-url (http|grpc).",
+            show_default=True,
+        ),
+    ] = "http",
+    wrkr_script: Annotated[
+        Path | None,
+        typer.Option(
+            "--wrkr-script",
+            help="wrkr script for --target-url (required with it).",
+            dir_okay=False,
+        ),
+    ] = None,
+    wrk_script: Annotated[
+        Path | None,
+        typer.Option(
+            "--wrk-script",
+            help="Optional wrk script for an HTTP --target-url; wrk is skipped without it.",
+            dir_okay=False,
+        ),
+    ] = None,
+    k6_script: Annotated[
+        Path | None,
+        typer.Option(
+            "--k6-script",
+            help="Optional k6 script for --target-url; k6 is skipped without it.",
+            dir_okay=False,
+        ),
+    ] = None,
+    results_json: Annotated[
+        Path | None,
+        typer.Option(
+            "--results-json",
+            help="Write wrkr's per-case RPS and p99 to this file (input for `gate`).",
+            envvar="WRKR_TOOLS_COMPARE_PERF_RESULTS_JSON",
+            dir_okay=False,
+        ),
+    ] = None,
 ) -> None:
     """
     Run the full perf comparison suite.
@@ -238,6 +298,14 @@ def run(
         )
     color = color_norm
 
+    target = _external_target(
+        url=target_url,
+        protocol=target_protocol,
+        wrkr_script=wrkr_script,
+        wrk_script=wrk_script,
+        k6_script=k6_script,
+    )
+
     cfg = config_from_values(
         root=root_path,
         duration=duration,
@@ -256,10 +324,11 @@ def run(
         ratio_ok_grpc_wrkr_over_wrk_hello=ratio_ok_grpc_wrkr_over_wrk_hello,
         require_wrk=require_wrk,
         require_k6=require_k6,
+        target=target,
     )
 
     try:
-        outcome = run_suite(cfg, color=color)
+        outcome = run_suite(cfg, color=color, results_json=results_json)
     except ConfigError as e:
         typer.secho(f"CONFIG ERROR: {e}", fg=typer.colors.RED, err=True)
         raise typer.Exit(code=2) from e
@@ -272,6 +341,116 @@ def run(
         raise typer.Exit(code=1)
 
 
+def _external_target(
+    *,
+    url: str | None,
+    protocol: str,
+    wrkr_script: Path | None,
+    wrk_script: Path | None,
+    k6_script: Path | None,
+) -> ExternalTarget | None:
+    if url is None:
+        if wrkr_script or wrk_script or k6_script:
+            raise ConfigError("--wrkr-script/--wrk-script/--k6-script require --target-url")
+        return None
+    if wrkr_script is None:
+        raise ConfigError("--target-url requires --wrkr-script")
+
+    # Tools run from the wrkr root, so pin user scripts to absolute paths first.
+    def resolve(p: Path | None) -> str | None:
+        return None if p is None else str(p.resolve())
+
+    return ExternalTarget(
+        url=url.strip(),
+        protocol=protocol.strip().lower(),
+        wrkr_script=str(wrkr_script.resolve()),
+        wrk_script=resolve(wrk_script),
+        k6_script=resolve(k6_script),
+    )
+
+
+@app.command()
+def gate(
+    baseline: Annotated[
+        Path,
+        typer.Argument(
+            help="Results file from the baseline run (`run --results-json`).",
+            dir_okay=False,
+        ),
+    ],
+    candidate: Annotated[
+        Path,
+        typer.Argument(help="Results file from the candidate run.", dir_okay=False),
+    ],
+    max_rps_drop: Annotated[
+        float | None,
+        typer.Option(
+            "--max-rps-drop",
+            help="Fail when a case's RPS drops by more than this many percent.",
+            min=0.0,
+        ),
+    ] = None,
+    max_p99_increase: Annotated[
+        float | None,
+        typer.Option(
+            "--max-p99-increase",
+            help="Fail when a case's p99 latency grows by more than this many percent.",
+            min=0.0,
+        ),
+    ] = None,
+    verdict_json: Annotated[
+        Path | None,
+        typer.Option(
+            "--verdict-json",
+            help="Also write the JSON verdict to this file.",
+            dir_okay=False,
+        ),
+    ] = None,
+) -> None:
+    """
+    Compare two saved runs and exit non-zero when the candidate regresses.
+
+    Prints a JSON verdict on stdout and one line per comparison on stderr.
+    Exit codes: 0 = pass, 1 = regression (or a baseline case is missing), 2 = usage error.
+    """
+    if max_rps_drop is None and max_p99_increase is None:
+        typer.secho(
+            "CONFIG ERROR: pass --max-rps-drop and/or --max-p99-increase",
+            fg=typer.colors.RED,
+            err=True,
+        )
+        raise typer.Exit(code=2)
+
+    try:
+        verdict = compare_results(
+            load_results(baseline),
+            load_results(candidate),
+            GateLimits(max_rps_drop_pct=max_rps_drop, max_p99_increase_pct=max_p99_increase),
+        )
+    except ReportError as e:
+        typer.secho(f"CONFIG ERROR: {e}", fg=typer.colors.RED, err=True)
+        raise typer.Exit(code=2) from e
+
+    for c in verdict.comparisons:
+        typer.secho(
+            format_gate_line(c),
+            fg=typer.colors.RED if c.regressed else typer.colors.GREEN,
+            err=True,
+        )
+    for title in verdict.missing_cases:
+        typer.secho(f"FAIL {title}: missing from candidate", fg=typer.colors.RED, err=True)
+    for title, metric in verdict.missing_metrics:
+        typer.secho(f"FAIL {title} {metric}: missing from candidate", fg=typer.colors.RED, err=True)
+
+    doc = json.dumps(verdict_document(verdict), indent=2)
+    typer.echo(doc)
+    if verdict_json is not None:
+        verdict_json.write_text(doc + "\n", encoding="utf-8")
+
+    if not verdict.ok():
+        raise typer.Exit(code=1)
+
+
 @app.callback(invoke_without_command=True)
 def _default(
     ctx: typer.Context,
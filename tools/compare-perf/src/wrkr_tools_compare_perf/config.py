@@ -54,6 +54,22 @@ class RunTuning:
     native: bool = True
 
 
+@dataclass(frozen=True, slots=True)
+class ExternalTarget:
+    """
+    A user-supplied service that replaces the built-in testserver cases.
+
+    Script paths are absolute (the CLI resolves them against the working directory). Each tool
+    gets the target URL as `BASE_URL`. wrk and k6 only run when a script for them is given.
+    """
+
+    url: str
+    protocol: str = "http"
+    wrkr_script: str = ""
+    wrk_script: str | None = None
+    k6_script: str | None = None
+
+
 @dataclass(frozen=True, slots=True)
 class Config:
     """
@@ -62,12 +78,15 @@ class Config:
     Notes:
     - `root` is the wrkr repo root (where `tools/perf/*` scripts are found and where cargo builds).
     - `duration` stays as a string for passing through to wrk/k6/wrkr, but can be validated/parsed.
+    - `target`, when set, runs a single case against an external service instead of the
+      built-in cases (no testserver, no ratio gates).
     """
 
     root: Path
     tuning: RunTuning = RunTuning()
     ratios: Ratios = Ratios()
     requirements: ToolRequirements = ToolRequirements()
+    target: ExternalTarget | None = None
 
     def effective_k6_vus(self) -> int:
         return self.tuning.k6_vus if self.tuning.k6_vus is not None else self.tuning.wrkr_vus
@@ -209,3 +228,20 @@ def validate_tuning(t: RunTuning) -> None:
         raise ConfigError(f"wrk_threads must be > 0, got {t.wrk_threads}.")
     if t.wrk_connections <= 0:
         raise ConfigError(f"wrk_connections must be > 0, got {t.wrk_connections}.")
+
+
+def validate_target(t: ExternalTarget) -> None:
+    """
+    Validate an external target.
+
+    - the URL must be absolute (`http://`, `https://` or `grpc://`-style)
+    - the protocol selects which scripts make sense (wrk only speaks HTTP)
+    """
+    if "://" not in t.url:
+        raise ConfigError(f"target URL must include a scheme, got {t.url!r}.")
+    if t.protocol not in {"http", "grpc"}:
+        raise ConfigError(f"target protocol must be http or grpc, got {t.protocol!r}.")
+    if not t.wrkr_script:
+        raise ConfigError("an external target needs a wrkr script (--wrkr-script).")
+    if t.protocol == "grpc" and t.wrk_script is not None:
+        raise ConfigError("wrk does not support gRPC; drop --wrk-script for a grpc target.")
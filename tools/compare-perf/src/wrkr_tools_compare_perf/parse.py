@@ -798,6 +798,49 @@ def parse_k6_completed_iterations(line: str) -> int | None:
     return None
 
 
+def json_mismatches(actual: object, expected: object, path: str = "$") -> list[str]:
+    """
+    Compare a decoded JSON value against the expected one and describe every difference.
+
+    Objects must have exactly the expected keys and arrays the expected length; an empty list
+    means the values match.
+    """
+    if isinstance(expected, dict):
+        if not isinstance(actual, dict):
+            return [f"{path}: expected object, got {_json_kind(actual)}"]
+        out: list[str] = []
+        for key in sorted(expected.keys() - actual.keys()):
+            out.append(f"{path}.{key}: missing")
+        for key in sorted(actual.keys() - expected.keys()):
+            out.append(f"{path}.{key}: unexpected key")
+        for key in sorted(expected.keys() & actual.keys()):
+            out.extend(json_mismatches(actual[key], expected[key], f"{path}.{key}"))
+        return out
+
+    if isinstance(expected, list):
+        if not isinstance(actual, list):
+            return [f"{path}: expected array, got {_json_kind(actual)}"]
+        if len(actual) != len(expected):
+            return [f"{path}: expected {len(expected)} elements, got {len(actual)}"]
+        out = []
+        for i, (a, e) in enumerate(zip(actual, expected, strict=True)):
+            out.extend(json_mismatches(a, e, f"{path}[{i}]"))
+        return out
+
+    # `True == 1` in Python; JSON keeps booleans and numbers apart.
+    if isinstance(expected, bool) != isinstance(actual, bool) or actual != expected:
+        return [f"{path}: expected {json.dumps(expected)}, got {json.dumps(actual)}"]
+    return []
+
+
+def _json_kind(value: object) -> str:
+    if isinstance(value, dict):
+        return "object"
+    if isinstance(value, list):
+        return "array"
+    return json.dumps(value)
+
+
 def tail_lines(text: str, n: int) -> str:
     if n <= 0:
         return ""
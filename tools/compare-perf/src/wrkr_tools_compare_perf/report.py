@@ -1,8 +1,61 @@
 from __future__ import annotations
 
+import json
+from dataclasses import dataclass
+from pathlib import Path
+
 from .exec import RunResult
 from .parse import Rps
 
+RESULTS_SCHEMA = "wrkr-tools-compare-perf.results.v1"
+VERDICT_SCHEMA = "wrkr-tools-compare-perf.verdict.v1"
+
+
+class ReportError(RuntimeError):
+    """Raised when a saved results file cannot be read."""
+
+
+@dataclass(frozen=True, slots=True)
+class CaseResult:
+    """wrkr's numbers for one case, as saved by `run --results-json`."""
+
+    title: str
+    rps: float | None
+    p99_seconds: float | None
+
+
+@dataclass(frozen=True, slots=True)
+class GateLimits:
+    """Allowed regression in percent; `None` disables the check."""
+
+    max_rps_drop_pct: float | None = None
+    max_p99_increase_pct: float | None = None
+
+
+@dataclass(frozen=True, slots=True)
+class MetricComparison:
+    case: str
+    metric: str
+    baseline: float
+    candidate: float
+    change_pct: float
+    limit_pct: float
+    regressed: bool
+
+
+@dataclass(frozen=True, slots=True)
+class GateVerdict:
+    comparisons: tuple[MetricComparison, ...]
+    missing_cases: tuple[str, ...]
+    missing_metrics: tuple[tuple[str, str], ...] = ()
+
+    def ok(self) -> bool:
+        return (
+            not self.missing_cases
+            and not self.missing_metrics
+            and not any(c.regressed for c in self.comparisons)
+        )
+
 
 def _mb_from_bytes(n: int) -> float:
     return float(n) / 1024.0 / 1024.0
@@ -53,7 +106,146 @@ def format_grpc_summary_line(
     )
 
 
+def write_results(path: Path, cases: list[CaseResult]) -> None:
+    doc = {
+        "schema": RESULTS_SCHEMA,
+        "cases": [{"title": c.title, "rps": c.rps, "p99Seconds": c.p99_seconds} for c in cases],
+    }
+    path.write_text(json.dumps(doc, indent=2) + "\n", encoding="utf-8")
+
+
+def load_results(path: Path) -> list[CaseResult]:
+    try:
+        doc = json.loads(path.read_text(encoding="utf-8"))
+    except (OSError, ValueError) as e:
+        raise ReportError(f"cannot read results from {path}: {e}") from e
+
+    if not isinstance(doc, dict) or doc.get("schema") != RESULTS_SCHEMA:
+        raise ReportError(f"{path} is not a compare-perf results file (schema {RESULTS_SCHEMA})")
+
+    out: list[CaseResult] = []
+    for c in doc.get("cases") or []:
+        if not isinstance(c, dict) or not isinstance(c.get("title"), str):
+            raise ReportError(f"{path}: malformed case entry: {c!r}")
+        out.append(
+            CaseResult(
+                title=c["title"],
+                rps=_opt_float(c.get("rps")),
+                p99_seconds=_opt_float(c.get("p99Seconds")),
+            )
+        )
+    return out
+
+
+def _opt_float(v: object) -> float | None:
+    return float(v) if isinstance(v, int | float) else None
+
+
+def compare_results(
+    baseline: list[CaseResult], candidate: list[CaseResult], limits: GateLimits
+) -> GateVerdict:
+    """
+    Compare wrkr's numbers case by case.
+
+    A case present in the baseline but missing from the candidate fails the gate, and so does a
+    gated metric the baseline has but the candidate does not. Metrics missing from the baseline
+    are skipped.
+    """
+    by_title = {c.title: c for c in candidate}
+    comparisons: list[MetricComparison] = []
+    missing: list[str] = []
+    missing_metrics: list[tuple[str, str]] = []
+
+    for base in baseline:
+        cand = by_title.get(base.title)
+        if cand is None:
+            missing.append(base.title)
+            continue
+
+        if limits.max_rps_drop_pct is not None and base.rps:
+            if cand.rps is None:
+                missing_metrics.append((base.title, "rps"))
+            else:
+                drop_pct = (base.rps - cand.rps) / base.rps * 100.0
+                comparisons.append(
+                    MetricComparison(
+                        case=base.title,
+                        metric="rps",
+                        baseline=base.rps,
+                        candidate=cand.rps,
+                        change_pct=-drop_pct,
+                        limit_pct=limits.max_rps_drop_pct,
+                        regressed=drop_pct > limits.max_rps_drop_pct,
+                    )
+                )
+
+        if limits.max_p99_increase_pct is not None and base.p99_seconds:
+            if cand.p99_seconds is None:
+                missing_metrics.append((base.title, "p99"))
+            else:
+                increase_pct = (cand.p99_seconds - base.p99_seconds) / base.p99_seconds * 100.0
+                comparisons.append(
+                    MetricComparison(
+                        case=base.title,
+                        metric="p99",
+                        baseline=base.p99_seconds,
+                        candidate=cand.p99_seconds,
+                        change_pct=increase_pct,
+                        limit_pct=limits.max_p99_increase_pct,
+                        regressed=increase_pct > limits.max_p99_increase_pct,
+                    )
+                )
+
+    return GateVerdict(
+        comparisons=tuple(comparisons),
+        missing_cases=tuple(missing),
+        missing_metrics=tuple(missing_metrics),
+    )
+
+
+def verdict_document(verdict: GateVerdict) -> dict[str, object]:
+    return {
+        "schema": VERDICT_SCHEMA,
+        "ok": verdict.ok(),
+        "missingCases": list(verdict.missing_cases),
+        "missingMetrics": [
+            {"case": case, "metric": metric} for case, metric in verdict.missing_metrics
+        ],
+        "comparisons": [
+            {
+                "case": c.case,
+                "metric": c.metric,
+                "baseline": c.baseline,
+                "candidate": c.candidate,
+                "changePct": round(c.change_pct, 3),
+                "limitPct": c.limit_pct,
+                "regressed": c.regressed,
+            }
+            for c in verdict.comparisons
+        ],
+    }
+
+
+def format_gate_line(c: MetricComparison) -> str:
+    status = "FAIL" if c.regressed else "OK"
+    bound = f"drop <= {c.limit_pct}%" if c.metric == "rps" else f"increase <= {c.limit_pct}%"
+    return (
+        f"{status} {c.case} {c.metric}: baseline={c.baseline:.6g} candidate={c.candidate:.6g} "
+        f"change={c.change_pct:+.2f}% ({bound})"
+    )
+
+
 __all__ = [
+    "CaseResult",
+    "GateLimits",
+    "GateVerdict",
+    "MetricComparison",
+    "ReportError",
+    "compare_results",
+    "format_gate_line",
     "format_grpc_summary_line",
     "format_http_summary_line",
+    "load_results",
+    "verdict_document",
+    "write_results",
 ]
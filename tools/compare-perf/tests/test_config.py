@@ -2,7 +2,13 @@ from __future__ import annotations
 
 import pytest
 
-from wrkr_tools_compare_perf.config import ConfigError, env_bool, parse_duration_to_seconds
+from wrkr_tools_compare_perf.config import (
+    ConfigError,
+    ExternalTarget,
+    env_bool,
+    parse_duration_to_seconds,
+    validate_target,
+)
 
 
 @pytest.mark.parametrize(
@@ -38,3 +44,31 @@ def test_env_bool_parsing(monkeypatch: pytest.MonkeyPatch) -> None:
     monkeypatch.setenv("X", "wat")
     with pytest.raises(ConfigError):
         env_bool("X", default=True)
+
+
+def test_validate_target_accepts_http_and_grpc() -> None:
+    validate_target(
+        ExternalTarget(
+            url="https://api.example.com",
+            wrkr_script="/work/bench.lua",
+            k6_script="/work/bench.js",
+            wrk_script="/work/bench_wrk.lua",
+        )
+    )
+    validate_target(
+        ExternalTarget(url="http://grpc.internal:50051", protocol="grpc", wrkr_script="/w.lua")
+    )
+
+
+@pytest.mark.parametrize(
+    "target",
+    [
+        ExternalTarget(url="api.example.com", wrkr_script="/w.lua"),
+        ExternalTarget(url="http://x", protocol="ws", wrkr_script="/w.lua"),
+        ExternalTarget(url="http://x"),
+        ExternalTarget(url="http://x", protocol="grpc", wrkr_script="/w.lua", wrk_script="/w"),
+    ],
+)
+def test_validate_target_rejects_invalid(target: ExternalTarget) -> None:
+    with pytest.raises(ConfigError):
+        validate_target(target)
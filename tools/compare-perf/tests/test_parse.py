@@ -7,6 +7,7 @@ import pytest
 from wrkr_tools_compare_perf.parse import (
     ParseError,
     detect_wrk_errors,
+    json_mismatches,
     parse_k6_http_rps,
     parse_wrk_rps,
     parse_wrkr_rps,
@@ -69,3 +70,26 @@ def test_try_parse_wrkr_json_summary() -> None:
     assert s.rps == pytest.approx(120.0)
     assert s.checks_failed_total == 0
     assert s.latency_p99_seconds == pytest.approx(0.009)
+
+
+def test_json_mismatches_accepts_equal_values() -> None:
+    expected = {"n": 3, "by": {"US": 3200}, "xs": [1, {"a": True}]}
+    assert json_mismatches({"xs": [1, {"a": True}], "by": {"US": 3200}, "n": 3}, expected) == []
+
+
+def test_json_mismatches_reports_each_difference_with_its_path() -> None:
+    actual = {"n": 2, "by": {"US": 3200, "FR": 1}, "xs": [1], "extra": None}
+    expected = {"n": 3, "by": {"US": 3200, "DE": 6000}, "xs": [1, 2], "flag": 1}
+    assert json_mismatches(actual, expected) == [
+        "$.flag: missing",
+        "$.extra: unexpected key",
+        "$.by.DE: missing",
+        "$.by.FR: unexpected key",
+        "$.n: expected 3, got 2",
+        "$.xs: expected 2 elements, got 1",
+    ]
+
+
+def test_json_mismatches_keeps_booleans_and_numbers_apart() -> None:
+    assert json_mismatches(True, 1) == ["$: expected 1, got true"]
+    assert json_mismatches([1], {"a": 1}) == ["$: expected object, got array"]
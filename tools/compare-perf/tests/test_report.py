@@ -0,0 +1,93 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+import pytest
+
+from wrkr_tools_compare_perf.report import (
+    CaseResult,
+    GateLimits,
+    ReportError,
+    compare_results,
+    load_results,
+    verdict_document,
+    write_results,
+)
+
+BASELINE = [
+    CaseResult(title="HTTP GET /hello", rps=100_000.0, p99_seconds=0.002),
+    CaseResult(title="gRPC Echo (plaintext)", rps=50_000.0, p99_seconds=0.004),
+]
+
+
+def test_results_round_trip(tmp_path: Path) -> None:
+    path = tmp_path / "results.json"
+    write_results(path, [*BASELINE, CaseResult(title="no json", rps=None, p99_seconds=None)])
+
+    assert load_results(path) == [
+        *BASELINE,
+        CaseResult(title="no json", rps=None, p99_seconds=None),
+    ]
+
+
+def test_load_results_rejects_other_files(tmp_path: Path) -> None:
+    path = tmp_path / "other.json"
+    path.write_text('{"cases": []}', encoding="utf-8")
+    with pytest.raises(ReportError):
+        load_results(path)
+
+
+def test_gate_flags_rps_drop_and_p99_increase_beyond_limits() -> None:
+    candidate = [
+        CaseResult(title="HTTP GET /hello", rps=94_000.0, p99_seconds=0.0021),
+        CaseResult(title="gRPC Echo (plaintext)", rps=49_000.0, p99_seconds=0.0046),
+    ]
+    verdict = compare_results(
+        BASELINE, candidate, GateLimits(max_rps_drop_pct=5.0, max_p99_increase_pct=10.0)
+    )
+
+    regressed = {(c.case, c.metric) for c in verdict.comparisons if c.regressed}
+    assert regressed == {("HTTP GET /hello", "rps"), ("gRPC Echo (plaintext)", "p99")}
+    assert not verdict.ok()
+
+    doc = verdict_document(verdict)
+    assert doc["ok"] is False
+    assert len(doc["comparisons"]) == 4
+
+
+def test_gate_passes_within_limits_and_checks_only_requested_metrics() -> None:
+    candidate = [
+        CaseResult(title="HTTP GET /hello", rps=97_000.0, p99_seconds=0.01),
+        CaseResult(title="gRPC Echo (plaintext)", rps=51_000.0, p99_seconds=None),
+    ]
+    verdict = compare_results(BASELINE, candidate, GateLimits(max_rps_drop_pct=5.0))
+
+    assert verdict.ok()
+    assert [c.metric for c in verdict.comparisons] == ["rps", "rps"]
+
+
+def test_gate_fails_when_a_baseline_case_is_missing() -> None:
+    verdict = compare_results(BASELINE, BASELINE[:1], GateLimits(max_rps_drop_pct=5.0))
+
+    assert verdict.missing_cases == ("gRPC Echo (plaintext)",)
+    assert not verdict.ok()
+
+
+def test_gate_fails_when_a_gated_metric_is_missing_from_the_candidate() -> None:
+    candidate = [
+        CaseResult(title="HTTP GET /hello", rps=None, p99_seconds=0.002),
+        CaseResult(title="gRPC Echo (plaintext)", rps=50_000.0, p99_seconds=None),
+    ]
+    verdict = compare_results(
+        BASELINE, candidate, GateLimits(max_rps_drop_pct=5.0, max_p99_increase_pct=10.0)
+    )
+
+    assert verdict.missing_metrics == (
+        ("HTTP GET /hello", "rps"),
+        ("gRPC Echo (plaintext)", "p99"),
+    )
+    assert not verdict.ok()
+    assert verdict_document(verdict)["missingMetrics"] == [
+        {"case": "HTTP GET /hello", "metric": "rps"},
+        {"case": "gRPC Echo (plaintext)", "metric": "p99"},
+    ]
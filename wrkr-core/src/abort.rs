@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Notify;
+
+/// Stops the run once the request error rate over the last `window` exceeds `max_rate`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorRateBreaker {
+    /// Failed requests / total requests, `0.0..=1.0`.
+    pub max_rate: f64,
+    pub window: Duration,
+}
+
+/// Run-wide stop request. VUs finish their current iteration and then exit.
+#[derive(Debug, Default)]
+pub struct AbortSignal {
+    reason: OnceLock<String>,
+    notify: Notify,
+}
+
+impl AbortSignal {
+    /// Request a stop. Only the first reason is kept.
+    pub fn abort(&self, reason: String) {
+        if self.reason.set(reason).is_ok() {
+            tracing::warn!(reason = self.reason().unwrap_or_default(), "aborting run");
+            self.notify.notify_waiters();
+        }
+    }
+
+    #[must_use]
+    pub fn is_aborted(&self) -> bool {
+        self.reason.get().is_some()
+    }
+
+    #[must_use]
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.get().map(String::as_str)
+    }
+
+    /// Resolve once the run has been aborted.
+    pub async fn wait(&self) {
+        let notified = self.notify.notified();
+        tokio::pin!(notified);
+        // Register before checking the flag so an `abort` in between is not missed.
+        notified.as_mut().enable();
+        if self.is_aborted() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// Rolling error rate over running request/error totals.
+#[derive(Debug)]
+pub(crate) struct ErrorRateWindow {
+    window: Duration,
+    /// `(observed_at, requests_total, errors_total)`, oldest first.
+    samples: VecDeque<(Instant, u64, u64)>,
+}
+
+impl ErrorRateWindow {
+    pub(crate) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Feed the totals observed at `now` and return the error rate over the last `window`.
+    ///
+    /// Returns `None` until a full window has been observed, and for windows without requests.
+    pub(crate) fn observe(
+        &mut self,
+        now: Instant,
+        requests_total: u64,
+        errors_total: u64,
+    ) -> Option<f64> {
+        self.samples.push_back((now, requests_total, errors_total));
+
+        // Keep the newest sample that is at least `window` old as the baseline.
+        while self
+            .samples
+            .get(1)
+            .is_some_and(|(at, _, _)| now.duration_since(*at) >= self.window)
+        {
+            self.samples.pop_front();
+        }
+
+        let (at, requests_base, errors_base) = *self.samples.front()?;
+        if now.duration_since(at) < self.window {
+            return None;
+        }
+
+        let requests = requests_total.saturating_sub(requests_base);
+        if requests == 0 {
+            return None;
+        }
+        let errors = errors_total.saturating_sub(errors_base);
+        Some(errors as f64 / requests as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_rate_covers_only_the_last_window() {
+        let mut w = ErrorRateWindow::new(Duration::from_secs(10));
+        let t0 = Instant::now();
+        let at = |s: u64| t0 + Duration::from_secs(s);
+
+        assert_eq!(w.observe(at(0), 0, 0), None);
+        // Healthy for the first 10s.
+        for s in 1..10 {
+            assert_eq!(
+                w.observe(at(s), s * 100, 0),
+                None,
+                "window not full at {s}s"
+            );
+        }
+        assert_eq!(w.observe(at(10), 1_000, 0), Some(0.0));
+
+        // Then every request fails; the healthy samples age out of the window.
+        for s in 11..20 {
+            let rate = w.observe(at(s), s * 100, (s - 10) * 100);
+            assert!(rate.is_some_and(|r| r < 1.0), "{s}s: {rate:?}");
+        }
+        assert_eq!(w.observe(at(20), 2_000, 1_000), Some(1.0));
+    }
+
+    #[test]
+    fn idle_window_has_no_rate() {
+        let mut w = ErrorRateWindow::new(Duration::from_secs(1));
+        let t0 = Instant::now();
+
+        assert_eq!(w.observe(t0, 5, 5), None);
+        assert_eq!(w.observe(t0 + Duration::from_secs(1), 5, 5), None);
+    }
+
+    #[tokio::test]
+    async fn wait_resolves_after_abort() {
+        let signal = std::sync::Arc::new(AbortSignal::default());
+        let waiter = tokio::spawn({
+            let signal = signal.clone();
+            async move { signal.wait().await }
+        });
+
+        signal.abort("first".to_string());
+        signal.abort("second".to_string());
+
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap_or_else(|e| panic!("waiter timed out: {e}"))
+            .unwrap_or_else(|e| panic!("waiter panicked: {e}"));
+        assert_eq!(signal.reason(), Some("first"));
+    }
+}
@@ -0,0 +1,390 @@
+//! Running scenarios from Rust code, without the CLI or a script runtime.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::progress::{ProgressFn, ProgressSink, progress_fn};
+use crate::{Result, RunScenariosContext, RunSummary, ScenarioConfig, ThresholdSet, VuContext};
+
+/// Configures and starts a run of `scenarios` with a Rust VU function.
+///
+/// ```no_run
+/// # async fn example(scenarios: Vec<wrkr_core::ScenarioConfig>) -> wrkr_core::Result<()> {
+/// let summary = wrkr_core::RunBuilder::new(scenarios, |vu: wrkr_core::VuContext| async move {
+///     vu.run_iterations(|| async {
+///         // One iteration of load against the system under test.
+///         Ok::<(), std::io::Error>(())
+///     })
+///     .await
+/// })
+/// .progress(|update: wrkr_core::ProgressUpdate| {
+///     println!("{}: {:.0} rps", update.scenario, update.metrics.rps_now);
+/// })
+/// .run()
+/// .await?;
+/// println!("{} requests", summary.scenarios[0].requests_total);
+/// # Ok(())
+/// # }
+/// ```
+pub struct RunBuilder<F> {
+    scenarios: Vec<ScenarioConfig>,
+    ctx: RunScenariosContext,
+    vu: F,
+    progress: Option<ProgressFn>,
+}
+
+impl<F, Fut, E> RunBuilder<F>
+where
+    F: Fn(VuContext) -> Fut + Clone + Send + Sync + 'static,
+    Fut: std::future::Future<Output = std::result::Result<(), E>> + Send + 'static,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// A run with an empty environment and no script; `vu` is spawned once per VU.
+    pub fn new(scenarios: Vec<ScenarioConfig>, vu: F) -> Self {
+        Self {
+            scenarios,
+            ctx: RunScenariosContext::new(Arc::from([]), String::new(), PathBuf::new()),
+            vu,
+            progress: None,
+        }
+    }
+
+    /// Replace the run context, e.g. to share a metrics registry or HTTP client with the VUs.
+    #[must_use]
+    pub fn context(mut self, ctx: RunScenariosContext) -> Self {
+        self.ctx = ctx;
+        self
+    }
+
+    /// Thresholds evaluated into [`RunSummary::threshold_violations`] at the end of the run.
+    #[must_use]
+    pub fn thresholds(mut self, thresholds: Vec<ThresholdSet>) -> Self {
+        self.ctx.thresholds = Arc::from(thresholds);
+        self
+    }
+
+    /// Hard cap on the measured run, see [`RunScenariosContext::max_duration`].
+    #[must_use]
+    pub fn max_duration(mut self, max: std::time::Duration) -> Self {
+        self.ctx.max_duration = Some(max);
+        self
+    }
+
+    #[must_use]
+    pub fn progress(mut self, sink: impl ProgressSink) -> Self {
+        self.progress = Some(progress_fn(sink));
+        self
+    }
+
+    pub async fn run(self) -> Result<RunSummary> {
+        crate::run_scenarios(self.scenarios, self.ctx, self.vu, self.progress).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+    use crate::{RunConfig, ScenarioOptions, ScriptOptions, scenarios_from_options};
+
+    #[tokio::test]
+    async fn builder_runs_rust_iterations() {
+        let opts = ScriptOptions {
+            scenarios: vec![ScenarioOptions {
+                vus: Some(2),
+                iterations: Some(10),
+                ..ScenarioOptions::new("main")
+            }],
+            ..Default::default()
+        };
+        let scenarios =
+            scenarios_from_options(opts, RunConfig::default()).unwrap_or_else(|e| panic!("{e}"));
+
+        let calls = Arc::new(AtomicU64::new(0));
+        let vu_calls = calls.clone();
+        let summary = RunBuilder::new(scenarios, move |vu: VuContext| {
+            let calls = vu_calls.clone();
+            async move {
+                vu.run_iterations(|| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    async { Ok::<(), std::io::Error>(()) }
+                })
+                .await
+            }
+        })
+        .progress(|_update: crate::ProgressUpdate| {})
+        .run()
+        .await
+        .unwrap_or_else(|e| panic!("{e}"));
+
+        assert_eq!(calls.load(Ordering::Relaxed), 10);
+        assert_eq!(summary.scenarios[0].iterations_total, 10);
+    }
+
+    #[tokio::test]
+    async fn scenario_think_time_paces_iterations() {
+        let opts = ScriptOptions {
+            scenarios: vec![ScenarioOptions {
+                vus: Some(1),
+                iterations: Some(3),
+                think_time: Some(crate::ThinkTimeSpec::fixed(
+                    std::time::Duration::from_millis(30),
+                )),
+                ..ScenarioOptions::new("paced")
+            }],
+            ..Default::default()
+        };
+        let scenarios =
+            scenarios_from_options(opts, RunConfig::default()).unwrap_or_else(|e| panic!("{e}"));
+        assert!(scenarios[0].think_time.is_some());
+
+        let started = std::time::Instant::now();
+        let summary = RunBuilder::new(scenarios, |vu: VuContext| async move {
+            vu.run_iterations(|| async { Ok::<(), std::io::Error>(()) })
+                .await
+        })
+        .run()
+        .await
+        .unwrap_or_else(|e| panic!("{e}"));
+
+        assert_eq!(summary.scenarios[0].iterations_total, 3);
+        assert!(
+            started.elapsed() >= std::time::Duration::from_millis(60),
+            "{:?}",
+            started.elapsed()
+        );
+    }
+
+    #[tokio::test]
+    async fn max_duration_stops_a_hung_run_and_still_summarizes() {
+        let opts = ScriptOptions {
+            scenarios: vec![ScenarioOptions {
+                vus: Some(2),
+                iterations: Some(1_000),
+                ..ScenarioOptions::new("hung")
+            }],
+            ..Default::default()
+        };
+        let scenarios =
+            scenarios_from_options(opts, RunConfig::default()).unwrap_or_else(|e| panic!("{e}"));
+
+        let started = std::time::Instant::now();
+        let summary = RunBuilder::new(scenarios, |vu: VuContext| async move {
+            vu.run_iterations(|| async {
+                // A request that never completes.
+                std::future::pending::<()>().await;
+                Ok::<(), std::io::Error>(())
+            })
+            .await
+        })
+        .max_duration(std::time::Duration::from_millis(100))
+        .run()
+        .await
+        .unwrap_or_else(|e| panic!("{e}"));
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert!(
+            summary
+                .aborted
+                .as_deref()
+                .is_some_and(|r| r.contains("--max-duration"))
+        );
+        assert_eq!(summary.scenarios[0].iterations_total, 0);
+        assert!(summary.scenarios[0].ended_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn metrics_recorded_in_setup_survive_the_warm_up_reset() {
+        for warmup_iterations in [None, Some(1)] {
+            let opts = ScriptOptions {
+                scenarios: vec![ScenarioOptions {
+                    iterations: Some(2),
+                    ..ScenarioOptions::new("main")
+                }],
+                ..Default::default()
+            };
+            let cfg = RunConfig {
+                warmup_iterations,
+                ..Default::default()
+            };
+            let scenarios = scenarios_from_options(opts, cfg).unwrap_or_else(|e| panic!("{e}"));
+
+            // What `Setup` records runs before any VU starts.
+            let ctx = RunScenariosContext::new(Arc::from([]), String::new(), PathBuf::new());
+            let seeded = ctx
+                .metrics
+                .register("seeded_users", crate::MetricKind::Counter);
+            if let Some(h) = ctx
+                .metrics
+                .get_handle(seeded, ctx.metrics.resolve_tags(&[]))
+            {
+                h.increment(5);
+            }
+
+            let summary = RunBuilder::new(scenarios, |vu: VuContext| async move {
+                vu.run_iterations(|| async { Ok::<(), std::io::Error>(()) })
+                    .await
+            })
+            .context(ctx)
+            .run()
+            .await
+            .unwrap_or_else(|e| panic!("{e}"));
+
+            let seeded = summary
+                .metrics
+                .iter()
+                .find(|m| m.name == "seeded_users")
+                .map(|m| &m.values);
+            assert!(
+                matches!(seeded, Some(crate::MetricValue::Counter(5))),
+                "warm-up {warmup_iterations:?}: {seeded:?}"
+            );
+            assert_eq!(summary.scenarios[0].iterations_total, 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn init_errors_name_the_vu_that_failed() {
+        let opts = ScriptOptions {
+            scenarios: vec![ScenarioOptions {
+                vus: Some(3),
+                iterations: Some(3),
+                ..ScenarioOptions::new("main")
+            }],
+            ..Default::default()
+        };
+        let cfg = RunConfig {
+            warmup_iterations: Some(1),
+            ..Default::default()
+        };
+        let scenarios = scenarios_from_options(opts, cfg).unwrap_or_else(|e| panic!("{e}"));
+
+        let res = RunBuilder::new(scenarios, |vu: VuContext| async move {
+            let vu_id = vu.vu_id;
+            vu.run_iterations(|| async move {
+                if vu_id == 2 {
+                    return Err(std::io::Error::other("boom"));
+                }
+                Ok(())
+            })
+            .await
+        })
+        .run()
+        .await;
+
+        match res {
+            Err(crate::Error::VuInit(err)) => {
+                assert_eq!(err.vu_id, 2);
+                assert_eq!(err.scenario, "main");
+                assert_eq!(
+                    err.to_string(),
+                    "VU 2 (scenario `main`): warm-up iteration failed: boom"
+                );
+            }
+            other => panic!("expected an init error, got {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn isolated_scenarios_get_http_clients_of_their_own() {
+        let scenario = |name: &str| ScenarioOptions {
+            vus: Some(2),
+            iterations: Some(2),
+            ..ScenarioOptions::new(name)
+        };
+        let opts = ScriptOptions {
+            scenarios: vec![scenario("a"), scenario("b")],
+            ..Default::default()
+        };
+        let scenarios =
+            scenarios_from_options(opts, RunConfig::default()).unwrap_or_else(|e| panic!("{e}"));
+
+        let mut ctx = RunScenariosContext::new(Arc::from([]), String::new(), PathBuf::new());
+        ctx.isolate_scenario_connections = true;
+        let run_client = Arc::as_ptr(&ctx.client) as usize;
+
+        let clients = Arc::new(std::sync::Mutex::new(std::collections::BTreeSet::new()));
+        let seen = clients.clone();
+        RunBuilder::new(scenarios, move |vu: VuContext| {
+            let seen = seen.clone();
+            async move {
+                seen.lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .insert((
+                        vu.metrics_ctx.scenario().to_string(),
+                        Arc::as_ptr(&vu.run_ctx.client) as usize,
+                    ));
+                vu.run_iterations(|| async { Ok::<(), std::io::Error>(()) })
+                    .await
+            }
+        })
+        .context(ctx)
+        .run()
+        .await
+        .unwrap_or_else(|e| panic!("{e}"));
+
+        let clients = clients
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        // Both VUs of a scenario share one client, and no scenario uses the run's own.
+        let by_scenario: Vec<usize> = clients.iter().map(|(_, client)| *client).collect();
+        assert_eq!(clients.len(), 2, "{clients:?}");
+        assert_ne!(by_scenario[0], by_scenario[1]);
+        assert!(!by_scenario.contains(&run_client));
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn a_scenario_can_ask_for_an_http_client_of_its_own() {
+        let opts = ScriptOptions {
+            scenarios: vec![
+                ScenarioOptions {
+                    iterations: Some(1),
+                    isolate_connections: true,
+                    ..ScenarioOptions::new("checkout")
+                },
+                ScenarioOptions {
+                    iterations: Some(1),
+                    ..ScenarioOptions::new("browse")
+                },
+            ],
+            ..Default::default()
+        };
+        let scenarios =
+            scenarios_from_options(opts, RunConfig::default()).unwrap_or_else(|e| panic!("{e}"));
+
+        let ctx = RunScenariosContext::new(Arc::from([]), String::new(), PathBuf::new());
+        let run_client = Arc::as_ptr(&ctx.client) as usize;
+
+        let clients = Arc::new(std::sync::Mutex::new(std::collections::BTreeMap::new()));
+        let seen = clients.clone();
+        RunBuilder::new(scenarios, move |vu: VuContext| {
+            let seen = seen.clone();
+            async move {
+                seen.lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .insert(
+                        vu.metrics_ctx.scenario().to_string(),
+                        Arc::as_ptr(&vu.run_ctx.client) as usize,
+                    );
+                vu.run_iterations(|| async { Ok::<(), std::io::Error>(()) })
+                    .await
+            }
+        })
+        .context(ctx)
+        .run()
+        .await
+        .unwrap_or_else(|e| panic!("{e}"));
+
+        let clients = clients
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        assert_eq!(clients.get("browse"), Some(&run_client));
+        assert_ne!(clients.get("checkout"), Some(&run_client));
+        assert!(clients.contains_key("checkout"), "{clients:?}");
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// Caps how many distinct values a dynamically computed tag (e.g. a check name) may take.
+///
+/// Every distinct tag value becomes its own metric series, so values derived from runtime data
+/// need an upper bound. Once `limit` distinct values were admitted, unseen values are rejected and
+/// callers fall back to a static value instead.
+#[derive(Debug)]
+pub struct CardinalityGuard {
+    limit: usize,
+    seen: Mutex<HashSet<String>>,
+}
+
+impl CardinalityGuard {
+    pub const DEFAULT_LIMIT: usize = 100;
+
+    #[must_use]
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            seen: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Returns `true` if `value` was already admitted or there is still room for it.
+    pub fn admit(&self, value: &str) -> bool {
+        let mut seen = self
+            .seen
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if seen.contains(value) {
+            return true;
+        }
+        if seen.len() >= self.limit {
+            return false;
+        }
+        seen.insert(value.to_string());
+        true
+    }
+}
+
+impl Default for CardinalityGuard {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_LIMIT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admits_known_values_after_limit_is_reached() {
+        let guard = CardinalityGuard::new(2);
+        assert!(guard.admit("status is 2xx"));
+        assert!(guard.admit("status is 4xx"));
+        assert!(!guard.admit("status is 5xx"));
+
+        // Values seen before the limit was reached keep being admitted.
+        assert!(guard.admit("status is 2xx"));
+    }
+}
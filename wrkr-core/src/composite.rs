@@ -0,0 +1,147 @@
+//! Runs made of several scripts (`wrkr run a.lua b.lua`).
+//!
+//! Each script's scenarios keep their own script source, so VUs load the script that defined
+//! their scenario, while metrics, thresholds and the summary stay shared by the whole run.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::{Error, MetricsContext, Result, RunScenariosContext, ScenarioConfig, ThresholdSet};
+
+/// Tag added to every series of a scenario that came from one script of a composite run.
+pub const SCRIPT_TAG: &str = "script";
+
+/// The source of one script taking part in a run.
+#[derive(Debug, Clone)]
+pub struct ScriptSource {
+    pub source: String,
+    pub path: PathBuf,
+}
+
+impl ScriptSource {
+    #[must_use]
+    pub fn new(source: String, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+
+    /// File name used as the `script` tag value (e.g. `users.lua`).
+    #[must_use]
+    pub fn label(&self) -> String {
+        self.path.file_name().map_or_else(
+            || self.path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+    }
+
+    /// File name without extension; replaces the implicit `Default` scenario name.
+    fn stem(&self) -> String {
+        self.path
+            .file_stem()
+            .map_or_else(|| self.label(), |stem| stem.to_string_lossy().into_owned())
+    }
+}
+
+impl RunScenariosContext {
+    /// A context sharing this run's state (metrics, shared store, clients, ...) that runs
+    /// `script` instead, e.g. for its `Setup`/`Teardown` in a composite run.
+    #[must_use]
+    pub fn with_script(&self, script: &ScriptSource) -> Self {
+        Self {
+            script: script.source.clone(),
+            script_path: script.path.clone(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Marks `scenarios` (built from `script`'s options) as part of a composite run.
+///
+/// VUs of these scenarios run `script`, every series gets a `script=<file name>` tag (unless the
+/// scenario sets one itself), and the implicit `Default` scenario is named after the file so
+/// scripts using top-level options don't collide.
+pub fn attach_script(scenarios: &mut [ScenarioConfig], script: &Arc<ScriptSource>) {
+    let label = script.label();
+    for s in scenarios {
+        let name = match s.metrics_ctx.scenario() {
+            "Default" => Arc::from(script.stem()),
+            _ => s.metrics_ctx.scenario_arc(),
+        };
+        let mut tags = s.metrics_ctx.scenario_tags().to_vec();
+        if !tags.iter().any(|(k, _)| k == SCRIPT_TAG) {
+            tags.push((SCRIPT_TAG.to_string(), label.clone()));
+        }
+        s.metrics_ctx = MetricsContext::new(name, Arc::from(tags));
+        s.script = Some(script.clone());
+    }
+}
+
+/// Restricts a script's thresholds to the series of that script in a composite run.
+pub fn scope_thresholds(sets: &mut [ThresholdSet], script: &ScriptSource) {
+    for set in sets {
+        if !set.tags.iter().any(|(k, _)| k == SCRIPT_TAG) {
+            set.tags.push((SCRIPT_TAG.to_string(), script.label()));
+        }
+    }
+}
+
+/// Scenario names must stay unique once several scripts are merged into one run.
+pub fn ensure_unique_scenarios(scenarios: &[ScenarioConfig]) -> Result<()> {
+    let mut seen = HashSet::with_capacity(scenarios.len());
+    for s in scenarios {
+        if !seen.insert(s.metrics_ctx.scenario()) {
+            return Err(Error::DuplicateScenario(
+                s.metrics_ctx.scenario().to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RunConfig, ScenarioOptions, ScriptOptions, scenarios_from_options};
+
+    fn scenarios(opts: ScriptOptions) -> Vec<ScenarioConfig> {
+        scenarios_from_options(opts, RunConfig::default()).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn script(path: &str) -> Arc<ScriptSource> {
+        Arc::new(ScriptSource::new(String::new(), PathBuf::from(path)))
+    }
+
+    #[test]
+    fn attach_script_names_default_scenario_and_tags_series() {
+        let mut users = scenarios(ScriptOptions::default());
+        attach_script(&mut users, &script("svc/users.lua"));
+        let mut orders = scenarios(ScriptOptions {
+            scenarios: vec![ScenarioOptions::new("checkout")],
+            ..Default::default()
+        });
+        attach_script(&mut orders, &script("svc/orders.lua"));
+
+        assert_eq!(users[0].metrics_ctx.scenario(), "users");
+        assert_eq!(orders[0].metrics_ctx.scenario(), "checkout");
+        assert_eq!(
+            orders[0].metrics_ctx.scenario_tags(),
+            &[("script".to_string(), "orders.lua".to_string())]
+        );
+        assert_eq!(
+            users[0].script.as_ref().map(|s| s.path.clone()),
+            Some(PathBuf::from("svc/users.lua"))
+        );
+
+        let mut all = users;
+        all.extend(orders);
+        assert!(ensure_unique_scenarios(&all).is_ok());
+
+        let mut again = scenarios(ScriptOptions::default());
+        attach_script(&mut again, &script("other/users.lua"));
+        all.extend(again);
+        match ensure_unique_scenarios(&all) {
+            Err(Error::DuplicateScenario(name)) => assert_eq!(name, "users"),
+            other => panic!("expected duplicate scenario error, got {other:?}"),
+        }
+    }
+}
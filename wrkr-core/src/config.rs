@@ -1,10 +1,52 @@
 use crate::MetricsContext;
+use std::sync::Arc;
 use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct Stage {
     pub duration: Duration,
     pub target: u64,
+    /// How the target moves from the previous stage's target to this one.
+    pub shape: StageShape,
+}
+
+/// Ramp curve of a stage (`shape` in `Options.stages`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, strum::EnumString, strum::Display)]
+#[strum(serialize_all = "kebab-case")]
+pub enum StageShape {
+    /// Straight line from the previous target to this one.
+    #[default]
+    Linear,
+    /// Jump to the target when the stage starts and hold it for the whole stage.
+    Step,
+    /// Slow start, fast finish (quadratic).
+    EaseIn,
+    /// Fast start, slow finish (quadratic).
+    EaseOut,
+    /// Slow start and finish, fastest in the middle.
+    EaseInOut,
+}
+
+impl StageShape {
+    /// Fraction of the way from the start to the end target after `progress` (`0.0..=1.0`) of
+    /// the stage.
+    #[must_use]
+    pub fn ease(self, progress: f64) -> f64 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::Step => 1.0,
+            Self::EaseIn => t * t,
+            Self::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -12,6 +54,9 @@ pub struct RunConfig {
     pub iterations: Option<u64>,
     pub vus: Option<u64>,
     pub duration: Option<Duration>,
+    pub warmup_iterations: Option<u64>,
+    /// Run-level metric tags (`--tag`); win over `ScriptOptions::tags` with the same key.
+    pub tags: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone)]
@@ -64,9 +109,36 @@ pub fn is_ramping(self) -> bool {
 pub struct ScenarioConfig {
     pub exec: String,
     pub metrics_ctx: MetricsContext,
+    /// Free-form, human-readable description (metadata only; carried into outputs).
+    pub description: Option<String>,
     pub executor: ScenarioExecutor,
     pub iterations: Option<u64>,
     pub duration: Option<Duration>,
+    /// Throwaway iterations each VU runs before measurement starts (0 = no warm-up).
+    pub warmup_iterations: u64,
+    /// Script the scenario's VUs run; `None` runs `RunScenariosContext::script`.
+    pub script: Option<Arc<crate::ScriptSource>>,
+    /// Argument for the exec function (see [`ScenarioOptions::params`]).
+    pub params: Option<Arc<wrkr_value::Value>>,
+    /// Pause each VU takes after every iteration (see [`ScenarioOptions::think_time`]).
+    pub think_time: Option<crate::ThinkTimeSpec>,
+    /// Whether the scenario gets an HTTP connection pool of its own (see
+    /// [`ScenarioOptions::isolate_connections`]).
+    pub isolate_connections: bool,
+}
+
+/// Who shares a client-side HTTP cache (`Options.httpCache`). Ordered by reach, so the widest
+/// scope asked for wins when several scripts set one.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, strum::EnumString, strum::Display,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum HttpCacheScope {
+    /// Each VU keeps its own cache, like one browser per user.
+    Vu,
+    /// One cache for the whole run, like a warm shared proxy cache (and with its rules: see
+    /// `wrkr_http::HttpCache::shared`).
+    Shared,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -74,8 +146,29 @@ pub struct ScriptOptions {
     pub vus: Option<u64>,
     pub iterations: Option<u64>,
     pub duration: Option<Duration>,
+    /// Per-VU warm-up iterations whose metrics are discarded before the measured run.
+    pub warmup_iterations: Option<u64>,
     pub scenarios: Vec<ScenarioOptions>,
 
+    /// Run-level metric tags (`Options.tags`), added to every scenario's tags.
+    pub tags: Vec<(String, String)>,
+
+    /// `Options.discardResponseBodies`: don't keep HTTP response bodies, only count their bytes.
+    pub discard_response_bodies: bool,
+
+    /// `Options.noConnectionReuse`: close every HTTP connection after its response.
+    pub no_connection_reuse: bool,
+
+    /// `Options.noVUConnectionReuse`: keep HTTP connections within an iteration, but open new
+    /// ones for the next.
+    pub no_vu_connection_reuse: bool,
+
+    /// `Options.isolateScenarioConnections`: one HTTP connection pool per scenario.
+    pub isolate_scenario_connections: bool,
+
+    /// `Options.httpCache`: serve repeated HTTP `GET`s from a client-side cache.
+    pub http_cache: Option<HttpCacheScope>,
+
     /// Threshold assertions.
     pub thresholds: Vec<super::thresholds::ThresholdSet>,
 }
@@ -88,6 +181,12 @@ pub struct ScenarioOptions {
     /// Scenario-level metric tags (k6-style `Options.scenarios[*].tags`).
     pub tags: Vec<(String, String)>,
 
+    /// Free-form, human-readable description of what the scenario represents.
+    pub description: Option<String>,
+
+    /// Value passed to the scenario's exec function on every iteration (`params`).
+    pub params: Option<Arc<wrkr_value::Value>>,
+
     /// Scenario executor. If missing, defaults to constant VUs.
     pub executor: Option<String>,
 
@@ -95,6 +194,15 @@ pub struct ScenarioOptions {
     pub iterations: Option<u64>,
     pub duration: Option<Duration>,
 
+    /// Pause drawn after every iteration of the scenario's VUs (`think_time`), so scenarios
+    /// modelling different kinds of users can pace themselves without branching in the script.
+    /// Not counted in `iteration_duration`; only for the VU-based executors.
+    pub think_time: Option<crate::ThinkTimeSpec>,
+
+    /// Give this scenario an HTTP connection pool of its own (`isolateConnections`), as
+    /// `Options.isolateScenarioConnections` does for every scenario.
+    pub isolate_connections: bool,
+
     // Ramping VUs
     pub start_vus: Option<u64>,
     pub stages: Vec<Stage>,
@@ -105,3 +213,113 @@ pub struct ScenarioOptions {
     pub pre_allocated_vus: Option<u64>,
     pub max_vus: Option<u64>,
 }
+
+impl ScenarioOptions {
+    /// A constant-VUs scenario named `name` with every other option unset.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            exec: None,
+            tags: Vec::new(),
+            description: None,
+            params: None,
+            executor: None,
+            vus: None,
+            iterations: None,
+            duration: None,
+            think_time: None,
+            isolate_connections: false,
+            start_vus: None,
+            stages: Vec::new(),
+            start_rate: None,
+            time_unit: None,
+            pre_allocated_vus: None,
+            max_vus: None,
+        }
+    }
+
+    /// Expand a scenario template over a parameter matrix: one scenario per combination of
+    /// values, named `<name>-<value>-...` (in `matrix` order) and tagged `key=value` for every
+    /// parameter.
+    ///
+    /// An empty matrix yields the scenario unchanged; keys without values are ignored.
+    #[must_use]
+    pub fn expand_matrix(self, matrix: &[(String, Vec<String>)]) -> Vec<Self> {
+        let mut out = vec![self];
+        for (key, values) in matrix.iter().filter(|(_, values)| !values.is_empty()) {
+            out = out
+                .into_iter()
+                .flat_map(|base| {
+                    values.iter().map(move |value| {
+                        let mut s = base.clone();
+                        s.name = format!("{}-{value}", s.name);
+                        s.tags.retain(|(k, _)| k != key);
+                        s.tags.push((key.clone(), value.clone()));
+                        s
+                    })
+                })
+                .collect();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_matrix_yields_one_tagged_scenario_per_combination() {
+        let base = ScenarioOptions {
+            name: "upload".to_string(),
+            exec: None,
+            tags: vec![("size".to_string(), "default".to_string())],
+            description: None,
+            params: None,
+            executor: None,
+            vus: Some(2),
+            iterations: None,
+            duration: None,
+            think_time: None,
+            isolate_connections: false,
+            start_vus: None,
+            stages: Vec::new(),
+            start_rate: None,
+            time_unit: None,
+            pre_allocated_vus: None,
+            max_vus: None,
+        };
+        let matrix = [
+            (
+                "size".to_string(),
+                vec!["1k".to_string(), "10k".to_string()],
+            ),
+            (
+                "method".to_string(),
+                vec!["PUT".to_string(), "POST".to_string()],
+            ),
+            ("unused".to_string(), Vec::new()),
+        ];
+
+        let expanded = base.expand_matrix(&matrix);
+        let names: Vec<&str> = expanded.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "upload-1k-PUT",
+                "upload-1k-POST",
+                "upload-10k-PUT",
+                "upload-10k-POST"
+            ]
+        );
+        assert_eq!(
+            expanded[3].tags,
+            [
+                ("size".to_string(), "10k".to_string()),
+                ("method".to_string(), "POST".to_string())
+            ]
+        );
+        assert!(expanded.iter().all(|s| s.vus == Some(2)));
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use crate::{Error, Result};
+
+/// Parse a duration the same way for CLI flags, script options and scenario YAML.
+///
+/// Accepts `humantime` syntax, including compound (`1h30m`, `2m 15s`), fractional (`1.5s`)
+/// and sub-second (`250ms`, `50us`, `10ns`) values. A bare number is a count of seconds.
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let s = input.trim();
+    let invalid = || Error::InvalidDuration(s.to_string());
+    if s.is_empty() {
+        return Err(invalid());
+    }
+
+    if s.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        let secs: f64 = s.parse().map_err(|_| invalid())?;
+        return Duration::try_from_secs_f64(secs).map_err(|_| invalid());
+    }
+
+    humantime::parse_duration(s).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_compound_fractional_and_bare_durations() {
+        let ok = |s: &str| parse_duration(s).unwrap_or_else(|e| panic!("{s}: {e}"));
+        assert_eq!(ok("1h30m"), Duration::from_secs(90 * 60));
+        assert_eq!(ok("2m 15s"), Duration::from_secs(135));
+        assert_eq!(ok("1s500ms"), Duration::from_millis(1500));
+        assert_eq!(ok("1.5s"), Duration::from_millis(1500));
+        assert_eq!(ok("250ms"), Duration::from_millis(250));
+        assert_eq!(ok("50us"), Duration::from_micros(50));
+        assert_eq!(ok("10"), Duration::from_secs(10));
+        assert_eq!(ok("0.25"), Duration::from_millis(250));
+        assert_eq!(ok(" 10s "), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        for s in ["", "  ", "abc", "10x", "1h30", "-5s", "1..5", "."] {
+            assert!(
+                matches!(parse_duration(s), Err(Error::InvalidDuration(_))),
+                "{s:?} should be rejected"
+            );
+        }
+    }
+}
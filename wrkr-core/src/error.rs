@@ -11,12 +11,18 @@ pub enum Error {
     #[error("virtual user error: {0}")]
     Vu(String),
 
+    #[error("virtual user initialization failed: {0}")]
+    VuInit(crate::VuInitError),
+
     #[error("`vus` must be a positive integer")]
     InvalidVus,
 
     #[error("`iterations` must be a positive integer")]
     InvalidIterations,
 
+    #[error("`duration` must be a positive duration")]
+    InvalidScenarioDuration,
+
     #[error(
         "invalid `executor` (expected `constant-vus`, `ramping-vus`, or `ramping-arrival-rate`)"
     )]
@@ -31,6 +37,9 @@ pub enum Error {
     #[error("`start_rate` must be a positive integer")]
     InvalidStartRate,
 
+    #[error("invalid duration `{0}` (expected e.g. 10s, 250ms, 1h30m)")]
+    InvalidDuration(String),
+
     #[error("`time_unit` must be a positive duration")]
     InvalidTimeUnit,
 
@@ -40,9 +49,23 @@ pub enum Error {
     #[error("`max_vus` must be >= `pre_allocated_vus`")]
     InvalidMaxVus,
 
+    #[error(
+        "`think_time` is not supported by `ramping-arrival-rate` (the arrival rate paces iterations)"
+    )]
+    ThinkTimeWithArrivalRate,
+
+    #[error("duplicate scenario name: `{0}` (scenario names must be unique)")]
+    DuplicateScenario(String),
+
+    #[error("invalid trace: {0}")]
+    InvalidTrace(String),
+
     #[error("invalid output path: `{0}`")]
     InvalidOutputPath(String),
 
     #[error(transparent)]
     ThresholdEval(#[from] crate::thresholds_eval::Error),
+
+    #[error(transparent)]
+    HistogramMerge(#[from] wrkr_metrics::HistogramMergeError),
 }
@@ -0,0 +1,208 @@
+use std::fmt::Write as _;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::Protocol;
+
+/// Response bodies longer than this are cut in failure log lines.
+const MAX_BODY_BYTES: usize = 512;
+
+/// A failed request, as reported to the failure log.
+#[derive(Debug, Clone, Copy)]
+pub struct FailureSample<'a> {
+    pub scenario: &'a str,
+    pub protocol: Protocol,
+    /// HTTP method or gRPC full method name.
+    pub method: &'a str,
+    /// Request URL (HTTP only).
+    pub target: Option<&'a str>,
+    pub status: Option<u16>,
+    pub error: Option<&'a str>,
+    pub body: &'a [u8],
+}
+
+#[derive(Debug, Default)]
+struct WindowState {
+    started: Option<Instant>,
+    logged: u32,
+    suppressed: u64,
+}
+
+/// Outcome of offering a failure to the log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Admit {
+    log: bool,
+    /// Failures dropped during the window that just ended (reported once, on rollover).
+    suppressed_before: u64,
+}
+
+/// Rate-limited log of failed requests, emitted as `warn` events.
+///
+/// At most `limit` failures are printed per `window`; the rest are counted and summarized when
+/// the next window opens (or by [`FailureLog::finish`] for the last one), so a high error rate
+/// yields a steady trickle of examples instead of a flood.
+#[derive(Debug)]
+pub struct FailureLog {
+    limit: u32,
+    window: Duration,
+    state: Mutex<WindowState>,
+}
+
+impl FailureLog {
+    #[must_use]
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            state: Mutex::new(WindowState::default()),
+        }
+    }
+
+    pub fn record(&self, sample: FailureSample<'_>) {
+        let admit = self.admit(Instant::now());
+        if admit.suppressed_before > 0 {
+            tracing::warn!(
+                "wrkr: {} more failures suppressed in the last {:?}",
+                admit.suppressed_before,
+                self.window
+            );
+        }
+        if admit.log {
+            tracing::warn!(
+                scenario = sample.scenario,
+                protocol = sample.protocol.as_str(),
+                "{}",
+                format_failure(&sample)
+            );
+        }
+    }
+
+    /// Reports the failures suppressed in the window that was still open when the run ended.
+    pub fn finish(&self) {
+        let suppressed = self.take_suppressed();
+        if suppressed > 0 {
+            tracing::warn!(
+                "wrkr: {suppressed} more failures suppressed in the last {:?}",
+                self.window
+            );
+        }
+    }
+
+    fn take_suppressed(&self) -> u64 {
+        std::mem::take(&mut self.state().suppressed)
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, WindowState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn admit(&self, now: Instant) -> Admit {
+        let mut state = self.state();
+
+        let mut suppressed_before = 0;
+        let expired = state
+            .started
+            .is_none_or(|started| now.duration_since(started) >= self.window);
+        if expired {
+            suppressed_before = std::mem::take(&mut state.suppressed);
+            state.started = Some(now);
+            state.logged = 0;
+        }
+
+        let log = state.logged < self.limit;
+        if log {
+            state.logged += 1;
+        } else {
+            state.suppressed = state.suppressed.saturating_add(1);
+        }
+
+        Admit {
+            log,
+            suppressed_before,
+        }
+    }
+}
+
+fn format_failure(sample: &FailureSample<'_>) -> String {
+    let mut out = format!(
+        "wrkr: failure scenario={} protocol={} {}",
+        sample.scenario,
+        sample.protocol.as_str(),
+        sample.method
+    );
+    if let Some(target) = sample.target {
+        write!(&mut out, " {target}").ok();
+    }
+    if let Some(status) = sample.status {
+        write!(&mut out, " status={status}").ok();
+    }
+    if let Some(error) = sample.error {
+        write!(&mut out, " error={error:?}").ok();
+    }
+    if !sample.body.is_empty() {
+        let shown = &sample.body[..sample.body.len().min(MAX_BODY_BYTES)];
+        write!(&mut out, " body={:?}", String::from_utf8_lossy(shown)).ok();
+        if shown.len() < sample.body.len() {
+            write!(&mut out, " (+{} bytes)", sample.body.len() - shown.len()).ok();
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admits_up_to_limit_per_window_and_reports_suppressed() {
+        let log = FailureLog::new(2, Duration::from_secs(10));
+        let t0 = Instant::now();
+
+        let logged = (0..5)
+            .filter(|i| log.admit(t0 + Duration::from_millis(*i)).log)
+            .count();
+        assert_eq!(logged, 2);
+
+        let next = log.admit(t0 + Duration::from_secs(10));
+        assert_eq!(
+            next,
+            Admit {
+                log: true,
+                suppressed_before: 3
+            }
+        );
+    }
+
+    #[test]
+    fn suppressed_failures_of_the_last_window_are_left_for_finish() {
+        let log = FailureLog::new(1, Duration::from_secs(10));
+        let t0 = Instant::now();
+        for i in 0..4 {
+            log.admit(t0 + Duration::from_millis(i));
+        }
+
+        assert_eq!(log.take_suppressed(), 3);
+        assert_eq!(log.take_suppressed(), 0);
+    }
+
+    #[test]
+    fn format_truncates_long_bodies() {
+        let body = vec![b'x'; MAX_BODY_BYTES + 10];
+        let line = format_failure(&FailureSample {
+            scenario: "main",
+            protocol: Protocol::Http,
+            method: "POST",
+            target: Some("http://localhost/orders"),
+            status: Some(500),
+            error: None,
+            body: &body,
+        });
+
+        assert!(line.starts_with(
+            "wrkr: failure scenario=main protocol=http POST http://localhost/orders status=500 body=\""
+        ));
+        assert!(line.ends_with("\" (+10 bytes)"), "{line}");
+    }
+}
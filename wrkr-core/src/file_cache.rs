@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use bytes::Bytes;
+
+/// Run-wide cache of file contents, shared by all VUs.
+///
+/// Each file is read from disk once; later lookups return a cheap `Bytes` clone of the same
+/// buffer, so large request payloads are not duplicated per VU.
+#[derive(Debug, Default)]
+pub struct FileCache {
+    files: Mutex<HashMap<PathBuf, Bytes>>,
+}
+
+impl FileCache {
+    pub async fn get_or_load(&self, path: &Path) -> std::io::Result<Bytes> {
+        if let Some(bytes) = self.files().get(path) {
+            return Ok(bytes.clone());
+        }
+
+        // Read without holding the lock so one slow read doesn't stall every VU. VUs that miss
+        // the same file at once may each read it; the first copy stored is the one kept.
+        let bytes = Bytes::from(tokio::fs::read(path).await?);
+        Ok(self
+            .files()
+            .entry(path.to_path_buf())
+            .or_insert(bytes)
+            .clone())
+    }
+
+    fn files(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, Bytes>> {
+        self.files
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn files_are_read_once_and_shared() {
+        let dir = tempfile::tempdir().unwrap_or_else(|e| panic!("tempdir: {e}"));
+        let path = dir.path().join("payload.bin");
+        std::fs::write(&path, b"first").unwrap_or_else(|e| panic!("write: {e}"));
+
+        let cache = FileCache::default();
+        let a = cache
+            .get_or_load(&path)
+            .await
+            .unwrap_or_else(|e| panic!("load: {e}"));
+        std::fs::write(&path, b"second").unwrap_or_else(|e| panic!("write: {e}"));
+        let b = cache
+            .get_or_load(&path)
+            .await
+            .unwrap_or_else(|e| panic!("load: {e}"));
+
+        assert_eq!(b, Bytes::from_static(b"first"));
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert!(
+            cache
+                .get_or_load(&dir.path().join("missing"))
+                .await
+                .is_err()
+        );
+    }
+}
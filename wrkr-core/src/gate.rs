@@ -34,6 +34,11 @@ pub fn start(&self) {
         self.start_at(Instant::now());
     }
 
+    /// When a duration-bounded gate stops admitting iterations, once it has started.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline.get().copied()
+    }
+
     pub fn next(&self) -> bool {
         // Hot path: avoid timekeeping entirely unless we're in duration mode.
         if self.duration.is_some() {
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use crate::{FailureLog, FailureSample, Protocol, RequestMetricIds, RequestSample};
+
+/// Bytes an HTTP request moved over the wire.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HttpBytes {
+    pub sent: u64,
+    /// Status line and headers of the response.
+    pub head_received: u64,
+    pub body_received: u64,
+}
+
+impl From<&wrkr_http::HttpResponse> for HttpBytes {
+    fn from(res: &wrkr_http::HttpResponse) -> Self {
+        Self {
+            sent: res.bytes_sent,
+            head_received: res.head_bytes(),
+            body_received: res.body_bytes,
+        }
+    }
+}
+
+/// Where the HTTP requests of one scenario are recorded: the request metrics and, when
+/// `--log-failures` is on, the failure log. Shared by the script HTTP modules and trace replay so
+/// a request counts the same however it was sent.
+#[derive(Clone, Copy)]
+pub struct HttpRecorder<'a> {
+    pub metrics: &'a wrkr_metrics::Registry,
+    pub request_metrics: &'a RequestMetricIds,
+    pub failures: Option<&'a FailureLog>,
+    pub scenario: &'a str,
+}
+
+impl HttpRecorder<'_> {
+    /// Record one request. `error_kind` is set when it failed on the transport; a response with
+    /// an error status still counts as a completed request.
+    pub fn record(
+        &self,
+        extra_tags: &[(&str, &str)],
+        latency: Duration,
+        bytes: HttpBytes,
+        connection_reused: Option<bool>,
+        error_kind: Option<&str>,
+    ) {
+        self.request_metrics.record_request(
+            self.metrics,
+            RequestSample {
+                scenario: self.scenario,
+                protocol: Protocol::Http,
+                ok: error_kind.is_none(),
+                latency,
+                bytes_received: bytes.head_received.saturating_add(bytes.body_received),
+                bytes_received_body: Some(bytes.body_received),
+                bytes_sent: bytes.sent,
+                error_kind,
+                connection_reused,
+            },
+            extra_tags,
+        );
+    }
+
+    /// Record a request that failed before a response arrived; returns its `error_kind`.
+    pub fn record_transport_error(
+        &self,
+        extra_tags: &[(&str, &str)],
+        latency: Duration,
+        err: &wrkr_http::Error,
+    ) -> String {
+        let kind = err.transport_error_kind().to_string();
+        self.record(
+            extra_tags,
+            latency,
+            HttpBytes::default(),
+            None,
+            Some(kind.as_str()),
+        );
+        kind
+    }
+
+    /// Whether [`Self::log_failure`] writes anything, so callers can skip preparing a sample.
+    pub fn logs_failures(&self) -> bool {
+        self.failures.is_some()
+    }
+
+    /// Offer a failed request (an error status or a transport error) to the failure log.
+    pub fn log_failure(
+        &self,
+        method: &wrkr_http::Method,
+        url: &str,
+        status: Option<u16>,
+        error: Option<&str>,
+        body: &[u8],
+    ) {
+        if let Some(log) = self.failures {
+            log.record(FailureSample {
+                scenario: self.scenario,
+                protocol: Protocol::Http,
+                method: method.as_str(),
+                target: Some(url),
+                status,
+                error,
+                body,
+            });
+        }
+    }
+}
@@ -6,6 +6,10 @@
 pub struct IterationMetricIds {
     pub iterations_total: MetricId,
     pub iteration_duration: MetricId,
+    /// `iteration_duration` minus the time the iteration spent in explicit sleeps.
+    pub iteration_active_duration: MetricId,
+    /// Arrival-rate iterations that were due but had no free VU to run them.
+    pub dropped_iterations: MetricId,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -13,6 +17,8 @@ pub struct IterationSample<'a> {
     pub scenario: &'a str,
     pub success: bool,
     pub duration: std::time::Duration,
+    /// Part of `duration` spent in explicit sleeps (think time).
+    pub slept: std::time::Duration,
 }
 
 impl IterationMetricIds {
@@ -20,6 +26,16 @@ pub fn register(metrics: &Registry) -> Self {
         Self {
             iterations_total: metrics.register("iterations_total", MetricKind::Counter),
             iteration_duration: metrics.register("iteration_duration", MetricKind::Histogram),
+            iteration_active_duration: metrics
+                .register("iteration_active_duration", MetricKind::Histogram),
+            dropped_iterations: metrics.register("dropped_iterations", MetricKind::Counter),
+        }
+    }
+
+    pub fn record_dropped(&self, metrics: &Registry, scenario: &str, dropped: u64) {
+        let tags = metrics.resolve_tags(&[("scenario", scenario)]);
+        if let Some(MetricHandle::Counter(c)) = metrics.get_handle(self.dropped_iterations, tags) {
+            c.fetch_add(dropped, Ordering::Relaxed);
         }
     }
 
@@ -52,10 +68,17 @@ pub fn record_iteration(
             c.fetch_add(1, Ordering::Relaxed);
         }
 
-        if let Some(MetricHandle::Histogram(h)) = metrics.get_handle(self.iteration_duration, tags)
-        {
-            let duration_us: u64 = sample.duration.as_micros().try_into().unwrap_or(u64::MAX);
-            let _ = h.lock().record(duration_us.max(1));
-        }
+        let record = |id: MetricId, tags, duration: std::time::Duration| {
+            if let Some(MetricHandle::Histogram(h)) = metrics.get_handle(id, tags) {
+                let duration_us: u64 = duration.as_micros().try_into().unwrap_or(u64::MAX);
+                let _ = h.lock().record(duration_us.max(1));
+            }
+        };
+        record(self.iteration_duration, tags.clone(), sample.duration);
+        record(
+            self.iteration_active_duration,
+            tags,
+            sample.duration.saturating_sub(sample.slept),
+        );
     }
 }
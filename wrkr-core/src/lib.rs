@@ -1,33 +1,66 @@
+mod abort;
+mod builder;
+mod cardinality;
+mod composite;
 mod config;
+mod duration;
 mod error;
+mod failure_log;
+mod file_cache;
 mod gate;
+#[cfg(feature = "http")]
+mod http_recorder;
 mod iteration_metrics;
 mod metrics_agg;
 mod metrics_context;
 mod outputs;
 mod pacer;
 mod progress;
+#[cfg(feature = "http")]
+mod replay;
 mod request_metrics;
 mod run;
 mod schedule;
 mod summary;
+mod think_time;
 mod thresholds;
 mod thresholds_eval;
 mod vu;
 
+pub use abort::{AbortSignal, ErrorRateBreaker};
+pub use builder::RunBuilder;
+pub use cardinality::CardinalityGuard;
+pub use composite::{
+    SCRIPT_TAG, ScriptSource, attach_script, ensure_unique_scenarios, scope_thresholds,
+};
 pub use config::*;
+pub use duration::parse_duration;
 pub use error::{Error, Result};
+pub use failure_log::{FailureLog, FailureSample};
+pub use file_cache::FileCache;
 pub use gate::IterationGate;
+#[cfg(feature = "http")]
+pub use http_recorder::{HttpBytes, HttpRecorder};
 pub use iteration_metrics::{IterationMetricIds, IterationSample};
 pub use metrics_context::MetricsContext;
 pub use outputs::write_output_files;
 pub use pacer::ArrivalPacer;
-pub use progress::{ProgressFn, ProgressUpdate, ScenarioProgress, StageProgress};
+pub use progress::{
+    LiveMetrics, ProgressFn, ProgressSink, ProgressUpdate, ScenarioProgress, StageProgress,
+    progress_fn,
+};
+#[cfg(feature = "http")]
+pub use replay::{REPLAY_DEFAULT_VUS, Replay, ReplayPacing, Trace, TraceRequest};
 pub use request_metrics::{Protocol, RequestMetricIds, RequestSample};
 pub use run::*;
 pub use schedule::{RampingU64Schedule, StageSnapshot};
 pub use summary::*;
+pub use think_time::{ThinkTime, ThinkTimeSpec};
 pub use thresholds::*;
 pub use thresholds_eval::*;
 pub use vu::*;
-pub use wrkr_metrics::{MetricKind, MetricSeriesSummary, MetricValue};
+#[cfg(feature = "http")]
+pub use wrkr_http::{HttpClient, ProxyConfig, TlsConfig as HttpTlsConfig};
+pub use wrkr_metrics::{
+    HISTOGRAM_SIG_FIGS_RANGE, HistogramSummary, MetricKind, MetricSeriesSummary, MetricValue,
+};
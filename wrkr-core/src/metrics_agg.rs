@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use wrkr_metrics::{KeyId, MetricId, Registry};
 
@@ -10,7 +10,7 @@
 
 pub(crate) type RunningStats = wrkr_metrics::agg::RunningStats;
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct ScenarioSnapshot {
     pub(crate) requests_total: u64,
     pub(crate) bytes_received_total: u64,
@@ -18,6 +18,8 @@ pub(crate) struct ScenarioSnapshot {
     pub(crate) failed_requests_total: u64,
     pub(crate) checks_failed_total: u64,
     pub(crate) iterations_total: u64,
+    /// Cumulative overall request latency, to take the next tick's interval from.
+    pub(crate) latency: Option<wrkr_metrics::HistogramSummary>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -26,7 +28,9 @@ struct TagKeys {
     protocol: KeyId,
     status: KeyId,
     name: KeyId,
+    pass: KeyId,
     fail: KeyId,
+    error_kind: KeyId,
 }
 
 impl TagKeys {
@@ -36,7 +40,9 @@ fn new(metrics: &Registry) -> Self {
             protocol: metrics.resolve_key("protocol"),
             status: metrics.resolve_key("status"),
             name: metrics.resolve_key("name"),
+            pass: metrics.resolve_key("pass"),
             fail: metrics.resolve_key("fail"),
+            error_kind: metrics.resolve_key("error_kind"),
         }
     }
 }
@@ -76,11 +82,60 @@ fn compute_checks_failed(
     (total, by_name)
 }
 
+/// `(passed, total)` check weight of a scenario; a check counts with its `weight` (default 1).
+fn compute_checks_weight(
+    metrics: &Registry,
+    checks_weight: MetricId,
+    keys: TagKeys,
+    scenario_value: KeyId,
+) -> (u64, u64) {
+    let scenario = metrics
+        .query(checks_weight)
+        .where_eq(keys.scenario, scenario_value);
+    let passed = scenario
+        .clone()
+        .where_eq(keys.status, keys.pass)
+        .sum_counter_total();
+    let failed = scenario
+        .where_eq(keys.status, keys.fail)
+        .sum_counter_total();
+    (passed, passed.saturating_add(failed))
+}
+
+/// Failed requests of a scenario per transport error kind (HTTP and gRPC kinds share names such
+/// as `connect` and `timeout`, so they are added up).
+fn compute_errors_by_kind(
+    metrics: &Registry,
+    errors_by_kind: MetricId,
+    keys: TagKeys,
+    scenario_value: KeyId,
+) -> HashMap<String, u64> {
+    let grouped = metrics
+        .query(errors_by_kind)
+        .where_eq(keys.scenario, scenario_value)
+        .group_by([keys.error_kind])
+        .sum_counter();
+
+    let mut by_kind: HashMap<String, u64> = HashMap::new();
+    for (tags, v) in grouped {
+        let Some(kind) = tags
+            .get(keys.error_kind)
+            .and_then(|id| metrics.resolve_key_id(id))
+        else {
+            continue;
+        };
+        let cur = by_kind.entry(kind.to_string()).or_default();
+        *cur = cur.saturating_add(v);
+    }
+    by_kind
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct MetricComputer {
     request_ids: RequestMetricIds,
     iteration_ids: IterationMetricIds,
     checks_metric: MetricId,
+    checks_weight: MetricId,
     keys: TagKeys,
 }
 
@@ -95,16 +150,30 @@ pub(crate) fn new(
             request_ids,
             iteration_ids,
             checks_metric,
+            // Registered in every run, checks or not; script runtimes record into it by name.
+            checks_weight: metrics.register("checks_weight", wrkr_metrics::MetricKind::Counter),
             keys: TagKeys::new(metrics),
         }
     }
 
+    /// Run-wide `(requests_total, failed_requests_total)`.
+    pub(crate) fn request_totals(&self, metrics: &Registry) -> (u64, u64) {
+        let requests = metrics
+            .query(self.request_ids.requests_total)
+            .sum_counter_total();
+        let failed = metrics
+            .query(self.request_ids.errors_total)
+            .sum_counter_total();
+        (requests, failed)
+    }
+
     pub(crate) fn compute_live_metrics(
         &self,
         metrics: &Registry,
         scenario: &str,
         prev: Option<ScenarioSnapshot>,
         dt_secs: f64,
+        percentile: f64,
         rps_stats: &mut RunningStats,
     ) -> (LiveMetrics, ScenarioSnapshot) {
         let keys = self.keys;
@@ -153,10 +222,17 @@ pub(crate) fn compute_live_metrics(
             failed_requests_total,
             checks_failed_total,
             iterations_total,
+            latency: latency.clone(),
         };
 
         let dt = dt_secs.max(1e-9);
         let prev = prev.unwrap_or_default();
+        // Only what completed since the previous tick, like the other `*_now` values.
+        let latency_now = match (&latency, &prev.latency) {
+            (Some(lat), Some(earlier)) => lat.since(earlier),
+            (lat, None) => lat.clone(),
+            (None, Some(_)) => None,
+        };
 
         let req_delta = snapshot.requests_total.saturating_sub(prev.requests_total);
         let bytes_in_delta = snapshot
@@ -171,6 +247,7 @@ pub(crate) fn compute_live_metrics(
 
         let mut latency_mean = 0.0;
         let mut latency_stdev = 0.0;
+        let mut latency_min = 0u64;
         let mut latency_max = 0u64;
         let mut latency_p50 = 0u64;
         let mut latency_p75 = 0u64;
@@ -181,6 +258,7 @@ pub(crate) fn compute_live_metrics(
         if let Some(lat) = latency {
             latency_mean = lat.mean.unwrap_or(0.0);
             latency_stdev = lat.stdev.unwrap_or(0.0);
+            latency_min = lat.min.unwrap_or(0.0) as u64;
             latency_max = lat.max.unwrap_or(0.0) as u64;
 
             latency_p50 = lat.p50.unwrap_or(0.0) as u64;
@@ -192,6 +270,9 @@ pub(crate) fn compute_live_metrics(
                 latency_stdev_pct = (latency_stdev / latency_mean) * 100.0;
             }
         }
+        let latency_selected = latency_now
+            .and_then(|lat| lat.percentile(percentile))
+            .map(|v| (percentile, v as u64));
 
         let live = LiveMetrics {
             rps_now,
@@ -213,12 +294,14 @@ pub(crate) fn compute_live_metrics(
 
             latency_mean,
             latency_stdev,
+            latency_min,
             latency_max,
             latency_p50,
             latency_p75,
             latency_p90,
             latency_p99,
             latency_stdev_pct,
+            latency_selected,
 
             iterations_total: snapshot.iterations_total,
 
@@ -228,6 +311,30 @@ pub(crate) fn compute_live_metrics(
         (live, snapshot)
     }
 
+    /// Sample the gauges `names` for a progress update, see [`LiveMetrics::gauges`].
+    pub(crate) fn sample_gauges(
+        &self,
+        metrics: &Registry,
+        scenario: &str,
+        names: &[String],
+    ) -> Vec<(String, i64)> {
+        let scenario_value = metrics.resolve_key(scenario);
+        names
+            .iter()
+            .filter_map(|name| {
+                let (id, kind) = metrics.lookup_metric(name)?;
+                if kind != wrkr_metrics::MetricKind::Gauge {
+                    return None;
+                }
+                let value = metrics
+                    .query(id)
+                    .where_eq(self.keys.scenario, scenario_value)
+                    .sum_gauge_total()?;
+                Some((name.clone(), value))
+            })
+            .collect()
+    }
+
     pub(crate) fn compute_scenario_summary(
         &self,
         metrics: &Registry,
@@ -261,8 +368,27 @@ pub(crate) fn compute_scenario_summary(
             .where_eq(keys.scenario, scenario_value)
             .sum_counter_total();
 
+        let dropped_iterations_total = metrics
+            .query(self.iteration_ids.dropped_iterations)
+            .where_eq(keys.scenario, scenario_value)
+            .sum_counter_total();
+
+        let (connection_reuse_samples, connection_reused_total, _) = metrics
+            .fold_rate_sum(self.request_ids.connection_reused, |tags| {
+                tags.get(keys.scenario) == Some(scenario_value)
+            });
+
+        let errors_by_kind = compute_errors_by_kind(
+            metrics,
+            self.request_ids.errors_by_kind_total,
+            keys,
+            scenario_value,
+        );
+
         let (checks_failed_total, checks_failed) =
             compute_checks_failed(metrics, self.checks_metric, keys, scenario_value);
+        let (checks_weight_passed, checks_weight_total) =
+            compute_checks_weight(metrics, self.checks_weight, keys, scenario_value);
 
         let latency = metrics
             .query(self.request_ids.latency)
@@ -272,13 +398,22 @@ pub(crate) fn compute_scenario_summary(
 
         ScenarioSummary {
             scenario: scenario.to_string(),
+            // Filled in by the runner, which owns the scenario's wall-clock timeline.
+            started_at: None,
+            ended_at: None,
             requests_total,
             failed_requests_total,
+            errors_by_kind,
             bytes_received_total,
             bytes_sent_total,
             iterations_total,
+            dropped_iterations_total,
+            connection_reuse_samples,
+            connection_reused_total,
             checks_failed_total,
             checks_failed,
+            checks_weight_total,
+            checks_weight_passed,
             latency,
         }
     }
@@ -293,17 +428,373 @@ pub(crate) fn build_run_summary(
     thresholds: &[crate::ThresholdSet],
 ) -> Result<RunSummary> {
     let computer = MetricComputer::new(metrics, request_ids, iteration_ids, checks_metric);
-    let scenarios = scenario_names
+    let scenarios: Vec<ScenarioSummary> = scenario_names
         .iter()
         .map(|name| computer.compute_scenario_summary(metrics, name))
         .collect();
+    let latency =
+        wrkr_metrics::HistogramSummary::merge(scenarios.iter().filter_map(|s| s.latency.as_ref()))?;
 
-    let metrics_summary = metrics.summarize();
-    let threshold_violations = crate::thresholds_eval::evaluate_thresholds(metrics, thresholds)?;
-
-    Ok(RunSummary {
+    let mut summary = RunSummary {
         scenarios,
-        metrics: metrics_summary,
-        threshold_violations,
-    })
+        latency,
+        ..Default::default()
+    };
+    summary.refresh_metrics(metrics, thresholds)?;
+    Ok(summary)
+}
+
+impl RunSummary {
+    /// Re-read the metric series and re-evaluate `thresholds` against them.
+    ///
+    /// For values recorded after the run, by `Teardown` or `HandleSummary`. Scenario totals
+    /// and latency keep their end-of-run values.
+    pub fn refresh_metrics(
+        &mut self,
+        metrics: &Registry,
+        thresholds: &[crate::ThresholdSet],
+    ) -> Result<()> {
+        let threshold_results =
+            crate::thresholds_eval::evaluate_threshold_results(metrics, thresholds)?;
+        self.threshold_violations = threshold_results
+            .iter()
+            .filter(|r| !r.passed)
+            .map(crate::ThresholdResult::to_violation)
+            .collect();
+        self.threshold_results = threshold_results;
+        self.metrics = metrics.summarize();
+        self.pooled_metrics = pool_scenario_histograms(&self.metrics)?;
+        Ok(())
+    }
+}
+
+/// Metric name and its tags without `scenario`, sorted.
+type PoolKey<'a> = (&'a str, Vec<(String, String)>);
+
+/// Merge the histogram series that differ only in their `scenario` tag, for metrics recorded by
+/// more than one scenario.
+fn pool_scenario_histograms(
+    series: &[wrkr_metrics::MetricSeriesSummary],
+) -> Result<Vec<wrkr_metrics::MetricSeriesSummary>> {
+    let mut groups: BTreeMap<PoolKey<'_>, Vec<&wrkr_metrics::HistogramSummary>> = BTreeMap::new();
+    for s in series {
+        let wrkr_metrics::MetricValue::Histogram(h) = &s.values else {
+            continue;
+        };
+        if s.tag("scenario").is_none() {
+            continue;
+        }
+        let mut tags: Vec<(String, String)> = s
+            .tags
+            .iter()
+            .filter(|(k, _)| k != "scenario")
+            .cloned()
+            .collect();
+        tags.sort();
+        groups.entry((s.name.as_str(), tags)).or_default().push(h);
+    }
+
+    let mut pooled = Vec::new();
+    for ((name, tags), histograms) in groups {
+        if histograms.len() < 2 {
+            continue;
+        }
+        if let Some(merged) = wrkr_metrics::HistogramSummary::merge(histograms)? {
+            pooled.push(wrkr_metrics::MetricSeriesSummary {
+                name: name.to_string(),
+                kind: wrkr_metrics::MetricKind::Histogram,
+                tags,
+                values: wrkr_metrics::MetricValue::Histogram(merged),
+            });
+        }
+    }
+    Ok(pooled)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{Protocol, RequestSample};
+
+    fn fixture() -> (
+        Registry,
+        RequestMetricIds,
+        IterationMetricIds,
+        MetricId,
+        MetricComputer,
+    ) {
+        let metrics = Registry::default();
+        let request_ids = RequestMetricIds::register(&metrics);
+        let iteration_ids = IterationMetricIds::register(&metrics);
+        let checks = metrics.register("checks", wrkr_metrics::MetricKind::Counter);
+        let computer = MetricComputer::new(&metrics, request_ids, iteration_ids, checks);
+        (metrics, request_ids, iteration_ids, checks, computer)
+    }
+
+    /// A successful 1ms HTTP request of `scenario`; tests override what they look at.
+    fn sample(scenario: &str) -> RequestSample<'_> {
+        RequestSample {
+            scenario,
+            protocol: Protocol::Http,
+            ok: true,
+            latency: Duration::from_millis(1),
+            bytes_received: 0,
+            bytes_received_body: None,
+            bytes_sent: 0,
+            error_kind: None,
+            connection_reused: None,
+        }
+    }
+
+    #[test]
+    fn live_metrics_report_the_selected_percentile() {
+        let (metrics, request_ids, _, _, computer) = fixture();
+
+        let record = |ms| {
+            let latency = Duration::from_millis(ms);
+            request_ids.record_request(
+                &metrics,
+                RequestSample {
+                    latency,
+                    ..sample("main")
+                },
+                &[],
+            );
+        };
+
+        (1..=100).for_each(record);
+        let mut stats = RunningStats::default();
+        let (live, first) =
+            computer.compute_live_metrics(&metrics, "main", None, 1.0, 95.0, &mut stats);
+        let Some((p, us)) = live.latency_selected else {
+            panic!("expected a selected percentile");
+        };
+        assert_eq!(p, 95.0);
+        assert!((94_000..=96_000).contains(&us), "p95={us}us");
+
+        // The next tick only sees its own requests, not the slow ones before it.
+        (0..100).for_each(|_| record(1));
+        let (live, second) =
+            computer.compute_live_metrics(&metrics, "main", Some(first), 1.0, 95.0, &mut stats);
+        assert_eq!(live.latency_selected, Some((95.0, 1_000)));
+        assert!(live.latency_p90 > 80_000, "p90={}us", live.latency_p90);
+
+        let (quiet, _) =
+            computer.compute_live_metrics(&metrics, "main", Some(second), 1.0, 95.0, &mut stats);
+        assert_eq!(quiet.latency_selected, None);
+
+        let (idle, _) =
+            computer.compute_live_metrics(&metrics, "other", None, 1.0, 95.0, &mut stats);
+        assert_eq!(idle.latency_selected, None);
+    }
+
+    #[test]
+    fn run_summary_latency_pools_all_scenarios() {
+        let (metrics, request_ids, iteration_ids, checks, _) = fixture();
+
+        let record = |scenario: &'static str, ms: u64, n: usize| {
+            let latency = Duration::from_millis(ms);
+            for _ in 0..n {
+                request_ids.record_request(
+                    &metrics,
+                    RequestSample {
+                        latency,
+                        ..sample(scenario)
+                    },
+                    &[],
+                );
+            }
+        };
+        record("fast", 1, 98);
+        record("slow", 500, 2);
+        // A custom trend from both scenarios, and one only `slow` records.
+        let cart = metrics.register("cart_size", wrkr_metrics::MetricKind::Histogram);
+        let retries = metrics.register("retries", wrkr_metrics::MetricKind::Histogram);
+        for (scenario, value) in [("fast", 3), ("slow", 7)] {
+            let tags = metrics.resolve_tags(&[("scenario", scenario), ("shop", "eu")]);
+            if let Some(h) = metrics.get_handle(cart, tags.clone()) {
+                h.observe_histogram(value);
+            }
+        }
+        let slow_only = metrics.resolve_tags(&[("scenario", "slow")]);
+        if let Some(h) = metrics.get_handle(retries, slow_only) {
+            h.observe_histogram(2);
+        }
+
+        let summary = build_run_summary(
+            &metrics,
+            request_ids,
+            iteration_ids,
+            checks,
+            &["fast".to_string(), "slow".to_string()],
+            &[],
+        )
+        .unwrap_or_else(|e| panic!("summary failed: {e}"));
+
+        let pooled = |name: &str, tags: &[(&str, &str)]| {
+            summary.pooled_metrics.iter().find_map(|m| match &m.values {
+                wrkr_metrics::MetricValue::Histogram(h)
+                    if m.name == name && m.has_tags(tags) && m.tags.len() == tags.len() =>
+                {
+                    Some(h.clone())
+                }
+                _ => None,
+            })
+        };
+        let cart = pooled("cart_size", &[("shop", "eu")])
+            .unwrap_or_else(|| panic!("{:?}", summary.pooled_metrics));
+        assert_eq!((cart.count, cart.min, cart.max), (2, Some(3.0), Some(7.0)));
+        assert!(pooled("retries", &[]).is_none());
+        assert_eq!(pooled("request_latency", &[]).map(|h| h.count), Some(100));
+
+        let latency = summary
+            .latency
+            .unwrap_or_else(|| panic!("expected pooled latency"));
+        assert_eq!(latency.count, 100);
+        // 2% of the pooled samples are slow, so p99 lands on them; p90 does not.
+        assert!(latency.p99.is_some_and(|us| us >= 499_000.0), "{latency:?}");
+        assert!(latency.p90.is_some_and(|us| us < 1_100.0), "{latency:?}");
+    }
+
+    #[test]
+    fn refreshed_summary_includes_metrics_recorded_after_the_run() {
+        let (metrics, request_ids, iteration_ids, checks, _) = fixture();
+        let thresholds = [crate::ThresholdSet {
+            metric: "orders_total".to_string(),
+            tags: Vec::new(),
+            expressions: vec!["count>=2".to_string()],
+            description: None,
+            when: Vec::new(),
+            no_data: crate::ThresholdNoData::Fail,
+        }];
+
+        let mut summary = build_run_summary(
+            &metrics,
+            request_ids,
+            iteration_ids,
+            checks,
+            &["main".to_string()],
+            &thresholds,
+        )
+        .unwrap_or_else(|e| panic!("summary failed: {e}"));
+        assert!(summary.series("orders_total").next().is_none());
+        assert!(summary.threshold_violations[0].no_data);
+
+        let kpi = metrics.register("orders_total", wrkr_metrics::MetricKind::Counter);
+        if let Some(wrkr_metrics::MetricHandle::Counter(c)) =
+            metrics.get_handle(kpi, wrkr_metrics::TagSet::from_sorted_iter([]))
+        {
+            c.fetch_add(3, std::sync::atomic::Ordering::Relaxed);
+        }
+        summary
+            .refresh_metrics(&metrics, &thresholds)
+            .unwrap_or_else(|e| panic!("refresh failed: {e}"));
+
+        assert!(summary.series("orders_total").next().is_some());
+        assert!(
+            summary.thresholds_passed(),
+            "{:?}",
+            summary.threshold_violations
+        );
+        assert!(summary.threshold_results[0].passed);
+    }
+
+    #[test]
+    fn scenario_summary_reports_connection_reuse_per_scenario() {
+        let (metrics, request_ids, _, _, computer) = fixture();
+
+        for (scenario, reused) in [
+            ("pooled", Some(false)),
+            ("pooled", Some(true)),
+            ("pooled", Some(true)),
+            ("pooled", Some(true)),
+            ("grpc", None),
+        ] {
+            let request = RequestSample {
+                connection_reused: reused,
+                ..sample(scenario)
+            };
+            request_ids.record_request(&metrics, request, &[]);
+        }
+
+        let pooled = computer.compute_scenario_summary(&metrics, "pooled");
+        assert_eq!(pooled.connection_reuse_samples, 4);
+        assert_eq!(pooled.connection_reused_total, 3);
+        assert_eq!(pooled.connection_reuse_rate(), Some(0.75));
+
+        let grpc = computer.compute_scenario_summary(&metrics, "grpc");
+        assert_eq!(grpc.connection_reuse_rate(), None);
+    }
+
+    #[test]
+    fn scenario_summary_breaks_failed_requests_down_by_error_kind() {
+        let (metrics, request_ids, _, _, computer) = fixture();
+
+        for (protocol, kind) in [
+            (Protocol::Http, Some("timeout")),
+            (Protocol::Http, Some("timeout")),
+            (Protocol::Http, Some("connect")),
+            (Protocol::Grpc, Some("connect")),
+            (Protocol::Http, None),
+        ] {
+            let request = RequestSample {
+                protocol,
+                ok: kind.is_none(),
+                error_kind: kind,
+                ..sample("main")
+            };
+            request_ids.record_request(&metrics, request, &[]);
+        }
+
+        let summary = computer.compute_scenario_summary(&metrics, "main");
+        assert_eq!(summary.failed_requests_total, 4);
+        assert_eq!(summary.errors_by_kind.len(), 2);
+        assert_eq!(summary.errors_by_kind.get("timeout"), Some(&2));
+        assert_eq!(summary.errors_by_kind.get("connect"), Some(&2));
+    }
+
+    #[test]
+    fn scenario_summary_scores_checks_by_weight() {
+        let (metrics, _, _, _, computer) = fixture();
+        let weight = metrics.register("checks_weight", wrkr_metrics::MetricKind::Counter);
+
+        for (name, status, n) in [("status is 200", "pass", 9), ("body matches", "fail", 3)] {
+            let tags =
+                metrics.resolve_tags(&[("scenario", "main"), ("name", name), ("status", status)]);
+            if let Some(wrkr_metrics::MetricHandle::Counter(c)) = metrics.get_handle(weight, tags) {
+                c.fetch_add(n, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+
+        let summary = computer.compute_scenario_summary(&metrics, "main");
+        assert_eq!(summary.checks_weight_passed, 9);
+        assert_eq!(summary.checks_weight_total, 12);
+        assert_eq!(summary.checks_score(), Some(0.75));
+
+        let idle = computer.compute_scenario_summary(&metrics, "idle");
+        assert_eq!(idle.checks_score(), None);
+    }
+
+    #[test]
+    fn sampled_gauges_sum_a_scenarios_series_and_skip_others() {
+        let (metrics, _, _, _, computer) = fixture();
+        let depth = metrics.register("queue_depth", wrkr_metrics::MetricKind::Gauge);
+
+        for (scenario, queue, value) in [("main", "a", 3), ("main", "b", 4), ("other", "a", 50)] {
+            let tags = metrics.resolve_tags(&[("scenario", scenario), ("queue", queue)]);
+            if let Some(wrkr_metrics::MetricHandle::Gauge(g)) = metrics.get_handle(depth, tags) {
+                g.store(value, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+
+        // Unknown names and non-gauge metrics are left out.
+        let names = ["queue_depth", "checks", "missing"].map(String::from);
+        assert_eq!(
+            computer.sample_gauges(&metrics, "main", &names),
+            vec![("queue_depth".to_string(), 7)]
+        );
+        assert!(computer.sample_gauges(&metrics, "idle", &names).is_empty());
+    }
 }
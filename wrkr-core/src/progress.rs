@@ -36,6 +36,7 @@ pub struct LiveMetrics {
     /// Aggregate latency stats (microseconds) across the whole run so far.
     pub latency_mean: f64,
     pub latency_stdev: f64,
+    pub latency_min: u64,
     pub latency_max: u64,
     pub latency_p50: u64,
     pub latency_p75: u64,
@@ -43,6 +44,10 @@ pub struct LiveMetrics {
     pub latency_p99: u64,
     pub latency_stdev_pct: f64,
 
+    /// Latency at the run's configured progress percentile, as `(percentile, microseconds)`,
+    /// over the requests completed since the previous update. `None` when there were none.
+    pub latency_selected: Option<(f64, u64)>,
+
     /// Percentiles 1..=99, values in microseconds.
     pub latency_distribution: Vec<(u8, u64)>,
 
@@ -51,6 +56,10 @@ pub struct LiveMetrics {
     pub latency_p50_now: Option<f64>,
     pub latency_p95_now: Option<f64>,
     pub iterations_total: u64,
+
+    /// Current value of each gauge in [`crate::RunScenariosContext::gauge_series`], summed over
+    /// the scenario's series of it. Gauges without a series in the scenario yet are left out.
+    pub gauges: Vec<(String, i64)>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -96,6 +105,84 @@ pub struct ProgressUpdate {
     pub exec: String,
     pub metrics: LiveMetrics,
     pub progress: ScenarioProgress,
+    /// How far the scenario is, `0.0..=100.0`: elapsed time over its duration, or finished
+    /// iterations over its iteration count, whichever bound it is closer to.
+    pub percent_complete: f64,
+}
+
+/// Completion of a scenario bounded by `duration` and/or `iterations`. With neither, it runs a
+/// single iteration (as [`crate::ScenarioConfig`] without bounds does).
+pub(crate) fn percent_complete(
+    elapsed: Duration,
+    duration: Option<Duration>,
+    iterations: Option<u64>,
+    iterations_done: u64,
+) -> f64 {
+    let by_time = duration.map(|total| {
+        if total.is_zero() {
+            1.0
+        } else {
+            elapsed.as_secs_f64() / total.as_secs_f64()
+        }
+    });
+    let by_iterations = match (iterations, duration) {
+        (Some(total), _) => Some(iterations_done as f64 / total.max(1) as f64),
+        (None, None) => Some(iterations_done.min(1) as f64),
+        (None, Some(_)) => None,
+    };
+    let fraction = by_time
+        .into_iter()
+        .chain(by_iterations)
+        .fold(0.0_f64, f64::max);
+    (fraction * 100.0).clamp(0.0, 100.0)
 }
 
 pub type ProgressFn = std::sync::Arc<dyn Fn(ProgressUpdate) + Send + Sync + 'static>;
+
+/// Receives a [`ProgressUpdate`] per scenario roughly once a second while a run is in flight.
+///
+/// Implemented for every `Fn(ProgressUpdate)` closure; implement it on your own type to collect
+/// live metrics when embedding wrkr (see [`crate::RunBuilder::progress`]). Updates are delivered
+/// from a background task, so `on_progress` should return quickly.
+pub trait ProgressSink: Send + Sync + 'static {
+    fn on_progress(&self, update: ProgressUpdate);
+}
+
+impl<F> ProgressSink for F
+where
+    F: Fn(ProgressUpdate) + Send + Sync + 'static,
+{
+    fn on_progress(&self, update: ProgressUpdate) {
+        self(update);
+    }
+}
+
+/// Adapt a [`ProgressSink`] to the [`ProgressFn`] taken by [`crate::run_scenarios`].
+pub fn progress_fn(sink: impl ProgressSink) -> ProgressFn {
+    std::sync::Arc::new(move |update| sink.on_progress(update))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_complete_follows_the_closer_bound() {
+        let secs = Duration::from_secs;
+        assert_eq!(percent_complete(secs(5), Some(secs(20)), None, 1000), 25.0);
+        assert_eq!(percent_complete(secs(5), None, Some(40), 10), 25.0);
+        // Both bounds: the scenario ends at whichever comes first.
+        assert_eq!(
+            percent_complete(secs(5), Some(secs(20)), Some(20), 10),
+            50.0
+        );
+        assert_eq!(percent_complete(secs(30), Some(secs(20)), None, 0), 100.0);
+        assert_eq!(
+            percent_complete(secs(1), Some(Duration::ZERO), None, 0),
+            100.0
+        );
+        // No bounds: a single iteration.
+        assert_eq!(percent_complete(secs(1), None, None, 0), 0.0);
+        assert_eq!(percent_complete(secs(1), None, None, 1), 100.0);
+    }
+}
@@ -0,0 +1,579 @@
+//! Replaying a recorded request trace (HAR or NDJSON) as load.
+//!
+//! Every iteration of the replay scenario sends the next request of the trace at its scheduled
+//! time, measured from the start of the run. Iterations past the end of the trace start over from
+//! the first request, so a larger iteration count stretches a short capture.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use bytes::Bytes;
+use serde::Deserialize;
+use wrkr_http::{HttpRequest, Method};
+
+use crate::{Error, HttpBytes, HttpRecorder, Result, ScenarioOptions, VuContext};
+
+/// Concurrent senders of a replay scenario unless `--vus` says otherwise.
+pub const REPLAY_DEFAULT_VUS: u64 = 50;
+
+/// Headers of the capture that the client sets itself (or that only make sense on the original
+/// connection), so they are not replayed.
+const SKIPPED_HEADERS: &[&str] = &["host", "content-length", "connection", "transfer-encoding"];
+
+#[derive(Debug, Clone)]
+pub struct TraceRequest {
+    /// When the request was sent, relative to the first request of the trace.
+    pub offset: Duration,
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Bytes,
+}
+
+/// The requests of a capture, ordered by [`TraceRequest::offset`].
+#[derive(Debug, Clone)]
+pub struct Trace {
+    requests: Vec<TraceRequest>,
+}
+
+#[derive(Deserialize)]
+struct Har {
+    log: HarLog,
+}
+
+#[derive(Deserialize)]
+struct HarLog {
+    entries: Vec<HarEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarEntry {
+    started_date_time: String,
+    request: HarRequest,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<HarHeader>,
+    post_data: Option<HarPostData>,
+}
+
+#[derive(Deserialize)]
+struct HarHeader {
+    name: String,
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct HarPostData {
+    #[serde(default)]
+    text: String,
+}
+
+/// One line of an NDJSON trace; unknown fields (e.g. a recorded status) are ignored.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NdjsonRequest {
+    #[serde(default)]
+    offset_ms: f64,
+    #[serde(default = "default_method")]
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default)]
+    body: String,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+impl Trace {
+    /// `log.entries` of an HTTP Archive, timed by each entry's `startedDateTime`.
+    pub fn from_har(text: &str) -> Result<Self> {
+        let har: Har =
+            serde_json::from_str(text).map_err(|e| Error::InvalidTrace(format!("HAR: {e}")))?;
+
+        let mut started = Vec::with_capacity(har.log.entries.len());
+        let mut requests = Vec::with_capacity(har.log.entries.len());
+        for (idx, entry) in har.log.entries.into_iter().enumerate() {
+            let at = parse_timestamp(&entry.started_date_time).ok_or_else(|| {
+                Error::InvalidTrace(format!(
+                    "HAR entry #{}: invalid `startedDateTime` `{}`",
+                    idx + 1,
+                    entry.started_date_time
+                ))
+            })?;
+            let req = entry.request;
+            started.push(at);
+            requests.push(TraceRequest {
+                offset: Duration::ZERO,
+                method: parse_method(&req.method, || format!("HAR entry #{}", idx + 1))?,
+                url: req.url,
+                headers: replayed_headers(req.headers.into_iter().map(|h| (h.name, h.value))),
+                body: req
+                    .post_data
+                    .map(|p| Bytes::from(p.text))
+                    .unwrap_or_default(),
+            });
+        }
+
+        if let Some(first) = started.iter().min().copied() {
+            for (req, at) in requests.iter_mut().zip(started) {
+                req.offset = at.duration_since(first).unwrap_or_default();
+            }
+        }
+        Ok(Self::new(requests))
+    }
+
+    /// One JSON object per line: `{"offsetMs": 12.5, "method": "POST", "url": "...",
+    /// "headers": {"name": "value"}, "body": "..."}`. Only `url` is required; `method` defaults
+    /// to `GET` and `offsetMs` to 0. Blank lines are skipped.
+    pub fn from_ndjson(text: &str) -> Result<Self> {
+        let mut requests = Vec::new();
+        for (idx, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let what = || format!("line {}", idx + 1);
+            let req: NdjsonRequest = serde_json::from_str(line)
+                .map_err(|e| Error::InvalidTrace(format!("{}: {e}", what())))?;
+            if !req.offset_ms.is_finite() || req.offset_ms < 0.0 {
+                return Err(Error::InvalidTrace(format!(
+                    "{}: `offsetMs` must be a non-negative number",
+                    what()
+                )));
+            }
+            let offset = Duration::try_from_secs_f64(req.offset_ms / 1_000.0).map_err(|_| {
+                Error::InvalidTrace(format!("{}: `offsetMs` is out of range", what()))
+            })?;
+            requests.push(TraceRequest {
+                offset,
+                method: parse_method(&req.method, what)?,
+                url: req.url,
+                headers: replayed_headers(req.headers),
+                body: Bytes::from(req.body),
+            });
+        }
+        Ok(Self::new(requests))
+    }
+
+    /// Sorts by offset (keeping the capture order of ties) and makes the first offset zero.
+    fn new(mut requests: Vec<TraceRequest>) -> Self {
+        requests.sort_by_key(|r| r.offset);
+        if let Some(first) = requests.first().map(|r| r.offset) {
+            for req in &mut requests {
+                req.offset -= first;
+            }
+        }
+        Self { requests }
+    }
+
+    pub fn requests(&self) -> &[TraceRequest] {
+        &self.requests
+    }
+
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Recorded time from the first to the last request.
+    pub fn span(&self) -> Duration {
+        self.requests.last().map_or(Duration::ZERO, |r| r.offset)
+    }
+}
+
+fn parse_method(method: &str, what: impl FnOnce() -> String) -> Result<Method> {
+    Method::from_bytes(method.as_bytes())
+        .map_err(|_| Error::InvalidTrace(format!("{}: invalid method `{method}`", what())))
+}
+
+fn replayed_headers(headers: impl IntoIterator<Item = (String, String)>) -> Vec<(String, String)> {
+    headers
+        .into_iter()
+        // HTTP/2 captures list pseudo-headers (`:authority`, `:path`, ...) among the headers.
+        .filter(|(name, _)| {
+            !name.starts_with(':')
+                && !SKIPPED_HEADERS
+                    .iter()
+                    .any(|skipped| name.eq_ignore_ascii_case(skipped))
+        })
+        .collect()
+}
+
+/// RFC 3339 timestamp as written by browsers and proxies (`2024-05-01T10:00:00.123+02:00`).
+/// The colon of the offset is optional.
+fn parse_timestamp(s: &str) -> Option<SystemTime> {
+    let s = s.trim();
+    let time_start = s.find('T')?;
+    let Some(sign_at) = s[time_start..].find(['+', '-']).map(|i| time_start + i) else {
+        return humantime::parse_rfc3339_weak(s).ok();
+    };
+
+    let (local, offset) = s.split_at(sign_at);
+    let local = humantime::parse_rfc3339_weak(local).ok()?;
+    let behind_utc = offset.starts_with('-');
+    // `+02:00`, or `+0200` as some exporters write it.
+    let digits = &offset[1..];
+    let (hours, minutes) = digits
+        .split_once(':')
+        .or_else(|| (digits.len() == 4).then(|| digits.split_at(2)))?;
+    let offset =
+        Duration::from_secs(hours.parse::<u64>().ok()? * 3_600 + minutes.parse::<u64>().ok()? * 60);
+    // Local time is UTC shifted by the offset.
+    if behind_utc {
+        local.checked_add(offset)
+    } else {
+        local.checked_sub(offset)
+    }
+}
+
+/// When the requests of a replay are sent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplayPacing {
+    /// Recorded gaps between requests divided by `speed` (2.0 replays twice as fast).
+    Recorded { speed: f64 },
+    /// Evenly spaced at `per_second` requests per second, ignoring the recorded timing.
+    Rate { per_second: f64 },
+}
+
+impl Default for ReplayPacing {
+    fn default() -> Self {
+        Self::Recorded { speed: 1.0 }
+    }
+}
+
+impl ReplayPacing {
+    /// Time after the start of the run at which the `index`-th request is due. Past the end of
+    /// the trace, [`Self::Recorded`] starts over one average gap after the last request. Saturates
+    /// at `Duration::MAX` for rates and speeds too small to reach the request.
+    pub fn due(&self, trace: &Trace, index: u64) -> Duration {
+        match *self {
+            Self::Rate { per_second } => {
+                Duration::try_from_secs_f64(index as f64 / per_second).unwrap_or(Duration::MAX)
+            }
+            Self::Recorded { speed } => {
+                let len = trace.len() as u64;
+                if len == 0 {
+                    return Duration::ZERO;
+                }
+                let lap = index / len;
+                let offset = trace.requests[(index % len) as usize].offset;
+                let lap_period = if len > 1 {
+                    trace.span().as_secs_f64() * len as f64 / (len - 1) as f64
+                } else {
+                    0.0
+                };
+                Duration::try_from_secs_f64(
+                    (lap as f64 * lap_period + offset.as_secs_f64()) / speed,
+                )
+                .unwrap_or(Duration::MAX)
+            }
+        }
+    }
+}
+
+/// A trace being replayed: hands the requests out to the VUs of the replay scenario in order.
+#[derive(Debug)]
+pub struct Replay {
+    trace: Trace,
+    pacing: ReplayPacing,
+    next: AtomicU64,
+}
+
+impl Replay {
+    pub fn new(trace: Trace, pacing: ReplayPacing) -> Self {
+        Self {
+            trace,
+            pacing,
+            next: AtomicU64::new(0),
+        }
+    }
+
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    /// A constant-VUs scenario that sends every request of the trace once.
+    pub fn scenario_options(&self, name: &str) -> ScenarioOptions {
+        let len = self.trace.len() as u64;
+        ScenarioOptions {
+            vus: Some(len.clamp(1, REPLAY_DEFAULT_VUS)),
+            iterations: Some(len),
+            ..ScenarioOptions::new(name)
+        }
+    }
+
+    /// Start the next run from the first request of the trace.
+    pub fn rewind(&self) {
+        self.next.store(0, Ordering::Relaxed);
+    }
+
+    /// The VU function of the replay scenario. Failed requests are recorded like any other and
+    /// don't stop the VU.
+    pub async fn run_vu(&self, vu: &VuContext) -> Result<()> {
+        vu.run_iterations(|| async {
+            self.send_next(vu).await;
+            Ok::<(), Error>(())
+        })
+        .await
+    }
+
+    async fn send_next(&self, vu: &VuContext) {
+        if self.trace.is_empty() {
+            return;
+        }
+        let index = self.next.fetch_add(1, Ordering::Relaxed);
+        let req = &self.trace.requests[(index % self.trace.len() as u64) as usize];
+
+        let run_started = vu.run_started.get().copied().unwrap_or_else(Instant::now);
+        // A request due beyond what `Instant` can represent only ends with the run.
+        let Some(due) = run_started.checked_add(self.pacing.due(&self.trace, index)) else {
+            vu.abort.wait().await;
+            return;
+        };
+        tokio::select! {
+            () = tokio::time::sleep_until(due.into()) => {}
+            () = vu.abort.wait() => return,
+        }
+
+        let run_ctx = &vu.run_ctx;
+        let request = HttpRequest {
+            method: req.method.clone(),
+            url: req.url.clone(),
+            headers: req.headers.clone(),
+            body: req.body.clone(),
+            timeout: None,
+        };
+        let started = Instant::now();
+        // Responses are never looked at, so their bodies are only counted.
+        let res = run_ctx.client.request_discard_body(request).await;
+        let latency = started.elapsed();
+
+        let recorder = HttpRecorder {
+            metrics: &run_ctx.metrics,
+            request_metrics: &run_ctx.request_metrics,
+            failures: run_ctx.failures.as_deref(),
+            scenario: vu.metrics_ctx.scenario(),
+        };
+        let mut tags =
+            vu.metrics_ctx
+                .scenario_tag_refs(&["scenario", "protocol", "error_kind", "method"]);
+        tags.push(("method", req.method.as_str()));
+
+        match &res {
+            Ok(res) => {
+                recorder.record(
+                    &tags,
+                    latency,
+                    HttpBytes::from(res),
+                    Some(res.connection_reused),
+                    None,
+                );
+                if res.status >= 400 {
+                    recorder.log_failure(&req.method, &req.url, Some(res.status), None, &[]);
+                }
+            }
+            Err(err) => {
+                recorder.record_transport_error(&tags, latency, err);
+                if recorder.logs_failures() {
+                    let error = err.to_string();
+                    recorder.log_failure(&req.method, &req.url, None, Some(&error), &[]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HAR: &str = r#"{"log": {"version": "1.2", "entries": [
+        {"startedDateTime": "2024-05-01T10:00:01.500+02:00", "time": 12,
+         "request": {"method": "POST", "url": "http://api/orders", "httpVersion": "HTTP/2",
+                     "headers": [{"name": ":authority", "value": "api"},
+                                 {"name": "Content-Length", "value": "2"},
+                                 {"name": "content-type", "value": "application/json"}],
+                     "postData": {"mimeType": "application/json", "text": "{}"}}},
+        {"startedDateTime": "2024-05-01T08:00:00.250Z",
+         "request": {"method": "GET", "url": "http://api/orders/1", "headers": []}}
+    ]}}"#;
+
+    fn trace(text: &str, har: bool) -> Trace {
+        let parsed = if har {
+            Trace::from_har(text)
+        } else {
+            Trace::from_ndjson(text)
+        };
+        parsed.unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    #[test]
+    fn har_entries_are_ordered_by_start_time_across_time_zones() {
+        let trace = trace(HAR, true);
+        let reqs = trace.requests();
+        assert_eq!(reqs.len(), 2);
+        assert_eq!(reqs[0].url, "http://api/orders/1");
+        assert_eq!(reqs[0].offset, Duration::ZERO);
+        assert_eq!(reqs[1].method, Method::POST);
+        assert_eq!(reqs[1].offset, Duration::from_millis(1_250));
+        assert_eq!(
+            reqs[1].headers,
+            [("content-type".to_string(), "application/json".to_string())]
+        );
+        assert_eq!(reqs[1].body, Bytes::from_static(b"{}"));
+    }
+
+    #[test]
+    fn timestamp_offsets_may_omit_the_colon() {
+        let with_colon = parse_timestamp("2024-05-01T10:00:00.500+02:00");
+        assert!(with_colon.is_some());
+        assert_eq!(parse_timestamp("2024-05-01T10:00:00.500+0200"), with_colon);
+        assert_eq!(
+            parse_timestamp("2024-05-01T03:30:00.500-0430"),
+            parse_timestamp("2024-05-01T08:00:00.500Z")
+        );
+        assert_eq!(parse_timestamp("2024-05-01T10:00:00+020"), None);
+    }
+
+    #[test]
+    fn ndjson_lines_default_method_and_offset() {
+        let trace = trace(
+            "{\"offsetMs\": 250, \"method\": \"PUT\", \"url\": \"http://api/b\", \"body\": \"x\"}\n\
+             \n\
+             {\"url\": \"http://api/a\", \"headers\": {\"x-trace\": \"1\"}, \"status\": 200}\n",
+            false,
+        );
+        let reqs = trace.requests();
+        assert_eq!(reqs[0].url, "http://api/a");
+        assert_eq!(reqs[0].method, Method::GET);
+        assert_eq!(reqs[0].headers, [("x-trace".to_string(), "1".to_string())]);
+        assert_eq!(reqs[1].offset, Duration::from_millis(250));
+        assert_eq!(trace.span(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn invalid_traces_name_the_offending_line() {
+        let err = Trace::from_ndjson("{\"url\": \"http://a\"}\n{\"method\": \"GET\"}")
+            .err()
+            .map(|e| e.to_string());
+        assert!(
+            err.as_deref().is_some_and(|e| e.contains("line 2")),
+            "{err:?}"
+        );
+
+        let err = Trace::from_ndjson("{\"url\": \"http://a\", \"offsetMs\": -1}")
+            .err()
+            .map(|e| e.to_string());
+        assert!(
+            err.as_deref().is_some_and(|e| e.contains("offsetMs")),
+            "{err:?}"
+        );
+
+        let err = Trace::from_ndjson("{\"url\": \"http://a\", \"offsetMs\": 1e300}")
+            .err()
+            .map(|e| e.to_string());
+        assert!(
+            err.as_deref().is_some_and(|e| e.contains("out of range")),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn pacing_scales_recorded_gaps_or_spaces_requests_evenly() {
+        let trace = trace(
+            "{\"url\": \"http://a\"}\n\
+             {\"url\": \"http://b\", \"offsetMs\": 100}\n\
+             {\"url\": \"http://c\", \"offsetMs\": 400}\n",
+            false,
+        );
+        let ms = |d: Duration| d.as_millis();
+
+        let recorded = ReplayPacing::default();
+        let due: Vec<_> = (0..4).map(|i| ms(recorded.due(&trace, i))).collect();
+        // The second lap starts one average gap (200ms) after the last request.
+        assert_eq!(due, [0, 100, 400, 600]);
+
+        let fast = ReplayPacing::Recorded { speed: 4.0 };
+        assert_eq!(ms(fast.due(&trace, 2)), 100);
+
+        let rate = ReplayPacing::Rate { per_second: 20.0 };
+        let due: Vec<_> = (0..4).map(|i| ms(rate.due(&trace, i))).collect();
+        assert_eq!(due, [0, 50, 100, 150]);
+
+        // Tiny rates and speeds push requests out to the end of time rather than panicking.
+        let crawl = ReplayPacing::Rate { per_second: 1e-300 };
+        assert_eq!(crawl.due(&trace, 1), Duration::MAX);
+        let frozen = ReplayPacing::Recorded { speed: 1e-300 };
+        assert_eq!(frozen.due(&trace, 1), Duration::MAX);
+    }
+
+    #[tokio::test]
+    async fn replay_sends_every_request_of_the_trace() {
+        use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap_or_else(|e| panic!("{e}"));
+        let addr = listener.local_addr().unwrap_or_else(|e| panic!("{e}"));
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0_u8; 4096];
+                    while let Ok(n) = socket.read(&mut buf).await {
+                        if n == 0
+                            || socket
+                                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                                .await
+                                .is_err()
+                        {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        let lines: String = (0..5)
+            .map(|i| {
+                format!(
+                    "{{\"url\": \"http://{addr}/{i}\", \"offsetMs\": {}}}\n",
+                    i * 10
+                )
+            })
+            .collect();
+        let replay =
+            std::sync::Arc::new(Replay::new(trace(&lines, false), ReplayPacing::default()));
+        let opts = crate::ScriptOptions {
+            scenarios: vec![replay.scenario_options("replay")],
+            ..Default::default()
+        };
+        let scenarios = crate::scenarios_from_options(opts, crate::RunConfig::default())
+            .unwrap_or_else(|e| panic!("{e}"));
+
+        let started = Instant::now();
+        let summary = crate::RunBuilder::new(scenarios, move |vu: VuContext| {
+            let replay = replay.clone();
+            async move { replay.run_vu(&vu).await }
+        })
+        .run()
+        .await
+        .unwrap_or_else(|e| panic!("{e}"));
+
+        assert!(started.elapsed() >= Duration::from_millis(40));
+        assert_eq!(summary.scenarios[0].requests_total, 5);
+        assert_eq!(summary.scenarios[0].failed_requests_total, 0);
+    }
+}
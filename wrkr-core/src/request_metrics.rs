@@ -23,11 +23,17 @@ pub fn as_str(self) -> &'static str {
 pub struct RequestMetricIds {
     pub requests_total: MetricId,
     pub bytes_received_total: MetricId,
+    /// Status line and headers part of `bytes_received_total` (HTTP only).
+    pub bytes_received_headers_total: MetricId,
+    /// Body part of `bytes_received_total` (HTTP only).
+    pub bytes_received_body_total: MetricId,
     pub bytes_sent_total: MetricId,
     pub errors_total: MetricId,
     pub errors_by_kind_total: MetricId,
     /// Request latency in microseconds.
     pub latency: MetricId,
+    /// Rate of requests sent on an already open (keep-alive) connection (HTTP only).
+    pub connection_reused: MetricId,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -38,8 +44,12 @@ pub struct RequestSample<'a> {
     pub ok: bool,
     pub latency: std::time::Duration,
     pub bytes_received: u64,
+    /// Body part of `bytes_received`, for protocols whose head and body are counted apart.
+    pub bytes_received_body: Option<u64>,
     pub bytes_sent: u64,
     pub error_kind: Option<&'a str>,
+    /// Whether the request reused a pooled connection, for protocols that can tell.
+    pub connection_reused: Option<bool>,
 }
 
 impl RequestMetricIds {
@@ -47,11 +57,16 @@ pub fn register(metrics: &Registry) -> Self {
         Self {
             requests_total: metrics.register("requests_total", MetricKind::Counter),
             bytes_received_total: metrics.register("bytes_received_total", MetricKind::Counter),
+            bytes_received_headers_total: metrics
+                .register("bytes_received_headers_total", MetricKind::Counter),
+            bytes_received_body_total: metrics
+                .register("bytes_received_body_total", MetricKind::Counter),
             bytes_sent_total: metrics.register("bytes_sent_total", MetricKind::Counter),
             errors_total: metrics.register("request_errors_total", MetricKind::Counter),
             errors_by_kind_total: metrics
                 .register("request_errors_by_kind_total", MetricKind::Counter),
             latency: metrics.register("request_latency", MetricKind::Histogram),
+            connection_reused: metrics.register("connection_reused", MetricKind::Rate),
         }
     }
 
@@ -93,12 +108,32 @@ pub fn record_request(
             c.fetch_add(sample.bytes_received, Ordering::Relaxed);
         }
 
+        if let Some(body) = sample.bytes_received_body {
+            let head = sample.bytes_received.saturating_sub(body);
+            for (metric, bytes) in [
+                (self.bytes_received_headers_total, head),
+                (self.bytes_received_body_total, body),
+            ] {
+                if let Some(MetricHandle::Counter(c)) =
+                    metrics.get_handle(metric, tags_protocol.clone())
+                {
+                    c.fetch_add(bytes, Ordering::Relaxed);
+                }
+            }
+        }
+
         if let Some(MetricHandle::Counter(c)) =
             metrics.get_handle(self.bytes_sent_total, tags_protocol.clone())
         {
             c.fetch_add(sample.bytes_sent, Ordering::Relaxed);
         }
 
+        if let Some(reused) = sample.connection_reused
+            && let Some(handle) = metrics.get_handle(self.connection_reused, tags_protocol.clone())
+        {
+            handle.add_rate(u64::from(reused), 1);
+        }
+
         // Errors (two series: total + by-kind)
         if !sample.ok {
             if let Some(MetricHandle::Counter(c)) =
@@ -136,3 +171,75 @@ pub fn record_request(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counter_sum(metrics: &Registry, id: MetricId) -> u64 {
+        metrics.fold_counter_sum(id, |_| true)
+    }
+
+    #[test]
+    fn received_bytes_are_split_into_headers_and_body_when_known() {
+        let metrics = Registry::default();
+        let ids = RequestMetricIds::register(&metrics);
+        let sample = RequestSample {
+            scenario: "main",
+            protocol: Protocol::Http,
+            ok: true,
+            latency: std::time::Duration::from_millis(1),
+            bytes_received: 150,
+            bytes_received_body: Some(100),
+            bytes_sent: 10,
+            error_kind: None,
+            connection_reused: None,
+        };
+        ids.record_request(&metrics, sample, &[]);
+        // gRPC only counts payload bytes, so it leaves the breakdown alone.
+        ids.record_request(
+            &metrics,
+            RequestSample {
+                protocol: Protocol::Grpc,
+                bytes_received_body: None,
+                ..sample
+            },
+            &[],
+        );
+
+        assert_eq!(counter_sum(&metrics, ids.bytes_received_total), 300);
+        assert_eq!(counter_sum(&metrics, ids.bytes_received_headers_total), 50);
+        assert_eq!(counter_sum(&metrics, ids.bytes_received_body_total), 100);
+    }
+
+    #[test]
+    fn connection_reuse_is_a_rate_over_requests_that_report_it() {
+        let metrics = Registry::default();
+        let ids = RequestMetricIds::register(&metrics);
+        let sample = RequestSample {
+            scenario: "main",
+            protocol: Protocol::Http,
+            ok: true,
+            latency: std::time::Duration::from_millis(1),
+            bytes_received: 0,
+            bytes_received_body: None,
+            bytes_sent: 0,
+            error_kind: None,
+            connection_reused: Some(false),
+        };
+        for reused in [Some(false), Some(true), Some(true), None] {
+            ids.record_request(
+                &metrics,
+                RequestSample {
+                    connection_reused: reused,
+                    ..sample
+                },
+                &[],
+            );
+        }
+
+        let (total, hits, rate) = metrics.fold_rate_sum(ids.connection_reused, |_| true);
+        assert_eq!((total, hits), (3, 2));
+        assert_eq!(rate, Some(2.0 / 3.0));
+    }
+}
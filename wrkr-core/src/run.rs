@@ -1,12 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::OnceLock;
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 
 use crate::RunSummary;
 
+use super::abort::{AbortSignal, ErrorRateWindow};
 use super::config::{
     RunConfig, ScenarioConfig, ScenarioExecutor, ScenarioExecutorKind, ScriptOptions,
 };
@@ -18,7 +19,7 @@
 use super::progress::{ProgressFn, ProgressUpdate, ScenarioProgress, StageProgress};
 use super::request_metrics::RequestMetricIds;
 use super::schedule::RampingU64Schedule;
-use super::vu::{EnvVars, StartSignal, VuContext, VuWork};
+use super::vu::{EnvVars, StartSignal, VuContext, VuInitError, VuWork};
 use tokio::sync::Barrier;
 use tokio::time::MissedTickBehavior;
 #[cfg(feature = "grpc")]
@@ -27,15 +28,58 @@
 use wrkr_http::HttpClient;
 use wrkr_shared::store::SharedStore;
 
+/// Run-level tags: `--tag` values override `Options.tags` with the same key.
+fn run_tags(script: Vec<(String, String)>, cli: Vec<(String, String)>) -> Vec<(String, String)> {
+    let mut out: Vec<(String, String)> = Vec::with_capacity(script.len() + cli.len());
+    for (k, v) in script.into_iter().chain(cli) {
+        match out.iter_mut().find(|(ek, _)| *ek == k) {
+            Some(existing) => existing.1 = v,
+            None => out.push((k, v)),
+        }
+    }
+    out
+}
+
+/// Scenario tags plus any run-level tag the scenario does not set itself.
+fn with_run_tags(
+    mut tags: Vec<(String, String)>,
+    run_tags: &[(String, String)],
+) -> Arc<[(String, String)]> {
+    for (k, v) in run_tags {
+        if !tags.iter().any(|(ek, _)| ek == k) {
+            tags.push((k.clone(), v.clone()));
+        }
+    }
+    Arc::from(tags)
+}
+
 pub fn scenarios_from_options(opts: ScriptOptions, cfg: RunConfig) -> Result<Vec<ScenarioConfig>> {
+    let run_tags = run_tags(opts.tags, cfg.tags);
     let cli_overrides_set = cfg.vus.is_some() || cfg.iterations.is_some() || cfg.duration.is_some();
+    let warmup_iterations = cfg
+        .warmup_iterations
+        .or(opts.warmup_iterations)
+        .unwrap_or(0);
 
     // If `Options.scenarios` exists, it wins. Otherwise we fall back to top-level options.
     if !opts.scenarios.is_empty() {
         let mut out = Vec::with_capacity(opts.scenarios.len());
+        let mut seen_names: HashSet<&str> = HashSet::with_capacity(opts.scenarios.len());
+        for s in &opts.scenarios {
+            // Metrics, progress and summaries are keyed by scenario name.
+            if !seen_names.insert(s.name.as_str()) {
+                return Err(Error::DuplicateScenario(s.name.clone()));
+            }
+        }
+
         for s in opts.scenarios {
             let exec = s.exec.unwrap_or_else(|| "Default".to_string());
-            let metrics_ctx = MetricsContext::new(Arc::<str>::from(s.name), Arc::from(s.tags));
+            let description = s.description;
+            let params = s.params;
+            let think_time = s.think_time;
+            let isolate_connections = s.isolate_connections;
+            let metrics_ctx =
+                MetricsContext::new(Arc::<str>::from(s.name), with_run_tags(s.tags, &run_tags));
             let executor_name = s.executor.as_deref().unwrap_or("constant-vus");
             let executor_kind: ScenarioExecutorKind =
                 executor_name.parse().map_err(|_| Error::InvalidExecutor)?;
@@ -55,13 +99,22 @@ pub fn scenarios_from_options(opts: ScriptOptions, cfg: RunConfig) -> Result<Vec
                 }
 
                 let duration = cfg.duration.or(s.duration).or(opts.duration);
+                if duration == Some(std::time::Duration::ZERO) {
+                    return Err(Error::InvalidScenarioDuration);
+                }
 
                 out.push(ScenarioConfig {
                     exec,
                     metrics_ctx,
+                    description,
                     executor: ScenarioExecutor::ConstantVus { vus },
                     iterations,
                     duration,
+                    warmup_iterations,
+                    script: None,
+                    params,
+                    think_time,
+                    isolate_connections,
                 });
                 continue;
             }
@@ -79,13 +132,22 @@ pub fn scenarios_from_options(opts: ScriptOptions, cfg: RunConfig) -> Result<Vec
                     }
 
                     let duration = cfg.duration.or(s.duration).or(opts.duration);
+                    if duration == Some(std::time::Duration::ZERO) {
+                        return Err(Error::InvalidScenarioDuration);
+                    }
 
                     out.push(ScenarioConfig {
                         exec,
                         metrics_ctx,
+                        description,
                         executor: ScenarioExecutor::ConstantVus { vus },
                         iterations,
                         duration,
+                        warmup_iterations,
+                        script: None,
+                        params,
+                        think_time,
+                        isolate_connections,
                     });
                 }
                 ScenarioExecutorKind::RampingVus => {
@@ -114,18 +176,27 @@ pub fn scenarios_from_options(opts: ScriptOptions, cfg: RunConfig) -> Result<Vec
                     out.push(ScenarioConfig {
                         exec,
                         metrics_ctx,
+                        description,
                         executor: ScenarioExecutor::RampingVus {
                             start_vus,
                             stages: s.stages,
                         },
                         iterations: None,
                         duration: Some(total_duration),
+                        warmup_iterations,
+                        script: None,
+                        params,
+                        think_time,
+                        isolate_connections,
                     });
                 }
                 ScenarioExecutorKind::RampingArrivalRate => {
                     if s.iterations.is_some() || opts.iterations.is_some() {
                         return Err(Error::InvalidIterations);
                     }
+                    if think_time.is_some() {
+                        return Err(Error::ThinkTimeWithArrivalRate);
+                    }
                     if s.stages.is_empty() {
                         return Err(Error::InvalidStages);
                     }
@@ -157,6 +228,7 @@ pub fn scenarios_from_options(opts: ScriptOptions, cfg: RunConfig) -> Result<Vec
                     out.push(ScenarioConfig {
                         exec,
                         metrics_ctx,
+                        description,
                         executor: ScenarioExecutor::RampingArrivalRate {
                             start_rate,
                             time_unit,
@@ -166,6 +238,11 @@ pub fn scenarios_from_options(opts: ScriptOptions, cfg: RunConfig) -> Result<Vec
                         },
                         iterations: None,
                         duration: Some(total_duration),
+                        warmup_iterations,
+                        script: None,
+                        params,
+                        think_time,
+                        isolate_connections,
                     });
                 }
             }
@@ -191,13 +268,22 @@ pub fn scenarios_from_options(opts: ScriptOptions, cfg: RunConfig) -> Result<Vec
     }
 
     let duration = cfg.duration.or(opts.duration);
+    if duration == Some(std::time::Duration::ZERO) {
+        return Err(Error::InvalidScenarioDuration);
+    }
 
     Ok(vec![ScenarioConfig {
         exec: "Default".to_string(),
-        metrics_ctx: MetricsContext::new(Arc::from("Default"), Arc::<[(String, String)]>::from([])),
+        metrics_ctx: MetricsContext::new(Arc::from("Default"), Arc::from(run_tags)),
+        description: None,
         executor: ScenarioExecutor::ConstantVus { vus },
         iterations,
         duration,
+        warmup_iterations,
+        script: None,
+        params: None,
+        think_time: None,
+        isolate_connections: false,
     }])
 }
 
@@ -207,15 +293,57 @@ pub struct RunScenariosContext {
     pub script: String,
     pub script_path: PathBuf,
     pub shared: Arc<SharedStore>,
+    /// Files loaded by scripts at runtime (e.g. request bodies), read once per run.
+    pub files: Arc<crate::FileCache>,
     pub metrics: Arc<wrkr_metrics::Registry>,
     pub request_metrics: RequestMetricIds,
     pub iteration_metrics: IterationMetricIds,
     pub checks_metric: wrkr_metrics::MetricId,
+    /// Bounds the number of distinct check names computed at runtime (shared across VUs).
+    pub check_names: Arc<crate::CardinalityGuard>,
+    /// Bounds the number of distinct request names set by scripts (`opts.name` or
+    /// `opts.tags.name`, HTTP and gRPC, shared across VUs).
+    pub request_names: Arc<crate::CardinalityGuard>,
+    /// Bounds the number of distinct URL-template names from `http.auto_name`, separately from
+    /// [`Self::request_names`] so explicit names cannot crowd them out.
+    pub url_names: Arc<crate::CardinalityGuard>,
     pub thresholds: Arc<[crate::ThresholdSet]>,
+    /// Sampled stderr log of failed requests (disabled unless configured).
+    pub failures: Option<Arc<crate::FailureLog>>,
+    /// Stop the run early when the request error rate stays too high (disabled unless configured).
+    pub abort_on_error_rate: Option<crate::ErrorRateBreaker>,
+    /// Hard cap on the measured run: once it elapses the run is aborted, and VUs still busy
+    /// after [`MAX_DURATION_GRACE`] (e.g. stuck on a hung server) are cancelled.
+    pub max_duration: Option<std::time::Duration>,
+    /// Read HTTP response bodies only to count their bytes; scripts see an empty `res.body`.
+    pub discard_response_bodies: bool,
+    /// Give each VU a connection pool of its own and replace it after every iteration, so no
+    /// HTTP connection outlives the iteration that opened it.
+    pub no_vu_connection_reuse: bool,
+    /// Give each scenario an HTTP connection pool of its own, shared by its VUs only, so a busy
+    /// scenario cannot hold the connections another one is waiting for.
+    pub isolate_scenario_connections: bool,
+    /// Scope of the client-side HTTP response cache; `None` sends every request.
+    pub http_cache: Option<crate::HttpCacheScope>,
+    /// Accept any TLS server certificate on gRPC connections (`--insecure`); the HTTP `client`
+    /// gets the same setting through its `HttpTlsConfig`.
+    pub insecure_skip_verify: bool,
+    /// Turn TLS session resumption off on gRPC connections (`--no-tls-resumption`); the HTTP
+    /// `client` gets the same setting through its `HttpTlsConfig`.
+    pub disable_tls_resumption: bool,
+    /// Latency percentile (`0.0..=100.0`) reported in progress updates as
+    /// `LiveMetrics::latency_selected`.
+    pub progress_percentile: f64,
+    /// Gauges (by metric name) sampled into every progress update as [`crate::LiveMetrics::gauges`],
+    /// so their values over time can be streamed instead of only the final one.
+    pub gauge_series: Arc<[String]>,
     #[cfg(feature = "grpc")]
     pub grpc: Arc<SharedGrpcRegistry>,
     #[cfg(feature = "http")]
     pub client: Arc<HttpClient>,
+    /// The cache every VU uses with [`crate::HttpCacheScope::Shared`].
+    #[cfg(feature = "http")]
+    pub shared_http_cache: Arc<wrkr_http::HttpCache>,
 }
 
 impl RunScenariosContext {
@@ -229,15 +357,99 @@ pub fn new(env: EnvVars, script: String, script_path: PathBuf) -> Self {
             script,
             script_path,
             shared: Arc::new(SharedStore::default()),
+            files: Arc::new(crate::FileCache::default()),
             metrics,
             request_metrics,
             iteration_metrics,
             checks_metric,
+            check_names: Arc::new(crate::CardinalityGuard::default()),
+            request_names: Arc::new(crate::CardinalityGuard::default()),
+            url_names: Arc::new(crate::CardinalityGuard::default()),
             thresholds: Arc::from([]),
+            failures: None,
+            abort_on_error_rate: None,
+            max_duration: None,
+            discard_response_bodies: false,
+            no_vu_connection_reuse: false,
+            isolate_scenario_connections: false,
+            http_cache: None,
+            insecure_skip_verify: false,
+            disable_tls_resumption: false,
+            progress_percentile: 99.0,
+            gauge_series: Arc::from([]),
             #[cfg(feature = "grpc")]
             grpc: Arc::new(SharedGrpcRegistry::default()),
             #[cfg(feature = "http")]
             client: Arc::new(HttpClient::default()),
+            #[cfg(feature = "http")]
+            shared_http_cache: Arc::new(wrkr_http::HttpCache::shared()),
+        }
+    }
+
+    /// A context sharing this run's state whose HTTP client has a connection pool of its own.
+    #[must_use]
+    pub fn with_own_connection_pool(&self) -> Self {
+        Self {
+            #[cfg(feature = "http")]
+            client: Arc::new(self.client.with_new_pool()),
+            ..self.clone()
+        }
+    }
+}
+
+/// How long VUs get to finish their current iteration once `max_duration` has elapsed.
+pub const MAX_DURATION_GRACE: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Join every task, popping each one once it has finished so the rest can still be cancelled if
+/// this future is dropped part-way.
+async fn join_tasks(handles: &mut Vec<tokio::task::JoinHandle<Result<()>>>) -> Result<()> {
+    while let Some(h) = handles.last_mut() {
+        h.await??;
+        handles.pop();
+    }
+    Ok(())
+}
+
+async fn abort_on_init_error(
+    init_error: &Mutex<Option<VuInitError>>,
+    handles: &mut Vec<tokio::task::JoinHandle<Result<()>>>,
+) -> Result<()> {
+    let init_err = init_error
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+
+    let Some(err) = init_err else {
+        return Ok(());
+    };
+
+    for h in handles.iter() {
+        h.abort();
+    }
+
+    // Ensure tasks have a chance to observe the abort before we return.
+    for h in handles.drain(..) {
+        let _ = h.await;
+    }
+
+    Err(Error::VuInit(err))
+}
+
+/// Warn about `--gauge-series` names that are not gauges of this run; progress lines leave them
+/// out, which would otherwise go unnoticed.
+fn warn_unsampled_gauges(metrics: &wrkr_metrics::Registry, names: &[String]) {
+    for name in names {
+        match metrics.lookup_metric(name) {
+            Some((_, wrkr_metrics::MetricKind::Gauge)) => {}
+            Some((_, kind)) => tracing::warn!(
+                metric = %name,
+                %kind,
+                "--gauge-series names a metric that is not a gauge; it was not sampled"
+            ),
+            None => tracing::warn!(
+                metric = %name,
+                "--gauge-series names a metric the run never registered; it was not sampled"
+            ),
         }
     }
 }
@@ -254,6 +466,11 @@ pub async fn run_scenarios<F, Fut, E>(
     E: std::error::Error + Send + Sync + 'static,
 {
     let run_ctx = Arc::new(ctx);
+    // What `Setup` recorded before the run must survive dropping the warm-up's metrics.
+    let before_warmup = scenarios
+        .iter()
+        .any(|s| s.warmup_iterations > 0)
+        .then(|| run_ctx.metrics.snapshot());
 
     let scenario_max_vus = |s: &ScenarioConfig| -> u64 {
         match &s.executor {
@@ -270,24 +487,63 @@ pub async fn run_scenarios<F, Fut, E>(
         .iter()
         .map(|s| scenario_max_vus(s).min(usize::MAX as u64) as usize)
         .sum();
-    let init_error: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+    tracing::info!(scenarios = scenarios.len(), vus = total_vus, "starting run");
+    let init_error: Arc<Mutex<Option<VuInitError>>> = Arc::new(Mutex::new(None));
     let ready_barrier: Arc<Barrier> = Arc::new(Barrier::new(total_vus.saturating_add(1)));
+    let warmup_barrier: Arc<Barrier> = Arc::new(Barrier::new(total_vus.saturating_add(1)));
     let start_signal: Arc<StartSignal> = Arc::new(StartSignal::new());
+    let abort: Arc<AbortSignal> = Arc::new(AbortSignal::default());
     let run_started: Arc<OnceLock<Instant>> = Arc::new(OnceLock::new());
 
     let mut scenario_gates: Vec<Arc<IterationGate>> = Vec::new();
-    let mut pacers: Vec<(
-        Arc<ArrivalPacer>,
-        Arc<RampingU64Schedule>,
-        std::time::Duration,
-        std::time::Duration,
-    )> = Vec::new();
+    struct PacerTask {
+        scenario: String,
+        pacer: Arc<ArrivalPacer>,
+        schedule: Arc<RampingU64Schedule>,
+        time_unit: std::time::Duration,
+        total_duration: std::time::Duration,
+    }
+
+    let mut pacers: Vec<PacerTask> = Vec::new();
+
+    /// Wall-clock time the last VU of a scenario finished.
+    #[derive(Default)]
+    struct ScenarioEnd(Mutex<Option<SystemTime>>);
+
+    impl ScenarioEnd {
+        fn mark(&self) {
+            let now = SystemTime::now();
+            let mut end = self
+                .0
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            *end = Some(end.map_or(now, |prev| prev.max(now)));
+        }
+
+        fn get(&self) -> Option<SystemTime> {
+            *self
+                .0
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+        }
+    }
+
+    struct ScenarioEndGuard(Arc<ScenarioEnd>);
+
+    impl Drop for ScenarioEndGuard {
+        fn drop(&mut self) {
+            self.0.mark();
+        }
+    }
+
+    let mut scenario_ends: HashMap<String, Arc<ScenarioEnd>> = HashMap::new();
 
     #[derive(Clone)]
     enum ScenarioProgressInfo {
         ConstantVus {
             vus: u64,
             duration: Option<std::time::Duration>,
+            iterations: Option<u64>,
         },
         RampingVus {
             schedule: Arc<RampingU64Schedule>,
@@ -323,6 +579,10 @@ struct ProgressScenario {
         if !scenario_names.iter().any(|s| s == &scenario_name_string) {
             scenario_names.push(scenario_name_string.clone());
         }
+        let scenario_end = scenario_ends
+            .entry(scenario_name_string.clone())
+            .or_default()
+            .clone();
 
         let work = match &scenario.executor {
             ScenarioExecutor::ConstantVus { vus } => {
@@ -336,6 +596,7 @@ struct ProgressScenario {
                         progress: ScenarioProgressInfo::ConstantVus {
                             vus: *vus,
                             duration: scenario.duration,
+                            iterations: scenario.iterations,
                         },
                     });
                 }
@@ -380,12 +641,13 @@ struct ProgressScenario {
                     });
                 }
 
-                pacers.push((
-                    pacer.clone(),
-                    schedule.clone(),
-                    *time_unit,
-                    schedule.total_duration(),
-                ));
+                pacers.push(PacerTask {
+                    scenario: scenario_name_string.clone(),
+                    pacer: pacer.clone(),
+                    schedule: schedule.clone(),
+                    time_unit: *time_unit,
+                    total_duration: schedule.total_duration(),
+                });
                 VuWork::RampingArrivalRate {
                     schedule,
                     time_unit: *time_unit,
@@ -394,6 +656,13 @@ struct ProgressScenario {
             }
         };
 
+        let scenario_run_ctx =
+            if run_ctx.isolate_scenario_connections || scenario.isolate_connections {
+                Arc::new(run_ctx.with_own_connection_pool())
+            } else {
+                run_ctx.clone()
+            };
+
         for scenario_vu in 1..=scenario_vus_max {
             let vu_id = next_vu_id;
             next_vu_id = next_vu_id.saturating_add(1);
@@ -403,19 +672,32 @@ struct ProgressScenario {
                 metrics_ctx: scenario.metrics_ctx.clone(),
                 scenario_vu,
                 exec: scenario.exec.clone(),
+                script: scenario.script.clone(),
+                params: scenario.params.clone(),
+                think_time: scenario.think_time,
                 work: work.clone(),
-                run_ctx: run_ctx.clone(),
+                run_ctx: scenario_run_ctx.clone(),
 
                 run_started: run_started.clone(),
 
                 init_error: init_error.clone(),
                 ready_barrier: ready_barrier.clone(),
+                warmup_iterations: scenario.warmup_iterations,
+                warmup_barrier: warmup_barrier.clone(),
                 start_signal: start_signal.clone(),
+                abort: abort.clone(),
             };
 
             let vu = vu.clone();
+            let scenario_end = scenario_end.clone();
             handles.push(tokio::spawn(async move {
-                vu(ctx).await.map_err(|err| Error::Vu(err.to_string()))
+                // Dropped last, so the end is also recorded for VUs cancelled by `max_duration`.
+                let _end = ScenarioEndGuard(scenario_end);
+                let scenario = ctx.metrics_ctx.scenario().to_string();
+                vu(ctx).await.map_err(|err| {
+                    tracing::error!(vu = vu_id, %scenario, error = %err, "VU stopped");
+                    Error::Vu(err.to_string())
+                })
             }));
         }
     }
@@ -423,26 +705,20 @@ struct ProgressScenario {
     // Block until all VUs have created their Lua state and loaded the script.
     // This keeps initialization out of the measured runtime and avoids per-VU start skew.
     ready_barrier.wait().await;
-
-    let init_err = init_error
-        .lock()
-        .unwrap_or_else(|poisoned| poisoned.into_inner())
-        .clone();
-
-    if let Some(err) = init_err {
-        for h in &handles {
-            h.abort();
-        }
-
-        // Ensure tasks have a chance to observe the abort before we return.
-        for h in handles {
-            let _ = h.await;
-        }
-
-        return Err(Error::Vu(err));
+    abort_on_init_error(&init_error, &mut handles).await?;
+    tracing::debug!("all VUs initialized");
+
+    // Warm-up: VUs run their throwaway iterations (establishing connections along the way),
+    // then everything they recorded is dropped so cold-start latency stays out of the results.
+    warmup_barrier.wait().await;
+    abort_on_init_error(&init_error, &mut handles).await?;
+    if let Some(before_warmup) = &before_warmup {
+        run_ctx.metrics.reset_to(before_warmup);
     }
+    tracing::debug!("warm-up finished, starting measurement");
 
     let started = Instant::now();
+    let started_at = SystemTime::now();
     let _ = run_started.set(started);
     for gate in scenario_gates {
         gate.start_at(started);
@@ -456,6 +732,8 @@ struct ProgressScenario {
         let request_ids = run_ctx.request_metrics;
         let iteration_ids = run_ctx.iteration_metrics;
         let checks_metric = run_ctx.checks_metric;
+        let percentile = run_ctx.progress_percentile;
+        let gauge_series = run_ctx.gauge_series.clone();
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
             interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
@@ -497,14 +775,17 @@ struct ScenarioLiveState {
                 for s in &scenarios {
                     let st = state_by_scenario.entry(s.name.clone()).or_default();
 
-                    let prev = st.has_prev.then_some(st.prev);
+                    let prev = st.has_prev.then(|| st.prev.clone());
                     let (mut metrics_live, snapshot) = computer.compute_live_metrics(
                         &metrics,
                         s.name.as_str(),
                         prev,
                         dt_secs,
+                        percentile,
                         &mut st.rps_stats,
                     );
+                    metrics_live.gauges =
+                        computer.sample_gauges(&metrics, s.name.as_str(), &gauge_series);
 
                     // Make `req_per_sec_avg` a true average across the run so far.
                     // This avoids sensitivity to sampling jitter and matches total/elapsed.
@@ -519,8 +800,30 @@ struct ScenarioLiveState {
                     st.prev = snapshot;
                     st.has_prev = true;
 
+                    let percent_complete = match &s.progress {
+                        ScenarioProgressInfo::ConstantVus {
+                            duration,
+                            iterations,
+                            ..
+                        } => super::progress::percent_complete(
+                            elapsed,
+                            *duration,
+                            *iterations,
+                            metrics_live.iterations_total,
+                        ),
+                        ScenarioProgressInfo::RampingVus { schedule }
+                        | ScenarioProgressInfo::RampingArrivalRate { schedule, .. } => {
+                            super::progress::percent_complete(
+                                elapsed,
+                                Some(schedule.total_duration()),
+                                None,
+                                metrics_live.iterations_total,
+                            )
+                        }
+                    };
+
                     let progress_val = match &s.progress {
-                        ScenarioProgressInfo::ConstantVus { vus, duration } => {
+                        ScenarioProgressInfo::ConstantVus { vus, duration, .. } => {
                             ScenarioProgress::ConstantVus {
                                 vus: *vus,
                                 duration: *duration,
@@ -578,15 +881,61 @@ struct ScenarioLiveState {
                         exec: s.exec.clone(),
                         metrics: metrics_live,
                         progress: progress_val,
+                        percent_complete,
                     });
                 }
             }
         })
     });
 
+    let breaker_handle = run_ctx.abort_on_error_rate.map(|breaker| {
+        let metrics = run_ctx.metrics.clone();
+        let computer = super::metrics_agg::MetricComputer::new(
+            &metrics,
+            run_ctx.request_metrics,
+            run_ctx.iteration_metrics,
+            run_ctx.checks_metric,
+        );
+        let abort = abort.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            // The first tick is immediate and records the zero baseline.
+            let mut window = ErrorRateWindow::new(breaker.window);
+            loop {
+                interval.tick().await;
+
+                let (requests, failed) = computer.request_totals(&metrics);
+                let Some(rate) = window.observe(Instant::now(), requests, failed) else {
+                    continue;
+                };
+                if rate > breaker.max_rate {
+                    abort.abort(format!(
+                        "error rate {:.1}% over the last {:?} exceeded --abort-on-error-rate {:.1}%",
+                        rate * 100.0,
+                        breaker.window,
+                        breaker.max_rate * 100.0
+                    ));
+                    break;
+                }
+            }
+        })
+    });
+
     // Start any arrival-rate pacers after we start the VUs (so we don't build up backlog
     // while VUs are still waiting on the start signal).
-    for (pacer, schedule, time_unit, total_duration) in pacers {
+    for PacerTask {
+        scenario: scenario_name,
+        pacer,
+        schedule,
+        time_unit,
+        total_duration,
+    } in pacers
+    {
+        let metrics = run_ctx.metrics.clone();
+        let iteration_ids = run_ctx.iteration_metrics;
+        let abort = abort.clone();
         handles.push(tokio::spawn(async move {
             let tick = std::time::Duration::from_millis(10);
             let mut interval = tokio::time::interval(tick);
@@ -599,7 +948,7 @@ struct ScenarioLiveState {
                 interval.tick().await;
 
                 let elapsed = started.elapsed();
-                if elapsed >= total_duration {
+                if elapsed >= total_duration || abort.is_aborted() {
                     break;
                 }
 
@@ -617,6 +966,7 @@ struct ScenarioLiveState {
                 let delta = dropped.saturating_sub(last_dropped);
                 if delta != 0 {
                     last_dropped = dropped;
+                    iteration_ids.record_dropped(&metrics, &scenario_name, delta);
                 }
             }
 
@@ -625,16 +975,43 @@ struct ScenarioLiveState {
         }));
     }
 
-    for h in handles {
-        h.await??;
+    match run_ctx.max_duration {
+        None => join_tasks(&mut handles).await?,
+        Some(max) => {
+            let deadline = tokio::time::Instant::from_std(started + max);
+            if let Ok(res) = tokio::time::timeout_at(deadline, join_tasks(&mut handles)).await {
+                res?;
+            } else {
+                abort.abort(format!("run exceeded --max-duration {max:?}"));
+                if let Ok(res) =
+                    tokio::time::timeout(MAX_DURATION_GRACE, join_tasks(&mut handles)).await
+                {
+                    res?;
+                }
+                if !handles.is_empty() {
+                    tracing::warn!(
+                        tasks = handles.len(),
+                        grace = ?MAX_DURATION_GRACE,
+                        "cancelling tasks still running after --max-duration"
+                    );
+                }
+                for h in &handles {
+                    h.abort();
+                }
+                for h in handles.drain(..) {
+                    let _ = h.await;
+                }
+            }
+        }
     }
 
-    if let Some(h) = progress_handle {
+    for h in [progress_handle, breaker_handle].into_iter().flatten() {
         h.abort();
         let _ = h.await;
     }
+    warn_unsampled_gauges(&run_ctx.metrics, &run_ctx.gauge_series);
 
-    let summary = super::metrics_agg::build_run_summary(
+    let mut summary = super::metrics_agg::build_run_summary(
         &run_ctx.metrics,
         run_ctx.request_metrics,
         run_ctx.iteration_metrics,
@@ -642,6 +1019,152 @@ struct ScenarioLiveState {
         &scenario_names,
         run_ctx.thresholds.as_ref(),
     )?;
+    summary.aborted = abort.reason().map(str::to_string);
+    tracing::info!(elapsed = ?started.elapsed(), aborted = summary.aborted.is_some(), "run finished");
+
+    // All scenarios start together once the warm-up barrier is passed.
+    for s in &mut summary.scenarios {
+        s.started_at = Some(started_at);
+        s.ended_at = scenario_ends.get(&s.scenario).and_then(|end| end.get());
+    }
 
     Ok(summary)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScenarioOptions;
+
+    fn scenario(name: &str) -> ScenarioOptions {
+        ScenarioOptions {
+            name: name.to_string(),
+            exec: None,
+            tags: Vec::new(),
+            description: None,
+            params: None,
+            executor: None,
+            vus: Some(1),
+            iterations: Some(1),
+            duration: None,
+            think_time: None,
+            isolate_connections: false,
+            start_vus: None,
+            stages: Vec::new(),
+            start_rate: None,
+            time_unit: None,
+            pre_allocated_vus: None,
+            max_vus: None,
+        }
+    }
+
+    #[test]
+    fn scenarios_from_options_rejects_duplicate_names() {
+        let opts = ScriptOptions {
+            scenarios: vec![scenario("main"), scenario("other"), scenario("main")],
+            ..Default::default()
+        };
+
+        match scenarios_from_options(opts, RunConfig::default()) {
+            Err(Error::DuplicateScenario(name)) => assert_eq!(name, "main"),
+            other => panic!("expected duplicate scenario error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn think_time_is_rejected_for_arrival_rate_scenarios() {
+        let mut paced = scenario("paced");
+        paced.iterations = None;
+        paced.think_time = Some(crate::ThinkTimeSpec::fixed(std::time::Duration::from_secs(
+            1,
+        )));
+        paced.executor = Some("ramping-arrival-rate".to_string());
+        paced.stages = vec![crate::Stage {
+            duration: std::time::Duration::from_secs(1),
+            target: 10,
+            shape: crate::StageShape::Linear,
+        }];
+        let opts = ScriptOptions {
+            scenarios: vec![paced],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            scenarios_from_options(opts, RunConfig::default()),
+            Err(Error::ThinkTimeWithArrivalRate)
+        ));
+    }
+
+    #[test]
+    fn a_zero_duration_is_rejected() {
+        let zero = Some(std::time::Duration::ZERO);
+        let mut timed = scenario("timed");
+        timed.iterations = None;
+        timed.duration = zero;
+        let scripted = ScriptOptions {
+            scenarios: vec![timed],
+            ..Default::default()
+        };
+        let from_cli = RunConfig {
+            duration: zero,
+            ..Default::default()
+        };
+
+        for (opts, cfg) in [
+            (scripted, RunConfig::default()),
+            (ScriptOptions::default(), from_cli),
+        ] {
+            assert!(matches!(
+                scenarios_from_options(opts, cfg),
+                Err(Error::InvalidScenarioDuration)
+            ));
+        }
+    }
+
+    #[test]
+    fn run_tags_apply_to_every_scenario() {
+        let mut tagged = scenario("tagged");
+        tagged.tags = vec![("region".to_string(), "eu-west".to_string())];
+        let opts = ScriptOptions {
+            scenarios: vec![scenario("main"), tagged],
+            tags: vec![
+                ("region".to_string(), "us-west".to_string()),
+                ("team".to_string(), "checkout".to_string()),
+            ],
+            ..Default::default()
+        };
+        let cfg = RunConfig {
+            tags: vec![("region".to_string(), "us-east".to_string())],
+            ..Default::default()
+        };
+
+        let scenarios = scenarios_from_options(opts, cfg).unwrap_or_else(|e| panic!("{e}"));
+        let tags = |i: usize| scenarios[i].metrics_ctx.scenario_tags().to_vec();
+        let pair = |k: &str, v: &str| (k.to_string(), v.to_string());
+
+        assert_eq!(
+            tags(0),
+            vec![pair("region", "us-east"), pair("team", "checkout")]
+        );
+        // Scenario-level tags are more specific than run-level ones.
+        assert_eq!(
+            tags(1),
+            vec![pair("region", "eu-west"), pair("team", "checkout")]
+        );
+    }
+
+    #[test]
+    fn run_tags_apply_to_the_default_scenario() {
+        let cfg = RunConfig {
+            tags: vec![("region".to_string(), "us-east".to_string())],
+            ..Default::default()
+        };
+
+        let scenarios =
+            scenarios_from_options(ScriptOptions::default(), cfg).unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(
+            scenarios[0].metrics_ctx.scenario_tags(),
+            &[("region".to_string(), "us-east".to_string())]
+        );
+    }
+}
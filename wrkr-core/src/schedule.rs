@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use super::config::Stage;
+use super::config::{Stage, StageShape};
 
 #[derive(Debug, Clone)]
 pub struct StageSnapshot {
@@ -95,7 +95,6 @@ pub fn target_at(&self, elapsed: Duration) -> u64 {
             return end_target;
         }
 
-        // Linear interpolation across the stage.
         let start_i = start_target as i128;
         let end_i = end_target as i128;
         let delta = end_i - start_i;
@@ -103,7 +102,14 @@ pub fn target_at(&self, elapsed: Duration) -> u64 {
         let num = stage_elapsed.as_nanos() as i128;
         let den = stage_duration.as_nanos() as i128;
 
-        let cur = start_i + (delta.saturating_mul(num) / den.max(1));
+        let cur = match stage.shape {
+            // Exact integer interpolation for the common case.
+            StageShape::Linear => start_i + (delta.saturating_mul(num) / den.max(1)),
+            shape => {
+                let eased = shape.ease(num as f64 / den.max(1) as f64);
+                start_i + (delta as f64 * eased) as i128
+            }
+        };
         cur.clamp(0, u64::MAX as i128) as u64
     }
 
@@ -206,6 +212,13 @@ pub fn next_recheck_in(&self, elapsed: Duration, vu_index: u64) -> Duration {
             return stage_end.saturating_sub(elapsed).min(default_sleep);
         }
 
+        // Curved ramps have no cheap closed form here; poll often enough to follow them.
+        if !matches!(stage.shape, StageShape::Linear) {
+            return stage_end
+                .saturating_sub(elapsed)
+                .min(Duration::from_millis(10));
+        }
+
         // Target is increasing: compute when the ramp reaches this VU index.
         // Solve for t where start + (end-start)*t/dur >= vu_index.
         let start_i = start_target as i128;
@@ -234,3 +247,52 @@ pub fn next_recheck_in(&self, elapsed: Duration, vu_index: u64) -> Duration {
         wait.min(default_sleep)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(secs: u64, target: u64, shape: StageShape) -> Stage {
+        Stage {
+            duration: Duration::from_secs(secs),
+            target,
+            shape,
+        }
+    }
+
+    #[test]
+    fn stage_shapes_shape_the_ramp() {
+        let at = |shape: StageShape, secs: u64| {
+            RampingU64Schedule::new(0, vec![stage(10, 100, shape)])
+                .target_at(Duration::from_secs(secs))
+        };
+
+        assert_eq!(at(StageShape::Linear, 5), 50);
+        assert_eq!(at(StageShape::Step, 1), 100);
+        assert_eq!(at(StageShape::EaseIn, 5), 25);
+        assert_eq!(at(StageShape::EaseOut, 5), 75);
+        assert_eq!(at(StageShape::EaseInOut, 2), 8);
+        assert_eq!(at(StageShape::EaseInOut, 5), 50);
+        assert_eq!(at(StageShape::EaseInOut, 10), 100);
+    }
+
+    #[test]
+    fn step_stages_hold_each_level() {
+        let schedule = RampingU64Schedule::new(
+            0,
+            vec![
+                stage(10, 10, StageShape::Step),
+                stage(10, 20, StageShape::Step),
+                stage(10, 0, StageShape::Step),
+            ],
+        );
+
+        assert_eq!(schedule.target_at(Duration::ZERO), 0);
+        assert_eq!(schedule.target_at(Duration::from_millis(1)), 10);
+        assert_eq!(schedule.target_at(Duration::from_secs(9)), 10);
+        assert_eq!(schedule.target_at(Duration::from_secs(15)), 20);
+        assert_eq!(schedule.target_at(Duration::from_secs(25)), 0);
+        // A VU beyond the current level keeps polling until the next step.
+        assert!(schedule.next_recheck_in(Duration::from_secs(5), 15) <= Duration::from_millis(10));
+    }
+}
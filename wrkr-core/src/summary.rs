@@ -1,6 +1,7 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
-use crate::ThresholdViolation;
+use crate::{ThresholdResult, ThresholdViolation};
 
 #[derive(Debug, Default, Clone)]
 pub struct RunSummary {
@@ -11,20 +12,207 @@ pub struct RunSummary {
 
     /// Threshold violations computed from `metrics` and the configured threshold sets.
     pub threshold_violations: Vec<ThresholdViolation>,
+
+    /// Every threshold expression's outcome, passed or not; the failed ones are also in
+    /// `threshold_violations`.
+    pub threshold_results: Vec<ThresholdResult>,
+
+    /// Request latency across all scenarios, merged from their histograms (so percentiles are
+    /// exact for the pooled samples, not averages of per-scenario percentiles).
+    pub latency: Option<wrkr_metrics::HistogramSummary>,
+
+    /// Every histogram metric recorded by more than one scenario, merged the same way: one
+    /// series per metric and remaining tags, without the `scenario` tag.
+    pub pooled_metrics: Vec<wrkr_metrics::MetricSeriesSummary>,
+
+    /// Why the run was stopped early (e.g. by the error-rate breaker), if it was.
+    pub aborted: Option<String>,
+}
+
+impl RunSummary {
+    #[must_use]
+    pub fn scenario(&self, name: &str) -> Option<&ScenarioSummary> {
+        self.scenarios.iter().find(|s| s.scenario == name)
+    }
+
+    /// Every series of the metric `name`, one per distinct tag set.
+    pub fn series<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a wrkr_metrics::MetricSeriesSummary> + 'a {
+        self.metrics.iter().filter(move |m| m.name == name)
+    }
+
+    /// The first series of `name` carrying all of `tags` (other tags may be present too).
+    #[must_use]
+    pub fn find_series(
+        &self,
+        name: &str,
+        tags: &[(&str, &str)],
+    ) -> Option<&wrkr_metrics::MetricSeriesSummary> {
+        self.metrics
+            .iter()
+            .find(|m| m.name == name && m.has_tags(tags))
+    }
+
+    /// No threshold was violated.
+    #[must_use]
+    pub fn thresholds_passed(&self) -> bool {
+        self.threshold_violations.is_empty()
+    }
+
+    /// From the first scenario start to the last scenario end.
+    #[must_use]
+    pub fn active_duration(&self) -> Option<Duration> {
+        let started = self.scenarios.iter().filter_map(|s| s.started_at).min()?;
+        let ended = self.scenarios.iter().filter_map(|s| s.ended_at).max()?;
+        ended.duration_since(started).ok()
+    }
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct ScenarioSummary {
     pub scenario: String,
 
+    /// Wall-clock time the scenario's VUs were released to start iterating.
+    pub started_at: Option<std::time::SystemTime>,
+    /// Wall-clock time the scenario's last VU finished.
+    pub ended_at: Option<std::time::SystemTime>,
+
     pub requests_total: u64,
     pub failed_requests_total: u64,
+    /// `failed_requests_total` per transport error kind (`connect`, `dns`, `tls`, `timeout`,
+    /// `reset`, ...).
+    pub errors_by_kind: HashMap<String, u64>,
     pub bytes_received_total: u64,
     pub bytes_sent_total: u64,
     pub iterations_total: u64,
+    /// Arrival-rate iterations that could not start because all VUs were busy.
+    pub dropped_iterations_total: u64,
+    /// Requests that know whether they went out on a new or a pooled connection (HTTP responses).
+    pub connection_reuse_samples: u64,
+    /// Part of `connection_reuse_samples` sent on an already open (keep-alive) connection.
+    pub connection_reused_total: u64,
 
     pub checks_failed_total: u64,
     pub checks_failed: HashMap<String, u64>,
+    /// Check results weighted by each check's `weight` (default 1).
+    pub checks_weight_total: u64,
+    /// Part of `checks_weight_total` from passed checks.
+    pub checks_weight_passed: u64,
 
     pub latency: Option<wrkr_metrics::HistogramSummary>,
 }
+
+impl ScenarioSummary {
+    /// The executor could not sustain the requested arrival rate (some iterations were dropped).
+    #[must_use]
+    pub fn saturated(&self) -> bool {
+        self.dropped_iterations_total > 0
+    }
+
+    #[must_use]
+    pub fn active_duration(&self) -> Option<Duration> {
+        self.ended_at?.duration_since(self.started_at?).ok()
+    }
+
+    /// Share of requests that reused a keep-alive connection; `None` without HTTP responses.
+    #[must_use]
+    pub fn connection_reuse_rate(&self) -> Option<f64> {
+        (self.connection_reuse_samples > 0)
+            .then(|| self.connection_reused_total as f64 / self.connection_reuse_samples as f64)
+    }
+
+    /// Weighted share of passed checks, so critical checks count more than cosmetic ones; `None`
+    /// without checks.
+    #[must_use]
+    pub fn checks_score(&self) -> Option<f64> {
+        (self.checks_weight_total > 0)
+            .then(|| self.checks_weight_passed as f64 / self.checks_weight_total as f64)
+    }
+
+    /// Average requests per second over the scenario's active window.
+    #[must_use]
+    pub fn rps(&self) -> Option<f64> {
+        let secs = self.active_duration()?.as_secs_f64();
+        (secs > 0.0).then(|| self.requests_total as f64 / secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::*;
+
+    fn series(name: &str, tags: &[(&str, &str)], n: u64) -> wrkr_metrics::MetricSeriesSummary {
+        wrkr_metrics::MetricSeriesSummary {
+            name: name.to_string(),
+            kind: wrkr_metrics::MetricKind::Counter,
+            tags: tags
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            values: wrkr_metrics::MetricValue::Counter(n),
+        }
+    }
+
+    #[test]
+    fn accessors_find_scenarios_and_series() {
+        let t0 = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let summary = RunSummary {
+            scenarios: vec![
+                ScenarioSummary {
+                    scenario: "a".to_string(),
+                    started_at: Some(t0),
+                    ended_at: Some(t0 + Duration::from_secs(4)),
+                    requests_total: 200,
+                    ..Default::default()
+                },
+                ScenarioSummary {
+                    scenario: "b".to_string(),
+                    started_at: Some(t0),
+                    ended_at: Some(t0 + Duration::from_secs(10)),
+                    ..Default::default()
+                },
+            ],
+            metrics: vec![
+                series(
+                    "requests_total",
+                    &[("scenario", "a"), ("status", "200")],
+                    190,
+                ),
+                series(
+                    "requests_total",
+                    &[("scenario", "a"), ("status", "500")],
+                    10,
+                ),
+                series("checks", &[("scenario", "a")], 3),
+            ],
+            ..Default::default()
+        };
+
+        let a = summary
+            .scenario("a")
+            .unwrap_or_else(|| panic!("scenario a"));
+        assert_eq!(a.rps(), Some(50.0));
+        assert!(summary.scenario("missing").is_none());
+        assert_eq!(summary.active_duration(), Some(Duration::from_secs(10)));
+
+        assert_eq!(summary.series("requests_total").count(), 2);
+        let errors = summary
+            .find_series("requests_total", &[("status", "500")])
+            .unwrap_or_else(|| panic!("500 series"));
+        assert!(matches!(
+            errors.values,
+            wrkr_metrics::MetricValue::Counter(10)
+        ));
+        assert_eq!(errors.tag("scenario"), Some("a"));
+        assert!(
+            summary
+                .find_series("checks", &[("scenario", "b")])
+                .is_none()
+        );
+        assert!(summary.thresholds_passed());
+    }
+}
@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+/// Randomized pause between user actions ("think time").
+///
+/// Parameters are durations; `LogNormal` takes the mean and standard deviation of the resulting
+/// pause (not of the underlying normal distribution), which is how think times are usually
+/// measured from real traffic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThinkTime {
+    Uniform { min: Duration, max: Duration },
+    Normal { mean: Duration, stddev: Duration },
+    Exponential { mean: Duration },
+    LogNormal { mean: Duration, stddev: Duration },
+}
+
+impl ThinkTime {
+    /// Draw one pause. Negative draws (possible for `Normal`) become zero.
+    pub fn sample(&self, rng: &mut fastrand::Rng) -> Duration {
+        let secs = match *self {
+            ThinkTime::Uniform { min, max } => {
+                let (min, max) = (min.as_secs_f64(), max.as_secs_f64());
+                min + rng.f64() * (max - min)
+            }
+            ThinkTime::Normal { mean, stddev } => {
+                mean.as_secs_f64() + stddev.as_secs_f64() * standard_normal(rng)
+            }
+            ThinkTime::Exponential { mean } => -mean.as_secs_f64() * open_unit(rng).ln(),
+            ThinkTime::LogNormal { mean, stddev } => {
+                let (m, s) = (mean.as_secs_f64(), stddev.as_secs_f64());
+                if m <= 0.0 {
+                    0.0
+                } else {
+                    let sigma2 = (1.0 + (s * s) / (m * m)).ln();
+                    let mu = m.ln() - sigma2 / 2.0;
+                    (mu + sigma2.sqrt() * standard_normal(rng)).exp()
+                }
+            }
+        };
+
+        if secs.is_nan() || secs <= 0.0 {
+            return Duration::ZERO;
+        }
+        Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX)
+    }
+}
+
+/// A [`ThinkTime`] distribution with optional bounds applied to every draw, as configured by
+/// `sleep.think{...}` and a scenario's `think_time`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThinkTimeSpec {
+    pub dist: ThinkTime,
+    pub min: Option<Duration>,
+    pub max: Option<Duration>,
+}
+
+impl ThinkTimeSpec {
+    /// The same pause every time.
+    #[must_use]
+    pub fn fixed(d: Duration) -> Self {
+        Self {
+            dist: ThinkTime::Uniform { min: d, max: d },
+            min: None,
+            max: None,
+        }
+    }
+
+    /// Draw one pause and clamp it to `min`/`max`.
+    pub fn sample(&self, rng: &mut fastrand::Rng) -> Duration {
+        let mut d = self.dist.sample(rng);
+        if let Some(min) = self.min {
+            d = d.max(min);
+        }
+        if let Some(max) = self.max {
+            d = d.min(max);
+        }
+        d
+    }
+}
+
+/// Uniform draw in `(0, 1]`, safe to pass to `ln`.
+fn open_unit(rng: &mut fastrand::Rng) -> f64 {
+    1.0 - rng.f64()
+}
+
+/// Box-Muller transform.
+fn standard_normal(rng: &mut fastrand::Rng) -> f64 {
+    let u1 = open_unit(rng);
+    let u2 = rng.f64();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean_secs(dist: ThinkTime, n: usize) -> f64 {
+        let mut rng = fastrand::Rng::with_seed(42);
+        let total: f64 = (0..n).map(|_| dist.sample(&mut rng).as_secs_f64()).sum();
+        total / n as f64
+    }
+
+    #[test]
+    fn uniform_stays_within_bounds() {
+        let dist = ThinkTime::Uniform {
+            min: Duration::from_millis(100),
+            max: Duration::from_millis(300),
+        };
+        let mut rng = fastrand::Rng::with_seed(7);
+        for _ in 0..10_000 {
+            let d = dist.sample(&mut rng);
+            assert!(d >= Duration::from_millis(100) && d <= Duration::from_millis(300));
+        }
+    }
+
+    #[test]
+    fn distributions_match_their_mean() {
+        let mean = Duration::from_secs(2);
+        let stddev = Duration::from_millis(500);
+
+        for dist in [
+            ThinkTime::Normal { mean, stddev },
+            ThinkTime::Exponential { mean },
+            ThinkTime::LogNormal { mean, stddev },
+        ] {
+            let got = mean_secs(dist, 50_000);
+            assert!((got - 2.0).abs() < 0.05, "{dist:?}: mean={got}");
+        }
+    }
+
+    #[test]
+    fn spec_clamps_draws_to_its_bounds() {
+        let spec = ThinkTimeSpec {
+            dist: ThinkTime::Exponential {
+                mean: Duration::from_secs(1),
+            },
+            min: Some(Duration::from_millis(200)),
+            max: Some(Duration::from_secs(2)),
+        };
+        let mut rng = fastrand::Rng::with_seed(3);
+        for _ in 0..10_000 {
+            let d = spec.sample(&mut rng);
+            assert!(d >= Duration::from_millis(200) && d <= Duration::from_secs(2));
+        }
+
+        let fixed = ThinkTimeSpec::fixed(Duration::from_millis(250));
+        assert_eq!(fixed.sample(&mut rng), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn negative_draws_clamp_to_zero() {
+        let dist = ThinkTime::Normal {
+            mean: Duration::ZERO,
+            stddev: Duration::from_secs(1),
+        };
+        let mut rng = fastrand::Rng::with_seed(1);
+        let zeros = (0..1_000)
+            .filter(|_| dist.sample(&mut rng) == Duration::ZERO)
+            .count();
+        assert!(zeros > 0);
+    }
+}
@@ -7,6 +7,74 @@ pub struct ThresholdSet {
     /// selector (order-insensitive).
     pub tags: Vec<(String, String)>,
     pub expressions: Vec<String>,
+    /// Free-form, human-readable description of the SLO (metadata only).
+    pub description: Option<String>,
+    /// Run condition. When non-empty, the set only applies if some scenario in the run carries
+    /// all of these tags (`scenario` is matched against the scenario name).
+    pub when: Vec<(String, String)>,
+    /// What to do when the metric (or selected series) never received a sample.
+    pub no_data: ThresholdNoData,
+}
+
+/// Outcome of a threshold whose metric has no data at the end of the run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThresholdNoData {
+    /// Report a violation marked as "no data" (the default).
+    #[default]
+    Fail,
+    /// Treat the threshold as passed.
+    Pass,
+}
+
+impl ThresholdNoData {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        match raw.trim() {
+            "fail" => Ok(Self::Fail),
+            "pass" => Ok(Self::Pass),
+            other => Err(format!("expected `fail` or `pass`, got `{other}`")),
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fail => "fail",
+            Self::Pass => "pass",
+        }
+    }
+}
+
+impl ThresholdSet {
+    /// Whether this set applies to a run made of `scenarios` (see [`ThresholdSet::when`]).
+    #[must_use]
+    pub fn is_active(&self, scenarios: &[crate::ScenarioConfig]) -> bool {
+        if self.when.is_empty() {
+            return true;
+        }
+        scenarios.iter().any(|s| {
+            self.when.iter().all(|(k, v)| {
+                if k == "scenario" {
+                    return s.metrics_ctx.scenario() == v;
+                }
+                s.metrics_ctx
+                    .scenario_tags()
+                    .iter()
+                    .any(|(tk, tv)| tk == k && tv == v)
+            })
+        })
+    }
+}
+
+/// The threshold sets whose `when` condition holds for this run.
+#[must_use]
+pub fn active_thresholds(
+    sets: &[ThresholdSet],
+    scenarios: &[crate::ScenarioConfig],
+) -> Vec<ThresholdSet> {
+    sets.iter()
+        .filter(|set| set.is_active(scenarios))
+        .cloned()
+        .collect()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,6 +94,8 @@ pub enum ThresholdAgg {
     Count,
     Rate,
     P(u32),
+    /// Fraction of histogram samples above the given target (e.g. an SLO latency).
+    RateOver(f64),
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +111,40 @@ pub struct ThresholdViolation {
     pub tags: Vec<(String, String)>,
     pub expression: String,
     pub observed: Option<f64>,
+    /// The metric was never registered, or none of the selected series received a sample.
+    pub no_data: bool,
+    /// Copied from the originating `ThresholdSet`.
+    pub description: Option<String>,
+}
+
+/// Outcome of one threshold expression, whether it passed or not.
+#[derive(Debug, Clone)]
+pub struct ThresholdResult {
+    pub metric: String,
+    pub tags: Vec<(String, String)>,
+    pub expression: String,
+    /// Value of the expression's aggregation, e.g. the observed p95 for `p(95)<200`.
+    pub observed: Option<f64>,
+    /// The right-hand side of the expression (`200` above).
+    pub limit: Option<f64>,
+    pub passed: bool,
+    /// No selected series received a sample; `passed` then follows the set's `no_data` policy.
+    pub no_data: bool,
+    pub description: Option<String>,
+}
+
+impl ThresholdResult {
+    #[must_use]
+    pub fn to_violation(&self) -> ThresholdViolation {
+        ThresholdViolation {
+            metric: self.metric.clone(),
+            tags: self.tags.clone(),
+            expression: self.expression.clone(),
+            observed: self.observed,
+            no_data: self.no_data,
+            description: self.description.clone(),
+        }
+    }
 }
 
 pub fn parse_threshold_metric_key(raw: &str) -> Result<(String, Vec<(String, String)>), String> {
@@ -141,6 +245,17 @@ pub fn parse_threshold_expr(raw: &str) -> Result<ThresholdExpr, String> {
         ThresholdAgg::Count
     } else if left.eq_ignore_ascii_case("rate") {
         ThresholdAgg::Rate
+    } else if let Some(inner) = left
+        .strip_prefix("rate_over(")
+        .and_then(|v| v.strip_suffix(')'))
+    {
+        let target: f64 = inner
+            .parse()
+            .map_err(|_| format!("invalid target in threshold: {raw}"))?;
+        if !target.is_finite() || target < 0.0 {
+            return Err(format!("target out of range in threshold: {raw}"));
+        }
+        ThresholdAgg::RateOver(target)
     } else if let Some(inner) = left.strip_prefix("p(").and_then(|v| v.strip_suffix(')')) {
         let p: u32 = inner
             .parse()
@@ -181,6 +296,18 @@ fn parse_threshold_expr_rejects_out_of_range_percentiles() {
         assert!(err.contains("out of range"));
     }
 
+    #[test]
+    fn parse_threshold_expr_rate_over_target() {
+        let expr =
+            parse_threshold_expr("rate_over(300000) < 0.01").unwrap_or_else(|e| panic!("{e}"));
+        assert!(matches!(expr.agg, ThresholdAgg::RateOver(t) if t == 300000.0));
+        assert!(matches!(expr.op, ThresholdOp::Lt));
+        assert_eq!(expr.value, 0.01);
+
+        assert!(parse_threshold_expr("rate_over(-1) < 0.01").is_err());
+        assert!(parse_threshold_expr("rate_over(abc) < 0.01").is_err());
+    }
+
     #[test]
     fn parse_threshold_metric_key_without_selector() {
         let (name, tags) =
@@ -203,4 +330,51 @@ fn parse_threshold_metric_key_with_selector_trims_and_sorts() {
             ]
         );
     }
+
+    #[test]
+    fn when_condition_matches_run_tags_and_scenario_names() {
+        let scenario = |name: &str, tags: &[(&str, &str)]| crate::ScenarioConfig {
+            exec: "Default".to_string(),
+            metrics_ctx: crate::MetricsContext::new(
+                std::sync::Arc::from(name),
+                tags.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            description: None,
+            executor: crate::ScenarioExecutor::ConstantVus { vus: 1 },
+            iterations: Some(1),
+            duration: None,
+            warmup_iterations: 0,
+            script: None,
+            params: None,
+            think_time: None,
+            isolate_connections: false,
+        };
+        let set = |when: &[(&str, &str)]| ThresholdSet {
+            metric: "request_latency".to_string(),
+            tags: Vec::new(),
+            expressions: vec!["p(95)<200000".to_string()],
+            description: None,
+            when: when
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            no_data: ThresholdNoData::Fail,
+        };
+        let run = [
+            scenario("browse", &[("env", "prod")]),
+            scenario("checkout", &[("env", "prod"), ("tier", "gold")]),
+        ];
+
+        assert!(set(&[]).is_active(&run));
+        assert!(set(&[("env", "prod")]).is_active(&run));
+        assert!(!set(&[("env", "staging")]).is_active(&run));
+        assert!(set(&[("scenario", "checkout"), ("tier", "gold")]).is_active(&run));
+        assert!(!set(&[("scenario", "browse"), ("tier", "gold")]).is_active(&run));
+
+        let active = active_thresholds(&[set(&[("env", "prod")]), set(&[("env", "dev")])], &run);
+        assert_eq!(active.len(), 1);
+    }
 }
@@ -1,4 +1,7 @@
-use crate::{ThresholdAgg, ThresholdOp, ThresholdSet, ThresholdViolation, parse_threshold_expr};
+use crate::{
+    ThresholdAgg, ThresholdNoData, ThresholdOp, ThresholdResult, ThresholdSet, ThresholdViolation,
+    parse_threshold_expr,
+};
 use wrkr_metrics::{MetricKind, Registry};
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -9,56 +12,93 @@ pub enum Error {
     InvalidThresholdExpr { metric: String, error: String },
 }
 
+/// The failed expressions of [`evaluate_threshold_results`].
 pub fn evaluate_thresholds(
     metrics: &Registry,
     sets: &[ThresholdSet],
 ) -> Result<Vec<ThresholdViolation>> {
-    let mut out: Vec<ThresholdViolation> = Vec::new();
+    Ok(evaluate_threshold_results(metrics, sets)?
+        .iter()
+        .filter(|r| !r.passed)
+        .map(ThresholdResult::to_violation)
+        .collect())
+}
+
+/// One result per expression of every set, in set order.
+pub fn evaluate_threshold_results(
+    metrics: &Registry,
+    sets: &[ThresholdSet],
+) -> Result<Vec<ThresholdResult>> {
+    let mut out: Vec<ThresholdResult> = Vec::new();
 
     for set in sets {
-        let selector = TagSelector::new(metrics, &set.tags);
-
-        let Some((metric_id, kind)) = metrics.lookup_metric(&set.metric) else {
-            // Missing metric => all expressions fail.
-            for expr in &set.expressions {
-                out.push(ThresholdViolation {
-                    metric: set.metric.clone(),
-                    tags: set.tags.clone(),
-                    expression: expr.clone(),
-                    observed: None,
-                });
-            }
-            continue;
-        };
+        let selector = TagSelector::new(metrics, &set.metric, &set.tags);
 
-        let any_series = selector.any_series(metrics, metric_id);
+        let data = metrics
+            .lookup_metric(&set.metric)
+            .filter(|&(metric_id, kind)| has_data(metrics, metric_id, kind, &selector));
 
         for expr_raw in &set.expressions {
-            let expr =
-                parse_threshold_expr(expr_raw).map_err(|error| Error::InvalidThresholdExpr {
-                    metric: set.metric.clone(),
-                    error,
-                })?;
-
-            let observed = any_series
-                .then(|| observed_value(metrics, metric_id, kind, &expr.agg, &selector))
-                .flatten();
-
-            let passed = observed.is_some_and(|v| compare(v, expr.op, expr.value));
-            if !passed {
-                out.push(ThresholdViolation {
-                    metric: set.metric.clone(),
-                    tags: set.tags.clone(),
-                    expression: expr_raw.clone(),
-                    observed,
-                });
-            }
+            let parsed = parse_threshold_expr(expr_raw);
+            let (observed, limit, passed) = match data {
+                Some((metric_id, kind)) => {
+                    let expr = parsed.map_err(|error| Error::InvalidThresholdExpr {
+                        metric: set.metric.clone(),
+                        error,
+                    })?;
+                    let observed = observed_value(metrics, metric_id, kind, &expr.agg, &selector);
+                    let passed = observed.is_some_and(|v| compare(v, expr.op, expr.value));
+                    (observed, Some(expr.value), passed)
+                }
+                // Without data there is nothing to evaluate, so the expression is not checked.
+                None => (
+                    None,
+                    parsed.ok().map(|expr| expr.value),
+                    set.no_data == ThresholdNoData::Pass,
+                ),
+            };
+            out.push(ThresholdResult {
+                metric: set.metric.clone(),
+                tags: set.tags.clone(),
+                expression: expr_raw.clone(),
+                observed,
+                limit,
+                passed,
+                no_data: data.is_none(),
+                description: set.description.clone(),
+            });
         }
     }
 
     Ok(out)
 }
 
+/// Whether any selected series received at least one sample.
+///
+/// Histograms and rates can have series registered without samples (e.g. a scenario that never
+/// reached its first request), so those check the sample count rather than series presence.
+fn has_data(
+    metrics: &Registry,
+    metric_id: wrkr_metrics::MetricId,
+    kind: MetricKind,
+    selector: &TagSelector,
+) -> bool {
+    if !selector.any_series(metrics, metric_id) {
+        return false;
+    }
+    match kind {
+        MetricKind::Counter | MetricKind::Gauge => true,
+        MetricKind::Rate => {
+            let (total, _hits, _rate) =
+                metrics.fold_rate_sum(metric_id, |tags| selector.matches(tags));
+            total > 0
+        }
+        MetricKind::Histogram => metrics
+            .fold_histogram_summary(metric_id, |tags| selector.matches(tags))
+            .is_some_and(|h| h.count > 0),
+    }
+}
+
 fn observed_value(
     metrics: &Registry,
     metric_id: wrkr_metrics::MetricId,
@@ -112,6 +152,13 @@ fn observed_value(
             _ => None,
         },
 
+        ThresholdAgg::RateOver(target) => match kind {
+            MetricKind::Histogram => metrics
+                .fold_histogram_summary(metric_id, |tags| selector.matches(tags))
+                .and_then(|h| h.rate_above(*target)),
+            _ => None,
+        },
+
         ThresholdAgg::P(p) => match kind {
             MetricKind::Histogram => metrics
                 .fold_histogram_summary(metric_id, |tags| selector.matches(tags))
@@ -131,6 +178,8 @@ fn observed_value(
 #[derive(Debug, Clone)]
 struct TagSelector {
     mode: TagSelectorMode,
+    /// Series carrying this tag are ignored (see `duplicate_series_key`).
+    skip_key: Option<wrkr_metrics::KeyId>,
 }
 
 #[derive(Debug, Clone)]
@@ -143,10 +192,15 @@ enum TagSelectorMode {
 }
 
 impl TagSelector {
-    fn new(metrics: &Registry, selector_tags: &[(String, String)]) -> Self {
+    fn new(metrics: &Registry, metric: &str, selector_tags: &[(String, String)]) -> Self {
+        let skip_key = duplicate_series_key(metric)
+            .filter(|key| !selector_tags.iter().any(|(k, _v)| k == key))
+            .map(|key| metrics.resolve_key(key));
+
         if selector_tags.is_empty() {
             return Self {
                 mode: TagSelectorMode::All,
+                skip_key,
             };
         }
 
@@ -166,10 +220,17 @@ fn new(metrics: &Registry, selector_tags: &[(String, String)]) -> Self {
                 keys: key_ids,
                 tags,
             },
+            skip_key,
         }
     }
 
     fn matches(&self, series_tags: &wrkr_metrics::TagSet) -> bool {
+        if self
+            .skip_key
+            .is_some_and(|key| series_tags.get(key).is_some())
+        {
+            return false;
+        }
         match &self.mode {
             TagSelectorMode::All => true,
             TagSelectorMode::Selector { keys, tags } => series_tags.project(keys) == tags.clone(),
@@ -187,6 +248,16 @@ fn any_series(&self, metrics: &Registry, metric_id: wrkr_metrics::MetricId) -> b
     }
 }
 
+/// Built-in metrics that record every sample into an overall series and again into a series
+/// refined by one extra tag. Unless a threshold selects on that tag, only the overall series is
+/// aggregated; otherwise whole-run and per-scenario counts would include each sample twice.
+fn duplicate_series_key(metric: &str) -> Option<&'static str> {
+    match metric {
+        "request_latency" => Some("protocol"),
+        _ => None,
+    }
+}
+
 fn compare(observed: f64, op: ThresholdOp, expected: f64) -> bool {
     match op {
         ThresholdOp::Lt => observed < expected,
@@ -210,6 +281,9 @@ fn missing_metric_fails_threshold() {
             metric: "nope".to_string(),
             tags: Vec::new(),
             expressions: vec!["count>0".to_string()],
+            description: Some("metric must exist".to_string()),
+            when: Vec::new(),
+            no_data: ThresholdNoData::Fail,
         }];
 
         let v = match evaluate_thresholds(&metrics, &sets) {
@@ -219,6 +293,90 @@ fn missing_metric_fails_threshold() {
         assert_eq!(v.len(), 1);
         assert_eq!(v[0].metric, "nope");
         assert!(v[0].observed.is_none());
+        assert!(v[0].no_data);
+        assert_eq!(v[0].description.as_deref(), Some("metric must exist"));
+    }
+
+    #[test]
+    fn histogram_without_samples_is_no_data() {
+        let metrics = Registry::default();
+        let id = metrics.register("http_req_duration", MetricKind::Histogram);
+        let tags = TagSet::from_sorted_iter([]);
+        assert!(metrics.get_handle(id, tags).is_some());
+
+        let set = |no_data| ThresholdSet {
+            metric: "http_req_duration".to_string(),
+            tags: Vec::new(),
+            expressions: vec!["count<100".to_string()],
+            description: None,
+            when: Vec::new(),
+            no_data,
+        };
+
+        let v = evaluate_thresholds(&metrics, &[set(ThresholdNoData::Fail)])
+            .unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(v.len(), 1);
+        assert!(v[0].no_data);
+        assert!(v[0].observed.is_none());
+
+        let v = evaluate_thresholds(&metrics, &[set(ThresholdNoData::Pass)])
+            .unwrap_or_else(|e| panic!("{e}"));
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn results_cover_passing_and_failing_expressions() {
+        let metrics = Registry::default();
+        let id = metrics.register("my_counter", MetricKind::Counter);
+        if let Some(MetricHandle::Counter(c)) = metrics.get_handle(id, TagSet::from_sorted_iter([]))
+        {
+            c.fetch_add(5, Ordering::Relaxed);
+        }
+
+        let sets = vec![
+            ThresholdSet {
+                metric: "my_counter".to_string(),
+                tags: Vec::new(),
+                expressions: vec!["count>1".to_string(), "count<3".to_string()],
+                description: None,
+                when: Vec::new(),
+                no_data: ThresholdNoData::Fail,
+            },
+            ThresholdSet {
+                metric: "absent".to_string(),
+                tags: Vec::new(),
+                expressions: vec!["count>0".to_string()],
+                description: None,
+                when: Vec::new(),
+                no_data: ThresholdNoData::Pass,
+            },
+        ];
+
+        let results = evaluate_threshold_results(&metrics, &sets).unwrap_or_else(|e| panic!("{e}"));
+        let outcomes: Vec<_> = results
+            .iter()
+            .map(|r| {
+                (
+                    r.expression.as_str(),
+                    r.observed,
+                    r.limit,
+                    r.passed,
+                    r.no_data,
+                )
+            })
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                ("count>1", Some(5.0), Some(1.0), true, false),
+                ("count<3", Some(5.0), Some(3.0), false, false),
+                ("count>0", None, Some(0.0), true, true),
+            ]
+        );
+
+        let violations = evaluate_thresholds(&metrics, &sets).unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].expression, "count<3");
     }
 
     #[test]
@@ -234,6 +392,9 @@ fn counter_count_uses_sum() {
             metric: "my_counter".to_string(),
             tags: Vec::new(),
             expressions: vec!["count==2".to_string()],
+            description: None,
+            when: Vec::new(),
+            no_data: ThresholdNoData::Fail,
         }];
 
         let v = match evaluate_thresholds(&metrics, &sets) {
@@ -257,6 +418,9 @@ fn rate_rate_uses_hits_over_total() {
             metric: "http_req_failed".to_string(),
             tags: Vec::new(),
             expressions: vec!["rate<0.2".to_string()],
+            description: None,
+            when: Vec::new(),
+            no_data: ThresholdNoData::Fail,
         }];
 
         let v = match evaluate_thresholds(&metrics, &sets) {
@@ -266,6 +430,38 @@ fn rate_rate_uses_hits_over_total() {
         assert!(v.is_empty());
     }
 
+    #[test]
+    fn histogram_rate_over_is_fraction_above_target() {
+        let metrics = Registry::default();
+        let id = metrics.register("http_req_duration", MetricKind::Histogram);
+        let tags = TagSet::from_sorted_iter([]);
+        if let Some(MetricHandle::Histogram(h)) = metrics.get_handle(id, tags) {
+            let mut h = h.lock();
+            for _ in 0..98 {
+                let _ = h.record(100);
+            }
+            let _ = h.record(500);
+            let _ = h.record(900);
+        }
+
+        let sets = vec![ThresholdSet {
+            metric: "http_req_duration".to_string(),
+            tags: Vec::new(),
+            expressions: vec![
+                "rate_over(300)<0.01".to_string(),
+                "rate_over(300)<=0.02".to_string(),
+            ],
+            description: None,
+            when: Vec::new(),
+            no_data: ThresholdNoData::Fail,
+        }];
+
+        let v = evaluate_thresholds(&metrics, &sets).unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].expression, "rate_over(300)<0.01");
+        assert_eq!(v[0].observed, Some(0.02));
+    }
+
     #[test]
     fn tag_scoped_threshold_matches_series_by_projected_keys() {
         let metrics = Registry::default();
@@ -285,6 +481,9 @@ fn tag_scoped_threshold_matches_series_by_projected_keys() {
             metric: "my_counter".to_string(),
             tags: vec![("group".to_string(), "login".to_string())],
             expressions: vec!["count==2".to_string()],
+            description: None,
+            when: Vec::new(),
+            no_data: ThresholdNoData::Fail,
         }];
 
         let v = evaluate_thresholds(&metrics, &sets).unwrap_or_else(|e| panic!("{e}"));
@@ -300,6 +499,9 @@ fn missing_tag_scoped_series_fails_with_observed_none() {
             metric: "my_counter".to_string(),
             tags: vec![("group".to_string(), "missing".to_string())],
             expressions: vec!["count>0".to_string()],
+            description: None,
+            when: Vec::new(),
+            no_data: ThresholdNoData::Fail,
         }];
 
         let v = evaluate_thresholds(&metrics, &sets).unwrap_or_else(|e| panic!("{e}"));
@@ -311,4 +513,86 @@ fn missing_tag_scoped_series_fails_with_observed_none() {
         );
         assert!(v[0].observed.is_none());
     }
+
+    #[test]
+    fn untagged_threshold_aggregates_across_scenarios() {
+        let metrics = Registry::default();
+        let id = metrics.register("http_req_failed", MetricKind::Rate);
+
+        // 1/10 failed in `a`, 9/10 in `b`: the run-wide rate is 10/20, not the mean of rates.
+        for (scenario, hits) in [("a", 1), ("b", 9)] {
+            let tags = metrics.resolve_tags(&[("scenario", scenario)]);
+            if let Some(MetricHandle::Rate(r)) = metrics.get_handle(id, tags) {
+                r.total.fetch_add(10, Ordering::Relaxed);
+                r.hits.fetch_add(hits, Ordering::Relaxed);
+            }
+        }
+
+        let sets = vec![
+            ThresholdSet {
+                metric: "http_req_failed".to_string(),
+                tags: Vec::new(),
+                expressions: vec!["rate<0.5".to_string()],
+                description: None,
+                when: Vec::new(),
+                no_data: ThresholdNoData::Fail,
+            },
+            ThresholdSet {
+                metric: "http_req_failed".to_string(),
+                tags: vec![("scenario".to_string(), "a".to_string())],
+                expressions: vec!["rate<0.5".to_string()],
+                description: None,
+                when: Vec::new(),
+                no_data: ThresholdNoData::Fail,
+            },
+        ];
+
+        let v = evaluate_thresholds(&metrics, &sets).unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(v.len(), 1);
+        assert!(v[0].tags.is_empty());
+        assert_eq!(v[0].observed, Some(0.5));
+    }
+
+    #[test]
+    fn request_latency_counts_each_request_once() {
+        let metrics = Registry::default();
+        let ids = crate::RequestMetricIds::register(&metrics);
+        for scenario in ["a", "b", "b"] {
+            ids.record_request(
+                &metrics,
+                crate::RequestSample {
+                    scenario,
+                    protocol: crate::Protocol::Http,
+                    ok: true,
+                    latency: std::time::Duration::from_millis(5),
+                    bytes_received: 0,
+                    bytes_received_body: None,
+                    bytes_sent: 0,
+                    error_kind: None,
+                    connection_reused: None,
+                },
+                &[],
+            );
+        }
+
+        let set = |tags: &[(&str, &str)], expr: &str| ThresholdSet {
+            metric: "request_latency".to_string(),
+            tags: tags
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            expressions: vec![expr.to_string()],
+            description: None,
+            when: Vec::new(),
+            no_data: ThresholdNoData::Fail,
+        };
+        let sets = vec![
+            set(&[], "count==3"),
+            set(&[("scenario", "b")], "count==2"),
+            set(&[("protocol", "http")], "count==3"),
+        ];
+
+        let v = evaluate_thresholds(&metrics, &sets).unwrap_or_else(|e| panic!("{e}"));
+        assert!(v.is_empty(), "{v:?}");
+    }
 }
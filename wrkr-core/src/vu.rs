@@ -1,13 +1,15 @@
+use std::borrow::Cow;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::OnceLock;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::Barrier;
 use tokio::sync::Notify;
 
 use wrkr_metrics::{MetricHandle, MetricKind};
 
+use super::abort::AbortSignal;
 use super::gate::IterationGate;
 use super::metrics_context::MetricsContext;
 use super::pacer::ArrivalPacer;
@@ -56,15 +58,36 @@ pub struct VuContext {
     pub metrics_ctx: MetricsContext,
     pub scenario_vu: u64,
     pub exec: String,
+    /// Script of the VU's scenario in a composite run (see [`VuContext::script_run_ctx`]).
+    pub script: Option<Arc<crate::ScriptSource>>,
+    /// Scenario `params`, passed to the exec function on every iteration.
+    pub params: Option<Arc<wrkr_value::Value>>,
+    /// Scenario think time, taken after every measured iteration (see [`VuContext::think`]).
+    pub think_time: Option<crate::ThinkTimeSpec>,
     pub work: VuWork,
 
     pub run_ctx: Arc<RunScenariosContext>,
 
     pub run_started: Arc<OnceLock<Instant>>,
 
-    pub init_error: Arc<Mutex<Option<String>>>,
+    pub init_error: Arc<Mutex<Option<VuInitError>>>,
     pub ready_barrier: Arc<Barrier>,
+    /// Throwaway iterations to run after `ready_barrier` and before `warmup_barrier`.
+    pub warmup_iterations: u64,
+    pub warmup_barrier: Arc<Barrier>,
     pub start_signal: Arc<StartSignal>,
+    /// Raised when the run is stopped early; VUs stop starting new iterations.
+    pub abort: Arc<AbortSignal>,
+}
+
+/// The first VU setup failure of a run (script load or warm-up), and the VU it happened in.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("VU {vu_id} (scenario `{scenario}`): {message}")]
+pub struct VuInitError {
+    pub vu_id: u64,
+    pub scenario: String,
+    /// The runtime's error, including its stack traceback when it has one.
+    pub message: String,
 }
 
 #[derive(Debug, Clone)]
@@ -82,6 +105,49 @@ pub enum VuWork {
     },
 }
 
+/// Tracks when a VU's scenario ends, so pauses can be kept inside it.
+#[derive(Debug, Clone)]
+pub struct ScenarioClock {
+    work: VuWork,
+    run_started: Arc<OnceLock<Instant>>,
+    abort: Arc<AbortSignal>,
+}
+
+impl ScenarioClock {
+    /// When the scenario stops starting iterations. `None` before the run starts and for
+    /// scenarios bounded only by an iteration count.
+    pub fn deadline(&self) -> Option<Instant> {
+        match &self.work {
+            VuWork::Constant { gate } => gate.deadline(),
+            VuWork::RampingVus { schedule } | VuWork::RampingArrivalRate { schedule, .. } => self
+                .run_started
+                .get()
+                .map(|started| *started + schedule.total_duration()),
+        }
+    }
+
+    /// Time left before [`Self::deadline`], or `None` when the scenario has no deadline.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline()
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Sleep for `pause`, clamped to the scenario's remaining time and cut short when the run
+    /// is aborted. Returns how long it actually slept.
+    pub async fn sleep(&self, pause: Duration) -> Duration {
+        let pause = self.remaining().map_or(pause, |left| pause.min(left));
+        if pause.is_zero() {
+            return pause;
+        }
+        let started = Instant::now();
+        tokio::select! {
+            () = tokio::time::sleep(pause) => {}
+            () = self.abort.wait() => {}
+        }
+        started.elapsed().min(pause)
+    }
+}
+
 pub struct ActiveVuGuard {
     metrics: Arc<wrkr_metrics::Registry>,
     handle: wrkr_metrics::MetricId,
@@ -99,6 +165,54 @@ fn drop(&mut self) {
 }
 
 impl VuContext {
+    /// The run context with `script`/`script_path` set to the script this VU runs.
+    #[must_use]
+    pub fn script_run_ctx(&self) -> Cow<'_, RunScenariosContext> {
+        match &self.script {
+            Some(script) => Cow::Owned(self.run_ctx.with_script(script)),
+            None => Cow::Borrowed(self.run_ctx.as_ref()),
+        }
+    }
+
+    /// Record the first VU setup failure; the runner reports it once all VUs reach a barrier.
+    pub fn set_init_error(&self, msg: String) {
+        tracing::error!(
+            vu = self.vu_id,
+            scenario = self.metrics_ctx.scenario(),
+            error = %msg,
+            "VU initialization failed"
+        );
+        let mut guard = self
+            .init_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if guard.is_none() {
+            *guard = Some(VuInitError {
+                vu_id: self.vu_id,
+                scenario: self.metrics_ctx.scenario().to_string(),
+                message: msg,
+            });
+        }
+    }
+
+    #[must_use]
+    pub fn has_init_error(&self) -> bool {
+        self.init_error
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .is_some()
+    }
+
+    /// A handle on this VU's scenario end, for sleeps that must not outlast it.
+    #[must_use]
+    pub fn scenario_clock(&self) -> ScenarioClock {
+        ScenarioClock {
+            work: self.work.clone(),
+            run_started: self.run_started.clone(),
+            abort: self.abort.clone(),
+        }
+    }
+
     pub fn enter_active_vu(&self) -> ActiveVuGuard {
         let handle = self
             .run_ctx
@@ -146,7 +260,137 @@ pub fn enter_active_vu(&self) -> ActiveVuGuard {
         }
     }
 
+    /// Pause for the scenario's think time, if it has one. Called after each measured iteration
+    /// of the VU-based executors; returns early when the run is aborted.
+    pub async fn think(&self, rng: &mut fastrand::Rng) {
+        let Some(spec) = &self.think_time else {
+            return;
+        };
+        if matches!(self.work, VuWork::RampingArrivalRate { .. }) {
+            return;
+        }
+
+        let pause = spec.sample(rng);
+        if pause.is_zero() {
+            return;
+        }
+        tokio::select! {
+            () = tokio::time::sleep(pause) => {}
+            () = self.abort.wait() => {}
+        }
+    }
+
+    /// Drive this VU through the run with `iteration` as the per-iteration body.
+    ///
+    /// Handles the start-up protocol (ready barrier, warm-up, start signal) and the executor's
+    /// scheduling, and records iteration metrics. Stops at the first iteration error. This is
+    /// the VU function for Rust callers that don't need per-VU setup; see [`crate::RunBuilder`].
+    pub async fn run_iterations<F, Fut, E>(&self, iteration: F) -> std::result::Result<(), E>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = std::result::Result<(), E>>,
+        E: std::fmt::Display,
+    {
+        self.run_iterations_with(iteration, |elapsed, ok| {
+            self.record_iteration(elapsed, ok);
+        })
+        .await
+    }
+
+    /// Like [`Self::run_iterations`], but each measured iteration is handed to `record` (its
+    /// duration and whether it succeeded) instead of [`Self::record_iteration`], so runtimes
+    /// can attach what they tracked during it, such as time spent sleeping.
+    pub async fn run_iterations_with<F, Fut, E, R>(
+        &self,
+        mut iteration: F,
+        mut record: R,
+    ) -> std::result::Result<(), E>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = std::result::Result<(), E>>,
+        E: std::fmt::Display,
+        R: FnMut(std::time::Duration, bool),
+    {
+        self.ready_barrier.wait().await;
+
+        // Warm-up iterations are not recorded; the runner discards their metrics.
+        if !self.has_init_error() {
+            for _ in 0..self.warmup_iterations {
+                if let Err(err) = iteration().await {
+                    self.set_init_error(format!("warm-up iteration failed: {err}"));
+                    self.warmup_barrier.wait().await;
+                    return Err(err);
+                }
+            }
+        }
+        self.warmup_barrier.wait().await;
+
+        self.start_signal.wait().await;
+        let run_started = self.run_started.get().copied().unwrap_or_else(Instant::now);
+        let _active = self.enter_active_vu();
+        let mut rng = fastrand::Rng::new();
+
+        loop {
+            if self.abort.is_aborted() {
+                break;
+            }
+
+            match &self.work {
+                VuWork::Constant { gate } => {
+                    if !gate.next() {
+                        break;
+                    }
+                }
+                VuWork::RampingVus { schedule } => {
+                    let elapsed = run_started.elapsed();
+                    if schedule.is_done(elapsed) {
+                        break;
+                    }
+                    if self.scenario_vu > schedule.target_at(elapsed) {
+                        let wait = schedule.next_recheck_in(elapsed, self.scenario_vu);
+                        tokio::select! {
+                            () = tokio::time::sleep(wait.max(std::time::Duration::from_millis(1))) => {}
+                            () = self.abort.wait() => {}
+                        }
+                        continue;
+                    }
+                }
+                VuWork::RampingArrivalRate {
+                    schedule, pacer, ..
+                } => {
+                    let draining = schedule.is_done(run_started.elapsed()) && pacer.is_done();
+                    if !draining && self.scenario_vu > pacer.active_vus() {
+                        pacer.wait_for_update().await;
+                        continue;
+                    }
+                    if !pacer.claim_next().await {
+                        break;
+                    }
+                }
+            }
+
+            let started = Instant::now();
+            let res = iteration().await;
+            record(started.elapsed(), res.is_ok());
+            res?;
+            self.think(&mut rng).await;
+        }
+
+        Ok(())
+    }
+
     pub fn record_iteration(&self, duration: std::time::Duration, success: bool) {
+        self.record_iteration_with_sleep(duration, std::time::Duration::ZERO, success);
+    }
+
+    /// Like [`Self::record_iteration`], for an iteration that spent `slept` of its `duration`
+    /// in explicit sleeps; that part is left out of `iteration_active_duration`.
+    pub fn record_iteration_with_sleep(
+        &self,
+        duration: std::time::Duration,
+        slept: std::time::Duration,
+        success: bool,
+    ) {
         let extra_tags = self
             .metrics_ctx
             .scenario_tag_refs(&["scenario", "status", "group"]);
@@ -157,8 +401,64 @@ pub fn record_iteration(&self, duration: std::time::Duration, success: bool) {
                 scenario: self.metrics_ctx.scenario(),
                 success,
                 duration,
+                slept,
             },
             &extra_tags,
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constant_clock(duration: Option<Duration>) -> (ScenarioClock, Arc<IterationGate>) {
+        let gate = Arc::new(IterationGate::new(None, duration));
+        let clock = ScenarioClock {
+            work: VuWork::Constant { gate: gate.clone() },
+            run_started: Arc::new(OnceLock::new()),
+            abort: Arc::new(AbortSignal::default()),
+        };
+        (clock, gate)
+    }
+
+    #[test]
+    fn clock_has_no_deadline_until_the_scenario_starts() {
+        let (clock, gate) = constant_clock(Some(Duration::from_secs(60)));
+        assert_eq!(clock.remaining(), None);
+
+        gate.start();
+        let left = clock.remaining().unwrap_or_default();
+        assert!(left > Duration::from_secs(59) && left <= Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn sleep_is_clamped_to_the_remaining_time() {
+        let (clock, gate) = constant_clock(Some(Duration::from_millis(50)));
+        gate.start();
+
+        let slept = clock.sleep(Duration::from_secs(10)).await;
+        assert!(slept <= Duration::from_millis(50), "slept {slept:?}");
+        assert_eq!(clock.remaining(), Some(Duration::ZERO));
+        assert_eq!(clock.sleep(Duration::from_secs(1)).await, Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn sleep_without_a_deadline_is_not_clamped() {
+        let (clock, _) = constant_clock(None);
+        let slept = clock.sleep(Duration::from_millis(20)).await;
+        assert_eq!(slept, Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn sleep_returns_early_when_the_run_is_aborted() {
+        let (clock, _) = constant_clock(None);
+        let abort = clock.abort.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            abort.abort("stop".to_string());
+        });
+        let slept = clock.sleep(Duration::from_secs(10)).await;
+        assert!(slept < Duration::from_secs(5), "slept {slept:?}");
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use tonic::metadata::{MetadataKey, MetadataValue};
 use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
@@ -9,8 +9,9 @@
 
 use super::codec_bytes::BytesCodec;
 use super::metadata::metadata_to_pairs;
+use super::status_details::decode_status_details;
 use super::wire::{decode_value_for_method, encode_value_for_method};
-use super::{ConnectOptions, Error, InvokeOptions, Result, UnaryResult};
+use super::{ConnectOptions, Error, GrpcTransportErrorKind, InvokeOptions, Result, UnaryResult};
 
 #[derive(Debug, Clone)]
 pub struct GrpcClient {
@@ -18,7 +19,90 @@ pub struct GrpcClient {
     rr: Arc<AtomicUsize>,
 }
 
+/// Which limit fired while waiting on a streaming call.
+#[derive(Debug, Clone, Copy)]
+enum StreamTimeout {
+    /// The overall call deadline (`InvokeOptions::timeout`) elapsed.
+    Call,
+    /// No message arrived within `InvokeOptions::message_timeout`.
+    Message,
+}
+
+/// Await `fut`, bounded by the overall call `deadline` and a fresh per-message idle window.
+async fn await_stream_step<F: std::future::Future>(
+    fut: F,
+    deadline: Option<tokio::time::Instant>,
+    message_timeout: Option<Duration>,
+) -> std::result::Result<F::Output, StreamTimeout> {
+    let idle_deadline = message_timeout.map(|t| tokio::time::Instant::now() + t);
+
+    let limit = match (deadline, idle_deadline) {
+        (Some(call), Some(idle)) if idle < call => Some((idle, StreamTimeout::Message)),
+        (Some(call), _) => Some((call, StreamTimeout::Call)),
+        (None, Some(idle)) => Some((idle, StreamTimeout::Message)),
+        (None, None) => None,
+    };
+
+    match limit {
+        None => Ok(fut.await),
+        Some((at, which)) => tokio::time::timeout_at(at, fut).await.map_err(|_| which),
+    }
+}
+
+fn build_request(
+    req_bytes: bytes::Bytes,
+    timeout: Option<Duration>,
+    metadata: Vec<(String, String)>,
+) -> Result<tonic::Request<bytes::Bytes>> {
+    let mut request = tonic::Request::new(req_bytes);
+
+    if let Some(timeout) = timeout {
+        request.set_timeout(timeout);
+    }
+
+    for (k, v) in metadata {
+        let key =
+            MetadataKey::from_bytes(k.as_bytes()).map_err(|_| Error::MetadataKey(k.clone()))?;
+        let value = MetadataValue::try_from(v.clone())
+            .map_err(|_| Error::MetadataValue { key: k, value: v })?;
+        request.metadata_mut().insert(key, value);
+    }
+
+    Ok(request)
+}
+
+fn status_result(
+    status: &tonic::Status,
+    response: wrkr_value::Value,
+    headers: Vec<(String, String)>,
+    elapsed: Duration,
+    bytes_sent: u64,
+    bytes_received: u64,
+) -> UnaryResult {
+    // Non-OK gRPC status is a normal protocol outcome.
+    UnaryResult {
+        ok: false,
+        status: Some(status.code() as u16),
+        message: Some(status.message().to_string()),
+        error: Some(status.to_string()),
+        transport_error_kind: None,
+        error_details: decode_status_details(status.details()),
+        response,
+        headers,
+        trailers: metadata_to_pairs(status.metadata()),
+        elapsed,
+        bytes_sent,
+        bytes_received,
+    }
+}
+
 impl GrpcClient {
+    fn next_channel(&self) -> Channel {
+        let i = self.rr.fetch_add(1, Ordering::Relaxed);
+        // Invariant: connect_pooled ensures at least 1 channel.
+        self.channels[i % self.channels.len()].clone()
+    }
+
     async fn unary_inner(
         &self,
         method: &GrpcMethod,
@@ -30,24 +114,9 @@ async fn unary_inner(
         let path = method.path().clone();
 
         let bytes_sent = req_bytes.len() as u64;
-        let mut request = tonic::Request::new(req_bytes);
+        let request = build_request(req_bytes, opts.timeout, opts.metadata)?;
 
-        if let Some(timeout) = opts.timeout {
-            request.set_timeout(timeout);
-        }
-
-        for (k, v) in opts.metadata {
-            let key =
-                MetadataKey::from_bytes(k.as_bytes()).map_err(|_| Error::MetadataKey(k.clone()))?;
-            let value = MetadataValue::try_from(v.clone())
-                .map_err(|_| Error::MetadataValue { key: k, value: v })?;
-            request.metadata_mut().insert(key, value);
-        }
-
-        let i = self.rr.fetch_add(1, Ordering::Relaxed);
-        // Invariant: connect_pooled ensures at least 1 channel.
-        let channel = self.channels[i % self.channels.len()].clone();
-        let mut grpc = tonic::client::Grpc::new(channel);
+        let mut grpc = tonic::client::Grpc::new(self.next_channel());
         let codec = BytesCodec;
 
         grpc.ready().await.map_err(Error::Connect)?;
@@ -70,6 +139,7 @@ async fn unary_inner(
                     message: None,
                     error: None,
                     transport_error_kind: None,
+                    error_details: None,
                     response,
                     headers,
                     trailers: Vec::new(),
@@ -78,26 +148,149 @@ async fn unary_inner(
                     bytes_received,
                 })
             }
-            Err(status) => {
-                // Non-OK gRPC status is a normal protocol outcome.
-                let code = status.code() as u16;
-                let trailers = metadata_to_pairs(status.metadata());
+            Err(status) => Ok(status_result(
+                &status,
+                wrkr_value::Value::Null,
+                Vec::new(),
+                elapsed,
+                bytes_sent,
+                0,
+            )),
+        }
+    }
 
-                Ok(UnaryResult {
-                    ok: false,
-                    status: Some(code),
-                    message: Some(status.message().to_string()),
-                    error: Some(status.to_string()),
-                    transport_error_kind: None,
-                    response: wrkr_value::Value::Null,
-                    headers: Vec::new(),
-                    trailers,
-                    elapsed,
+    async fn server_streaming_inner(
+        &self,
+        method: &GrpcMethod,
+        req_bytes: bytes::Bytes,
+        opts: InvokeOptions,
+    ) -> Result<UnaryResult> {
+        let started = Instant::now();
+        let deadline = opts.timeout.map(|t| tokio::time::Instant::now() + t);
+        let message_timeout = opts.message_timeout;
+
+        let path = method.path().clone();
+
+        let bytes_sent = req_bytes.len() as u64;
+        let request = build_request(req_bytes, opts.timeout, opts.metadata)?;
+
+        let mut grpc = tonic::client::Grpc::new(self.next_channel());
+        let codec = BytesCodec;
+
+        grpc.ready().await.map_err(Error::Connect)?;
+
+        let mut headers = Vec::new();
+        let mut messages = Vec::new();
+        let mut bytes_received = 0u64;
+
+        let timed_out = |which: StreamTimeout,
+                         messages: Vec<wrkr_value::Value>,
+                         headers: Vec<(String, String)>,
+                         bytes_received: u64| {
+            let error = match which {
+                // Reported as the status a server-side deadline would have produced.
+                StreamTimeout::Call => {
+                    let status = tonic::Status::deadline_exceeded(format!(
+                        "stream exceeded call timeout of {:?}",
+                        opts.timeout.unwrap_or_default()
+                    ));
+                    return status_result(
+                        &status,
+                        wrkr_value::Value::Array(messages),
+                        headers,
+                        started.elapsed(),
+                        bytes_sent,
+                        bytes_received,
+                    );
+                }
+                StreamTimeout::Message => format!(
+                    "no stream message received within {:?}",
+                    message_timeout.unwrap_or_default()
+                ),
+            };
+
+            UnaryResult {
+                ok: false,
+                status: None,
+                message: Some(error.clone()),
+                error: Some(error),
+                transport_error_kind: Some(GrpcTransportErrorKind::MessageTimeout),
+                error_details: None,
+                response: wrkr_value::Value::Array(messages),
+                headers,
+                trailers: Vec::new(),
+                elapsed: started.elapsed(),
+                bytes_sent,
+                bytes_received,
+            }
+        };
+
+        let mut stream = match await_stream_step(
+            grpc.server_streaming(request, path, codec),
+            deadline,
+            message_timeout,
+        )
+        .await
+        {
+            Ok(Ok(res)) => {
+                headers = metadata_to_pairs(res.metadata());
+                res.into_inner()
+            }
+            Ok(Err(status)) => {
+                return Ok(status_result(
+                    &status,
+                    wrkr_value::Value::Array(messages),
+                    headers,
+                    started.elapsed(),
                     bytes_sent,
-                    bytes_received: 0,
-                })
+                    bytes_received,
+                ));
+            }
+            Err(which) => return Ok(timed_out(which, messages, headers, bytes_received)),
+        };
+
+        loop {
+            match await_stream_step(stream.message(), deadline, message_timeout).await {
+                Ok(Ok(Some(decoded))) => {
+                    bytes_received = bytes_received.saturating_add(decoded.bytes.len() as u64);
+                    let msg =
+                        decode_value_for_method(method, decoded.bytes).map_err(Error::Decode)?;
+                    messages.push(msg);
+                }
+                Ok(Ok(None)) => break,
+                Ok(Err(status)) => {
+                    return Ok(status_result(
+                        &status,
+                        wrkr_value::Value::Array(messages),
+                        headers,
+                        started.elapsed(),
+                        bytes_sent,
+                        bytes_received,
+                    ));
+                }
+                Err(which) => return Ok(timed_out(which, messages, headers, bytes_received)),
             }
         }
+
+        let trailers = match stream.trailers().await {
+            Ok(Some(md)) => metadata_to_pairs(&md),
+            _ => Vec::new(),
+        };
+
+        Ok(UnaryResult {
+            ok: true,
+            status: Some(0),
+            message: None,
+            error: None,
+            transport_error_kind: None,
+            error_details: None,
+            response: wrkr_value::Value::Array(messages),
+            headers,
+            trailers,
+            elapsed: started.elapsed(),
+            bytes_sent,
+            bytes_received,
+        })
     }
 
     pub async fn connect(target: &str, opts: ConnectOptions) -> Result<Self> {
@@ -119,7 +312,20 @@ pub async fn connect_pooled(
             format!("http://{target}")
         };
 
-        let mut endpoint = Endpoint::from_shared(uri)?;
+        // tonic can neither skip certificate verification nor disable session resumption, so
+        // those channels do their own TLS.
+        let own_tls = match opts
+            .tls
+            .as_ref()
+            .filter(|tls| super::tls::needs_own_tls(tls))
+        {
+            Some(tls) => Some(super::tls::OwnTls::new(tls)?),
+            None => None,
+        };
+        let mut endpoint = match own_tls {
+            Some(_) => super::tls::own_tls_endpoint(&uri)?,
+            None => Endpoint::from_shared(uri)?,
+        };
 
         // Throughput-sensitive defaults for local perf runs.
         // A larger buffer reduces time spent waiting for tower Buffer capacity
@@ -133,15 +339,13 @@ pub async fn connect_pooled(
             endpoint = endpoint.connect_timeout(timeout);
         }
 
-        if let Some(tls) = opts.tls {
+        if let Some(tls) = opts.tls.filter(|tls| !super::tls::needs_own_tls(tls)) {
             let mut tls_cfg = ClientTlsConfig::new();
 
             if let Some(domain) = tls.domain_name {
                 tls_cfg = tls_cfg.domain_name(domain);
             }
 
-            let _ = tls.insecure_skip_verify;
-
             if let Some(ca_pem) = tls.ca_pem {
                 tls_cfg = tls_cfg.ca_certificate(Certificate::from_pem(ca_pem));
             }
@@ -155,7 +359,10 @@ pub async fn connect_pooled(
 
         let mut channels: Vec<Channel> = Vec::with_capacity(pool_size);
         for _ in 0..pool_size {
-            let channel = endpoint.clone().connect().await.map_err(Error::Connect)?;
+            let channel = match &own_tls {
+                Some(tls) => tls.connect(&endpoint).await?,
+                None => endpoint.clone().connect().await.map_err(Error::Connect)?,
+            };
             channels.push(channel);
         }
 
@@ -183,4 +390,395 @@ pub async fn unary_bytes(
     ) -> Result<UnaryResult> {
         self.unary_inner(method, req_bytes, opts).await
     }
+
+    /// Server-streaming call: reads messages until the server closes the stream.
+    ///
+    /// `response` is an array of every decoded message (including those received before a
+    /// failure). `opts.timeout` bounds the whole call and surfaces as `DEADLINE_EXCEEDED`;
+    /// `opts.message_timeout` bounds the gap between messages and surfaces as a
+    /// `message_timeout` transport error.
+    pub async fn server_streaming(
+        &self,
+        method: &GrpcMethod,
+        req: wrkr_value::Value,
+        opts: InvokeOptions,
+    ) -> Result<UnaryResult> {
+        let bytes = encode_value_for_method(method, &req).map_err(Error::Encode)?;
+        self.server_streaming_inner(method, bytes, opts).await
+    }
+
+    pub async fn server_streaming_bytes(
+        &self,
+        method: &GrpcMethod,
+        req_bytes: bytes::Bytes,
+        opts: InvokeOptions,
+    ) -> Result<UnaryResult> {
+        self.server_streaming_inner(method, req_bytes, opts).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+
+    use tokio_stream::{Stream, StreamExt as _};
+    use tonic::codegen::{BoxFuture, Service, http};
+
+    use super::*;
+    use crate::codec_bytes::DecodedBytes;
+
+    type MessageStream =
+        Pin<Box<dyn Stream<Item = std::result::Result<bytes::Bytes, tonic::Status>> + Send>>;
+
+    /// Serves `grpc.health.v1.Health/Check` as a server stream: `count` `SERVING` messages
+    /// `gap` apart, then `end`.
+    #[derive(Clone)]
+    struct HealthStream {
+        count: usize,
+        gap: Duration,
+        end: StreamEnd,
+    }
+
+    #[derive(Clone)]
+    enum StreamEnd {
+        Close,
+        Stall,
+        Fail(tonic::Status),
+    }
+
+    impl tonic::server::NamedService for HealthStream {
+        const NAME: &'static str = "grpc.health.v1.Health";
+    }
+
+    impl tonic::server::ServerStreamingService<DecodedBytes> for HealthStream {
+        type Response = bytes::Bytes;
+        type ResponseStream = MessageStream;
+        type Future = BoxFuture<tonic::Response<MessageStream>, tonic::Status>;
+
+        fn call(&mut self, _request: tonic::Request<DecodedBytes>) -> Self::Future {
+            let Self { count, gap, end } = self.clone();
+            Box::pin(async move {
+                let messages = tokio_stream::iter(0..count).then(move |_| async move {
+                    tokio::time::sleep(gap).await;
+                    Ok(bytes::Bytes::from_static(b"\x08\x01"))
+                });
+                let stream: MessageStream = match end {
+                    StreamEnd::Close => Box::pin(messages),
+                    StreamEnd::Stall => Box::pin(messages.chain(tokio_stream::pending())),
+                    StreamEnd::Fail(status) => {
+                        Box::pin(messages.chain(tokio_stream::once(Err(status))))
+                    }
+                };
+                Ok(tonic::Response::new(stream))
+            })
+        }
+    }
+
+    impl Service<http::Request<tonic::body::Body>> for HealthStream {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::result::Result<(), Self::Error>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: http::Request<tonic::body::Body>) -> Self::Future {
+            let svc = self.clone();
+            Box::pin(async move {
+                Ok(tonic::server::Grpc::new(BytesCodec)
+                    .server_streaming(svc, req)
+                    .await)
+            })
+        }
+    }
+
+    async fn serve(svc: HealthStream) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap_or_else(|e| panic!("bind: {e}"));
+        let addr = listener
+            .local_addr()
+            .unwrap_or_else(|e| panic!("local_addr: {e}"));
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(svc)
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+        addr.to_string()
+    }
+
+    /// Like [`serve`], over TLS with the self-signed `localhost` certificate from
+    /// `tests/fixtures`. Returns `localhost:<port>`.
+    async fn serve_tls(svc: HealthStream) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap_or_else(|e| panic!("bind: {e}"));
+        let port = listener
+            .local_addr()
+            .unwrap_or_else(|e| panic!("local_addr: {e}"))
+            .port();
+        let identity = Identity::from_pem(
+            include_bytes!("../tests/fixtures/localhost.crt"),
+            include_bytes!("../tests/fixtures/localhost.key"),
+        );
+        let mut server = tonic::transport::Server::builder()
+            .tls_config(tonic::transport::ServerTlsConfig::new().identity(identity))
+            .unwrap_or_else(|e| panic!("tls config: {e}"));
+        tokio::spawn(
+            server
+                .add_service(svc)
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+        format!("localhost:{port}")
+    }
+
+    /// Like [`serve_tls`], but TLS 1.2 only (its sessions are stored as soon as the handshake
+    /// ends) and counting the handshakes that resumed a session.
+    async fn serve_tls_counting_resumptions(svc: HealthStream) -> (String, Arc<AtomicUsize>) {
+        use rustls::pki_types::pem::PemObject as _;
+        use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+        let cert =
+            CertificateDer::from_pem_slice(include_bytes!("../tests/fixtures/localhost.crt"))
+                .unwrap_or_else(|e| panic!("cert: {e}"));
+        let key = PrivateKeyDer::from_pem_slice(include_bytes!("../tests/fixtures/localhost.key"))
+            .unwrap_or_else(|e| panic!("key: {e}"));
+        let mut config = rustls::ServerConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_protocol_versions(&[&rustls::version::TLS12])
+        .unwrap_or_else(|e| panic!("versions: {e}"))
+        .with_no_client_auth()
+        .with_single_cert(vec![cert], key)
+        .unwrap_or_else(|e| panic!("server config: {e}"));
+        config.alpn_protocols = vec![b"h2".to_vec()];
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap_or_else(|e| panic!("bind: {e}"));
+        let port = listener
+            .local_addr()
+            .unwrap_or_else(|e| panic!("local_addr: {e}"))
+            .port();
+        let resumed = Arc::new(AtomicUsize::new(0));
+        let counter = resumed.clone();
+        let incoming = tokio_stream::wrappers::TcpListenerStream::new(listener).then(move |tcp| {
+            let acceptor = acceptor.clone();
+            let counter = counter.clone();
+            async move {
+                let tls = acceptor.accept(tcp?).await?;
+                if tls.get_ref().1.handshake_kind() == Some(rustls::HandshakeKind::Resumed) {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+                Ok::<_, std::io::Error>(tls)
+            }
+        });
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(svc)
+                .serve_with_incoming(incoming),
+        );
+        (format!("localhost:{port}"), resumed)
+    }
+
+    async fn stream_health(svc: HealthStream, opts: InvokeOptions) -> UnaryResult {
+        let target = serve(svc).await;
+        let client = GrpcClient::connect(&target, ConnectOptions::default())
+            .await
+            .unwrap_or_else(|e| panic!("connect: {e}"));
+        let method = crate::health_check_method().unwrap_or_else(|e| panic!("method: {e}"));
+        client
+            .server_streaming_bytes(&method, bytes::Bytes::from_static(b"\x0a\x01x"), opts)
+            .await
+            .unwrap_or_else(|e| panic!("call: {e}"))
+    }
+
+    #[tokio::test]
+    async fn stream_steps_report_which_limit_fired() {
+        let never = std::future::pending::<()>;
+        let now = tokio::time::Instant::now();
+        let short = Duration::from_millis(20);
+        let long = Duration::from_secs(30);
+
+        let res = await_stream_step(never(), Some(now + long), Some(short)).await;
+        assert!(matches!(res, Err(StreamTimeout::Message)), "{res:?}");
+
+        let res = await_stream_step(never(), Some(now + short), Some(long)).await;
+        assert!(matches!(res, Err(StreamTimeout::Call)), "{res:?}");
+
+        let res = await_stream_step(async { 7 }, None, None).await;
+        assert!(matches!(res, Ok(7)), "{res:?}");
+    }
+
+    #[tokio::test]
+    async fn server_streaming_collects_every_message() {
+        let svc = HealthStream {
+            count: 3,
+            gap: Duration::from_millis(5),
+            end: StreamEnd::Close,
+        };
+        let res = stream_health(svc, InvokeOptions::default()).await;
+
+        assert!(res.ok, "{:?}", res.error);
+        assert_eq!(res.status, Some(0));
+        assert_eq!(res.bytes_received, 6);
+        let wrkr_value::Value::Array(messages) = &res.response else {
+            panic!("expected an array, got {:?}", res.response);
+        };
+        assert_eq!(messages.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn a_stalled_stream_fails_on_the_message_timeout() {
+        let svc = HealthStream {
+            count: 2,
+            gap: Duration::from_millis(5),
+            end: StreamEnd::Stall,
+        };
+        let opts = InvokeOptions {
+            timeout: Some(Duration::from_secs(30)),
+            message_timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+        let res = stream_health(svc, opts).await;
+
+        assert!(!res.ok);
+        assert_eq!(res.status, None);
+        assert!(matches!(
+            res.transport_error_kind,
+            Some(GrpcTransportErrorKind::MessageTimeout)
+        ));
+        // Messages received before the stall are kept.
+        assert!(matches!(&res.response, wrkr_value::Value::Array(m) if m.len() == 2));
+    }
+
+    #[tokio::test]
+    async fn a_stream_past_the_call_timeout_is_deadline_exceeded() {
+        let svc = HealthStream {
+            count: 100,
+            gap: Duration::from_millis(20),
+            end: StreamEnd::Close,
+        };
+        let opts = InvokeOptions {
+            timeout: Some(Duration::from_millis(150)),
+            message_timeout: Some(Duration::from_secs(10)),
+            ..Default::default()
+        };
+        let res = stream_health(svc, opts).await;
+
+        assert!(!res.ok);
+        assert_eq!(res.status, Some(tonic::Code::DeadlineExceeded as u16));
+        assert!(res.transport_error_kind.is_none());
+    }
+
+    #[tokio::test]
+    async fn a_stream_failing_mid_way_keeps_the_error_details() {
+        // google.rpc.Status { code: 8, message: "quota" }
+        let details = bytes::Bytes::from_static(b"\x08\x08\x12\x05quota");
+        let svc = HealthStream {
+            count: 1,
+            gap: Duration::from_millis(5),
+            end: StreamEnd::Fail(tonic::Status::with_details(
+                tonic::Code::ResourceExhausted,
+                "quota",
+                details,
+            )),
+        };
+        let res = stream_health(svc, InvokeOptions::default()).await;
+
+        assert!(!res.ok);
+        assert_eq!(res.status, Some(tonic::Code::ResourceExhausted as u16));
+        assert!(matches!(&res.response, wrkr_value::Value::Array(m) if m.len() == 1));
+        let Some(wrkr_value::Value::Object(fields)) = &res.error_details else {
+            panic!("expected error details, got {:?}", res.error_details);
+        };
+        assert!(matches!(
+            fields.get("code"),
+            Some(wrkr_value::Value::I64(8))
+        ));
+    }
+
+    #[tokio::test]
+    async fn insecure_tls_accepts_a_self_signed_certificate() {
+        let svc = HealthStream {
+            count: 1,
+            gap: Duration::ZERO,
+            end: StreamEnd::Close,
+        };
+        let target = serve_tls(svc).await;
+        let opts = |insecure_skip_verify| ConnectOptions {
+            timeout: Some(Duration::from_secs(5)),
+            tls: Some(crate::TlsConfig {
+                insecure_skip_verify,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let verified = GrpcClient::connect(&target, opts(false)).await;
+        assert!(matches!(verified, Err(Error::Connect(_))), "{verified:?}");
+
+        let client = GrpcClient::connect(&target, opts(true))
+            .await
+            .unwrap_or_else(|e| panic!("connect: {e}"));
+        let method = crate::health_check_method().unwrap_or_else(|e| panic!("method: {e}"));
+        let res = client
+            .server_streaming_bytes(
+                &method,
+                bytes::Bytes::from_static(b"\x0a\x01x"),
+                InvokeOptions::default(),
+            )
+            .await
+            .unwrap_or_else(|e| panic!("call: {e}"));
+        assert!(res.ok, "{:?}", res.error);
+        assert_eq!(res.status, Some(0));
+    }
+
+    #[tokio::test]
+    async fn pooled_channels_resume_tls_sessions_unless_disabled() {
+        for (disable_session_resumption, expected) in [(false, 1), (true, 0)] {
+            let svc = HealthStream {
+                count: 1,
+                gap: Duration::ZERO,
+                end: StreamEnd::Close,
+            };
+            let (target, resumed) = serve_tls_counting_resumptions(svc).await;
+            let opts = ConnectOptions {
+                timeout: Some(Duration::from_secs(5)),
+                tls: Some(crate::TlsConfig {
+                    insecure_skip_verify: true,
+                    disable_session_resumption,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+
+            let client = GrpcClient::connect_pooled(&target, opts, 2)
+                .await
+                .unwrap_or_else(|e| panic!("connect: {e}"));
+            // A call on each channel: the server counts a handshake before serving on it.
+            let method = crate::health_check_method().unwrap_or_else(|e| panic!("method: {e}"));
+            for _ in 0..2 {
+                let res = client
+                    .server_streaming_bytes(
+                        &method,
+                        bytes::Bytes::from_static(b"\x0a\x01x"),
+                        InvokeOptions::default(),
+                    )
+                    .await
+                    .unwrap_or_else(|e| panic!("call: {e}"));
+                assert!(res.ok, "{:?}", res.error);
+            }
+            assert_eq!(
+                resumed.load(Ordering::Relaxed),
+                expected,
+                "disable_session_resumption: {disable_session_resumption}"
+            );
+        }
+    }
 }
@@ -10,6 +10,9 @@ pub enum Error {
     #[error("failed to connect: {0}")]
     Connect(#[source] tonic::transport::Error),
 
+    #[error("invalid TLS config: {0}")]
+    InvalidTls(String),
+
     #[error("invalid metadata key: {0}")]
     MetadataKey(String),
 
@@ -32,6 +35,7 @@ pub fn transport_error_kind(&self) -> GrpcTransportErrorKind {
         match self {
             Self::InvalidEndpoint(_) => GrpcTransportErrorKind::InvalidEndpoint,
             Self::Connect(_) => GrpcTransportErrorKind::Connect,
+            Self::InvalidTls(_) => GrpcTransportErrorKind::InvalidEndpoint,
             Self::MetadataKey(_) => GrpcTransportErrorKind::MetadataKey,
             Self::MetadataValue { .. } => GrpcTransportErrorKind::MetadataValue,
             Self::InvalidMethodPath => GrpcTransportErrorKind::InvalidMethodPath,
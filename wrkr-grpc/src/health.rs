@@ -0,0 +1,123 @@
+//! Bundled `grpc.health.v1` schema, so health probes work without loading `health.proto`.
+
+use std::sync::{Arc, OnceLock};
+
+use prost_types::{
+    DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+    FileDescriptorProto, FileDescriptorSet, MethodDescriptorProto, ServiceDescriptorProto,
+    field_descriptor_proto::{Label, Type},
+};
+
+use crate::proto::{GrpcMethod, ProtoSchema, Result};
+
+pub const HEALTH_CHECK_METHOD: &str = "grpc.health.v1.Health/Check";
+
+static METHOD: OnceLock<Arc<GrpcMethod>> = OnceLock::new();
+
+/// `grpc.health.v1.Health/Check`, resolved from the bundled descriptor.
+pub fn health_check_method() -> Result<Arc<GrpcMethod>> {
+    if let Some(method) = METHOD.get() {
+        return Ok(method.clone());
+    }
+
+    let schema = ProtoSchema::from_file_descriptor_set(FileDescriptorSet {
+        file: vec![health_proto()],
+    })?;
+    let method = Arc::new(schema.method(HEALTH_CHECK_METHOD)?);
+    Ok(METHOD.get_or_init(|| method).clone())
+}
+
+fn field(name: &str, number: i32, r#type: Type, type_name: Option<&str>) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        label: Some(Label::Optional as i32),
+        r#type: Some(r#type as i32),
+        type_name: type_name.map(str::to_string),
+        json_name: Some(name.to_string()),
+        ..Default::default()
+    }
+}
+
+/// The unary part of `grpc/health/v1/health.proto` (`Watch` is left out).
+fn health_proto() -> FileDescriptorProto {
+    let serving_status = EnumDescriptorProto {
+        name: Some("ServingStatus".to_string()),
+        value: ["UNKNOWN", "SERVING", "NOT_SERVING", "SERVICE_UNKNOWN"]
+            .into_iter()
+            .zip(0..)
+            .map(|(name, number)| EnumValueDescriptorProto {
+                name: Some(name.to_string()),
+                number: Some(number),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    };
+
+    FileDescriptorProto {
+        name: Some("grpc/health/v1/health.proto".to_string()),
+        package: Some("grpc.health.v1".to_string()),
+        message_type: vec![
+            DescriptorProto {
+                name: Some("HealthCheckRequest".to_string()),
+                field: vec![field("service", 1, Type::String, None)],
+                ..Default::default()
+            },
+            DescriptorProto {
+                name: Some("HealthCheckResponse".to_string()),
+                field: vec![field(
+                    "status",
+                    1,
+                    Type::Enum,
+                    Some(".grpc.health.v1.HealthCheckResponse.ServingStatus"),
+                )],
+                enum_type: vec![serving_status],
+                ..Default::default()
+            },
+        ],
+        service: vec![ServiceDescriptorProto {
+            name: Some("Health".to_string()),
+            method: vec![MethodDescriptorProto {
+                name: Some("Check".to_string()),
+                input_type: Some(".grpc.health.v1.HealthCheckRequest".to_string()),
+                output_type: Some(".grpc.health.v1.HealthCheckResponse".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wire::{decode_value_for_method, encode_value_for_method};
+
+    #[test]
+    fn bundled_health_check_round_trips() {
+        let method = health_check_method().unwrap_or_else(|e| panic!("health schema: {e}"));
+        assert_eq!(method.path().path(), "/grpc.health.v1.Health/Check");
+
+        let mut req = wrkr_value::ObjectMap::default();
+        req.insert(
+            Arc::from("service"),
+            wrkr_value::Value::String(Arc::from("x")),
+        );
+        let bytes = encode_value_for_method(&method, &wrkr_value::Value::Object(req))
+            .unwrap_or_else(|e| panic!("encode: {e}"));
+        assert_eq!(bytes.as_ref(), b"\x0a\x01x");
+
+        let res = decode_value_for_method(&method, bytes::Bytes::from_static(b"\x08\x01"))
+            .unwrap_or_else(|e| panic!("decode: {e}"));
+        let wrkr_value::Value::Object(res) = res else {
+            panic!("expected object, got {res:?}");
+        };
+        assert_eq!(
+            res.get("status"),
+            Some(&wrkr_value::Value::String(Arc::from("SERVING")))
+        );
+    }
+}
@@ -8,4 +8,5 @@ pub enum GrpcTransportErrorKind {
     InvalidMethodPath,
     Encode,
     Decode,
+    MessageTimeout,
 }
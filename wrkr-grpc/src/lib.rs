@@ -3,15 +3,19 @@
 mod client;
 mod codec_bytes;
 mod error;
+mod health;
 mod kind;
 mod metadata;
 mod proto;
 pub mod shared;
+mod status_details;
+mod tls;
 mod types;
 mod wire;
 
 pub use client::GrpcClient;
 pub use error::{Error, Result};
+pub use health::{HEALTH_CHECK_METHOD, health_check_method};
 pub use kind::GrpcTransportErrorKind;
 pub use proto::{Error as ProtoError, GrpcMethod, ProtoSchema};
 pub use shared::SharedGrpcRegistry;
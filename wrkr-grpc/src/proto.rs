@@ -247,8 +247,11 @@ pub fn compile_from_proto(proto_file: &Path, include_paths: &[PathBuf]) -> Resul
 
         let bytes = std::fs::read(out_path)?;
         let fds = prost_types::FileDescriptorSet::decode(bytes.as_slice())?;
-        let pool = DescriptorPool::from_file_descriptor_set(fds)?;
+        Self::from_file_descriptor_set(fds)
+    }
 
+    pub(crate) fn from_file_descriptor_set(fds: prost_types::FileDescriptorSet) -> Result<Self> {
+        let pool = DescriptorPool::from_file_descriptor_set(fds)?;
         Ok(Self { pool })
     }
 
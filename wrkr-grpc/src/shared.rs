@@ -5,7 +5,9 @@
 
 use tokio::sync::OnceCell;
 
-use crate::{ConnectOptions, GrpcClient, GrpcMethod, ProtoError, ProtoSchema};
+use crate::{
+    ConnectOptions, GrpcClient, GrpcMethod, InvokeOptions, ProtoError, ProtoSchema, UnaryResult,
+};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -20,6 +22,9 @@ pub enum Error {
     #[error("grpc client: call load() first")]
     NotLoaded,
 
+    #[error("grpc client: call connect() first")]
+    NotConnected,
+
     #[error(transparent)]
     Proto(#[from] ProtoError),
 
@@ -38,6 +43,7 @@ struct ConnectSpec {
     target: String,
     timeout: Option<Duration>,
     tls: Option<ConnectSpecTls>,
+    metadata: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -47,6 +53,7 @@ struct ConnectSpecTls {
     identity_key_pem: Option<Vec<u8>>,
     domain_name: Option<String>,
     insecure_skip_verify: bool,
+    disable_session_resumption: bool,
 }
 
 #[derive(Debug)]
@@ -59,6 +66,7 @@ pub struct SharedGrpcClient {
     methods: RwLock<HashMap<Arc<str>, Arc<GrpcMethod>>>,
 
     connect_spec: Mutex<Option<ConnectSpec>>,
+    default_metadata: OnceLock<Vec<(String, String)>>,
     client: OnceCell<Arc<GrpcClient>>,
 }
 
@@ -71,6 +79,7 @@ fn new(pool_size: usize) -> Self {
             schema: OnceLock::new(),
             methods: RwLock::new(HashMap::new()),
             connect_spec: Mutex::new(None),
+            default_metadata: OnceLock::new(),
             client: OnceCell::new(),
         }
     }
@@ -133,7 +142,9 @@ pub async fn connect(&self, target: String, opts: ConnectOptions) -> Result<()>
                 identity_key_pem: tls.identity_key_pem.clone(),
                 domain_name: tls.domain_name.clone(),
                 insecure_skip_verify: tls.insecure_skip_verify,
+                disable_session_resumption: tls.disable_session_resumption,
             }),
+            metadata: opts.metadata.clone(),
         };
 
         {
@@ -143,6 +154,7 @@ pub async fn connect(&self, target: String, opts: ConnectOptions) -> Result<()>
                     return Err(Error::ConnectSpecMismatch);
                 }
             } else {
+                let _ = self.default_metadata.set(spec.metadata.clone());
                 *guard = Some(spec);
             }
         }
@@ -162,6 +174,51 @@ pub async fn connect(&self, target: String, opts: ConnectOptions) -> Result<()>
     pub fn client(&self) -> Option<Arc<GrpcClient>> {
         self.client.get().cloned()
     }
+
+    /// The metadata for one call: the `connect()` defaults, then `per_call`.
+    #[must_use]
+    pub fn call_metadata(&self, per_call: Vec<(String, String)>) -> Vec<(String, String)> {
+        match self.default_metadata.get() {
+            Some(defaults) if !defaults.is_empty() => merge_metadata(defaults, per_call),
+            _ => per_call,
+        }
+    }
+
+    /// Call `grpc.health.v1.Health/Check` for `service` (`""` is the whole server).
+    ///
+    /// Uses the bundled health schema, so it works whether or not `load()` was called.
+    pub async fn health_check(&self, service: &str, opts: InvokeOptions) -> Result<UnaryResult> {
+        let client = self.client().ok_or(Error::NotConnected)?;
+        let method = crate::health_check_method()?;
+        let opts = InvokeOptions {
+            metadata: self.call_metadata(opts.metadata),
+            ..opts
+        };
+
+        let mut req = wrkr_value::ObjectMap::default();
+        req.insert(
+            Arc::from("service"),
+            wrkr_value::Value::String(Arc::from(service)),
+        );
+        Ok(client
+            .unary(&method, wrkr_value::Value::Object(req), opts)
+            .await?)
+    }
+}
+
+/// Defaults whose key appears in `per_call` are dropped, so a call can replace a default value
+/// (including a multi-valued one) without sending both.
+fn merge_metadata(
+    defaults: &[(String, String)],
+    per_call: Vec<(String, String)>,
+) -> Vec<(String, String)> {
+    let mut out: Vec<(String, String)> = defaults
+        .iter()
+        .filter(|(key, _)| !per_call.iter().any(|(k, _)| k.eq_ignore_ascii_case(key)))
+        .cloned()
+        .collect();
+    out.extend(per_call);
+    out
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -197,3 +254,43 @@ pub fn get_or_create(&self, pool_size: usize) -> Arc<SharedGrpcClient> {
             .clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(v: &[(&str, &str)]) -> Vec<(String, String)> {
+        v.iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn per_call_metadata_replaces_defaults_with_the_same_key() {
+        let defaults = pairs(&[
+            ("authorization", "Bearer default"),
+            ("x-tenant", "a"),
+            ("x-tenant", "b"),
+        ]);
+        let merged = merge_metadata(
+            &defaults,
+            pairs(&[("Authorization", "Bearer call"), ("x-request-id", "1")]),
+        );
+        assert_eq!(
+            merged,
+            pairs(&[
+                ("x-tenant", "a"),
+                ("x-tenant", "b"),
+                ("Authorization", "Bearer call"),
+                ("x-request-id", "1"),
+            ])
+        );
+    }
+
+    #[test]
+    fn call_metadata_is_unchanged_before_connect() {
+        let shared = SharedGrpcClient::new(1);
+        let per_call = pairs(&[("x-request-id", "1")]);
+        assert_eq!(shared.call_metadata(per_call.clone()), per_call);
+    }
+}
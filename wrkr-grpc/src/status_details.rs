@@ -0,0 +1,335 @@
+//! Decoding of rich error details (`grpc-status-details-bin`, a `google.rpc.Status`).
+//!
+//! The standard `google.rpc` error detail messages are decoded into objects with a `type` key
+//! (the message's full name) plus their fields in snake_case. Details of any other type are
+//! passed through as `{ type, value }` with the raw protobuf bytes.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use prost::Message as _;
+use wrkr_value::{ObjectMap, Value};
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<prost_types::Any>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ErrorInfo {
+    #[prost(string, tag = "1")]
+    reason: String,
+    #[prost(string, tag = "2")]
+    domain: String,
+    #[prost(map = "string, string", tag = "3")]
+    metadata: HashMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct RetryInfo {
+    #[prost(message, optional, tag = "1")]
+    retry_delay: Option<prost_types::Duration>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct QuotaFailure {
+    #[prost(message, repeated, tag = "1")]
+    violations: Vec<QuotaViolation>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct QuotaViolation {
+    #[prost(string, tag = "1")]
+    subject: String,
+    #[prost(string, tag = "2")]
+    description: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct PreconditionFailure {
+    #[prost(message, repeated, tag = "1")]
+    violations: Vec<PreconditionViolation>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct PreconditionViolation {
+    #[prost(string, tag = "1")]
+    r#type: String,
+    #[prost(string, tag = "2")]
+    subject: String,
+    #[prost(string, tag = "3")]
+    description: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct BadRequest {
+    #[prost(message, repeated, tag = "1")]
+    field_violations: Vec<FieldViolation>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct FieldViolation {
+    #[prost(string, tag = "1")]
+    field: String,
+    #[prost(string, tag = "2")]
+    description: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct RequestInfo {
+    #[prost(string, tag = "1")]
+    request_id: String,
+    #[prost(string, tag = "2")]
+    serving_data: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ResourceInfo {
+    #[prost(string, tag = "1")]
+    resource_type: String,
+    #[prost(string, tag = "2")]
+    resource_name: String,
+    #[prost(string, tag = "3")]
+    owner: String,
+    #[prost(string, tag = "4")]
+    description: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct LocalizedMessage {
+    #[prost(string, tag = "1")]
+    locale: String,
+    #[prost(string, tag = "2")]
+    message: String,
+}
+
+fn object<const N: usize>(fields: [(&str, Value); N]) -> Value {
+    let mut map = ObjectMap::default();
+    for (k, v) in fields {
+        map.insert(Arc::from(k), v);
+    }
+    Value::Object(map)
+}
+
+fn string(s: String) -> Value {
+    Value::String(Arc::from(s))
+}
+
+fn array<T>(items: Vec<T>, f: impl Fn(T) -> Value) -> Value {
+    Value::Array(items.into_iter().map(f).collect())
+}
+
+/// Decode the payload of a `grpc-status-details-bin` trailer.
+///
+/// Returns `None` when the trailer is absent/empty or is not a valid `google.rpc.Status`.
+pub(crate) fn decode_status_details(bytes: &[u8]) -> Option<Value> {
+    if bytes.is_empty() {
+        return None;
+    }
+    let status = RpcStatus::decode(bytes).ok()?;
+
+    Some(object([
+        ("code", Value::I64(i64::from(status.code))),
+        ("message", string(status.message)),
+        ("details", array(status.details, decode_detail)),
+    ]))
+}
+
+fn decode_detail(any: prost_types::Any) -> Value {
+    let type_name = any
+        .type_url
+        .rsplit_once('/')
+        .map_or(any.type_url.as_str(), |(_, name)| name)
+        .to_string();
+
+    decode_known_detail(&type_name, &any.value).unwrap_or_else(|| {
+        object([
+            ("type", string(type_name)),
+            ("value", Value::Bytes(bytes::Bytes::from(any.value))),
+        ])
+    })
+}
+
+fn decode_known_detail(type_name: &str, bytes: &[u8]) -> Option<Value> {
+    let ty = || string(type_name.to_string());
+
+    let value = match type_name {
+        "google.rpc.ErrorInfo" => {
+            let m = ErrorInfo::decode(bytes).ok()?;
+            let mut metadata = ObjectMap::default();
+            for (k, v) in m.metadata {
+                metadata.insert(Arc::from(k), string(v));
+            }
+            object([
+                ("type", ty()),
+                ("reason", string(m.reason)),
+                ("domain", string(m.domain)),
+                ("metadata", Value::Object(metadata)),
+            ])
+        }
+        "google.rpc.RetryInfo" => {
+            let m = RetryInfo::decode(bytes).ok()?;
+            let delay = m.retry_delay.map_or(Value::Null, |d| {
+                Value::F64(d.seconds as f64 + f64::from(d.nanos) / 1e9)
+            });
+            object([("type", ty()), ("retry_delay", delay)])
+        }
+        "google.rpc.QuotaFailure" => {
+            let m = QuotaFailure::decode(bytes).ok()?;
+            let violations = array(m.violations, |v| {
+                object([
+                    ("subject", string(v.subject)),
+                    ("description", string(v.description)),
+                ])
+            });
+            object([("type", ty()), ("violations", violations)])
+        }
+        "google.rpc.PreconditionFailure" => {
+            let m = PreconditionFailure::decode(bytes).ok()?;
+            let violations = array(m.violations, |v| {
+                object([
+                    ("type", string(v.r#type)),
+                    ("subject", string(v.subject)),
+                    ("description", string(v.description)),
+                ])
+            });
+            object([("type", ty()), ("violations", violations)])
+        }
+        "google.rpc.BadRequest" => {
+            let m = BadRequest::decode(bytes).ok()?;
+            let violations = array(m.field_violations, |v| {
+                object([
+                    ("field", string(v.field)),
+                    ("description", string(v.description)),
+                ])
+            });
+            object([("type", ty()), ("field_violations", violations)])
+        }
+        "google.rpc.RequestInfo" => {
+            let m = RequestInfo::decode(bytes).ok()?;
+            object([
+                ("type", ty()),
+                ("request_id", string(m.request_id)),
+                ("serving_data", string(m.serving_data)),
+            ])
+        }
+        "google.rpc.ResourceInfo" => {
+            let m = ResourceInfo::decode(bytes).ok()?;
+            object([
+                ("type", ty()),
+                ("resource_type", string(m.resource_type)),
+                ("resource_name", string(m.resource_name)),
+                ("owner", string(m.owner)),
+                ("description", string(m.description)),
+            ])
+        }
+        "google.rpc.LocalizedMessage" => {
+            let m = LocalizedMessage::decode(bytes).ok()?;
+            object([
+                ("type", ty()),
+                ("locale", string(m.locale)),
+                ("message", string(m.message)),
+            ])
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn any<M: prost::Message>(type_name: &str, msg: &M) -> prost_types::Any {
+        prost_types::Any {
+            type_url: format!("type.googleapis.com/{type_name}"),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    fn field<'a>(v: &'a Value, key: &str) -> &'a Value {
+        match v {
+            Value::Object(map) => map
+                .get(key)
+                .unwrap_or_else(|| panic!("missing key `{key}` in {v:?}")),
+            other => panic!("expected object, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decodes_known_and_unknown_details() {
+        let status = RpcStatus {
+            code: 8,
+            message: "quota exhausted".to_string(),
+            details: vec![
+                any(
+                    "google.rpc.QuotaFailure",
+                    &QuotaFailure {
+                        violations: vec![QuotaViolation {
+                            subject: "project:42".to_string(),
+                            description: "daily limit".to_string(),
+                        }],
+                    },
+                ),
+                any(
+                    "google.rpc.BadRequest",
+                    &BadRequest {
+                        field_violations: vec![FieldViolation {
+                            field: "email".to_string(),
+                            description: "must not be empty".to_string(),
+                        }],
+                    },
+                ),
+                prost_types::Any {
+                    type_url: "type.googleapis.com/acme.Custom".to_string(),
+                    value: vec![1, 2, 3],
+                },
+            ],
+        };
+
+        let decoded = decode_status_details(&status.encode_to_vec())
+            .unwrap_or_else(|| panic!("expected details"));
+
+        assert_eq!(field(&decoded, "code"), &Value::I64(8));
+        let Value::Array(details) = field(&decoded, "details") else {
+            panic!("details must be an array");
+        };
+        assert_eq!(details.len(), 3);
+
+        assert_eq!(
+            field(&details[0], "type"),
+            &Value::String(Arc::from("google.rpc.QuotaFailure"))
+        );
+        let Value::Array(violations) = field(&details[0], "violations") else {
+            panic!("violations must be an array");
+        };
+        assert_eq!(
+            field(&violations[0], "subject"),
+            &Value::String(Arc::from("project:42"))
+        );
+
+        let Value::Array(fields) = field(&details[1], "field_violations") else {
+            panic!("field_violations must be an array");
+        };
+        assert_eq!(
+            field(&fields[0], "field"),
+            &Value::String(Arc::from("email"))
+        );
+
+        assert_eq!(
+            field(&details[2], "value"),
+            &Value::Bytes(bytes::Bytes::from_static(&[1, 2, 3]))
+        );
+    }
+
+    #[test]
+    fn empty_or_garbage_trailer_yields_none() {
+        assert!(decode_status_details(&[]).is_none());
+        assert!(decode_status_details(&[0xff, 0xff, 0xff]).is_none());
+    }
+}
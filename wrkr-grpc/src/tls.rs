@@ -0,0 +1,123 @@
+//! Channels that run the TLS handshake themselves instead of leaving it to tonic, for what
+//! tonic's `ClientTlsConfig` cannot express: skipping server certificate verification
+//! (`TlsConfig::insecure_skip_verify`) and turning session resumption off
+//! (`TlsConfig::disable_session_resumption`).
+//!
+//! These channels are given a plain `http://` endpoint and a connector that does the TLS; the
+//! request origin keeps the `https://` scheme.
+
+use std::sync::Arc;
+
+use rustls::pki_types::pem::PemObject as _;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use tonic::transport::{Channel, Endpoint, Uri};
+use wrkr_tls::SkipVerification;
+
+use super::{Error, Result, TlsConfig};
+
+/// Whether `tls` needs [`OwnTls`] rather than tonic's TLS.
+pub(crate) fn needs_own_tls(tls: &TlsConfig) -> bool {
+    tls.insecure_skip_verify || tls.disable_session_resumption
+}
+
+/// Endpoint for `target` (an `https://` URI) that tonic dials as plain `http://host:port`,
+/// while requests keep the `https://` origin. Connect it with [`OwnTls::connect`].
+pub(crate) fn own_tls_endpoint(target: &str) -> Result<Endpoint> {
+    let origin: Uri = target
+        .parse()
+        .map_err(|_| Error::InvalidTls(format!("invalid endpoint URI: {target}")))?;
+    let host = origin
+        .host()
+        .ok_or_else(|| Error::InvalidTls(format!("missing host in {target}")))?;
+    let port = origin.port_u16().unwrap_or(443);
+    Ok(Endpoint::from_shared(format!("http://{host}:{port}"))?.origin(origin))
+}
+
+/// TLS settings shared by the channels of one client, so that they resume each other's sessions
+/// unless resumption is disabled.
+pub(crate) struct OwnTls {
+    connector: tokio_rustls::TlsConnector,
+    domain_name: Option<String>,
+}
+
+impl OwnTls {
+    pub(crate) fn new(tls: &TlsConfig) -> Result<Self> {
+        Ok(Self {
+            connector: tokio_rustls::TlsConnector::from(Arc::new(client_config(tls)?)),
+            domain_name: tls.domain_name.clone(),
+        })
+    }
+
+    /// One channel over TLS to `endpoint`.
+    pub(crate) async fn connect(&self, endpoint: &Endpoint) -> Result<Channel> {
+        let host = endpoint
+            .uri()
+            .host()
+            .unwrap_or_default()
+            .trim_matches(['[', ']'])
+            .to_string();
+        let server_name =
+            ServerName::try_from(self.domain_name.clone().unwrap_or_else(|| host.clone()))
+                .map_err(|e| Error::InvalidTls(e.to_string()))?;
+        let connector = self.connector.clone();
+
+        let connect = tower::service_fn(move |uri: Uri| {
+            let connector = connector.clone();
+            let server_name = server_name.clone();
+            let host = host.clone();
+            async move {
+                let port = uri.port_u16().unwrap_or(443);
+                let tcp = tokio::net::TcpStream::connect((host.as_str(), port)).await?;
+                tcp.set_nodelay(true)?;
+                let tls = connector.connect(server_name, tcp).await?;
+                Ok::<_, std::io::Error>(hyper_util::rt::TokioIo::new(tls))
+            }
+        });
+
+        endpoint
+            .connect_with_connector(connect)
+            .await
+            .map_err(Error::Connect)
+    }
+}
+
+fn client_config(tls: &TlsConfig) -> Result<rustls::ClientConfig> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| Error::InvalidTls(e.to_string()))?;
+    let builder = if tls.insecure_skip_verify {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(SkipVerification::new(provider)))
+    } else {
+        // Trusts `ca_pem` only, like the `ClientTlsConfig` tonic builds otherwise.
+        let mut roots = rustls::RootCertStore::empty();
+        if let Some(ca) = &tls.ca_pem {
+            let certs = CertificateDer::pem_slice_iter(ca)
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| Error::InvalidTls(e.to_string()))?;
+            roots.add_parsable_certificates(certs);
+        }
+        builder.with_root_certificates(roots)
+    };
+
+    let mut config = match (&tls.identity_pem, &tls.identity_key_pem) {
+        (Some(cert), Some(key)) => {
+            let certs = CertificateDer::pem_slice_iter(cert)
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| Error::InvalidTls(e.to_string()))?;
+            let key =
+                PrivateKeyDer::from_pem_slice(key).map_err(|e| Error::InvalidTls(e.to_string()))?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|e| Error::InvalidTls(e.to_string()))?
+        }
+        _ => builder.with_no_client_auth(),
+    };
+    if tls.disable_session_resumption {
+        config.resumption = rustls::client::Resumption::disabled();
+    }
+    config.alpn_protocols = vec![b"h2".to_vec()];
+    Ok(config)
+}
@@ -8,18 +8,28 @@ pub struct TlsConfig {
     pub identity_pem: Option<Vec<u8>>,
     pub identity_key_pem: Option<Vec<u8>>,
     pub domain_name: Option<String>,
+    /// Accept any server certificate; `ca_pem` is then ignored. Test environments only.
     pub insecure_skip_verify: bool,
+    /// Neither offer session tickets nor session IDs, so every new channel pays for a full
+    /// handshake. By default the channels of a client resume each other's sessions.
+    pub disable_session_resumption: bool,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct ConnectOptions {
     pub timeout: Option<Duration>,
     pub tls: Option<TlsConfig>,
+    /// Sent with every call made through a [`crate::shared::SharedGrpcClient`]; per-call
+    /// metadata with the same key replaces it.
+    pub metadata: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct InvokeOptions {
+    /// Overall call deadline (sent as `grpc-timeout` and enforced locally for streams).
     pub timeout: Option<Duration>,
+    /// Streaming only: maximum idle gap between consecutive messages.
+    pub message_timeout: Option<Duration>,
     pub metadata: Vec<(String, String)>,
 }
 
@@ -30,6 +40,9 @@ pub struct UnaryResult {
     pub message: Option<String>,
     pub error: Option<String>,
     pub transport_error_kind: Option<GrpcTransportErrorKind>,
+    /// Decoded `google.rpc.Status` from the `grpc-status-details-bin` trailer, if the server
+    /// sent one.
+    pub error_details: Option<wrkr_value::Value>,
 
     pub response: wrkr_value::Value,
     pub headers: Vec<(String, String)>,
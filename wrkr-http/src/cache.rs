@@ -0,0 +1,535 @@
+//! Client-side response cache used by [`HttpClient::request_cached`](crate::HttpClient::request_cached).
+//!
+//! Behaves like a browser's private cache: `GET` responses are kept per URL and served without a
+//! request while fresh (`Cache-Control: max-age`, else `Expires`). Stale ones are revalidated with
+//! `If-None-Match`/`If-Modified-Since` when they carry an `ETag`/`Last-Modified`, and a
+//! `304 Not Modified` is answered from the stored copy. There is no heuristic freshness: a response
+//! without `max-age`/`Expires` is revalidated every time (or not kept, without validators).
+//!
+//! A [shared](HttpCache::shared) cache follows the rules of a proxy cache instead (RFC 9111
+//! §3.5, §5.2.2.7): it prefers `s-maxage` to `max-age`, never stores `Cache-Control: private`
+//! responses, and stores the response to a request carrying `Authorization` only when the
+//! response allows it with `public`, `s-maxage` or `must-revalidate`.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use super::{HttpRequest, HttpResponse};
+
+/// Responses kept at most; once full, only URLs already cached are updated.
+const MAX_ENTRIES: usize = 10_000;
+
+/// How a response came out of an [`HttpCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheHit {
+    /// Still fresh: no request was sent.
+    Fresh,
+    /// The server answered a conditional request with `304 Not Modified`.
+    Revalidated,
+}
+
+/// Stored `GET` responses, shared by whoever holds the cache (one VU, or the whole run).
+#[derive(Debug, Default)]
+pub struct HttpCache {
+    entries: Mutex<HashMap<String, Entry>>,
+    shared: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    response: HttpResponse,
+    /// Request headers named by the response's `Vary` (lowercased) and the values they were sent
+    /// with; a request only gets this response back when it sends the same ones.
+    vary: Vec<(String, Option<String>)>,
+    stored_at: Instant,
+    fresh_for: Duration,
+}
+
+/// What to do with a request before it is sent.
+pub(crate) enum Lookup {
+    /// Nothing usable is stored (or the request bypasses the cache): send it as-is.
+    Miss,
+    /// A fresh stored response, to return instead of sending the request.
+    Fresh(HttpResponse),
+    /// Send the request with these validator headers added.
+    Revalidate(Vec<(String, String)>),
+}
+
+impl HttpCache {
+    /// A private cache, like a browser's.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache shared by several users, like a proxy's: one user's personalised responses are
+    /// never served to another.
+    #[must_use]
+    pub fn shared() -> Self {
+        Self {
+            shared: true,
+            ..Self::default()
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn lookup(&self, req: &HttpRequest) -> Lookup {
+        if !uses_cache(req) {
+            return Lookup::Miss;
+        }
+        let entries = self.entries();
+        let Some(entry) = entries.get(&req.url).filter(|e| e.matches(req)) else {
+            return Lookup::Miss;
+        };
+
+        if entry.stored_at.elapsed() < entry.fresh_for && !directives(&req.headers).no_cache {
+            return Lookup::Fresh(HttpResponse {
+                bytes_sent: 0,
+                bytes_received: 0,
+                body_bytes: 0,
+                connection_reused: false,
+                cache_hit: Some(CacheHit::Fresh),
+                ..entry.response.clone()
+            });
+        }
+
+        let headers = &entry.response.headers;
+        let validators: Vec<(String, String)> = [
+            ("if-none-match", header(headers, "etag")),
+            ("if-modified-since", header(headers, "last-modified")),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value?.to_string())))
+        .collect();
+        if validators.is_empty() {
+            Lookup::Miss
+        } else {
+            Lookup::Revalidate(validators)
+        }
+    }
+
+    /// Update the cache with `res`, the response to `req` (as given, before any validators were
+    /// added), and return what the caller gets: the stored response for a `304`, else `res`.
+    pub(crate) fn store(&self, req: &HttpRequest, res: HttpResponse) -> HttpResponse {
+        let mut entries = self.entries();
+        if !uses_cache(req) {
+            // A successful unsafe request may have changed the resource.
+            if !req.method.is_safe() && res.status < 400 {
+                entries.remove(&req.url);
+            }
+            return res;
+        }
+
+        if res.status == 304
+            && let Some(entry) = entries.get_mut(&req.url).filter(|e| e.matches(req))
+        {
+            entry.refresh(&res.headers, self.shared);
+            return HttpResponse {
+                bytes_sent: res.bytes_sent,
+                bytes_received: res.bytes_received,
+                body_bytes: res.body_bytes,
+                connection_reused: res.connection_reused,
+                cache_hit: Some(CacheHit::Revalidated),
+                ..entry.response.clone()
+            };
+        }
+
+        match Entry::new(req, &res, self.shared) {
+            Some(entry) => {
+                if entries.len() < MAX_ENTRIES || entries.contains_key(&req.url) {
+                    entries.insert(req.url.clone(), entry);
+                }
+            }
+            None => {
+                entries.remove(&req.url);
+            }
+        }
+        res
+    }
+}
+
+impl Entry {
+    fn new(req: &HttpRequest, res: &HttpResponse, shared: bool) -> Option<Self> {
+        let d = directives(&res.headers);
+        if res.status != 200 || d.no_store {
+            return None;
+        }
+        if shared {
+            let authorized = header(&req.headers, "authorization").is_some();
+            let shareable = d.public || d.must_revalidate || d.s_maxage.is_some();
+            if d.private || (authorized && !shareable) {
+                return None;
+            }
+        }
+        let mut vary = Vec::new();
+        if let Some(names) = header(&res.headers, "vary") {
+            for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                if name == "*" {
+                    return None;
+                }
+                let value = header(&req.headers, name).map(str::to_string);
+                vary.push((name.to_ascii_lowercase(), value));
+            }
+        }
+
+        let fresh_for = freshness(&res.headers, shared);
+        let has_validator = header(&res.headers, "etag").is_some()
+            || header(&res.headers, "last-modified").is_some();
+        if fresh_for.is_zero() && !has_validator {
+            return None;
+        }
+        Some(Self {
+            response: HttpResponse {
+                cache_hit: None,
+                ..res.clone()
+            },
+            vary,
+            stored_at: Instant::now(),
+            fresh_for,
+        })
+    }
+
+    fn matches(&self, req: &HttpRequest) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| header(&req.headers, name) == value.as_deref())
+    }
+
+    /// Take the headers of a `304` into the stored response and restart its freshness.
+    fn refresh(&mut self, not_modified: &[(String, String)], shared: bool) {
+        let headers = &mut self.response.headers;
+        for (name, value) in not_modified {
+            if matches!(name.as_str(), "content-length" | "transfer-encoding") {
+                continue;
+            }
+            match headers.iter_mut().find(|(k, _)| k == name) {
+                Some((_, v)) => v.clone_from(value),
+                None => headers.push((name.clone(), value.clone())),
+            }
+        }
+        headers.sort_by(|a, b| a.0.cmp(&b.0));
+        self.fresh_for = freshness(headers, shared);
+        self.stored_at = Instant::now();
+    }
+}
+
+/// Whether `req` can be answered from (and stored in) the cache. Requests carrying their own
+/// conditional or range headers are left to the script.
+fn uses_cache(req: &HttpRequest) -> bool {
+    req.method == http::Method::GET
+        && ["if-none-match", "if-modified-since", "range"]
+            .iter()
+            .all(|name| header(&req.headers, name).is_none())
+        && !directives(&req.headers).no_store
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+/// The `Cache-Control` directives the cache acts on.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Directives {
+    no_store: bool,
+    no_cache: bool,
+    private: bool,
+    public: bool,
+    must_revalidate: bool,
+    max_age: Option<u64>,
+    s_maxage: Option<u64>,
+}
+
+fn directives(headers: &[(String, String)]) -> Directives {
+    let mut out = Directives::default();
+    let Some(value) = header(headers, "cache-control") else {
+        return out;
+    };
+    for directive in value.split(',') {
+        let (name, arg) = match directive.split_once('=') {
+            Some((name, arg)) => (name.trim(), Some(arg.trim().trim_matches('"'))),
+            None => (directive.trim(), None),
+        };
+        if name.eq_ignore_ascii_case("no-store") {
+            out.no_store = true;
+        } else if name.eq_ignore_ascii_case("no-cache") {
+            out.no_cache = true;
+        } else if name.eq_ignore_ascii_case("private") {
+            out.private = true;
+        } else if name.eq_ignore_ascii_case("public") {
+            out.public = true;
+        } else if name.eq_ignore_ascii_case("must-revalidate") {
+            out.must_revalidate = true;
+        } else if name.eq_ignore_ascii_case("max-age") {
+            // An unparsable `max-age` makes the response stale, as RFC 9111 asks.
+            out.max_age = Some(arg.and_then(|a| a.parse().ok()).unwrap_or(0));
+        } else if name.eq_ignore_ascii_case("s-maxage") {
+            out.s_maxage = Some(arg.and_then(|a| a.parse().ok()).unwrap_or(0));
+        }
+    }
+    out
+}
+
+/// How long a response stays fresh from now: `max-age` (else `Expires` minus `Date`) minus `Age`.
+/// A shared cache reads `s-maxage` first.
+fn freshness(headers: &[(String, String)], shared: bool) -> Duration {
+    let d = directives(headers);
+    if d.no_cache || d.no_store {
+        return Duration::ZERO;
+    }
+    let max_age = if shared {
+        d.s_maxage.or(d.max_age)
+    } else {
+        d.max_age
+    };
+    let lifetime = match max_age {
+        Some(secs) => Duration::from_secs(secs),
+        None => header(headers, "expires")
+            .map(|expires| {
+                // An invalid `Expires` (e.g. `0`) means already expired.
+                let Ok(expires) = httpdate::parse_http_date(expires) else {
+                    return Duration::ZERO;
+                };
+                let date = header(headers, "date")
+                    .and_then(|d| httpdate::parse_http_date(d).ok())
+                    .unwrap_or_else(SystemTime::now);
+                expires.duration_since(date).unwrap_or_default()
+            })
+            .unwrap_or_default(),
+    };
+    let age = header(headers, "age")
+        .and_then(|a| a.trim().parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or_default();
+    lifetime.saturating_sub(age)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn response(status: u16, pairs: &[(&str, &str)], body: &'static str) -> HttpResponse {
+        HttpResponse {
+            status,
+            body: Bytes::from_static(body.as_bytes()),
+            headers: headers(pairs),
+            bytes_sent: 40,
+            bytes_received: 60,
+            body_bytes: body.len() as u64,
+            connection_reused: false,
+            cache_hit: None,
+        }
+    }
+
+    const URL: &str = "http://example.test/app.js";
+
+    #[test]
+    fn freshness_comes_from_max_age_then_expires_minus_age() {
+        let f = |pairs: &[(&str, &str)]| freshness(&headers(pairs), false);
+        assert_eq!(f(&[("cache-control", "public, max-age=60")]).as_secs(), 60);
+        assert_eq!(
+            f(&[("cache-control", "max-age=\"60\""), ("age", "15")]).as_secs(),
+            45
+        );
+        assert_eq!(
+            f(&[
+                ("date", "Wed, 21 Oct 2015 07:28:00 GMT"),
+                ("expires", "Wed, 21 Oct 2015 07:30:00 GMT"),
+            ])
+            .as_secs(),
+            120
+        );
+        // `max-age` wins over `Expires`; `no-cache` and broken values are stale.
+        assert_eq!(
+            f(&[
+                ("cache-control", "max-age=5"),
+                ("expires", "Wed, 21 Oct 2015 07:30:00 GMT"),
+            ])
+            .as_secs(),
+            5
+        );
+        assert!(f(&[("cache-control", "no-cache, max-age=60")]).is_zero());
+        assert!(f(&[("cache-control", "max-age=soon")]).is_zero());
+        assert!(f(&[("expires", "0")]).is_zero());
+        assert!(f(&[]).is_zero());
+    }
+
+    #[test]
+    fn fresh_responses_are_served_without_a_request() {
+        let cache = HttpCache::new();
+        let req = HttpRequest::get(URL);
+        assert!(matches!(cache.lookup(&req), Lookup::Miss));
+
+        let res = cache.store(
+            &req,
+            response(200, &[("cache-control", "max-age=60")], "js"),
+        );
+        assert_eq!(res.cache_hit, None);
+
+        let Lookup::Fresh(hit) = cache.lookup(&req) else {
+            panic!("expected a fresh hit");
+        };
+        assert_eq!(hit.cache_hit, Some(CacheHit::Fresh));
+        assert_eq!(hit.body, Bytes::from_static(b"js"));
+        assert_eq!((hit.bytes_sent, hit.bytes_received), (0, 0));
+
+        // Other methods and requests opting out bypass it.
+        let post = HttpRequest::post(URL, Bytes::new());
+        assert!(matches!(cache.lookup(&post), Lookup::Miss));
+        let no_store = HttpRequest {
+            headers: headers(&[("Cache-Control", "no-store")]),
+            ..HttpRequest::get(URL)
+        };
+        assert!(matches!(cache.lookup(&no_store), Lookup::Miss));
+    }
+
+    #[test]
+    fn stale_responses_are_revalidated_and_a_304_serves_the_stored_body() {
+        let cache = HttpCache::new();
+        let req = HttpRequest::get(URL);
+        cache.store(
+            &req,
+            response(
+                200,
+                &[
+                    ("cache-control", "no-cache"),
+                    ("etag", "\"v1\""),
+                    ("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT"),
+                ],
+                "js",
+            ),
+        );
+
+        let Lookup::Revalidate(validators) = cache.lookup(&req) else {
+            panic!("expected a revalidation");
+        };
+        assert_eq!(
+            validators,
+            headers(&[
+                ("if-none-match", "\"v1\""),
+                ("if-modified-since", "Wed, 21 Oct 2015 07:28:00 GMT"),
+            ])
+        );
+
+        let res = cache.store(
+            &req,
+            response(
+                304,
+                &[("cache-control", "max-age=60"), ("etag", "\"v1\"")],
+                "",
+            ),
+        );
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body, Bytes::from_static(b"js"));
+        assert_eq!(res.cache_hit, Some(CacheHit::Revalidated));
+        assert_eq!(res.bytes_received, 60);
+        // The 304's `Cache-Control` replaced the stored one, so the entry is fresh now.
+        assert!(matches!(cache.lookup(&req), Lookup::Fresh(_)));
+    }
+
+    #[test]
+    fn uncacheable_responses_and_unsafe_requests_drop_the_entry() {
+        let cache = HttpCache::new();
+        let req = HttpRequest::get(URL);
+        let fresh = || response(200, &[("cache-control", "max-age=60")], "js");
+
+        cache.store(&req, response(200, &[], "js"));
+        assert!(matches!(cache.lookup(&req), Lookup::Miss));
+        cache.store(
+            &req,
+            response(200, &[("cache-control", "no-store, max-age=60")], "js"),
+        );
+        assert!(matches!(cache.lookup(&req), Lookup::Miss));
+
+        cache.store(&req, fresh());
+        cache.store(
+            &HttpRequest::post(URL, Bytes::new()),
+            response(201, &[], ""),
+        );
+        assert!(matches!(cache.lookup(&req), Lookup::Miss));
+
+        cache.store(&req, fresh());
+        cache.store(&req, response(500, &[], ""));
+        assert!(matches!(cache.lookup(&req), Lookup::Miss));
+    }
+
+    #[test]
+    fn shared_caches_keep_personalised_responses_out() {
+        let stored = |cache: &HttpCache, req: &HttpRequest, cache_control: &str| {
+            cache.store(
+                req,
+                response(200, &[("cache-control", cache_control)], "me"),
+            );
+            matches!(cache.lookup(req), Lookup::Fresh(_))
+        };
+        let anonymous = HttpRequest::get(URL);
+        let authorized = HttpRequest {
+            headers: headers(&[("Authorization", "Bearer alice")]),
+            ..HttpRequest::get(URL)
+        };
+
+        let shared = HttpCache::shared();
+        assert!(!stored(&shared, &anonymous, "private, max-age=60"));
+        assert!(!stored(&shared, &authorized, "max-age=60"));
+        assert!(stored(&shared, &authorized, "public, max-age=60"));
+        assert!(stored(&shared, &authorized, "s-maxage=60"));
+        // A private cache keeps both.
+        let private = HttpCache::new();
+        assert!(stored(&private, &anonymous, "private, max-age=60"));
+        assert!(stored(&private, &authorized, "max-age=60"));
+    }
+
+    #[test]
+    fn shared_caches_prefer_s_maxage() {
+        let pairs = headers(&[("cache-control", "max-age=60, s-maxage=5")]);
+        assert_eq!(freshness(&pairs, false).as_secs(), 60);
+        assert_eq!(freshness(&pairs, true).as_secs(), 5);
+        assert_eq!(
+            freshness(&headers(&[("cache-control", "max-age=60")]), true).as_secs(),
+            60
+        );
+    }
+
+    #[test]
+    fn vary_keeps_responses_to_their_request_headers() {
+        let cache = HttpCache::new();
+        let with_lang = |lang: &str| HttpRequest {
+            headers: headers(&[("Accept-Language", lang)]),
+            ..HttpRequest::get(URL)
+        };
+        cache.store(
+            &with_lang("en"),
+            response(
+                200,
+                &[("cache-control", "max-age=60"), ("vary", "Accept-Language")],
+                "hello",
+            ),
+        );
+        assert!(matches!(cache.lookup(&with_lang("en")), Lookup::Fresh(_)));
+        assert!(matches!(cache.lookup(&with_lang("de")), Lookup::Miss));
+        assert!(matches!(cache.lookup(&HttpRequest::get(URL)), Lookup::Miss));
+
+        cache.store(
+            &HttpRequest::get(URL),
+            response(200, &[("cache-control", "max-age=60"), ("vary", "*")], ""),
+        );
+        assert!(matches!(cache.lookup(&HttpRequest::get(URL)), Lookup::Miss));
+    }
+}
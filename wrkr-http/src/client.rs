@@ -7,15 +7,27 @@
 use hyper_util::client::legacy::connect::HttpConnector;
 use hyper_util::rt::TokioExecutor;
 use std::collections::BTreeMap;
+use std::sync::Arc;
 use std::time::Duration;
 
+use super::cache::Lookup;
+use super::dns::Resolver;
 use super::estimate::{estimate_http_request_bytes_parts, estimate_http1_response_head_bytes};
+use super::proxy::{ConnectionUses, ProxyConnector};
 use super::util::{has_header, host_header_value};
-use super::{Error, HttpRequest, HttpResponse, Result};
+use super::{
+    Error, HttpCache, HttpRequest, HttpResponse, HttpStreamResponse, ProxyConfig, Result, TlsConfig,
+};
 
 #[derive(Debug, Clone)]
 pub struct HttpClient {
-    inner: Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
+    inner: Client<HttpsConnector<ProxyConnector>, Full<Bytes>>,
+    connector: HttpsConnector<ProxyConnector>,
+    tcp: ProxyConnector,
+    tls: TlsConfig,
+    tls_config: Arc<rustls::ClientConfig>,
+    proxy: Arc<ProxyConfig>,
+    keep_alive: bool,
 }
 
 impl Default for HttpClient {
@@ -24,47 +36,231 @@ fn default() -> Self {
         // short runs to appear “hung” when the target host is unreachable.
         //
         // We apply a sane default so failed connects surface promptly.
-        Self::new(Some(Duration::from_secs(3)))
+        Self::new(Some(Self::DEFAULT_CONNECT_TIMEOUT))
     }
 }
 
 impl HttpClient {
+    pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+    /// Client that honors the `HTTP_PROXY`/`HTTPS_PROXY`/`NO_PROXY` environment variables.
     #[must_use]
     pub fn new(connect_timeout: Option<Duration>) -> Self {
-        let mut http_connector = HttpConnector::new();
+        Self::with_proxy(connect_timeout, ProxyConfig::from_env())
+    }
+
+    #[must_use]
+    pub fn with_proxy(connect_timeout: Option<Duration>, proxy: ProxyConfig) -> Self {
+        match Self::with_config(connect_timeout, proxy, TlsConfig::default()) {
+            Ok(client) => client,
+            // ring supports every protocol version rustls enables by default.
+            Err(err) => panic!("default TLS settings rejected: {err}"),
+        }
+    }
+
+    /// Fails when rustls rejects the TLS settings.
+    pub fn with_config(
+        connect_timeout: Option<Duration>,
+        proxy: ProxyConfig,
+        tls: TlsConfig,
+    ) -> Result<Self> {
+        let proxy = Arc::new(proxy);
+        let mut http_connector = HttpConnector::new_with_resolver(Resolver::new());
         http_connector.enforce_http(false);
         http_connector.set_connect_timeout(connect_timeout);
+        let tcp = ProxyConnector::new(http_connector, proxy.clone());
 
-        let https_connector = HttpsConnectorBuilder::new()
-            .with_webpki_roots()
-            .https_or_http()
-            .enable_http1()
-            .wrap_connector(http_connector);
+        let tls_config = super::tls::client_config(&tls)?;
+        let https_connector = https_connector(tls_config.clone(), tcp.clone());
 
-        let inner = Client::builder(TokioExecutor::new()).build(https_connector);
+        Ok(Self {
+            inner: Client::builder(TokioExecutor::new()).build(https_connector.clone()),
+            connector: https_connector,
+            tcp,
+            tls,
+            tls_config: Arc::new(tls_config),
+            proxy,
+            keep_alive: true,
+        })
+    }
+
+    /// With `false`, every request is sent with `Connection: close` (unless it sets its own
+    /// `Connection` header) and no connection is kept idle, so each request opens a fresh
+    /// connection that is closed after the response, whatever the server answers. Requests are
+    /// never pipelined: a connection carries one request at a time either way.
+    #[must_use]
+    pub fn keep_alive(mut self, enabled: bool) -> Self {
+        let mut builder = Client::builder(TokioExecutor::new());
+        if !enabled {
+            builder.pool_max_idle_per_host(0);
+        }
+        self.inner = builder.build(self.connector.clone());
+        self.keep_alive = enabled;
+        self
+    }
 
-        Self { inner }
+    /// A client with the same settings but a pool and a TLS session cache of its own, so its
+    /// requests never reuse a connection opened by `self` (or by any other copy), nor resume a
+    /// TLS session one of them cached.
+    #[must_use]
+    pub fn with_new_pool(&self) -> Self {
+        let mut client = self.clone();
+        client.connector = https_connector(
+            super::tls::with_own_session_cache(&self.tls_config, &self.tls),
+            self.tcp.clone(),
+        );
+        client.keep_alive(self.keep_alive)
     }
 
     pub async fn request(&self, req: HttpRequest) -> Result<HttpResponse> {
+        let (res, bytes_sent) = self.send(req).await?;
+        let (parts, body) = res.into_parts();
+        let connection_reused = ConnectionUses::reused(&parts.extensions);
+
+        let head_bytes =
+            estimate_http1_response_head_bytes(parts.version, parts.status, &parts.headers);
+        let body = body.collect().await?.to_bytes();
+        let body_bytes = body.len() as u64;
+
+        Ok(HttpResponse {
+            status: parts.status.as_u16(),
+            body,
+            headers: normalize_headers(&parts.headers),
+            bytes_sent,
+            bytes_received: head_bytes.saturating_add(body_bytes),
+            body_bytes,
+            connection_reused,
+            cache_hit: None,
+        })
+    }
+
+    /// Like [`Self::request`], but the body is read and dropped chunk by chunk instead of being
+    /// buffered: the returned `body` is empty, while `body_bytes` and `bytes_received` still
+    /// count everything that arrived.
+    pub async fn request_discard_body(&self, req: HttpRequest) -> Result<HttpResponse> {
+        let mut res = self.request_stream(req).await?;
+        while res.next_chunk().await?.is_some() {}
+
+        Ok(HttpResponse {
+            status: res.status,
+            body: Bytes::new(),
+            bytes_received: res.bytes_received(),
+            body_bytes: res.body_bytes(),
+            connection_reused: res.connection_reused,
+            headers: res.headers,
+            bytes_sent: res.bytes_sent,
+            cache_hit: None,
+        })
+    }
+
+    /// Like [`Self::request`], but through `cache`: a fresh stored response is returned without
+    /// sending anything, a stale one is revalidated, and cacheable responses are stored. See
+    /// [`HttpCache`] for the rules.
+    pub async fn request_cached(
+        &self,
+        req: HttpRequest,
+        cache: &HttpCache,
+    ) -> Result<HttpResponse> {
+        self.request_through(req, cache, false).await
+    }
+
+    /// Like [`Self::request_cached`], but a response that is sent has its body dropped as it
+    /// arrives, as with [`Self::request_discard_body`], so neither the result nor the stored
+    /// copy holds a body.
+    pub async fn request_cached_discard_body(
+        &self,
+        req: HttpRequest,
+        cache: &HttpCache,
+    ) -> Result<HttpResponse> {
+        self.request_through(req, cache, true).await
+    }
+
+    async fn request_through(
+        &self,
+        req: HttpRequest,
+        cache: &HttpCache,
+        discard_body: bool,
+    ) -> Result<HttpResponse> {
+        let sent = match cache.lookup(&req) {
+            Lookup::Fresh(res) => return Ok(res),
+            Lookup::Miss => req.clone(),
+            Lookup::Revalidate(validators) => {
+                let mut sent = req.clone();
+                sent.headers.extend(validators);
+                sent
+            }
+        };
+        let res = if discard_body {
+            self.request_discard_body(sent).await?
+        } else {
+            self.request(sent).await?
+        };
+        Ok(cache.store(&req, res))
+    }
+
+    /// Send a request and return as soon as the response head arrives; the body is then read
+    /// chunk by chunk via [`HttpStreamResponse::next_chunk`].
+    ///
+    /// `req.timeout` bounds the wait for the response head only, so long-lived streams (e.g.
+    /// Server-Sent Events) are not cut off.
+    pub async fn request_stream(&self, req: HttpRequest) -> Result<HttpStreamResponse> {
+        let (res, bytes_sent) = self.send(req).await?;
+        let (parts, body) = res.into_parts();
+
+        let head_bytes =
+            estimate_http1_response_head_bytes(parts.version, parts.status, &parts.headers);
+
+        let mut res = HttpStreamResponse::new(
+            parts.status.as_u16(),
+            normalize_headers(&parts.headers),
+            bytes_sent,
+            head_bytes,
+            body,
+        );
+        res.connection_reused = ConnectionUses::reused(&parts.extensions);
+        Ok(res)
+    }
+
+    /// Send the request and wait for the response head. Returns it with the estimated bytes sent.
+    async fn send(&self, mut req: HttpRequest) -> Result<(hyper::Response<Incoming>, u64)> {
         let timeout = req.timeout;
         let parsed = url::Url::parse(&req.url).map_err(|_| Error::InvalidUrl(req.url.clone()))?;
         if parsed.scheme() != "http" && parsed.scheme() != "https" {
             return Err(Error::UnsupportedScheme(req.url));
         }
 
+        let uri: hyper::Uri = req
+            .url
+            .parse()
+            .map_err(|_| Error::InvalidUrl(req.url.to_string()))?;
+
+        // Plain HTTP goes to the proxy as an absolute-form request carrying the proxy credentials;
+        // HTTPS is tunneled, so the request itself is unchanged.
+        let proxy = self
+            .proxy
+            .intercept(&uri)
+            .filter(|_| parsed.scheme() == "http");
+        if let Some(auth) = proxy.and_then(|p| p.auth())
+            && !has_header(&req.headers, "proxy-authorization")
+            && let Ok(auth) = auth.to_str()
+        {
+            req.headers
+                .push(("proxy-authorization".to_string(), auth.to_string()));
+        }
+
+        if !self.keep_alive && !has_header(&req.headers, "connection") {
+            req.headers
+                .push(("connection".to_string(), "close".to_string()));
+        }
+
         let bytes_sent = estimate_http_request_bytes_parts(
             &req.method,
             &req.url,
             &req.headers,
             req.body.len() as u64,
+            proxy.is_some(),
         )?;
 
-        let uri: hyper::Uri = req
-            .url
-            .parse()
-            .map_err(|_| Error::InvalidUrl(req.url.to_string()))?;
-
         let mut builder = Request::builder().method(req.method).uri(uri);
 
         // Make implicit headers explicit so our byte accounting is deterministic.
@@ -95,39 +291,7 @@ pub async fn request(&self, req: HttpRequest) -> Result<HttpResponse> {
             self.inner.request(req).await?
         };
 
-        let (parts, body) = res.into_parts();
-        let status = parts.status.as_u16();
-
-        // Normalize headers to lowercase keys for scripting ergonomics.
-        // If there are multiple values for a header, join them with ", ".
-        let mut merged: BTreeMap<String, String> = BTreeMap::new();
-        for (name, value) in parts.headers.iter() {
-            let key = name.as_str().to_ascii_lowercase();
-            let v = String::from_utf8_lossy(value.as_bytes()).to_string();
-            merged
-                .entry(key)
-                .and_modify(|cur| {
-                    if !cur.is_empty() {
-                        cur.push_str(", ");
-                    }
-                    cur.push_str(&v);
-                })
-                .or_insert(v);
-        }
-        let headers: Vec<(String, String)> = merged.into_iter().collect();
-
-        let head_bytes =
-            estimate_http1_response_head_bytes(parts.version, parts.status, &parts.headers);
-        let body = body.collect().await?.to_bytes();
-        let bytes_received = head_bytes.saturating_add(body.len() as u64);
-
-        Ok(HttpResponse {
-            status,
-            body,
-            headers,
-            bytes_sent,
-            bytes_received,
-        })
+        Ok((res, bytes_sent))
     }
 
     pub async fn get(&self, url: &str) -> Result<HttpResponse> {
@@ -135,11 +299,45 @@ pub async fn get(&self, url: &str) -> Result<HttpResponse> {
     }
 }
 
+/// HTTP/1.1 connector over `tcp` that speaks TLS with `tls_config` for `https://` URLs.
+fn https_connector(
+    tls_config: rustls::ClientConfig,
+    tcp: ProxyConnector,
+) -> HttpsConnector<ProxyConnector> {
+    HttpsConnectorBuilder::new()
+        .with_tls_config(tls_config)
+        .https_or_http()
+        .enable_http1()
+        .wrap_connector(tcp)
+}
+
+/// Normalize headers to lowercase keys for scripting ergonomics.
+/// If there are multiple values for a header, join them with ", ".
+fn normalize_headers(headers: &http::HeaderMap) -> Vec<(String, String)> {
+    let mut merged: BTreeMap<String, String> = BTreeMap::new();
+    for (name, value) in headers.iter() {
+        let key = name.as_str().to_ascii_lowercase();
+        let v = String::from_utf8_lossy(value.as_bytes()).to_string();
+        merged
+            .entry(key)
+            .and_modify(|cur| {
+                if !cur.is_empty() {
+                    cur.push_str(", ");
+                }
+                cur.push_str(&v);
+            })
+            .or_insert(v);
+    }
+    merged.into_iter().collect()
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
 
     use super::*;
+    use crate::HttpTransportErrorKind;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::time::Instant;
 
     #[tokio::test]
@@ -158,4 +356,519 @@ async fn unreachable_host_fails_fast_with_connect_timeout() {
             "expected fast failure, elapsed={elapsed:?}"
         );
     }
+
+    #[tokio::test]
+    async fn connect_timeout_fails_first_under_a_longer_request_timeout() {
+        let client = HttpClient::new(Some(Duration::from_millis(200)));
+        let req = HttpRequest {
+            timeout: Some(Duration::from_secs(30)),
+            ..HttpRequest::get("http://192.0.2.1:81/")
+        };
+
+        let started = Instant::now();
+        let err = client.request(req).await.unwrap_err();
+
+        assert!(started.elapsed() < Duration::from_secs(2));
+        // The connector gave up, not the request deadline.
+        assert!(
+            matches!(&err, Error::Request(e) if e.is_connect()),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn refused_connection_is_classified_as_connect() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let client = HttpClient::default();
+        let err = client
+            .request(HttpRequest::get_owned(format!("http://{addr}/")))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.transport_error_kind(), HttpTransportErrorKind::Connect);
+    }
+
+    #[tokio::test]
+    async fn unresolvable_host_is_classified_as_dns() {
+        let client = HttpClient::default();
+        let err = client
+            .request(HttpRequest::get("http://wrkr-test.invalid/"))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.transport_error_kind(), HttpTransportErrorKind::Dns);
+    }
+
+    #[tokio::test]
+    async fn dropped_connection_is_classified_as_reset() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            // Accept, then close without writing a response.
+            let (stream, _) = listener.accept().unwrap();
+            drop(stream);
+        });
+
+        let client = HttpClient::default();
+        let err = client
+            .request(HttpRequest::get_owned(format!("http://{addr}/")))
+            .await
+            .unwrap_err();
+        server.join().unwrap();
+
+        assert_eq!(err.transport_error_kind(), HttpTransportErrorKind::Reset);
+    }
+
+    #[tokio::test]
+    async fn request_stream_yields_chunks_before_the_body_ends() {
+        use std::io::{Read as _, Write as _};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).unwrap();
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\
+                      transfer-encoding: chunked\r\n\r\n6\r\ndata:1\r\n",
+                )
+                .unwrap();
+            stream.flush().unwrap();
+            // Hold the rest back until the client has seen the first chunk.
+            release_rx.recv().unwrap();
+            stream.write_all(b"6\r\ndata:2\r\n0\r\n\r\n").unwrap();
+        });
+
+        let client = HttpClient::default();
+        let mut res = client
+            .request_stream(HttpRequest::get_owned(format!("http://{addr}/events")))
+            .await
+            .unwrap();
+        assert_eq!(res.status, 200);
+
+        let first = res.next_chunk().await.unwrap();
+        assert_eq!(first.as_deref(), Some(&b"data:1"[..]));
+        release_tx.send(()).unwrap();
+
+        let mut rest = Vec::new();
+        while let Some(chunk) = res.next_chunk().await.unwrap() {
+            rest.extend_from_slice(&chunk);
+        }
+        server.join().unwrap();
+
+        assert_eq!(rest, b"data:2");
+        assert_eq!(res.body_bytes(), 12);
+        assert!(res.bytes_received() > res.body_bytes());
+        assert_eq!(res.head_bytes() + res.body_bytes(), res.bytes_received());
+    }
+
+    #[tokio::test]
+    async fn request_discard_body_counts_bytes_without_keeping_them() {
+        let (addr, server) =
+            fake_proxy(b"HTTP/1.1 200 OK\r\ncontent-length: 11\r\n\r\nhello world");
+
+        let client = HttpClient::default();
+        let res = client
+            .request_discard_body(HttpRequest::get_owned(format!("http://{addr}/")))
+            .await
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(res.status, 200);
+        assert!(res.body.is_empty());
+        assert_eq!(res.body_bytes, 11);
+        assert_eq!(res.head_bytes() + 11, res.bytes_received);
+    }
+
+    #[tokio::test]
+    async fn second_request_on_a_keep_alive_connection_is_marked_reused() {
+        use std::io::Write as _;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            // A single connection serving both requests.
+            let (mut stream, _) = listener.accept().unwrap();
+            for _ in 0..2 {
+                read_request_head(&mut stream);
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                    .unwrap();
+            }
+        });
+
+        let client = HttpClient::default();
+        let req = || HttpRequest {
+            timeout: Some(Duration::from_secs(5)),
+            ..HttpRequest::get_owned(format!("http://{addr}/"))
+        };
+        let first = client.request(req()).await.unwrap();
+        // Give the pool a moment to take the idle connection back.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let second = client.request_discard_body(req()).await.unwrap();
+        server.join().unwrap();
+
+        assert!(!first.connection_reused);
+        assert!(second.connection_reused);
+    }
+
+    #[tokio::test]
+    async fn cached_requests_revalidate_with_the_stored_etag() {
+        use std::io::Write as _;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut heads = Vec::new();
+            for reply in [
+                &b"HTTP/1.1 200 OK\r\ncache-control: no-cache\r\netag: \"v1\"\r\ncontent-length: 2\r\n\r\nok"[..],
+                &b"HTTP/1.1 304 Not Modified\r\ncache-control: max-age=60\r\netag: \"v1\"\r\n\r\n"[..],
+            ] {
+                let head = read_request_head(&mut stream);
+                heads.push(head.to_ascii_lowercase());
+                stream.write_all(reply).unwrap();
+            }
+            heads
+        });
+
+        let client = HttpClient::default();
+        let cache = HttpCache::new();
+        let req = || HttpRequest {
+            timeout: Some(Duration::from_secs(5)),
+            ..HttpRequest::get_owned(format!("http://{addr}/app.js"))
+        };
+        let first = client.request_cached(req(), &cache).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let second = client.request_cached(req(), &cache).await.unwrap();
+        // The 304 made the entry fresh: this one never reaches the server.
+        let third = client.request_cached(req(), &cache).await.unwrap();
+        let heads = tokio::task::spawn_blocking(move || server.join().unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(first.cache_hit, None);
+        assert!(!heads[0].contains("if-none-match"));
+        assert!(heads[1].contains("if-none-match: \"v1\""), "{}", heads[1]);
+        assert_eq!(second.cache_hit, Some(crate::CacheHit::Revalidated));
+        assert_eq!((second.status, second.body.as_ref()), (200, &b"ok"[..]));
+        assert_eq!(second.body_bytes, 0);
+        assert_eq!(third.cache_hit, Some(crate::CacheHit::Fresh));
+        assert_eq!(third.bytes_sent, 0);
+    }
+
+    #[tokio::test]
+    async fn cached_requests_can_discard_bodies() {
+        let (addr, server) = fake_proxy(
+            b"HTTP/1.1 200 OK\r\ncache-control: max-age=60\r\ncontent-length: 11\r\n\r\nhello world",
+        );
+
+        let client = HttpClient::default();
+        let cache = HttpCache::new();
+        let req = || HttpRequest::get_owned(format!("http://{addr}/"));
+        let first = client
+            .request_cached_discard_body(req(), &cache)
+            .await
+            .unwrap();
+        server.join().unwrap();
+        let second = client
+            .request_cached_discard_body(req(), &cache)
+            .await
+            .unwrap();
+
+        assert!(first.body.is_empty());
+        assert_eq!(first.body_bytes, 11);
+        assert_eq!(second.cache_hit, Some(crate::CacheHit::Fresh));
+        assert!(second.body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn client_with_a_new_pool_does_not_reuse_the_original_connections() {
+        use std::io::Write as _;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            // Both connections stay open until each has served one request.
+            let mut streams = Vec::new();
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                read_request_head(&mut stream);
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                    .unwrap();
+                streams.push(stream);
+            }
+        });
+
+        let client = HttpClient::default();
+        let req = || HttpRequest {
+            timeout: Some(Duration::from_secs(5)),
+            ..HttpRequest::get_owned(format!("http://{addr}/"))
+        };
+        let first = client.request(req()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let second = client.with_new_pool().request(req()).await.unwrap();
+        tokio::task::spawn_blocking(move || server.join().unwrap())
+            .await
+            .unwrap();
+
+        assert!(!first.connection_reused);
+        assert!(!second.connection_reused);
+    }
+
+    #[tokio::test]
+    async fn client_without_keep_alive_opens_a_connection_per_request() {
+        use std::io::{Read as _, Write as _};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let mut heads = Vec::new();
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().unwrap();
+                let head = read_request_head(&mut stream);
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                    .unwrap();
+                // The client hangs up after the response instead of waiting for another one.
+                assert_eq!(stream.read(&mut [0u8; 1024]).unwrap(), 0);
+                heads.push(head.to_ascii_lowercase());
+            }
+            heads
+        });
+
+        let client = HttpClient::default().keep_alive(false);
+        let req = || HttpRequest {
+            timeout: Some(Duration::from_secs(5)),
+            ..HttpRequest::get_owned(format!("http://{addr}/"))
+        };
+        let first = client.request(req()).await.unwrap();
+        let second = client.request(req()).await.unwrap();
+        // Join off the runtime: the client closes the connection from a task on it.
+        let heads = tokio::task::spawn_blocking(move || server.join().unwrap())
+            .await
+            .unwrap();
+
+        assert!(!first.connection_reused);
+        assert!(!second.connection_reused);
+        assert!(heads.iter().all(|h| h.contains("connection: close\r\n")));
+    }
+
+    #[tokio::test]
+    async fn insecure_client_accepts_a_self_signed_certificate() {
+        let (addr, _) = self_signed_https_server().await;
+        let url = format!("https://localhost:{}/", addr.port());
+
+        let err = HttpClient::default()
+            .request(HttpRequest::get(&url))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.transport_error_kind(),
+            HttpTransportErrorKind::Tls,
+            "{err:?}"
+        );
+
+        let insecure = HttpClient::with_config(
+            None,
+            ProxyConfig::default(),
+            TlsConfig {
+                insecure_skip_verify: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let res = insecure.request(HttpRequest::get(&url)).await.unwrap();
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body_utf8(), Some("ok"));
+    }
+
+    #[tokio::test]
+    async fn client_resumes_tls_sessions_unless_disabled() {
+        let (addr, resumed) = self_signed_https_server().await;
+        let url = format!("https://localhost:{}/", addr.port());
+
+        for disable_session_resumption in [false, true] {
+            let client = HttpClient::with_config(
+                None,
+                ProxyConfig::default(),
+                TlsConfig {
+                    insecure_skip_verify: true,
+                    disable_session_resumption,
+                },
+            )
+            .unwrap()
+            .keep_alive(false);
+            resumed.store(0, Ordering::Relaxed);
+            for _ in 0..3 {
+                let res = client.request(HttpRequest::get(&url)).await.unwrap();
+                assert_eq!(res.status, 200);
+            }
+            let expected = if disable_session_resumption { 0 } else { 2 };
+            assert_eq!(
+                resumed.load(Ordering::Relaxed),
+                expected,
+                "disable_session_resumption={disable_session_resumption}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn new_pools_do_not_resume_each_others_tls_sessions() {
+        let (addr, resumed) = self_signed_https_server().await;
+        let url = format!("https://localhost:{}/", addr.port());
+        let client = HttpClient::with_config(
+            None,
+            ProxyConfig::default(),
+            TlsConfig {
+                insecure_skip_verify: true,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .keep_alive(false);
+
+        client.request(HttpRequest::get(&url)).await.unwrap();
+        let own = client.with_new_pool();
+        own.request(HttpRequest::get(&url)).await.unwrap();
+        assert_eq!(resumed.load(Ordering::Relaxed), 0);
+
+        own.request(HttpRequest::get(&url)).await.unwrap();
+        assert_eq!(resumed.load(Ordering::Relaxed), 1);
+    }
+
+    /// HTTPS server with the self-signed `localhost` certificate from `tests/fixtures`, answering
+    /// every connection with one `200 ok`. Also counts the handshakes that resumed a session.
+    async fn self_signed_https_server() -> (std::net::SocketAddr, Arc<AtomicUsize>) {
+        use rustls::pki_types::pem::PemObject as _;
+        use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+        use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+
+        let cert =
+            CertificateDer::from_pem_slice(include_bytes!("../tests/fixtures/localhost.crt"))
+                .unwrap();
+        let key = PrivateKeyDer::from_pem_slice(include_bytes!("../tests/fixtures/localhost.key"))
+            .unwrap();
+        let config = rustls::ServerConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_no_client_auth()
+        .with_single_cert(vec![cert], key)
+        .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+        let resumed = Arc::new(AtomicUsize::new(0));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let counter = resumed.clone();
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                let counter = counter.clone();
+                tokio::spawn(async move {
+                    // Handshakes the client rejects end here.
+                    let Ok(mut tls) = acceptor.accept(tcp).await else {
+                        return;
+                    };
+                    if tls.get_ref().1.handshake_kind() == Some(rustls::HandshakeKind::Resumed) {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                    }
+                    let mut buf = [0u8; 1024];
+                    let _ = tls.read(&mut buf).await;
+                    let _ = tls
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                        .await;
+                    let _ = tls.flush().await;
+                });
+            }
+        });
+        (addr, resumed)
+    }
+
+    /// One-shot fake proxy: records the request head it receives and answers with `response`.
+    fn fake_proxy(
+        response: &'static [u8],
+    ) -> (std::net::SocketAddr, std::thread::JoinHandle<String>) {
+        use std::io::Write as _;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let head = read_request_head(&mut stream);
+            stream.write_all(response).unwrap();
+            head
+        });
+        (addr, server)
+    }
+
+    /// Reads one request head off `stream`, failing if the client hangs up first.
+    fn read_request_head(stream: &mut std::net::TcpStream) -> String {
+        use std::io::Read as _;
+
+        let mut head = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf).unwrap();
+            assert!(n > 0, "client closed before sending a request head");
+            head.extend_from_slice(&buf[..n]);
+        }
+        String::from_utf8(head).unwrap()
+    }
+
+    #[tokio::test]
+    async fn plain_http_goes_to_the_proxy_in_absolute_form() {
+        let (addr, proxy) = fake_proxy(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok");
+
+        let config = ProxyConfig::all(&format!("http://user:pass@{addr}")).unwrap();
+        let client = HttpClient::with_proxy(Some(Duration::from_secs(1)), config);
+        let req = HttpRequest::get("http://upstream.invalid/items?id=1");
+        let expected_sent = crate::estimate_http_request_bytes(&req).unwrap();
+        let res = client.request(req).await.unwrap();
+        let head = proxy.join().unwrap();
+
+        assert_eq!(res.status, 200);
+        assert_eq!(res.body.as_ref(), b"ok");
+        assert!(
+            head.starts_with("GET http://upstream.invalid/items?id=1 HTTP/1.1\r\n"),
+            "{head}"
+        );
+        // base64("user:pass")
+        assert!(
+            head.contains("proxy-authorization: Basic dXNlcjpwYXNz\r\n"),
+            "{head}"
+        );
+        assert_eq!(res.bytes_sent, head.len() as u64);
+        assert!(res.bytes_sent > expected_sent);
+    }
+
+    #[tokio::test]
+    async fn https_is_tunneled_with_connect() {
+        let (addr, proxy) =
+            fake_proxy(b"HTTP/1.1 407 Proxy Authentication Required\r\ncontent-length: 0\r\n\r\n");
+
+        let config = ProxyConfig::all(&addr.to_string()).unwrap();
+        let client = HttpClient::with_proxy(Some(Duration::from_secs(1)), config);
+        let err = client
+            .request(HttpRequest::get("https://upstream.invalid/"))
+            .await
+            .unwrap_err();
+        let head = proxy.join().unwrap();
+
+        assert_eq!(
+            head,
+            "CONNECT upstream.invalid:443 HTTP/1.1\r\nHost: upstream.invalid:443\r\n\r\n"
+        );
+        assert_eq!(err.transport_error_kind(), HttpTransportErrorKind::Connect);
+        assert!(format!("{err:?}").contains("407"), "{err:?}");
+    }
 }
@@ -0,0 +1,46 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use hyper_util::client::legacy::connect::dns::{GaiAddrs, GaiFuture, GaiResolver, Name};
+use tower_service::Service;
+
+/// `getaddrinfo` resolver whose failures carry a type of their own, so they can be told apart
+/// from connect errors in a request error's source chain.
+#[derive(Debug, Clone)]
+pub(crate) struct Resolver(GaiResolver);
+
+impl Resolver {
+    pub(crate) fn new() -> Self {
+        Self(GaiResolver::new())
+    }
+}
+
+/// Host name resolution failed.
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub(crate) struct ResolveError(std::io::Error);
+
+pub(crate) struct ResolveFuture(GaiFuture);
+
+impl Service<Name> for Resolver {
+    type Response = GaiAddrs;
+    type Error = ResolveError;
+    type Future = ResolveFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), ResolveError>> {
+        self.0.poll_ready(cx).map_err(ResolveError)
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        ResolveFuture(self.0.call(name))
+    }
+}
+
+impl Future for ResolveFuture {
+    type Output = Result<GaiAddrs, ResolveError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx).map_err(ResolveError)
+    }
+}
@@ -10,6 +10,15 @@ pub enum HttpTransportErrorKind {
     RequestBuild,
     HeaderName,
     HeaderValue,
+    /// Host name resolution failed.
+    Dns,
+    /// The TCP connection could not be established (e.g. refused or unreachable).
+    Connect,
+    /// The TLS handshake failed.
+    Tls,
+    /// The connection was reset or closed before a complete response arrived.
+    Reset,
+    /// Any other request failure.
     Request,
     Timeout,
     BodyRead,
@@ -23,6 +32,9 @@ pub enum Error {
     #[error("unsupported URL scheme: {0} (only http:// and https:// are supported)")]
     UnsupportedScheme(String),
 
+    #[error("invalid proxy url: {0} (expected http://[user:pass@]host[:port])")]
+    InvalidProxy(String),
+
     #[error("http request build failed: {0}")]
     RequestBuild(#[from] http::Error),
 
@@ -40,6 +52,9 @@ pub enum Error {
 
     #[error("failed to read response body: {0}")]
     BodyRead(#[from] hyper::Error),
+
+    #[error("invalid TLS configuration: {0}")]
+    Tls(#[from] rustls::Error),
 }
 
 impl Error {
@@ -48,12 +63,68 @@ pub fn transport_error_kind(&self) -> HttpTransportErrorKind {
         match self {
             Self::InvalidUrl(_) => HttpTransportErrorKind::InvalidUrl,
             Self::UnsupportedScheme(_) => HttpTransportErrorKind::UnsupportedScheme,
+            Self::InvalidProxy(_) => HttpTransportErrorKind::InvalidUrl,
             Self::RequestBuild(_) => HttpTransportErrorKind::RequestBuild,
             Self::HeaderName(_) => HttpTransportErrorKind::HeaderName,
             Self::HeaderValue(_) => HttpTransportErrorKind::HeaderValue,
-            Self::Request(_) => HttpTransportErrorKind::Request,
+            Self::Request(err) => request_error_kind(err),
             Self::Timeout(_) => HttpTransportErrorKind::Timeout,
             Self::BodyRead(_) => HttpTransportErrorKind::BodyRead,
+            Self::Tls(_) => HttpTransportErrorKind::Tls,
+        }
+    }
+}
+
+fn request_error_kind(err: &hyper_util::client::legacy::Error) -> HttpTransportErrorKind {
+    let mut io_kind = None;
+    let mut dns = false;
+    let mut tls = false;
+    let mut incomplete = false;
+
+    let mut source = std::error::Error::source(err);
+    while let Some(cause) = source {
+        if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+            io_kind.get_or_insert(io.kind());
+            // tokio-rustls reports handshake failures as `InvalidData`, which hyper-rustls
+            // wraps in another I/O error (`source()` skips over the wrapped one).
+            let wrapped = io
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<std::io::Error>());
+            tls |= std::iter::once(io)
+                .chain(wrapped)
+                .any(|e| e.kind() == std::io::ErrorKind::InvalidData);
         }
+        if let Some(hyper_err) = cause.downcast_ref::<hyper::Error>() {
+            incomplete |= hyper_err.is_incomplete_message();
+        }
+        dns |= cause.is::<crate::dns::ResolveError>();
+        source = cause.source();
+    }
+
+    if dns {
+        return HttpTransportErrorKind::Dns;
+    }
+
+    if err.is_connect() {
+        return match io_kind {
+            Some(std::io::ErrorKind::TimedOut) => HttpTransportErrorKind::Timeout,
+            _ if tls => HttpTransportErrorKind::Tls,
+            _ => HttpTransportErrorKind::Connect,
+        };
+    }
+
+    let reset = matches!(
+        io_kind,
+        Some(
+            std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe
+                | std::io::ErrorKind::UnexpectedEof
+        )
+    );
+    if reset || incomplete {
+        HttpTransportErrorKind::Reset
+    } else {
+        HttpTransportErrorKind::Request
     }
 }
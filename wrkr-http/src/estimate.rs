@@ -8,14 +8,23 @@
 /// This is a best-effort estimate of HTTP/1.1 framing: request line + headers + CRLF + body.
 /// It intentionally makes Host/Content-Length explicit (if they are missing) for determinism.
 pub fn estimate_http_request_bytes(req: &HttpRequest) -> Result<u64> {
-    estimate_http_request_bytes_parts(&req.method, &req.url, &req.headers, req.body.len() as u64)
+    estimate_http_request_bytes_parts(
+        &req.method,
+        &req.url,
+        &req.headers,
+        req.body.len() as u64,
+        false,
+    )
 }
 
+/// `absolute_form` is set for plain-HTTP requests sent to a proxy, whose request line carries the
+/// full URL instead of just the path.
 pub(super) fn estimate_http_request_bytes_parts(
     method: &http::Method,
     url: &str,
     headers: &[(String, String)],
     body_len: u64,
+    absolute_form: bool,
 ) -> Result<u64> {
     let parsed = url::Url::parse(url).map_err(|_| Error::InvalidUrl(url.to_string()))?;
     if parsed.scheme() != "http" && parsed.scheme() != "https" {
@@ -27,7 +36,11 @@ pub(super) fn estimate_http_request_bytes_parts(
         .map_err(|_| Error::InvalidUrl(url.to_string()))?;
 
     let mut bytes = 0u64;
-    bytes = bytes.saturating_add(estimate_http1_request_line_bytes(method, &uri));
+    bytes = bytes.saturating_add(estimate_http1_request_line_bytes(
+        method,
+        &uri,
+        absolute_form,
+    ));
 
     // Headers (original + implicit ones we may add).
     for (k, v) in headers {
@@ -51,10 +64,20 @@ pub(super) fn estimate_http_request_bytes_parts(
     Ok(bytes)
 }
 
-fn estimate_http1_request_line_bytes(method: &http::Method, uri: &hyper::Uri) -> u64 {
+fn estimate_http1_request_line_bytes(
+    method: &http::Method,
+    uri: &hyper::Uri,
+    absolute_form: bool,
+) -> u64 {
     let method_len = method.as_str().len() as u64;
     let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
-    let path_len = path.len() as u64;
+    let mut path_len = path.len() as u64;
+    if absolute_form {
+        // "scheme://authority" in front of the path.
+        let scheme = uri.scheme_str().unwrap_or("http");
+        let authority = uri.authority().map_or("", |a| a.as_str());
+        path_len = path_len.saturating_add((scheme.len() + 3 + authority.len()) as u64);
+    }
     let version_len = "HTTP/1.1".len() as u64;
 
     // "METHOD SP path SP HTTP/1.1 CRLF"
@@ -1,13 +1,22 @@
 #![forbid(unsafe_code)]
 
+mod cache;
 mod client;
+mod dns;
 mod error;
 mod estimate;
+mod proxy;
+mod stream;
+mod tls;
 mod types;
 mod util;
 
+pub use cache::{CacheHit, HttpCache};
 pub use client::HttpClient;
 pub use error::{Error, HttpTransportErrorKind, Result};
 pub use estimate::estimate_http_request_bytes;
 pub use http::Method;
+pub use proxy::ProxyConfig;
+pub use stream::HttpStreamResponse;
+pub use tls::TlsConfig;
 pub use types::{HttpRequest, HttpResponse};
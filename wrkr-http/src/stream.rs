@@ -0,0 +1,78 @@
+use bytes::Bytes;
+use http_body_util::BodyExt as _;
+use hyper::body::Incoming;
+
+use super::Result;
+
+/// A response whose body is read incrementally instead of being buffered.
+///
+/// Returned by [`crate::HttpClient::request_stream`] once the status line and headers have
+/// arrived. Dropping it before the body ends closes the connection.
+#[derive(Debug)]
+pub struct HttpStreamResponse {
+    pub status: u16,
+    /// Response headers (lowercased header names). Multiple values are joined with ", ".
+    pub headers: Vec<(String, String)>,
+    /// Estimated bytes sent on the wire for this request.
+    pub bytes_sent: u64,
+    /// The request went out on a pooled keep-alive connection instead of a newly opened one.
+    pub connection_reused: bool,
+    head_bytes: u64,
+    body_bytes: u64,
+    body: Incoming,
+}
+
+impl HttpStreamResponse {
+    pub(crate) fn new(
+        status: u16,
+        headers: Vec<(String, String)>,
+        bytes_sent: u64,
+        head_bytes: u64,
+        body: Incoming,
+    ) -> Self {
+        Self {
+            status,
+            headers,
+            bytes_sent,
+            connection_reused: false,
+            head_bytes,
+            body_bytes: 0,
+            body,
+        }
+    }
+
+    /// Wait for the next chunk of body data; `None` once the body is complete.
+    ///
+    /// Chunks are returned as they are read from the connection, so their boundaries follow the
+    /// server's writes only loosely.
+    pub async fn next_chunk(&mut self) -> Result<Option<Bytes>> {
+        while let Some(frame) = self.body.frame().await {
+            // Trailer frames carry no body data.
+            if let Ok(data) = frame?.into_data()
+                && !data.is_empty()
+            {
+                self.body_bytes = self.body_bytes.saturating_add(data.len() as u64);
+                return Ok(Some(data));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Estimated bytes of the status line and headers.
+    #[must_use]
+    pub fn head_bytes(&self) -> u64 {
+        self.head_bytes
+    }
+
+    /// Body bytes read so far.
+    #[must_use]
+    pub fn body_bytes(&self) -> u64 {
+        self.body_bytes
+    }
+
+    /// Estimated bytes received on the wire so far (status line + headers + body read so far).
+    #[must_use]
+    pub fn bytes_received(&self) -> u64 {
+        self.head_bytes.saturating_add(self.body_bytes)
+    }
+}
@@ -0,0 +1,49 @@
+//! TLS settings of the HTTP client.
+
+use std::sync::Arc;
+
+use hyper_rustls::ConfigBuilderExt as _;
+use wrkr_tls::SkipVerification;
+
+use super::Result;
+
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    /// Accept any server certificate (self-signed, expired, wrong host). Test environments only.
+    pub insecure_skip_verify: bool,
+    /// Neither offer session tickets nor session IDs, so every new connection pays for a full
+    /// handshake. By default sessions are cached per client and resumed on reconnect.
+    pub disable_session_resumption: bool,
+}
+
+pub(crate) fn client_config(tls: &TlsConfig) -> Result<rustls::ClientConfig> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+    let mut config = if tls.insecure_skip_verify {
+        // Completes the handshake with any server certificate.
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(SkipVerification::new(provider)))
+            .with_no_client_auth()
+    } else {
+        builder.with_webpki_roots().with_no_client_auth()
+    };
+    if tls.disable_session_resumption {
+        config.resumption = rustls::client::Resumption::disabled();
+    }
+    Ok(config)
+}
+
+/// `config` with a session cache of its own, so its connections never resume a session that a
+/// connection made with another copy cached.
+pub(crate) fn with_own_session_cache(
+    config: &rustls::ClientConfig,
+    tls: &TlsConfig,
+) -> rustls::ClientConfig {
+    let mut config = config.clone();
+    if !tls.disable_session_resumption {
+        config.resumption = rustls::client::Resumption::default();
+    }
+    config
+}
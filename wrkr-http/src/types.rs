@@ -12,12 +12,25 @@ pub struct HttpResponse {
     pub bytes_sent: u64,
     /// Estimated bytes received on the wire for this response (HTTP/1.1 status line + headers + body).
     pub bytes_received: u64,
+    /// Body bytes read from the wire; `body.len()` unless the body was discarded.
+    pub body_bytes: u64,
+    /// The request went out on a pooled keep-alive connection instead of a newly opened one.
+    pub connection_reused: bool,
+    /// Set when the response came out of an [`HttpCache`](crate::HttpCache); the byte counts
+    /// then cover only what a revalidation put on the wire (nothing for a fresh hit).
+    pub cache_hit: Option<crate::CacheHit>,
 }
 
 impl HttpResponse {
     pub fn body_utf8(&self) -> Option<&str> {
         std::str::from_utf8(&self.body).ok()
     }
+
+    /// Estimated bytes of the status line and headers: `bytes_received` without the body.
+    #[must_use]
+    pub fn head_bytes(&self) -> u64 {
+        self.bytes_received.saturating_sub(self.body_bytes)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -4,7 +4,10 @@
 ---@field status integer HTTP status code, or 0 on transport error
 ---@field body string Response body decoded as UTF-8 (may be empty)
 ---@field headers table<string, string> Response headers (lowercased header names)
+---@field bytes_sent integer Request bytes written, including the request line and headers
+---@field bytes_received integer Response bytes read, including the status line and headers
 ---@field error? string Error message (present when status==0)
+---@field error_kind? "dns"|"connect"|"tls"|"timeout"|"reset"|"body_read"|"request"|"invalid_url"|"unsupported_scheme"|"request_build"|"header_name"|"header_value" Transport error kind (present when status==0)
 
 ---@class wrkr.HttpRequestOptions
 ---@field headers? table<string, string|number>
@@ -12,6 +15,32 @@
 ---@field timeout? number|string Timeout in seconds or duration string (e.g. "250ms", "10s")
 ---@field tags? table<string, string|number|boolean>
 ---@field name? string
+---@field body_file? string Script-relative file sent as the request body (read once per run)
+
+---@class wrkr.HttpBatchRequest
+---@field method? string HTTP method (default "GET")
+---@field url string
+---@field body? any Same encoding rules as `http.post`
+---@field opts? wrkr.HttpRequestOptions
+
+---@class wrkr.HttpOutgoingRequest
+---@field method string
+---@field url string Final URL, query params included
+---@field headers table<string, string[]|string> Values of each header; a hook may set a single string
+---@field body string Encoded body ("" when there is none)
+
+---@class wrkr.HttpBatchOptions
+---@field max_concurrent? integer Requests in flight at once (default: all)
+
+---@class wrkr.HttpStreamResponse
+---@field status integer HTTP status code, or 0 on transport error
+---@field headers table<string, string> Response headers (lowercased header names)
+---@field bytes integer Body bytes read before the stream ended or was stopped
+---@field chunks integer Number of `on_chunk` calls
+---@field bytes_sent integer Request bytes written, including the request line and headers
+---@field bytes_received integer Response bytes read, including the status line and headers
+---@field error? string Error message (transport error or broken stream)
+---@field error_kind? string Transport error kind
 
 ---@class wrkr.CheckSummary
 ---@field name string
@@ -27,13 +56,22 @@
 
 ---@class wrkr.MetricValuesTrend
 ---@field count integer
----@field min number
----@field max number
----@field avg number
----@field p50 number
----@field p90 number
----@field p95 number
----@field p99 number
+---@field min number|nil
+---@field max number|nil
+---@field mean number|nil
+---@field stdev number|nil
+---@field p50 number|nil
+---@field p75 number|nil
+---@field p90 number|nil
+---@field p95 number|nil
+---@field p99 number|nil
+local MetricValuesTrend = {}
+
+---Value at an arbitrary percentile (0..100), e.g. `values:percentile(99.99)`.
+---Returns nil when there are no samples or `p` is out of range.
+---@param p number
+---@return number|nil
+function MetricValuesTrend:percentile(p) end
 
 ---@class wrkr.MetricValuesCounter
 ---@field value number
@@ -43,8 +81,8 @@
 
 ---@class wrkr.MetricValuesRate
 ---@field total integer
----@field trues integer
----@field rate number
+---@field hits integer
+---@field rate number|nil
 
 ---@alias wrkr.MetricValues wrkr.MetricValuesTrend|wrkr.MetricValuesCounter|wrkr.MetricValuesGauge|wrkr.MetricValuesRate
 
@@ -79,3 +117,10 @@
 ---@field latency_max integer|nil
 ---@field latency_distribution wrkr.LatencyDistributionPoint[]
 ---@field metrics wrkr.MetricSeriesSummary[]
+
+---@class wrkr.WeightedCheck
+---@field weight? integer How many times the check counts towards the weighted score (default 1)
+---@field [1] fun(value: any): boolean, string? The predicate
+
+---A JSON Schema compiled by `check.compileSchema`.
+---@class wrkr.CompiledSchema
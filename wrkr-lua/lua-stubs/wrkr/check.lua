@@ -1,10 +1,30 @@
 ---@meta
 
----Run checks against any value.
----@generic T
----@param value T
----@param checks table<string, fun(value: T): boolean>
+---Run checks against any value: `check(value, checks) -> ok`.
+---A predicate may return a second value to override the recorded check name.
+---An entry may also be `{ weight = n, predicate }`: the check counts `n` times towards the
+---weighted score (`checks_weight`).
+---@class wrkr.check
+---@overload fun(value: any, checks: table<string, (fun(value: any): boolean, string?)|wrkr.WeightedCheck>): boolean
+local M = {}
+
+---Record one check that passes when `value` conforms to the JSON Schema `schema`.
+---Strings are parsed as JSON text (a response body, a schema file from `fs.read_file`);
+---tables are taken as already decoded. `errors` lists each violation as `path: message`.
+---@param value any
+---@param schema table|string|wrkr.CompiledSchema
+---@param name? string check name (default `json schema`)
 ---@return boolean ok
-return function(value, checks)
-  return true
+---@return string[] errors
+function M.jsonSchema(value, schema, name)
+  return true, {}
+end
+
+---Compile a JSON Schema (a table or JSON text) once, for `check.jsonSchema` calls on a hot path.
+---@param schema table|string
+---@return wrkr.CompiledSchema
+function M.compileSchema(schema)
+  return {}
 end
+
+return M
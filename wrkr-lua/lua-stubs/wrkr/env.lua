@@ -4,4 +4,12 @@
 ---@field [string] string
 local M = {}
 
+---Join `BASE_URL` and `path` with exactly one `/` between them.
+---Errors when `BASE_URL` is not set; an absolute `path` URL is returned unchanged.
+---@param path string
+---@return string url
+function M.url(path)
+	return ""
+end
+
 return M
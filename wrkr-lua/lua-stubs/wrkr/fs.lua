@@ -10,4 +10,13 @@ function M.read_file(rel)
   return ""
 end
 
+---Run another Lua file relative to the script path in the same global environment.
+---Extra arguments are passed to the file as `...`; its return values are returned.
+---@param rel string
+---@param ... any
+---@return any ...
+function M.include(rel, ...)
+  return nil
+end
+
 return M
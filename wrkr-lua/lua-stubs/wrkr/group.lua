@@ -12,4 +12,14 @@ function M.group(name, f)
   return nil
 end
 
+---Run a function as a named step, recording its duration (`step_duration`) and whether it
+---passed (`step_checks`). The step fails when `f` raises or returns `false`; errors are re-raised.
+---@async
+---@param name string
+---@param f fun(): any
+---@return any
+function M.step(name, f)
+  return nil
+end
+
 return M
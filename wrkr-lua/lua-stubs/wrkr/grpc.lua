@@ -19,9 +19,11 @@ local ClientModule = {}
 ---@class wrkr.grpc.ConnectOptions
 ---@field timeout string? e.g. "3s"
 ---@field tls wrkr.grpc.TlsOptions?
+---@field metadata table<string, string|string[]>? Sent with every call; per-call metadata with the same key replaces it.
 
 ---@class wrkr.grpc.InvokeOptions
 ---@field timeout string? e.g. "1s"
+---@field message_timeout string? `server_stream` only: longest gap between two messages, e.g. "10s"
 ---@field metadata table<string, string|string[]>?
 ---@field tags table<string, string|number|boolean>?
 ---@field int64 'integer'|'string'? How to represent int64 values in the response (default: 'integer').
@@ -32,7 +34,15 @@ local ClientModule = {}
 ---@field message string?
 ---@field error string?
 ---@field error_kind string?
+---@field error_details wrkr.grpc.ErrorDetails? Decoded `grpc-status-details-bin` trailer, if the server sent one.
 ---@field response table?
+---@field bytes_sent integer Encoded request message bytes
+---@field bytes_received integer Encoded response message bytes (0 on transport error)
+
+---@class wrkr.grpc.ErrorDetails
+---@field code integer
+---@field message string
+---@field details table[] One entry per detail; `type` is the message name (e.g. `google.rpc.BadRequest`).
 
 ---@class wrkr.grpc.Client
 local Client = {}
@@ -65,6 +75,25 @@ function Client:invoke(full_method, req, opts)
 	return { ok = true, status = 0, response = {} }
 end
 
+---Server-streaming call; `res.response` is the list of messages received, including those
+---before a failure.
+---@param full_method string @"pkg.Service/Method"
+---@param req any|string Either a request table/object, or protobuf-encoded request bytes (Lua string).
+---@param opts wrkr.grpc.InvokeOptions?
+---@return wrkr.grpc.UnaryResponse
+function Client:server_stream(full_method, req, opts)
+	return { ok = true, status = 0, response = {} }
+end
+
+---Alias of `Client:server_stream`.
+---@param full_method string
+---@param req any|string
+---@param opts wrkr.grpc.InvokeOptions?
+---@return wrkr.grpc.UnaryResponse
+function Client:serverStream(full_method, req, opts)
+	return { ok = true, status = 0, response = {} }
+end
+
 ---@param full_method string @"pkg.Service/Method"
 ---@param req any
 ---@return string|nil, string? err Protobuf-encoded request bytes
@@ -72,6 +101,24 @@ function Client:encode(full_method, req)
 	return ""
 end
 
+---Calls `grpc.health.v1.Health/Check` with a bundled schema; no `load()` needed.
+---@param service string? Service name; empty or nil checks the whole server.
+---@param opts wrkr.grpc.InvokeOptions?
+---@return string|nil status `"SERVING"`, `"NOT_SERVING"`, `"SERVICE_UNKNOWN"` or `"UNKNOWN"`
+---@return string? err
+function Client:health_check(service, opts)
+	return "SERVING"
+end
+
+---Alias of `Client:health_check`.
+---@param service string?
+---@param opts wrkr.grpc.InvokeOptions?
+---@return string|nil status
+---@return string? err
+function Client:healthCheck(service, opts)
+	return "SERVING"
+end
+
 M.Client = ClientModule
 
 return M
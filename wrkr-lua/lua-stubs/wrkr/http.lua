@@ -83,4 +83,34 @@ function M.request(method, url, body, opts)
   return { status = 200, body = "", headers = {}, error = nil }
 end
 
+---Send several requests concurrently and return the responses in request order.
+---@param requests wrkr.HttpBatchRequest[]
+---@param opts? wrkr.HttpBatchOptions
+---@async
+---@return wrkr.HttpResponse[]
+function M.batch(requests, opts)
+  return {}
+end
+
+---Stream a GET response body through `on_chunk`; return `false` from it to stop reading.
+---@param url string
+---@param opts? wrkr.HttpRequestOptions
+---@param on_chunk fun(chunk: string): boolean?
+---@async
+---@return wrkr.HttpStreamResponse
+function M.stream(url, opts, on_chunk)
+  return { status = 0, headers = {}, bytes = 0, chunks = 0 }
+end
+
+---Name requests without `opts.name` after their URL path, with numeric and UUID segments
+---replaced by `:id` (e.g. `/users/:id`). Applies to this VU; call it at the top of the script.
+---@param enabled boolean
+function M.auto_name(enabled) end
+
+---Run `hook` on every request of this VU right before it is sent (signing, tracing headers).
+---Change the table in place or return a new one; `nil` removes the hook. The hook must not
+---yield, so it cannot send requests or sleep.
+---@param hook fun(req: wrkr.HttpOutgoingRequest): wrkr.HttpOutgoingRequest?|nil
+function M.on_request(hook) end
+
 return M
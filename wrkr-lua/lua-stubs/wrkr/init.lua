@@ -11,6 +11,7 @@ M.uuid = require("wrkr/uuid")
 M.group = require("wrkr/group")
 M.metrics = require("wrkr/metrics")
 M.shared = require("wrkr/shared")
+M.sleep = require("wrkr/sleep")
 M.vu = require("wrkr/vu")
 
 local ok_http, http = pcall(require, "wrkr/http")
@@ -3,17 +3,23 @@
 ---@class wrkr.json
 local M = {}
 
+---@class wrkr.json.Options
+---@field int64 'integer'|'string'? 'string' decodes JSON integers as strings (no precision loss past 2^53) and encodes the integer strings of `int64_fields` as numbers (default: 'integer').
+---@field int64_fields string[]? Keys (at any depth) whose integer strings `encode` writes as numbers; required by `encode` with int64 = 'string'.
+
 ---Encode a Lua value as JSON.
 ---@param value any
+---@param opts wrkr.json.Options?
 ---@return string
-function M.encode(value)
+function M.encode(value, opts)
   return "{}"
 end
 
 ---Decode a JSON string into Lua values (tables/numbers/strings/bools/nil).
 ---@param json string
+---@param opts wrkr.json.Options?
 ---@return any
-function M.decode(json)
+function M.decode(json, opts)
   return {}
 end
 
@@ -26,6 +26,14 @@ local RateMetric = {}
 ---@param tags? wrkr.MetricTags
 function RateMetric:add(value, tags) end
 
+---@class wrkr.Timer
+local Timer = {}
+---Record the microseconds since the timer was created into the Trend `name`.
+---@param name string
+---@param tags? wrkr.MetricTags
+---@return integer elapsed_us
+function Timer:observe(name, tags) end
+
 ---@class wrkr.metrics
 local M = {}
 
@@ -49,4 +57,8 @@ function M.Gauge(name) return GaugeMetric end
 ---@return wrkr.RateMetric
 function M.Rate(name) return RateMetric end
 
+---Start a timer for an arbitrary sub-operation.
+---@return wrkr.Timer
+function M.timer() return Timer end
+
 return M
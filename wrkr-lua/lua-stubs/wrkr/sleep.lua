@@ -0,0 +1,26 @@
+---@meta
+
+---@alias wrkr.SleepDuration number|string Seconds, or a duration string like "250ms"
+
+---@class wrkr.ThinkTimeOptions
+---@field distribution "uniform"|"normal"|"exponential"|"lognormal"
+---@field min? wrkr.SleepDuration Lower bound (uniform: range start, default 0; others: clamp)
+---@field max? wrkr.SleepDuration Upper bound (uniform: range end, required; others: clamp)
+---@field mean? wrkr.SleepDuration Required for normal, exponential and lognormal
+---@field stddev? wrkr.SleepDuration Required for normal and lognormal
+
+---@class wrkr.sleep
+local M = {}
+
+---Pause the current VU for a fixed duration.
+---@param duration wrkr.SleepDuration
+function M.fixed(duration) end
+
+---Pause the current VU for a random duration drawn from a distribution.
+---@param opts wrkr.ThinkTimeOptions
+---@return number seconds The pause that was taken
+function M.think(opts)
+	return 0
+end
+
+return M
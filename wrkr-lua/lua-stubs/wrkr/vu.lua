@@ -1,6 +1,8 @@
 ---@meta
 
 ---@class wrkr.vu
+---Per-VU table that persists across iterations (not shared with other VUs).
+---@field state table<any, any>
 local M = {}
 
 ---Return the numeric id of the current virtual user.
@@ -9,4 +11,18 @@ function M.id()
   return 0
 end
 
+---Return the current scenario's tags, including run-level tags and matrix parameters.
+---@return table<string, string>
+function M.tags()
+  return {}
+end
+
+---Pause the current VU, but never past the end of a duration-bounded scenario.
+---Returns the seconds actually slept.
+---@param duration number|string seconds or a duration string such as `"2s"`
+---@return number slept
+function M.sleep(duration)
+  return 0
+end
+
 return M
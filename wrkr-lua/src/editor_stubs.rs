@@ -52,6 +52,10 @@ pub fn luals_stub_files() -> &'static [StubFile] {
                     path: "wrkr/shared.lua",
                     contents: include_str!("../lua-stubs/wrkr/shared.lua"),
                 },
+                StubFile {
+                    path: "wrkr/sleep.lua",
+                    contents: include_str!("../lua-stubs/wrkr/sleep.lua"),
+                },
                 StubFile {
                     path: "wrkr/uuid.lua",
                     contents: include_str!("../lua-stubs/wrkr/uuid.lua"),
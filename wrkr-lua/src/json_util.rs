@@ -1,6 +1,7 @@
-use mlua::{Lua, Value};
+use mlua::{Lua, LuaSerdeExt as _, Value};
 
 use crate::Result;
+use crate::value_util::Int64Repr;
 
 pub fn encode_to_vec(value: Value) -> Result<Vec<u8>> {
     // Transcode from Lua's serde Deserializer straight into serde_json's Serializer,
@@ -13,14 +14,37 @@ pub fn encode_to_vec(value: Value) -> Result<Vec<u8>> {
     Ok(out)
 }
 
-pub fn encode(_lua: &Lua, value: Value) -> Result<String> {
-    let out = encode_to_vec(value)?;
+/// With [`Int64Repr::String`], strings holding an integer (e.g. `"9007199254740993"`) under one of
+/// the object keys `int64_fields` are written as JSON numbers, so 64-bit IDs decoded as strings
+/// round-trip unchanged. Other strings are left alone, even if they look like integers.
+pub fn encode(
+    lua: &Lua,
+    value: Value,
+    int64_repr: Int64Repr,
+    int64_fields: &[String],
+) -> Result<String> {
+    let out = match int64_repr {
+        Int64Repr::Integer => encode_to_vec(value)?,
+        Int64Repr::String => {
+            let mut json: serde_json::Value = lua.from_value(value)?;
+            unquote_integer_fields(&mut json, int64_fields);
+            serde_json::to_vec(&json).map_err(mlua::Error::external)?
+        }
+    };
     // serde_json always emits UTF-8.
     let s = String::from_utf8(out).map_err(mlua::Error::external)?;
     Ok(s)
 }
 
-pub fn decode(lua: &Lua, s: &str) -> Result<Value> {
+/// With [`Int64Repr::String`], every JSON integer is decoded as a decimal string; Lua numbers are
+/// doubles and silently round integers beyond 2^53.
+pub fn decode(lua: &Lua, s: &str, int64_repr: Int64Repr) -> Result<Value> {
+    if let Int64Repr::String = int64_repr {
+        let mut json: serde_json::Value = serde_json::from_str(s).map_err(mlua::Error::external)?;
+        quote_integers(&mut json);
+        return Ok(lua.to_value(&json)?);
+    }
+
     // Transcode from JSON directly into Lua values via mlua's serde Serializer.
     let mut deserializer = serde_json::Deserializer::from_str(s);
     let serializer = mlua::serde::ser::Serializer::new(lua);
@@ -28,3 +52,56 @@ pub fn decode(lua: &Lua, s: &str) -> Result<Value> {
         serde_transcode::transcode(&mut deserializer, serializer).map_err(mlua::Error::external)?;
     Ok(v)
 }
+
+fn quote_integers(json: &mut serde_json::Value) {
+    match json {
+        serde_json::Value::Number(n) if !n.is_f64() => {
+            *json = serde_json::Value::String(n.to_string());
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(quote_integers),
+        serde_json::Value::Object(map) => map.values_mut().for_each(quote_integers),
+        _ => {}
+    }
+}
+
+/// Unquote the values of `fields`, wherever they are nested.
+fn unquote_integer_fields(json: &mut serde_json::Value, fields: &[String]) {
+    match json {
+        serde_json::Value::Array(items) => items
+            .iter_mut()
+            .for_each(|v| unquote_integer_fields(v, fields)),
+        serde_json::Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if fields.iter().any(|f| f == key) {
+                    unquote_integers(v);
+                } else {
+                    unquote_integer_fields(v, fields);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Unquote an integer field's value: a string, or an array of them (e.g. a list of IDs).
+fn unquote_integers(json: &mut serde_json::Value) {
+    match json {
+        serde_json::Value::String(s) => {
+            if let Some(n) = parse_integer(s) {
+                *json = serde_json::Value::Number(n);
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(unquote_integers),
+        _ => {}
+    }
+}
+
+/// Parse a canonical decimal integer; `"007"` or `"+1"` stay strings.
+fn parse_integer(s: &str) -> Option<serde_json::Number> {
+    let n = if s.starts_with('-') {
+        serde_json::Number::from(s.parse::<i64>().ok()?)
+    } else {
+        serde_json::Number::from(s.parse::<u64>().ok()?)
+    };
+    (n.to_string() == s).then_some(n)
+}
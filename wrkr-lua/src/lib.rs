@@ -28,13 +28,24 @@ pub enum Error {
     #[error("`Options.iterations` must be a positive integer")]
     InvalidIterations,
 
+    #[error("`Options.warmup_iterations` must be a non-negative integer")]
+    InvalidWarmupIterations,
+
+    #[error("`Options.{0}` must be a boolean")]
+    InvalidBooleanOption(&'static str),
+
+    #[error("`Options.httpCache` must be \"vu\" or \"shared\"")]
+    InvalidHttpCache,
+
     #[error("`Options.vus` must be a positive integer")]
     InvalidVus,
 
     #[error("`Options.scenarios[*].executor` must be a string")]
     InvalidExecutor,
 
-    #[error("`Options.scenarios[*].stages` must be an array of {{ duration, target }}")]
+    #[error(
+        "`Options.scenarios[*].stages` must be an array of {{ duration, target, shape? }} (shape: linear, step, ease-in, ease-out, ease-in-out)"
+    )]
     InvalidStages,
 
     #[error("`Options.duration` must be a valid duration, e.g. 10s, 250ms")]
@@ -46,6 +57,15 @@ pub enum Error {
     #[error("`Options.scenarios[*].tags` must be a table of string -> scalar")]
     InvalidScenarioTags,
 
+    #[error("`Options.tags` must be a table of string -> scalar")]
+    InvalidTags,
+
+    #[error("`Options.scenarios[*].matrix` must be a table of string -> list of scalars")]
+    InvalidMatrix,
+
+    #[error("`description` must be a string")]
+    InvalidDescription,
+
     #[error("`Options.thresholds` must be a table of metric -> [expr, ...]")]
     InvalidThresholds,
 
@@ -54,6 +74,9 @@ pub enum Error {
 
     #[error("invalid metric value")]
     InvalidMetricValue,
+
+    #[error("invalid think time: {0}")]
+    InvalidThinkTime(String),
 }
 
 mod debugger;
@@ -1,7 +1,7 @@
 use crate::Result;
 use crate::loader::{chunk_name, configure_module_path};
 use crate::modules;
-use mlua::{Lua, Value};
+use mlua::{Lua, Table, Value};
 use std::sync::Arc;
 
 pub struct HandleSummaryOutputs {
@@ -60,6 +60,75 @@ pub fn run_teardown(run_ctx: &wrkr_core::RunScenariosContext) -> Result<()> {
     Ok(())
 }
 
+fn histogram_summary_table(lua: &Lua, h: &wrkr_core::HistogramSummary) -> Result<Table> {
+    let tbl = lua.create_table()?;
+    tbl.set("p50", h.p50)?;
+    tbl.set("p75", h.p75)?;
+    tbl.set("p90", h.p90)?;
+    tbl.set("p95", h.p95)?;
+    tbl.set("p99", h.p99)?;
+    tbl.set("min", h.min)?;
+    tbl.set("max", h.max)?;
+    tbl.set("mean", h.mean)?;
+    tbl.set("stdev", h.stdev)?;
+    tbl.set("count", h.count)?;
+
+    // `percentile` lives on the metatable so `pairs()`/`json.encode(summary)` only see data.
+    let percentile = {
+        let h = h.clone();
+        lua.create_function(move |_lua, (_this, p): (Value, f64)| Ok(h.percentile(p)))?
+    };
+    let methods = lua.create_table()?;
+    methods.set("percentile", percentile)?;
+    let mt = lua.create_table()?;
+    mt.set("__index", methods)?;
+    tbl.set_metatable(Some(mt))?;
+
+    Ok(tbl)
+}
+
+fn metric_series_table(lua: &Lua, series: &[wrkr_core::MetricSeriesSummary]) -> Result<Table> {
+    let out = lua.create_table()?;
+
+    for (idx, m) in series.iter().enumerate() {
+        let series_tbl = lua.create_table()?;
+        series_tbl.set("name", m.name.as_str())?;
+
+        let tags_tbl = lua.create_table()?;
+        for (k, v) in &m.tags {
+            tags_tbl.set(k.as_str(), v.as_str())?;
+        }
+        series_tbl.set("tags", tags_tbl)?;
+
+        let (kind, values) = match &m.values {
+            wrkr_core::MetricValue::Counter(v) => {
+                let t = lua.create_table()?;
+                t.set("value", *v)?;
+                ("counter", t)
+            }
+            wrkr_core::MetricValue::Gauge(v) => {
+                let t = lua.create_table()?;
+                t.set("value", *v)?;
+                ("gauge", t)
+            }
+            wrkr_core::MetricValue::Rate { total, hits, rate } => {
+                let t = lua.create_table()?;
+                t.set("total", *total)?;
+                t.set("hits", *hits)?;
+                t.set("rate", *rate)?;
+                ("rate", t)
+            }
+            wrkr_core::MetricValue::Histogram(h) => ("trend", histogram_summary_table(lua, h)?),
+        };
+        series_tbl.set("type", kind)?;
+        series_tbl.set("values", values)?;
+
+        out.set(idx + 1, series_tbl)?;
+    }
+
+    Ok(out)
+}
+
 pub fn run_handle_summary(
     run_ctx: &wrkr_core::RunScenariosContext,
     summary: &wrkr_core::RunSummary,
@@ -83,6 +152,7 @@ pub fn run_handle_summary(
     let mut bytes_sent_total = 0u64;
     let mut iterations_total = 0u64;
     let mut checks_failed_total = 0u64;
+    let mut dropped_iterations_total = 0u64;
 
     let checks_failed_tbl = lua.create_table()?;
     let scenarios_tbl = lua.create_table()?;
@@ -94,6 +164,8 @@ pub fn run_handle_summary(
         bytes_sent_total = bytes_sent_total.saturating_add(s.bytes_sent_total);
         iterations_total = iterations_total.saturating_add(s.iterations_total);
         checks_failed_total = checks_failed_total.saturating_add(s.checks_failed_total);
+        dropped_iterations_total =
+            dropped_iterations_total.saturating_add(s.dropped_iterations_total);
 
         for (name, count) in &s.checks_failed {
             let cur: Option<u64> = checks_failed_tbl.get(name.as_str()).ok();
@@ -108,6 +180,16 @@ pub fn run_handle_summary(
         scenario_tbl.set("bytes_sent_total", s.bytes_sent_total)?;
         scenario_tbl.set("iterations_total", s.iterations_total)?;
         scenario_tbl.set("checks_failed_total", s.checks_failed_total)?;
+        scenario_tbl.set("dropped_iterations_total", s.dropped_iterations_total)?;
+        scenario_tbl.set("saturated", s.saturated())?;
+        scenario_tbl.set("connection_reuse_rate", s.connection_reuse_rate())?;
+        let unix_secs = |t: std::time::SystemTime| {
+            t.duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs_f64())
+                .unwrap_or_default()
+        };
+        scenario_tbl.set("started_at", s.started_at.map(unix_secs))?;
+        scenario_tbl.set("ended_at", s.ended_at.map(unix_secs))?;
 
         let scenario_checks_failed_tbl = lua.create_table()?;
         for (name, count) in &s.checks_failed {
@@ -116,18 +198,7 @@ pub fn run_handle_summary(
         scenario_tbl.set("checks_failed", scenario_checks_failed_tbl)?;
 
         if let Some(lat) = &s.latency {
-            let latency_tbl = lua.create_table()?;
-            latency_tbl.set("p50", lat.p50)?;
-            latency_tbl.set("p75", lat.p75)?;
-            latency_tbl.set("p90", lat.p90)?;
-            latency_tbl.set("p95", lat.p95)?;
-            latency_tbl.set("p99", lat.p99)?;
-            latency_tbl.set("min", lat.min)?;
-            latency_tbl.set("max", lat.max)?;
-            latency_tbl.set("mean", lat.mean)?;
-            latency_tbl.set("stdev", lat.stdev)?;
-            latency_tbl.set("count", lat.count)?;
-            scenario_tbl.set("latency", latency_tbl)?;
+            scenario_tbl.set("latency", histogram_summary_table(&lua, lat)?)?;
         }
 
         scenarios_tbl.set(idx + 1, scenario_tbl)?;
@@ -139,8 +210,17 @@ pub fn run_handle_summary(
     summary_tbl.set("bytes_sent_total", bytes_sent_total)?;
     summary_tbl.set("iterations_total", iterations_total)?;
     summary_tbl.set("checks_failed_total", checks_failed_total)?;
+    summary_tbl.set("dropped_iterations_total", dropped_iterations_total)?;
     summary_tbl.set("checks_failed", checks_failed_tbl)?;
+    if let Some(lat) = &summary.latency {
+        summary_tbl.set("latency", histogram_summary_table(&lua, lat)?)?;
+    }
     summary_tbl.set("scenarios", scenarios_tbl)?;
+    summary_tbl.set("metrics", metric_series_table(&lua, &summary.metrics)?)?;
+    summary_tbl.set(
+        "pooled_metrics",
+        metric_series_table(&lua, &summary.pooled_metrics)?,
+    )?;
 
     let out: Value = handle_summary.call(summary_tbl)?;
     let Value::Table(out_tbl) = out else {
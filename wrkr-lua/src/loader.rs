@@ -56,6 +56,12 @@ pub fn configure_module_path(lua: &Lua, script_path: &Path) -> Result<()> {
 }
 
 pub fn read_script_relative_text(script_path: &Path, rel: &str) -> Result<String> {
+    let path = resolve_script_relative_path(script_path, rel)?;
+    Ok(std::fs::read_to_string(path)?)
+}
+
+/// Resolve `rel` against the script directory, rejecting absolute paths and paths that escape it.
+pub fn resolve_script_relative_path(script_path: &Path, rel: &str) -> Result<PathBuf> {
     if Path::new(rel).is_absolute() {
         return Err(Error::InvalidPath(rel.to_string()));
     }
@@ -70,7 +76,7 @@ pub fn read_script_relative_text(script_path: &Path, rel: &str) -> Result<String
         return Err(Error::InvalidPath(rel.to_string()));
     }
 
-    Ok(std::fs::read_to_string(candidate)?)
+    Ok(candidate)
 }
 
 #[cfg(test)]
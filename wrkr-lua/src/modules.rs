@@ -13,9 +13,13 @@
 mod grpc;
 #[cfg(feature = "http")]
 mod http;
+#[cfg(feature = "http")]
+pub(crate) use http::reset_vu_connections;
 mod json;
 mod metrics;
 mod shared;
+mod sleep;
+pub(crate) use sleep::{parse_think_time, take_slept};
 mod uuid;
 mod vu;
 mod wrkr;
@@ -27,6 +31,18 @@ fn preload_set(lua: &Lua, name: &str, loader: mlua::Function) -> Result<()> {
     Ok(())
 }
 
+/// The request name a script asked for: `opts.name`, else `opts.tags.name`. Any `name` tag is
+/// removed from `tags`, so callers push the name they settle on.
+#[cfg(any(feature = "http", feature = "grpc"))]
+fn take_request_name(name: Option<String>, tags: &mut Vec<(String, String)>) -> Option<String> {
+    let tagged = tags
+        .iter()
+        .position(|(k, _)| k == "name")
+        .map(|idx| tags.remove(idx).1);
+    tags.retain(|(k, _)| k != "name");
+    name.or(tagged)
+}
+
 pub struct RegisterContext<'a> {
     pub vu_id: u64,
     pub max_vus: u64,
@@ -58,8 +74,9 @@ pub fn register(lua: &Lua, ctx: RegisterContext<'_>) -> Result<()> {
     debug::register(lua)?;
     json::register(lua)?;
     uuid::register(lua)?;
-    vu::register(lua, ctx.vu_id)?;
-    group::register(lua)?;
+    sleep::register(lua)?;
+    vu::register(lua, ctx.vu_id, metrics_ctx.clone())?;
+    group::register(lua, run_ctx.clone(), metrics_ctx.clone())?;
     shared::register_runtime(lua, run_ctx.clone())?;
     wrkr::register(lua)?;
     Ok(())
@@ -5,6 +5,7 @@
 
 use super::preload_set;
 
+mod json_schema;
 mod record;
 
 pub fn register(
@@ -13,25 +14,49 @@ pub fn register(
     metrics_ctx: wrkr_core::MetricsContext,
 ) -> Result<()> {
     let metrics = run_ctx.metrics.clone();
-    let metric_checks = metrics.register("checks", MetricKind::Counter);
+    let ids = record::CheckMetricIds {
+        checks: metrics.register("checks", MetricKind::Counter),
+        weight: metrics.register("checks_weight", MetricKind::Counter),
+    };
+    let check_names = run_ctx.check_names.clone();
 
     let loader = lua.create_function(move |lua, ()| {
         let check_fn = {
             let metrics = metrics.clone();
             let metrics_ctx = metrics_ctx.clone();
-            lua.create_function(move |lua, (data, checks): (Value, Table)| {
+            let check_names = check_names.clone();
+            lua.create_function(move |lua, (_this, data, checks): (Value, Value, Table)| {
                 record::run_checks(
                     lua,
                     data,
                     checks,
                     metrics.clone(),
-                    metric_checks,
+                    ids,
                     metrics_ctx.clone(),
+                    check_names.as_ref(),
                 )
             })?
         };
+        let json_schema_fn = {
+            let metrics = metrics.clone();
+            let metrics_ctx = metrics_ctx.clone();
+            lua.create_function(move |lua, args: (Value, Value, Option<String>)| {
+                record::run_json_schema_check(lua, args, metrics.clone(), ids, metrics_ctx.clone())
+            })?
+        };
+
+        let compile_schema_fn =
+            lua.create_function(|lua, schema: Value| record::compile_schema(lua, schema))?;
+
+        // The module is still called as `check(value, checks)`; helpers hang off the table.
+        let module = lua.create_table()?;
+        module.set("jsonSchema", json_schema_fn)?;
+        module.set("compileSchema", compile_schema_fn)?;
+        let mt = lua.create_table()?;
+        mt.set("__call", check_fn)?;
+        module.set_metatable(Some(mt))?;
 
-        Ok(check_fn)
+        Ok(module)
     })?;
 
     preload_set(lua, "wrkr/check", loader).map_err(mlua::Error::external)
@@ -0,0 +1,159 @@
+//! JSON Schema validation for `check.jsonSchema`, backed by the `jsonschema` crate.
+//!
+//! Schemas default to draft 2020-12 unless they name another draft in `$schema`. `format` is an
+//! annotation only, and `$ref`s must resolve within the schema itself: nothing is fetched.
+
+use std::sync::Arc;
+
+use jsonschema::paths::LocationSegment;
+use serde_json::Value;
+
+/// A schema compiled once, by `check.compileSchema` or for a single `check.jsonSchema` call.
+#[derive(Clone)]
+pub(super) struct CompiledSchema(Arc<jsonschema::Validator>);
+
+impl mlua::UserData for CompiledSchema {}
+
+impl CompiledSchema {
+    /// `Err` means the schema itself is unusable (an invalid `pattern`, an unresolved `$ref`).
+    pub(super) fn compile(schema: &Value) -> Result<Self, String> {
+        jsonschema::validator_for(schema)
+            .map(|validator| Self(Arc::new(validator)))
+            .map_err(|e| format!("invalid schema: {e}"))
+    }
+
+    /// One `path: message` per violation; empty when `instance` conforms.
+    pub(super) fn errors(&self, instance: &Value) -> Vec<String> {
+        self.0
+            .iter_errors(instance)
+            .map(|e| {
+                let mut path = String::from("$");
+                for segment in e.instance_path().iter() {
+                    match segment {
+                        LocationSegment::Property(name) => {
+                            path.push('.');
+                            path.push_str(&name);
+                        }
+                        LocationSegment::Index(i) => path.push_str(&format!("[{i}]")),
+                    }
+                }
+                format!("{path}: {e}")
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn errors(instance: Value, schema: Value) -> Vec<String> {
+        CompiledSchema::compile(&schema)
+            .unwrap_or_else(|e| panic!("{e}"))
+            .errors(&instance)
+    }
+
+    #[test]
+    fn conforming_document_has_no_errors() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id", "tags"],
+            "properties": {
+                "id": { "type": "integer", "minimum": 1 },
+                "name": { "type": "string", "minLength": 1, "pattern": "^[a-z]+$" },
+                "tags": { "type": "array", "items": { "type": "string" }, "uniqueItems": true },
+                "kind": { "enum": ["a", "b"] },
+                "price": { "type": "number", "multipleOf": 0.01 }
+            },
+            "additionalProperties": false
+        });
+        // Lua hands integers over as floats.
+        let doc =
+            json!({ "id": 7.0, "name": "bob", "tags": ["x", "y"], "kind": "a", "price": 19.99 });
+        assert_eq!(errors(doc, schema), Vec::<String>::new());
+    }
+
+    #[test]
+    fn multiple_of_tolerates_decimal_steps() {
+        let schema = json!({ "multipleOf": 0.1 });
+        assert!(errors(json!(0.3), schema.clone()).is_empty());
+        assert_eq!(errors(json!(0.35), schema).len(), 1);
+    }
+
+    #[test]
+    fn violations_name_the_offending_path() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id", "items"],
+            "properties": {
+                "id": { "type": "integer" },
+                "items": { "type": "array", "items": { "$ref": "#/$defs/item" } }
+            },
+            "additionalProperties": false,
+            "$defs": {
+                "item": { "type": "object", "properties": { "qty": { "maximum": 10 } } }
+            }
+        });
+        let doc = json!({ "id": "7", "items": [{ "qty": 3 }, { "qty": 11 }], "extra": true });
+        let mut found = errors(doc, schema);
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                "$.id: \"7\" is not of type \"integer\"",
+                "$.items[1].qty: 11 is greater than the maximum of 10",
+                "$: Additional properties are not allowed ('extra' was unexpected)",
+            ]
+        );
+        assert_eq!(
+            errors(json!({}), json!({ "required": ["id"] })),
+            ["$: \"id\" is a required property"]
+        );
+    }
+
+    #[test]
+    fn combinators_and_conditionals() {
+        let one_of = json!({ "oneOf": [{ "type": "integer" }, { "type": "number" }] });
+        assert!(errors(json!(1.5), one_of.clone()).is_empty());
+        assert_eq!(errors(json!(2), one_of).len(), 1);
+
+        let any_of = json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] });
+        assert!(errors(Value::Null, any_of.clone()).is_empty());
+        assert_eq!(errors(json!(1), any_of).len(), 1);
+
+        let conditional = json!({
+            "if": { "properties": { "kind": { "const": "card" } } },
+            "then": { "required": ["last4"] },
+            "else": { "required": ["iban"] }
+        });
+        assert!(
+            errors(
+                json!({ "kind": "card", "last4": "1234" }),
+                conditional.clone()
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            errors(json!({ "kind": "bank" }), conditional),
+            ["$: \"iban\" is a required property"]
+        );
+        assert_eq!(
+            errors(json!("x"), json!({ "not": { "type": "string" } })).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn unusable_schemas_are_errors() {
+        for schema in [
+            json!({ "pattern": "(" }),
+            json!({ "$ref": "#/$defs/missing" }),
+            json!({ "$ref": "other.json" }),
+            json!({ "type": "int" }),
+        ] {
+            assert!(CompiledSchema::compile(&schema).is_err(), "{schema}");
+        }
+    }
+}
@@ -1,11 +1,20 @@
 use std::sync::Arc;
 
-use mlua::{Lua, Table, Value};
+use mlua::{Lua, LuaSerdeExt as _, Table, Value};
 use wrkr_metrics::{MetricHandle, MetricId, Registry};
 
+use super::json_schema::CompiledSchema;
+
+/// The `checks` counter and its weighted twin `checks_weight`, recorded with the same tags.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct CheckMetricIds {
+    pub(super) checks: MetricId,
+    pub(super) weight: MetricId,
+}
+
 pub(super) struct CheckRecorder {
     metrics: Arc<Registry>,
-    metric_checks: MetricId,
+    ids: CheckMetricIds,
     group: Option<String>,
     metrics_ctx: wrkr_core::MetricsContext,
 }
@@ -14,19 +23,19 @@ impl CheckRecorder {
     pub(super) fn new(
         lua: &Lua,
         metrics: Arc<Registry>,
-        metric_checks: MetricId,
+        ids: CheckMetricIds,
         metrics_ctx: wrkr_core::MetricsContext,
     ) -> Self {
         let group = super::super::group::current_group(lua);
         Self {
             metrics,
-            metric_checks,
+            ids,
             group,
             metrics_ctx,
         }
     }
 
-    pub(super) fn record(&self, name: &str, passed: bool) {
+    pub(super) fn record(&self, name: &str, passed: bool, weight: u64) {
         let status = if passed { "pass" } else { "fail" };
 
         let mut tags: Vec<(String, String)> = Vec::with_capacity(
@@ -48,8 +57,10 @@ pub(super) fn record(&self, name: &str, passed: bool) {
             tags.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
         let tags = self.metrics.resolve_tags(&tag_refs);
 
-        if let Some(MetricHandle::Counter(c)) = self.metrics.get_handle(self.metric_checks, tags) {
-            c.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        for (metric, n) in [(self.ids.checks, 1), (self.ids.weight, weight)] {
+            if let Some(MetricHandle::Counter(c)) = self.metrics.get_handle(metric, tags.clone()) {
+                c.fetch_add(n, std::sync::atomic::Ordering::Relaxed);
+            }
         }
     }
 }
@@ -59,24 +70,194 @@ pub(super) fn run_checks(
     data: Value,
     checks: Table,
     metrics: Arc<Registry>,
-    metric_checks: MetricId,
+    ids: CheckMetricIds,
     metrics_ctx: wrkr_core::MetricsContext,
+    check_names: &wrkr_core::CardinalityGuard,
 ) -> mlua::Result<bool> {
-    let recorder = CheckRecorder::new(lua, metrics, metric_checks, metrics_ctx);
+    let recorder = CheckRecorder::new(lua, metrics, ids, metrics_ctx);
 
     let mut all_passed = true;
 
     // Iterate over the checks table: { "status is 200": function(v) return ... end }
-    for pair in checks.pairs::<String, mlua::Function>() {
-        let Ok((name, predicate)) = pair else {
+    // A predicate may return a second value to override the recorded name, e.g.
+    // `return r.status < 400, ("status is %dxx"):format(r.status // 100)`.
+    for pair in checks.pairs::<String, Value>() {
+        let Ok((name, entry)) = pair else {
+            continue;
+        };
+        let Some((predicate, weight)) = parse_entry(&name, entry)? else {
             continue;
         };
 
-        let passed = predicate.call::<bool>(data.clone()).unwrap_or_default();
+        // A predicate that raises is a failed check. A computed name of the wrong type is a bug
+        // in the script, reported rather than recorded as a failure.
+        let (passed, computed_name) = match predicate.call::<mlua::MultiValue>(data.clone()) {
+            Ok(mut ret) => {
+                let passed = !matches!(
+                    ret.pop_front(),
+                    None | Some(Value::Nil | Value::Boolean(false))
+                );
+                let computed_name = match ret.pop_front() {
+                    None | Some(Value::Nil) => None,
+                    Some(Value::String(s)) => Some(s.to_str()?.to_string()),
+                    Some(Value::Integer(n)) => Some(n.to_string()),
+                    Some(Value::Number(n)) => Some(n.to_string()),
+                    Some(other) => {
+                        return Err(mlua::Error::external(format!(
+                            "check `{name}`: computed name must be a string, got {}",
+                            other.type_name()
+                        )));
+                    }
+                };
+                (passed, computed_name)
+            }
+            Err(_) => (false, None),
+        };
         all_passed &= passed;
 
-        recorder.record(name.as_str(), passed);
+        // Computed names past the cardinality limit fall back to the static key.
+        let name = match computed_name {
+            Some(computed) if check_names.admit(&computed) => computed,
+            _ => name,
+        };
+
+        recorder.record(name.as_str(), passed, weight);
     }
 
     Ok(all_passed)
 }
+
+/// `check.compileSchema(schema)`: `schema` compiled once, so that `check.jsonSchema` calls on a
+/// hot path skip parsing it and compiling its patterns.
+pub(super) fn compile_schema(lua: &Lua, schema: Value) -> mlua::Result<CompiledSchema> {
+    compile(lua, schema, "check.compileSchema")
+}
+
+/// `check.jsonSchema(value, schema, name?)`: one check named `name` (default `json schema`) that
+/// passes when `value` conforms to `schema`. Returns the result and the violations found.
+pub(super) fn run_json_schema_check(
+    lua: &Lua,
+    (value, schema, name): (Value, Value, Option<String>),
+    metrics: Arc<Registry>,
+    ids: CheckMetricIds,
+    metrics_ctx: wrkr_core::MetricsContext,
+) -> mlua::Result<(bool, Table)> {
+    let schema = match schema {
+        Value::UserData(compiled) => CompiledSchema::clone(&compiled.borrow::<CompiledSchema>()?),
+        other => compile(lua, other, "check.jsonSchema")?,
+    };
+    let errors = match to_json(lua, value, "value") {
+        Ok(value) => schema.errors(&value),
+        // A body that is not JSON at all is a failed check, not a script error.
+        Err(err) => vec![format!("$: {err}")],
+    };
+
+    let passed = errors.is_empty();
+    let name = name.unwrap_or_else(|| "json schema".to_string());
+    CheckRecorder::new(lua, metrics, ids, metrics_ctx).record(&name, passed, 1);
+    Ok((passed, lua.create_sequence_from(errors)?))
+}
+
+fn compile(lua: &Lua, schema: Value, caller: &str) -> mlua::Result<CompiledSchema> {
+    to_json(lua, schema, "schema")
+        .and_then(|schema| CompiledSchema::compile(&schema))
+        .map_err(|e| mlua::Error::external(format!("{caller}: {e}")))
+}
+
+/// A string is parsed as JSON text (e.g. a response body or a schema file read with `wrkr/fs`);
+/// any other value is taken as already decoded.
+fn to_json(lua: &Lua, value: Value, what: &str) -> Result<serde_json::Value, String> {
+    match value {
+        Value::String(text) => serde_json::from_slice(&text.as_bytes())
+            .map_err(|e| format!("{what} is not valid JSON: {e}")),
+        other => lua
+            .from_value(other)
+            .map_err(|e| format!("{what} cannot be converted to JSON: {e}")),
+    }
+}
+
+/// A `checks` entry: a predicate, or `{ weight = n, predicate }` for a check that counts `n`
+/// times towards the weighted score. Entries of any other type are skipped.
+fn parse_entry(name: &str, entry: Value) -> mlua::Result<Option<(mlua::Function, u64)>> {
+    let table = match entry {
+        Value::Function(predicate) => return Ok(Some((predicate, 1))),
+        Value::Table(table) => table,
+        _ => return Ok(None),
+    };
+
+    let invalid = |what: &str| mlua::Error::external(format!("check `{name}`: {what}"));
+    let predicate = table
+        .get::<Option<mlua::Function>>(1)?
+        .ok_or_else(|| invalid("expected `{ weight = n, function(v) ... end }`"))?;
+    let weight = match table.get::<Value>("weight")? {
+        Value::Nil => 1,
+        Value::Integer(n) => u64::try_from(n).map_err(|_| invalid("`weight` must be >= 0"))?,
+        Value::Number(n) if n >= 0.0 && n.fract() == 0.0 && n <= u64::MAX as f64 => n as u64,
+        _ => return Err(invalid("`weight` must be a non-negative integer")),
+    };
+    Ok(Some((predicate, weight)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(lua: &Lua, src: &str) -> Value {
+        lua.load(src)
+            .eval()
+            .unwrap_or_else(|err| panic!("eval `{src}`: {err}"))
+    }
+
+    fn weight_of(lua: &Lua, src: &str) -> mlua::Result<Option<u64>> {
+        parse_entry("ok", entry(lua, src)).map(|parsed| parsed.map(|(_, weight)| weight))
+    }
+
+    #[test]
+    fn parse_entry_accepts_predicates_and_weighted_tables() {
+        let lua = Lua::new();
+        let weight = |src| weight_of(&lua, src).unwrap_or_else(|err| panic!("`{src}`: {err}"));
+
+        assert_eq!(weight("return function(v) return true end"), Some(1));
+        assert_eq!(weight("return { function(v) return true end }"), Some(1));
+        assert_eq!(
+            weight("return { weight = 3, function(v) return true end }"),
+            Some(3)
+        );
+        assert_eq!(
+            weight("return { weight = 2.0, function(v) return true end }"),
+            Some(2)
+        );
+        assert_eq!(
+            weight("return { weight = 0, function(v) return true end }"),
+            Some(0)
+        );
+        assert_eq!(weight("return 'not a check'"), None);
+    }
+
+    #[test]
+    fn parse_entry_rejects_invalid_weights_and_missing_predicates() {
+        let lua = Lua::new();
+        for src in [
+            "return { weight = -1, function(v) return true end }",
+            "return { weight = -2.0, function(v) return true end }",
+            "return { weight = 1.5, function(v) return true end }",
+            "return { weight = 'heavy', function(v) return true end }",
+        ] {
+            let err = weight_of(&lua, src).err().map(|e| e.to_string());
+            assert!(
+                err.as_deref()
+                    .is_some_and(|e| e.contains("check `ok`") && e.contains("`weight`")),
+                "`{src}`: {err:?}"
+            );
+        }
+
+        let err = weight_of(&lua, "return { weight = 2 }")
+            .err()
+            .map(|e| e.to_string());
+        assert!(
+            err.as_deref()
+                .is_some_and(|e| e.contains("expected `{ weight = n, function(v) ... end }`")),
+            "{err:?}"
+        );
+    }
+}
@@ -14,7 +14,84 @@ pub(super) fn register_runtime(
         for (k, v) in env_vars.iter() {
             t.set(k.as_ref(), v.as_ref())?;
         }
+
+        // Helpers live on the metatable so `pairs(env)` only yields variables, and a variable
+        // that happens to be named like a helper still wins.
+        let base_url = env_vars
+            .iter()
+            .find_map(|(k, v)| (k.as_ref() == "BASE_URL").then(|| v.clone()));
+        let url = lua.create_function(move |_, path: String| {
+            let Some(base) = base_url.as_deref().filter(|b| !b.trim().is_empty()) else {
+                return Err(mlua::Error::external(
+                    "env.url: BASE_URL is not set (pass `--env BASE_URL=http://host:port`)",
+                ));
+            };
+            Ok(join_url(base, &path))
+        })?;
+        let helpers = lua.create_table()?;
+        helpers.set("url", url)?;
+        let mt = lua.create_table()?;
+        mt.set("__index", helpers)?;
+        t.set_metatable(Some(mt))?;
+
         Ok::<Table, mlua::Error>(t)
     })?;
     super::preload_set(lua, "wrkr/env", loader)
 }
+
+/// `base` and `path` joined by exactly one `/`; an absolute `path` URL is returned unchanged.
+fn join_url(base: &str, path: &str) -> String {
+    if has_scheme(path) {
+        return path.to_string();
+    }
+
+    let base = base.trim().trim_end_matches('/');
+    let path = path.trim_start_matches('/');
+    if path.is_empty() {
+        return format!("{base}/");
+    }
+    format!("{base}/{path}")
+}
+
+/// Starts with `scheme://` (RFC 3986 scheme characters), unlike a path that merely carries a URL
+/// further on, e.g. in its query.
+fn has_scheme(path: &str) -> bool {
+    let Some((scheme, _)) = path.split_once("://") else {
+        return false;
+    };
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_url_normalizes_slashes() {
+        for (base, path) in [
+            ("http://api", "users"),
+            ("http://api/", "users"),
+            ("http://api", "/users"),
+            ("http://api//", "//users"),
+        ] {
+            assert_eq!(join_url(base, path), "http://api/users", "{base} + {path}");
+        }
+        assert_eq!(
+            join_url("http://api/v1/", "/users?id=1"),
+            "http://api/v1/users?id=1"
+        );
+        assert_eq!(join_url("http://api", ""), "http://api/");
+        assert_eq!(join_url("http://api", "https://other/x"), "https://other/x");
+        assert_eq!(
+            join_url("http://api", "/cb?next=https://x"),
+            "http://api/cb?next=https://x"
+        );
+        assert_eq!(
+            join_url("http://api", "grpc+tls://other"),
+            "grpc+tls://other"
+        );
+    }
+}
@@ -1,19 +1,40 @@
 use std::path::Path;
 
-use mlua::{Lua, Table};
+use mlua::{Lua, MultiValue, Table};
 
 use crate::Result;
-use crate::loader::read_script_relative_text;
+use crate::loader::{chunk_name, read_script_relative_text, resolve_script_relative_path};
 
 pub(super) fn register(lua: &Lua, script_path: &Path) -> Result<()> {
     let script_path = script_path.to_path_buf();
     let loader = lua.create_function(move |lua, ()| {
         let t = lua.create_table()?;
-        let script_path = script_path.clone();
-        let read_file = lua.create_function(move |_lua, rel: String| {
-            read_script_relative_text(script_path.as_path(), &rel).map_err(mlua::Error::external)
-        })?;
+
+        let read_file = {
+            let script_path = script_path.clone();
+            lua.create_function(move |_lua, rel: String| {
+                read_script_relative_text(script_path.as_path(), &rel)
+                    .map_err(mlua::Error::external)
+            })?
+        };
+
+        // Runs another script file in the caller's global environment (like `dofile`, but
+        // resolved and sandboxed like `read_file`). The chunk is named after the included file so
+        // errors point at its own lines.
+        let include = {
+            let script_path = script_path.clone();
+            lua.create_function(move |lua, (rel, args): (String, MultiValue)| {
+                let path = resolve_script_relative_path(script_path.as_path(), &rel)
+                    .map_err(mlua::Error::external)?;
+                let source = std::fs::read_to_string(&path).map_err(mlua::Error::external)?;
+                lua.load(source)
+                    .set_name(chunk_name(&path))
+                    .call::<MultiValue>(args)
+            })?
+        };
+
         t.set("read_file", read_file)?;
+        t.set("include", include)?;
         Ok::<Table, mlua::Error>(t)
     })?;
 
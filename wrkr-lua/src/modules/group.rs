@@ -1,4 +1,8 @@
-use mlua::{Function, Lua, Value};
+use std::sync::Arc;
+use std::time::Instant;
+
+use mlua::{Function, Lua, MultiValue, Value};
+use wrkr_metrics::{MetricId, MetricKind, Registry};
 
 use crate::Result;
 
@@ -21,8 +25,51 @@ fn set_current_group(lua: &Lua, group: Option<&str>) -> mlua::Result<()> {
     }
 }
 
-pub(super) fn register(lua: &Lua) -> Result<()> {
-    let loader = lua.create_function(|lua, ()| {
+/// The `step_duration` Trend and `step_checks` Rate that `group.step` records into.
+#[derive(Clone)]
+struct StepMetrics {
+    metrics: Arc<Registry>,
+    duration: MetricId,
+    checks: MetricId,
+    metrics_ctx: wrkr_core::MetricsContext,
+}
+
+impl StepMetrics {
+    fn record(&self, lua: &Lua, name: &str, elapsed_us: u64, passed: bool) {
+        let mut tags = vec![("step".to_string(), name.to_string())];
+        self.metrics_ctx
+            .merge_base_tags_if_missing(&mut tags, &["step", "group"]);
+        if let Some(group) = current_group(lua) {
+            tags.push(("group".to_string(), group));
+        }
+
+        let tag_refs: Vec<(&str, &str)> =
+            tags.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        let tags = self.metrics.resolve_tags(&tag_refs);
+
+        if let Some(handle) = self.metrics.get_handle(self.duration, tags.clone()) {
+            handle.observe_histogram(elapsed_us);
+        }
+        if let Some(handle) = self.metrics.get_handle(self.checks, tags) {
+            handle.add_rate(u64::from(passed), 1);
+        }
+    }
+}
+
+pub(super) fn register(
+    lua: &Lua,
+    run_ctx: Arc<wrkr_core::RunScenariosContext>,
+    metrics_ctx: wrkr_core::MetricsContext,
+) -> Result<()> {
+    let metrics = run_ctx.metrics.clone();
+    let steps = StepMetrics {
+        duration: metrics.register("step_duration", MetricKind::Histogram),
+        checks: metrics.register("step_checks", MetricKind::Rate),
+        metrics,
+        metrics_ctx,
+    };
+
+    let loader = lua.create_function(move |lua, ()| {
         let t = lua.create_table()?;
 
         let group = lua.create_async_function(|lua, (name, f): (String, Function)| async move {
@@ -40,7 +87,28 @@ pub(super) fn register(lua: &Lua) -> Result<()> {
             res
         })?;
 
+        // A step fails when `f` raises (the error is re-raised after recording) or returns
+        // `false` as its first value; every other return value passes through.
+        let steps = steps.clone();
+        let step = lua.create_async_function(move |lua, (name, f): (String, Function)| {
+            let steps = steps.clone();
+            async move {
+                let started = Instant::now();
+                let res: mlua::Result<MultiValue> = f.call_async(()).await;
+                let elapsed_us: u64 = started.elapsed().as_micros().try_into().unwrap_or(u64::MAX);
+
+                let passed = match &res {
+                    Ok(values) => !matches!(values.front(), Some(Value::Boolean(false))),
+                    Err(_) => false,
+                };
+                steps.record(&lua, &name, elapsed_us, passed);
+
+                res
+            }
+        })?;
+
         t.set("group", group)?;
+        t.set("step", step)?;
         Ok::<mlua::Table, mlua::Error>(t)
     })?;
 
@@ -14,6 +14,29 @@ fn grpc_error_kind(err: &wrkr_grpc::Error) -> wrkr_grpc::GrpcTransportErrorKind
     err.transport_error_kind()
 }
 
+/// The serving status name from a health check, or the reason the check failed.
+fn health_status(
+    res: std::result::Result<wrkr_grpc::UnaryResult, wrkr_grpc::shared::Error>,
+) -> std::result::Result<String, String> {
+    let res = res.map_err(|err| err.to_string())?;
+    if !res.ok {
+        return Err(res
+            .error
+            .unwrap_or_else(|| "grpc health check failed".to_string()));
+    }
+
+    let status = match &res.response {
+        wrkr_value::Value::Object(obj) => obj.get("status"),
+        _ => None,
+    };
+    Ok(match status {
+        Some(wrkr_value::Value::String(s)) => s.to_string(),
+        Some(wrkr_value::Value::I64(n)) => n.to_string(),
+        // proto3 leaves the zero value off the wire.
+        _ => "UNKNOWN".to_string(),
+    })
+}
+
 pub(super) fn create_client_table(
     lua: &Lua,
     run_ctx: Arc<wrkr_core::RunScenariosContext>,
@@ -28,10 +51,15 @@ pub(super) fn create_client_table(
     let metrics = run_ctx.metrics.clone();
     let request_metrics = run_ctx.request_metrics;
     let grpc_registry = run_ctx.grpc.clone();
+    let failures = run_ctx.failures.clone();
+    let request_names = run_ctx.request_names.clone();
+    let insecure_skip_verify = run_ctx.insecure_skip_verify;
+    let disable_tls_resumption = run_ctx.disable_tls_resumption;
 
     let new_fn = {
         let script_path = script_path.to_path_buf();
         let metrics = metrics.clone();
+        let failures = failures.clone();
         let metrics_ctx = metrics_ctx.clone();
         let grpc_registry = grpc_registry.clone();
         lua.create_function(move |lua, opts: Option<Table>| {
@@ -70,7 +98,15 @@ pub(super) fn create_client_table(
                     move |lua, (_this, target, opts): (Table, String, Option<Table>)| {
                         let shared = shared.clone();
                         async move {
-                            let options = ConnectLuaOptions::parse(opts)?.into_connect_options();
+                            let mut options =
+                                ConnectLuaOptions::parse(opts)?.into_connect_options();
+                            // `--insecure` and `--no-tls-resumption` also cover `https://`
+                            // targets without a `tls` table.
+                            if options.tls.is_some() || target.starts_with("https://") {
+                                let tls = options.tls.get_or_insert_with(Default::default);
+                                tls.insecure_skip_verify |= insecure_skip_verify;
+                                tls.disable_session_resumption |= disable_tls_resumption;
+                            }
 
                             match shared.connect(target, options).await {
                                 Ok(()) => {
@@ -90,12 +126,16 @@ pub(super) fn create_client_table(
             };
 
             // invoke(full_method, req, opts?) -> res_tbl (never throws on runtime errors)
+            // server_stream(full_method, req, opts?) -> res_tbl, `res.response` being the list of
+            // received messages.
             // If `req` is a Lua string, it's treated as protobuf-encoded request bytes.
             // Otherwise `req` is converted from Lua -> wrkr_value::Value and encoded.
-            let invoke_fn = {
+            let make_invoke_fn = |streaming: bool| {
                 let shared = shared.clone();
                 let metrics = metrics.clone();
                 let metrics_ctx = metrics_ctx.clone();
+                let failures = failures.clone();
+                let request_names = request_names.clone();
                 lua.create_async_function(
                     move |lua,
                           (_this, full_method, req, opts): (
@@ -107,6 +147,8 @@ pub(super) fn create_client_table(
                         let shared = shared.clone();
                         let metrics = metrics.clone();
                         let metrics_ctx = metrics_ctx.clone();
+                        let failures = failures.clone();
+                        let request_names = request_names.clone();
                         async move {
                             let client = shared.client();
 
@@ -133,31 +175,40 @@ pub(super) fn create_client_table(
                                 }
                             };
 
-                            let mut _tags: Vec<(String, String)> = Vec::new();
                             let parsed =
                                 InvokeLuaOptions::parse(opts).map_err(mlua::Error::external)?;
-                            _tags = parsed.tags;
+                            let mut tags = parsed.tags;
                             let timeout = parsed.timeout;
-                            let metadata = parsed.metadata;
+                            let metadata = shared.call_metadata(parsed.metadata);
                             let int64_repr = parsed.int64_repr;
+                            let expected_statuses = parsed.expected_statuses;
+
+                            // Same rule as HTTP: names past the run-wide limit fall back to the
+                            // default, which for gRPC is the (proto-bounded) method name.
+                            let name = super::super::take_request_name(parsed.name, &mut tags)
+                                .filter(|name| request_names.admit(name))
+                                .unwrap_or_else(|| full_method_str.to_string());
+                            tags.push(("name".to_string(), name));
 
                             metrics_ctx.merge_scenario_tags_if_missing(
-                                &mut _tags,
+                                &mut tags,
                                 &["scenario", "protocol", "error_kind", "group"],
                             );
 
                             if let Some(group) = super::super::group::current_group(&lua)
-                                && !_tags.iter().any(|(k, _)| k == "group")
+                                && !tags.iter().any(|(k, _)| k == "group")
                             {
-                                _tags.push(("group".to_string(), group));
+                                tags.push(("group".to_string(), group));
                             }
 
-                            let extra_tags: Vec<(&str, &str)> = _tags
-                                .iter()
-                                .map(|(k, v)| (k.as_str(), v.as_str()))
-                                .collect();
+                            let extra_tags: Vec<(&str, &str)> =
+                                tags.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
 
-                            let invoke_opts = wrkr_grpc::InvokeOptions { timeout, metadata };
+                            let invoke_opts = wrkr_grpc::InvokeOptions {
+                                timeout,
+                                message_timeout: parsed.message_timeout,
+                                metadata,
+                            };
 
                             // Always encode to bytes here so we can account bytes_sent without
                             // double-encoding inside the client.
@@ -191,28 +242,70 @@ pub(super) fn create_client_table(
                             };
 
                             let started = Instant::now();
-                            let res = client
-                                .unary_bytes(method.as_ref(), req_bytes.clone(), invoke_opts)
-                                .await;
+                            let res = if streaming {
+                                client
+                                    .server_streaming_bytes(
+                                        method.as_ref(),
+                                        req_bytes.clone(),
+                                        invoke_opts,
+                                    )
+                                    .await
+                            } else {
+                                client
+                                    .unary_bytes(method.as_ref(), req_bytes.clone(), invoke_opts)
+                                    .await
+                            };
                             let elapsed = started.elapsed();
 
                             match res {
-                                Ok(res) => {
-                                    // Transport succeeded (even if gRPC status is non-OK).
+                                Ok(mut res) => {
+                                    // A status the caller declared as expected is a success
+                                    // for `res.ok` and the failure log; `status` and `message`
+                                    // are kept.
+                                    if !res.ok
+                                        && res.transport_error_kind.is_none()
+                                        && res
+                                            .status
+                                            .is_some_and(|code| expected_statuses.contains(&code))
+                                    {
+                                        res.ok = true;
+                                        res.error = None;
+                                    }
+
+                                    // A non-OK gRPC status still counts as a transport success;
+                                    // a stream that stopped sending (`message_timeout`) does not.
+                                    let error_kind =
+                                        res.transport_error_kind.map(|kind| kind.to_string());
                                     request_metrics.record_request(
                                         &metrics,
                                         wrkr_core::RequestSample {
                                             scenario: metrics_ctx.scenario(),
                                             protocol: wrkr_core::Protocol::Grpc,
-                                            ok: true,
+                                            ok: error_kind.is_none(),
                                             latency: elapsed,
                                             bytes_received: res.bytes_received,
+                                            bytes_received_body: None,
                                             bytes_sent: res.bytes_sent,
-                                            error_kind: None,
+                                            error_kind: error_kind.as_deref(),
+                                            connection_reused: None,
                                         },
                                         &extra_tags,
                                     );
 
+                                    if !res.ok
+                                        && let Some(log) = &failures
+                                    {
+                                        log.record(wrkr_core::FailureSample {
+                                            scenario: metrics_ctx.scenario(),
+                                            protocol: wrkr_core::Protocol::Grpc,
+                                            method: full_method_str,
+                                            target: None,
+                                            status: res.status,
+                                            error: res.message.as_deref(),
+                                            body: &[],
+                                        });
+                                    }
+
                                     InvokeLuaResult::from_unary_result(res)
                                         .into_lua_table(&lua, int64_repr)
                                 }
@@ -228,20 +321,38 @@ pub(super) fn create_client_table(
                                             ok: false,
                                             latency: elapsed,
                                             bytes_received: 0,
+                                            bytes_received_body: None,
                                             bytes_sent: req_bytes.len() as u64,
                                             error_kind: Some(kind_s.as_str()),
+                                            connection_reused: None,
                                         },
                                         &extra_tags,
                                     );
 
-                                    InvokeLuaResult::transport_error(kind, err.to_string())
+                                    let error = err.to_string();
+                                    if let Some(log) = &failures {
+                                        log.record(wrkr_core::FailureSample {
+                                            scenario: metrics_ctx.scenario(),
+                                            protocol: wrkr_core::Protocol::Grpc,
+                                            method: full_method_str,
+                                            target: None,
+                                            status: None,
+                                            error: Some(&error),
+                                            body: &[],
+                                        });
+                                    }
+
+                                    InvokeLuaResult::transport_error(kind, error)
+                                        .with_bytes_sent(req_bytes.len() as u64)
                                         .into_lua_table(&lua, Int64Repr::Integer)
                                 }
                             }
                         }
                     },
-                )?
+                )
             };
+            let invoke_fn = make_invoke_fn(false)?;
+            let server_stream_fn = make_invoke_fn(true)?;
 
             // encode(full_method, req) -> bytes | (nil, err)
             // Encodes a request message to protobuf bytes, allowing callers to cache/reuse the
@@ -298,10 +409,47 @@ pub(super) fn create_client_table(
                 )?
             };
 
+            // health_check(service?, opts?) -> status | (nil, err)
+            // Calls grpc.health.v1.Health/Check using the bundled schema (no load() needed).
+            // Health probes are not recorded in the request metrics.
+            let health_check_fn = {
+                let shared = shared.clone();
+                lua.create_async_function(
+                    move |lua, (_this, service, opts): (Table, Option<String>, Option<Table>)| {
+                        let shared = shared.clone();
+                        async move {
+                            let parsed =
+                                InvokeLuaOptions::parse(opts).map_err(mlua::Error::external)?;
+                            let invoke_opts = wrkr_grpc::InvokeOptions {
+                                timeout: parsed.timeout,
+                                metadata: parsed.metadata,
+                                ..Default::default()
+                            };
+
+                            let service = service.unwrap_or_default();
+                            let res = shared.health_check(&service, invoke_opts).await;
+                            match health_status(res) {
+                                Ok(status) => Ok(mlua::MultiValue::from_vec(vec![Value::String(
+                                    lua.create_string(status.as_bytes())?,
+                                )])),
+                                Err(msg) => Ok(mlua::MultiValue::from_vec(vec![
+                                    Value::Nil,
+                                    Value::String(lua.create_string(msg.as_bytes())?),
+                                ])),
+                            }
+                        }
+                    },
+                )?
+            };
+
             client_obj.set("load", load_fn)?;
             client_obj.set("connect", connect_fn)?;
             client_obj.set("invoke", invoke_fn)?;
+            client_obj.set("server_stream", server_stream_fn.clone())?;
+            client_obj.set("serverStream", server_stream_fn)?;
             client_obj.set("encode", encode_fn)?;
+            client_obj.set("health_check", health_check_fn.clone())?;
+            client_obj.set("healthCheck", health_check_fn)?;
 
             Ok::<_, mlua::Error>(client_obj)
         })?
@@ -25,6 +25,8 @@ pub(super) fn parse(opts: Option<Table>, max_vus: u64) -> mlua::Result<Self> {
 pub(super) struct ConnectLuaOptions {
     pub(super) timeout: Option<Duration>,
     pub(super) tls: Option<TlsLuaOptions>,
+    /// Default metadata for every call on the client.
+    pub(super) metadata: Vec<(String, String)>,
 }
 
 impl ConnectLuaOptions {
@@ -33,6 +35,7 @@ pub(super) fn parse(opts: Option<Table>) -> mlua::Result<Self> {
             return Ok(Self {
                 timeout: None,
                 tls: None,
+                metadata: Vec::new(),
             });
         };
 
@@ -46,13 +49,20 @@ pub(super) fn parse(opts: Option<Table>) -> mlua::Result<Self> {
             None => None,
         };
 
-        Ok(Self { timeout, tls })
+        let metadata = parse_metadata(&opts).map_err(mlua::Error::external)?;
+
+        Ok(Self {
+            timeout,
+            tls,
+            metadata,
+        })
     }
 
     pub(super) fn into_connect_options(self) -> wrkr_grpc::ConnectOptions {
         wrkr_grpc::ConnectOptions {
             timeout: self.timeout,
             tls: self.tls.map(TlsLuaOptions::into_tls_config),
+            metadata: self.metadata,
         }
     }
 }
@@ -95,15 +105,22 @@ fn into_tls_config(self) -> wrkr_grpc::TlsConfig {
             ca_pem: self.ca_pem,
             identity_pem: self.cert_pem,
             identity_key_pem: self.key_pem,
+            disable_session_resumption: false,
         }
     }
 }
 
 pub(super) struct InvokeLuaOptions {
     pub(super) tags: Vec<(String, String)>,
+    /// `name` metric tag; defaults to the full method name.
+    pub(super) name: Option<String>,
     pub(super) timeout: Option<Duration>,
+    /// `server_stream` only: longest gap allowed between two messages.
+    pub(super) message_timeout: Option<Duration>,
     pub(super) metadata: Vec<(String, String)>,
     pub(super) int64_repr: Int64Repr,
+    /// Non-OK status codes that still make `res.ok` true (e.g. `NOT_FOUND` for a probe).
+    pub(super) expected_statuses: Vec<u16>,
 }
 
 impl InvokeLuaOptions {
@@ -111,43 +128,107 @@ pub(super) fn parse(opts: Option<Table>) -> mlua::Result<Self> {
         let Some(opts) = opts else {
             return Ok(Self {
                 tags: Vec::new(),
+                name: None,
                 timeout: None,
+                message_timeout: None,
                 metadata: Vec::new(),
                 int64_repr: Int64Repr::Integer,
+                expected_statuses: Vec::new(),
             });
         };
 
         let tags = parse_tags(&opts).map_err(mlua::Error::external)?;
 
+        let name = match opts.get::<Value>("name")? {
+            Value::String(s) => Some(s.to_string_lossy().to_string()),
+            Value::Integer(i) => Some(i.to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        };
+
         let timeout = match opts.get::<Option<String>>("timeout")? {
             Some(v) => Some(parse_duration(&v)?),
             None => None,
         };
 
+        let message_timeout = match opts.get::<Option<String>>("message_timeout")? {
+            Some(v) => Some(parse_duration(&v)?),
+            None => None,
+        };
+
         let metadata = parse_metadata(&opts).map_err(mlua::Error::external)?;
 
-        let int64_repr = match opts.get::<Option<String>>("int64")? {
-            Some(int64_str) => match int64_str.as_str() {
-                "integer" => Int64Repr::Integer,
-                "string" => Int64Repr::String,
-                _ => {
-                    return Err(mlua::Error::external(
-                        "grpc invoke opts.int64 must be 'integer' or 'string'",
-                    ));
-                }
-            },
-            None => Int64Repr::Integer,
-        };
+        let int64_repr = Int64Repr::from_opts(&opts, "grpc invoke opts")?;
+
+        let expected_statuses = parse_expected_statuses(&opts)?;
 
         Ok(Self {
             tags,
+            name,
             timeout,
+            message_timeout,
             metadata,
             int64_repr,
+            expected_statuses,
         })
     }
 }
 
+/// Canonical gRPC status code names, indexed by code.
+const STATUS_NAMES: [&str; 17] = [
+    "OK",
+    "CANCELLED",
+    "UNKNOWN",
+    "INVALID_ARGUMENT",
+    "DEADLINE_EXCEEDED",
+    "NOT_FOUND",
+    "ALREADY_EXISTS",
+    "PERMISSION_DENIED",
+    "RESOURCE_EXHAUSTED",
+    "FAILED_PRECONDITION",
+    "ABORTED",
+    "OUT_OF_RANGE",
+    "UNIMPLEMENTED",
+    "INTERNAL",
+    "UNAVAILABLE",
+    "DATA_LOSS",
+    "UNAUTHENTICATED",
+];
+
+/// `expected_statuses`: a list of status codes, as numbers or names (`"NOT_FOUND"`).
+fn parse_expected_statuses(opts: &Table) -> mlua::Result<Vec<u16>> {
+    let Some(list) = opts.get::<Option<Table>>("expected_statuses")? else {
+        return Ok(Vec::new());
+    };
+
+    let invalid = |v: &str| {
+        mlua::Error::external(format!(
+            "grpc invoke opts: expected_statuses: `{v}` is not a gRPC status code"
+        ))
+    };
+
+    let mut out = Vec::new();
+    for v in list.sequence_values::<Value>() {
+        let code = match v? {
+            Value::Integer(i) => u16::try_from(i)
+                .ok()
+                .filter(|&c| usize::from(c) < STATUS_NAMES.len())
+                .ok_or_else(|| invalid(&i.to_string()))?,
+            Value::String(s) => {
+                let name = s.to_string_lossy().to_string();
+                STATUS_NAMES
+                    .iter()
+                    .position(|known| known.eq_ignore_ascii_case(&name))
+                    .and_then(|c| u16::try_from(c).ok())
+                    .ok_or_else(|| invalid(&name))?
+            }
+            other => return Err(invalid(other.type_name())),
+        };
+        out.push(code);
+    }
+    Ok(out)
+}
+
 fn parse_pool_size(opts: &Table, max_vus: u64) -> mlua::Result<Option<usize>> {
     let Some(pool_val) = opts.get::<Option<Value>>("pool_size")? else {
         return Ok(None);
@@ -191,8 +272,7 @@ fn parse_pool_size(opts: &Table, max_vus: u64) -> mlua::Result<Option<usize>> {
 }
 
 pub(super) fn parse_duration(v: &str) -> mlua::Result<Duration> {
-    humantime::parse_duration(v)
-        .map_err(|e| mlua::Error::external(format!("invalid duration '{v}': {e}")))
+    wrkr_core::parse_duration(v).map_err(mlua::Error::external)
 }
 
 pub(super) fn parse_metadata(opts: &Table) -> Result<Vec<(String, String)>> {
@@ -256,6 +336,23 @@ pub(super) fn parse_tags(opts: &Table) -> Result<Vec<(String, String)>> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_expected_statuses_accepts_codes_and_names() {
+        let lua = mlua::Lua::new();
+        let opts: Table = lua
+            .load(r#"return { expected_statuses = { 5, "already_exists", "UNAVAILABLE" } }"#)
+            .eval()
+            .unwrap_or_else(|err| panic!("eval: {err}"));
+        let parsed = InvokeLuaOptions::parse(Some(opts)).unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(parsed.expected_statuses, vec![5, 6, 14]);
+
+        let bad: Table = lua
+            .load(r#"return { expected_statuses = { "NOPE" } }"#)
+            .eval()
+            .unwrap_or_else(|err| panic!("eval: {err}"));
+        assert!(InvokeLuaOptions::parse(Some(bad)).is_err());
+    }
+
     #[test]
     fn parse_metadata_supports_multi_values() {
         let lua = mlua::Lua::new();
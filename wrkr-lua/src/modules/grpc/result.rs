@@ -8,7 +8,11 @@ pub(super) struct InvokeLuaResult {
     pub(super) message: Option<String>,
     pub(super) error_kind: Option<String>,
     pub(super) error: Option<String>,
+    pub(super) error_details: Option<wrkr_value::Value>,
     pub(super) response: Option<wrkr_value::Value>,
+    /// Wire bytes as recorded in `bytes_sent_total` / `bytes_received_total`.
+    pub(super) bytes_sent: u64,
+    pub(super) bytes_received: u64,
 }
 
 impl InvokeLuaResult {
@@ -19,7 +23,10 @@ pub(super) fn not_connected() -> Self {
             message: None,
             error_kind: Some("not_connected".to_string()),
             error: Some("grpc client: call connect() first".to_string()),
+            error_details: None,
             response: None,
+            bytes_sent: 0,
+            bytes_received: 0,
         }
     }
 
@@ -30,7 +37,10 @@ pub(super) fn invalid_method() -> Self {
             message: None,
             error_kind: Some("invalid_method".to_string()),
             error: Some("grpc client: method name must be utf-8".to_string()),
+            error_details: None,
             response: None,
+            bytes_sent: 0,
+            bytes_received: 0,
         }
     }
 
@@ -41,7 +51,10 @@ pub(super) fn not_loaded() -> Self {
             message: None,
             error_kind: Some("not_loaded".to_string()),
             error: Some("grpc client: call load() first".to_string()),
+            error_details: None,
             response: None,
+            bytes_sent: 0,
+            bytes_received: 0,
         }
     }
 
@@ -52,7 +65,10 @@ pub(super) fn encode_error(err: String) -> Self {
             message: None,
             error_kind: Some("encode".to_string()),
             error: Some(err),
+            error_details: None,
             response: None,
+            bytes_sent: 0,
+            bytes_received: 0,
         }
     }
 
@@ -63,7 +79,10 @@ pub(super) fn transport_error(kind: wrkr_grpc::GrpcTransportErrorKind, err: Stri
             message: None,
             error_kind: Some(kind.to_string()),
             error: Some(err),
+            error_details: None,
             response: None,
+            bytes_sent: 0,
+            bytes_received: 0,
         }
     }
 
@@ -74,10 +93,18 @@ pub(super) fn from_unary_result(res: wrkr_grpc::UnaryResult) -> Self {
             message: res.message,
             error_kind: res.transport_error_kind.map(|k| k.to_string()),
             error: res.error,
+            error_details: res.error_details,
             response: Some(res.response),
+            bytes_sent: res.bytes_sent,
+            bytes_received: res.bytes_received,
         }
     }
 
+    pub(super) fn with_bytes_sent(mut self, bytes_sent: u64) -> Self {
+        self.bytes_sent = bytes_sent;
+        self
+    }
+
     pub(super) fn into_lua_table(self, lua: &Lua, int64_repr: Int64Repr) -> mlua::Result<Table> {
         let t = lua.create_table()?;
 
@@ -87,6 +114,8 @@ pub(super) fn into_lua_table(self, lua: &Lua, int64_repr: Int64Repr) -> mlua::Re
         } else {
             t.set("status", Value::Nil)?;
         }
+        t.set("bytes_sent", self.bytes_sent)?;
+        t.set("bytes_received", self.bytes_received)?;
 
         if let Some(message) = self.message {
             t.set("message", message)?;
@@ -98,6 +127,11 @@ pub(super) fn into_lua_table(self, lua: &Lua, int64_repr: Int64Repr) -> mlua::Re
             t.set("error", error)?;
         }
 
+        if let Some(details) = self.error_details {
+            let details = value_to_lua(lua, &details, int64_repr).map_err(mlua::Error::external)?;
+            t.set("error_details", details)?;
+        }
+
         if let Some(response) = self.response {
             let resp = value_to_lua(lua, &response, int64_repr).map_err(mlua::Error::external)?;
             t.set("response", resp)?;
@@ -1,7 +1,9 @@
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
-use mlua::{Lua, Table, Value};
+use mlua::{Function, Lua, Table, Value};
 
 use crate::Result;
 
@@ -10,30 +12,119 @@
 mod url;
 
 use opts::parse_http_opts;
-use result::HttpLuaResponse;
-use url::{apply_params_owned, resolve_base_url};
+use result::{HttpLuaResponse, HttpLuaStreamResponse};
+use url::{apply_params_owned, resolve_base_url, url_template};
+
+const REG_ON_REQUEST: &str = "wrkr_http_on_request";
 
 fn has_header(headers: &[(String, String)], key: &str) -> bool {
     headers.iter().any(|(k, _)| k.eq_ignore_ascii_case(key))
 }
 
+/// The client a VU's requests go out on: the run's shared one, or with
+/// `Options.noVUConnectionReuse` one of the VU's own whose pool is swapped after each iteration.
+#[derive(Clone)]
+enum VuClient {
+    Shared(Arc<wrkr_http::HttpClient>),
+    PerIteration(Arc<Mutex<Arc<wrkr_http::HttpClient>>>),
+}
+
+impl VuClient {
+    fn get(&self) -> Arc<wrkr_http::HttpClient> {
+        match self {
+            Self::Shared(client) => client.clone(),
+            Self::PerIteration(slot) => slot
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone(),
+        }
+    }
+}
+
+/// Lua app data pointing at the VU's [`VuClient::PerIteration`] slot.
+struct IterationConnections(Arc<Mutex<Arc<wrkr_http::HttpClient>>>);
+
+/// Called after every iteration: with `Options.noVUConnectionReuse`, the next iteration starts
+/// with an empty pool. Connections still held by in-flight requests close once those are done.
+pub(crate) fn reset_vu_connections(lua: &Lua) {
+    if let Some(conns) = lua.app_data_ref::<IterationConnections>() {
+        let mut client = conns
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *client = Arc::new(client.with_new_pool());
+    }
+}
+
 #[derive(Clone)]
 struct HttpRuntime {
-    client: Arc<wrkr_http::HttpClient>,
+    client: VuClient,
     env_vars: wrkr_core::EnvVars,
     metrics: Arc<wrkr_metrics::Registry>,
     request_metrics: wrkr_core::RequestMetricIds,
     metrics_ctx: wrkr_core::MetricsContext,
+    script_path: PathBuf,
+    files: Arc<wrkr_core::FileCache>,
+    failures: Option<Arc<wrkr_core::FailureLog>>,
+    /// Set by `http.auto_name(true)`: requests without a `name` are named by URL template.
+    auto_name: Arc<AtomicBool>,
+    request_names: Arc<wrkr_core::CardinalityGuard>,
+    url_names: Arc<wrkr_core::CardinalityGuard>,
+    /// `Options.discardResponseBodies`: count body bytes without keeping them.
+    discard_bodies: bool,
+    /// `Options.httpCache`: the cache this VU's requests go through.
+    cache: Option<ResponseCache>,
 }
 
-async fn request_impl(
+/// A client-side HTTP cache and the `http_cache_hits` rate its `GET` lookups feed.
+#[derive(Clone)]
+struct ResponseCache {
+    cache: Arc<wrkr_http::HttpCache>,
+    hits: wrkr_metrics::MetricId,
+}
+
+fn encode_body(
+    headers: &mut Vec<(String, String)>,
+    body: Option<Value>,
+) -> mlua::Result<bytes::Bytes> {
+    let bytes = match body {
+        None | Some(Value::Nil) => bytes::Bytes::new(),
+        Some(Value::String(s)) => {
+            if !has_header(headers, "content-type") {
+                headers.push((
+                    "content-type".to_string(),
+                    "text/plain; charset=utf-8".to_string(),
+                ));
+            }
+            bytes::Bytes::copy_from_slice(s.as_bytes().as_ref())
+        }
+        Some(v) => {
+            if !has_header(headers, "content-type") {
+                headers.push((
+                    "content-type".to_string(),
+                    "application/json; charset=utf-8".to_string(),
+                ));
+            }
+            bytes::Bytes::from(crate::json_util::encode_to_vec(v).map_err(mlua::Error::external)?)
+        }
+    };
+    Ok(bytes)
+}
+
+/// A request with its metric tags resolved, ready to send without touching Lua.
+struct PreparedRequest {
+    req: wrkr_http::HttpRequest,
+    tags: Vec<(String, String)>,
+}
+
+async fn prepare_request(
     lua: &Lua,
     rt: &HttpRuntime,
     method: wrkr_http::Method,
     url: String,
     body: Option<Value>,
     opts: Option<Table>,
-) -> mlua::Result<Table> {
+) -> mlua::Result<PreparedRequest> {
     let opts = parse_http_opts(opts).map_err(mlua::Error::external)?;
     let request_url = resolve_base_url(&rt.env_vars, apply_params_owned(url, &opts.params));
 
@@ -43,9 +134,15 @@ async fn request_impl(
     tags.retain(|(k, _)| k != "method");
     tags.push(("method".to_string(), method.as_str().to_string()));
 
-    if let Some(name) = opts.name {
-        tags.retain(|(k, _)| k != "name");
+    // Names past the run-wide limit fall back to what an unnamed request would get.
+    let name = super::take_request_name(opts.name, &mut tags);
+    if let Some(name) = name.filter(|name| rt.request_names.admit(name)) {
         tags.push(("name".to_string(), name));
+    } else if rt.auto_name.load(Ordering::Relaxed) {
+        let name = url_template(&request_url);
+        if rt.url_names.admit(&name) {
+            tags.push(("name".to_string(), name));
+        }
     }
 
     rt.metrics_ctx.merge_scenario_tags_if_missing(
@@ -59,81 +156,427 @@ async fn request_impl(
         tags.push(("group".to_string(), group));
     }
 
-    let extra_tags: Vec<(&str, &str)> =
-        tags.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
-
     let mut headers = opts.headers;
-    let body_bytes = match body {
-        None | Some(Value::Nil) => bytes::Bytes::new(),
-        Some(Value::String(s)) => {
-            if !has_header(&headers, "content-type") {
-                headers.push((
-                    "content-type".to_string(),
-                    "text/plain; charset=utf-8".to_string(),
+    let body_bytes = match opts.body_file {
+        Some(rel) => {
+            if body.as_ref().is_some_and(|v| !v.is_nil()) {
+                return Err(mlua::Error::external(
+                    "http: `body` and `opts.body_file` cannot both be set",
                 ));
             }
-            bytes::Bytes::copy_from_slice(s.as_bytes().as_ref())
-        }
-        Some(v) => {
             if !has_header(&headers, "content-type") {
                 headers.push((
                     "content-type".to_string(),
-                    "application/json; charset=utf-8".to_string(),
+                    "application/octet-stream".to_string(),
                 ));
             }
-            bytes::Bytes::from(crate::json_util::encode_to_vec(v).map_err(mlua::Error::external)?)
+            // Script-relative like `wrkr/fs`; the bytes are shared by every VU.
+            let path = crate::loader::resolve_script_relative_path(&rt.script_path, &rel)
+                .map_err(mlua::Error::external)?;
+            rt.files
+                .get_or_load(&path)
+                .await
+                .map_err(mlua::Error::external)?
         }
+        None => encode_body(&mut headers, body)?,
     };
 
-    let req = wrkr_http::HttpRequest {
+    let mut req = wrkr_http::HttpRequest {
         method,
         url: request_url,
         headers,
         body: body_bytes,
         timeout: opts.timeout,
     };
+    apply_request_hook(lua, &mut req)?;
+
+    Ok(PreparedRequest { req, tags })
+}
+
+/// Pass `req` through the function registered with `http.on_request`, if any.
+///
+/// The hook gets `{ method, url, headers, body }`, with every header name mapped to the list of its
+/// values, and returns the table to send (or `nil` to send the one it was given, changed in
+/// place). It runs after metric tags are resolved, so signing or rewriting a request never changes
+/// how it is recorded.
+fn apply_request_hook(lua: &Lua, req: &mut wrkr_http::HttpRequest) -> mlua::Result<()> {
+    let Some(hook) = lua.named_registry_value::<Option<Function>>(REG_ON_REQUEST)? else {
+        return Ok(());
+    };
+
+    // Each name maps to the list of its values, so repeated headers (`Cookie`, `Accept`) survive.
+    let headers = lua.create_table()?;
+    for (k, v) in &req.headers {
+        let values = match headers.get::<Option<Table>>(k.as_str())? {
+            Some(values) => values,
+            None => {
+                let values = lua.create_table()?;
+                headers.set(k.as_str(), values.clone())?;
+                values
+            }
+        };
+        values.push(v.as_str())?;
+    }
+    let tbl = lua.create_table()?;
+    tbl.set("method", req.method.as_str())?;
+    tbl.set("url", req.url.as_str())?;
+    tbl.set("headers", headers)?;
+    tbl.set("body", lua.create_string(&req.body)?)?;
+
+    let out = match hook.call::<Value>(tbl.clone())? {
+        Value::Nil => tbl,
+        Value::Table(out) => out,
+        other => {
+            return Err(mlua::Error::external(format!(
+                "http.on_request: hook must return the request table or nil, got {}",
+                other.type_name()
+            )));
+        }
+    };
+
+    if let Some(method) = out.get::<Option<String>>("method")? {
+        req.method = parse_method(&method)?;
+    }
+    req.url = out
+        .get::<Option<String>>("url")?
+        .ok_or_else(|| mlua::Error::external("http.on_request: request is missing `url`"))?;
+    req.headers = match out.get::<Option<Table>>("headers")? {
+        Some(headers) => hook_headers(lua, &headers)?,
+        None => Vec::new(),
+    };
+    req.body = match out.get::<Value>("body")? {
+        Value::Nil => bytes::Bytes::new(),
+        Value::String(body) => bytes::Bytes::copy_from_slice(body.as_bytes().as_ref()),
+        other => {
+            return Err(mlua::Error::external(format!(
+                "http.on_request: `body` must be a string, got {}",
+                other.type_name()
+            )));
+        }
+    };
+    Ok(())
+}
+
+/// Headers handed back by an `on_request` hook: each name maps to a list of values, as the hook
+/// received them, or to a single value.
+fn hook_headers(lua: &Lua, headers: &Table) -> mlua::Result<Vec<(String, String)>> {
+    let invalid = |name: &str, err: mlua::Error| {
+        mlua::Error::external(format!("http.on_request: header `{name}`: {err}"))
+    };
+    let mut out = Vec::new();
+    for pair in headers.pairs::<String, Value>() {
+        let (name, values) = pair?;
+        match values {
+            Value::Table(values) => {
+                for value in values.sequence_values::<String>() {
+                    let value = value.map_err(|err| invalid(&name, err))?;
+                    out.push((name.clone(), value));
+                }
+            }
+            value => {
+                let value = lua
+                    .unpack::<String>(value)
+                    .map_err(|err| invalid(&name, err))?;
+                out.push((name, value));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Method and URL of a request, kept around only when failures are being logged.
+type FailureTarget = Option<(wrkr_http::Method, String)>;
+
+fn failure_target(rt: &HttpRuntime, req: &wrkr_http::HttpRequest) -> FailureTarget {
+    rt.failures
+        .is_some()
+        .then(|| (req.method.clone(), req.url.clone()))
+}
+
+impl HttpRuntime {
+    fn recorder(&self) -> wrkr_core::HttpRecorder<'_> {
+        wrkr_core::HttpRecorder {
+            metrics: &self.metrics,
+            request_metrics: &self.request_metrics,
+            failures: self.failures.as_deref(),
+            scenario: self.metrics_ctx.scenario(),
+        }
+    }
+}
+
+/// Count a `GET` answered through `Options.httpCache` as a hit (fresh or revalidated) or a miss.
+fn record_cache_lookup(rt: &HttpRuntime, extra_tags: &[(&str, &str)], hit: bool) {
+    let Some(cache) = &rt.cache else {
+        return;
+    };
+    let mut tags = vec![("scenario", rt.metrics_ctx.scenario())];
+    tags.extend(extra_tags.iter().copied().filter(|(k, _)| *k != "scenario"));
+    if let Some(handle) = rt
+        .metrics
+        .get_handle(cache.hits, rt.metrics.resolve_tags(&tags))
+    {
+        handle.add_rate(u64::from(hit), 1);
+    }
+}
+
+fn log_failure(
+    rt: &HttpRuntime,
+    target: &FailureTarget,
+    status: Option<u16>,
+    error: Option<&str>,
+    body: &[u8],
+) {
+    if let Some((method, url)) = target {
+        rt.recorder().log_failure(method, url, status, error, body);
+    }
+}
+
+/// Record a request that failed before a response arrived.
+fn record_transport_error(
+    rt: &HttpRuntime,
+    extra_tags: &[(&str, &str)],
+    target: &FailureTarget,
+    latency: std::time::Duration,
+    err: &wrkr_http::Error,
+) -> String {
+    let kind = rt
+        .recorder()
+        .record_transport_error(extra_tags, latency, err);
+    if target.is_some() {
+        log_failure(rt, target, None, Some(&err.to_string()), &[]);
+    }
+    kind
+}
+
+async fn send_request(rt: &HttpRuntime, prepared: PreparedRequest) -> HttpLuaResponse {
+    let PreparedRequest { req, tags } = prepared;
+    let extra_tags: Vec<(&str, &str)> =
+        tags.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    let target = failure_target(rt, &req);
+
+    let cacheable = req.method == wrkr_http::Method::GET;
 
     let started = Instant::now();
-    let res = rt.client.request(req).await;
+    let res = match &rt.cache {
+        Some(cache) if rt.discard_bodies => {
+            rt.client
+                .get()
+                .request_cached_discard_body(req, &cache.cache)
+                .await
+        }
+        Some(cache) => rt.client.get().request_cached(req, &cache.cache).await,
+        None if rt.discard_bodies => rt.client.get().request_discard_body(req).await,
+        None => rt.client.get().request(req).await,
+    };
     let elapsed = started.elapsed();
 
     match res {
         Ok(res) => {
-            rt.request_metrics.record_request(
-                &rt.metrics,
-                wrkr_core::RequestSample {
-                    scenario: rt.metrics_ctx.scenario(),
-                    protocol: wrkr_core::Protocol::Http,
-                    ok: true,
-                    latency: elapsed,
-                    bytes_received: res.bytes_received,
-                    bytes_sent: res.bytes_sent,
-                    error_kind: None,
-                },
-                &extra_tags,
-            );
-
-            HttpLuaResponse::ok(res).into_lua_table(lua)
+            if cacheable {
+                record_cache_lookup(rt, &extra_tags, res.cache_hit.is_some());
+            }
+            // A fresh cache hit never reached the network, so it is not a request.
+            if res.cache_hit != Some(wrkr_http::CacheHit::Fresh) {
+                rt.recorder().record(
+                    &extra_tags,
+                    elapsed,
+                    wrkr_core::HttpBytes::from(&res),
+                    Some(res.connection_reused),
+                    None,
+                );
+            }
+            if res.status >= 400 {
+                log_failure(rt, &target, Some(res.status), None, &res.body);
+            }
+            HttpLuaResponse::ok(res)
+        }
+        Err(err) => {
+            let kind = record_transport_error(rt, &extra_tags, &target, elapsed, &err);
+            HttpLuaResponse::err(err, kind)
+        }
+    }
+}
+
+async fn request_impl(
+    lua: &Lua,
+    rt: &HttpRuntime,
+    method: wrkr_http::Method,
+    url: String,
+    body: Option<Value>,
+    opts: Option<Table>,
+) -> mlua::Result<Table> {
+    let prepared = prepare_request(lua, rt, method, url, body, opts).await?;
+    send_request(rt, prepared).await.into_lua_table(lua)
+}
+
+fn parse_method(method: &str) -> mlua::Result<wrkr_http::Method> {
+    // Methods are case-sensitive and passed through as-is, so extension verbs (e.g. WebDAV
+    // `PROPFIND`) reach the server and the `method` tag unchanged.
+    wrkr_http::Method::from_bytes(method.as_bytes())
+        .map_err(|_| mlua::Error::external(format!("http: invalid method `{method}`")))
+}
+
+/// `http.batch(requests, opts?)`: send every request concurrently (at most `max_concurrent` in
+/// flight) and return the responses in request order.
+async fn batch_impl(
+    lua: &Lua,
+    rt: &HttpRuntime,
+    requests: Table,
+    opts: Option<Table>,
+) -> mlua::Result<Table> {
+    let max_concurrent = match &opts {
+        Some(opts) => opts.get::<Option<i64>>("max_concurrent")?,
+        None => None,
+    };
+
+    let mut prepared = Vec::new();
+    for (idx, item) in requests.sequence_values::<Table>().enumerate() {
+        let item = item.map_err(|_| {
+            mlua::Error::external(format!("http.batch: request #{} must be a table", idx + 1))
+        })?;
+        let method = match item.get::<Option<String>>("method")? {
+            Some(method) => parse_method(&method)?,
+            None => wrkr_http::Method::GET,
+        };
+        let url: String = item.get::<Option<String>>("url")?.ok_or_else(|| {
+            mlua::Error::external(format!("http.batch: request #{} is missing `url`", idx + 1))
+        })?;
+        let body: Option<Value> = item.get("body")?;
+        let req_opts: Option<Table> = item.get("opts")?;
+        prepared.push(prepare_request(lua, rt, method, url, body, req_opts).await?);
+    }
+
+    let limit = match max_concurrent {
+        None => prepared.len().max(1),
+        Some(n) if n >= 1 => usize::try_from(n).unwrap_or(usize::MAX),
+        Some(_) => {
+            return Err(mlua::Error::external(
+                "http.batch: `max_concurrent` must be a positive integer",
+            ));
         }
+    };
+    let permits = Arc::new(tokio::sync::Semaphore::new(
+        limit.min(tokio::sync::Semaphore::MAX_PERMITS),
+    ));
+
+    let rt = Arc::new(rt.clone());
+    let mut tasks = tokio::task::JoinSet::new();
+    for (idx, req) in prepared.into_iter().enumerate() {
+        let rt = rt.clone();
+        let permits = permits.clone();
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            (idx, send_request(&rt, req).await)
+        });
+    }
+
+    let mut responses: Vec<Option<HttpLuaResponse>> = Vec::new();
+    responses.resize_with(tasks.len(), || None);
+    while let Some(done) = tasks.join_next().await {
+        let (idx, res) = done.map_err(mlua::Error::external)?;
+        responses[idx] = Some(res);
+    }
+
+    let out = lua.create_table()?;
+    for res in responses.into_iter().flatten() {
+        out.raw_push(res.into_lua_table(lua)?)?;
+    }
+    Ok(out)
+}
+
+/// `http.stream(url, opts?, on_chunk)`: GET `url` and pass the body to `on_chunk` as it
+/// arrives instead of buffering it. Reading stops early when `on_chunk` returns `false`.
+///
+/// The request is recorded once, when the stream ends: latency is the time to the response
+/// head, bytes received count everything read.
+async fn stream_impl(
+    lua: &Lua,
+    rt: &HttpRuntime,
+    url: String,
+    opts: Option<Table>,
+    on_chunk: Function,
+) -> mlua::Result<Table> {
+    let PreparedRequest { req, tags } =
+        prepare_request(lua, rt, wrkr_http::Method::GET, url, None, opts).await?;
+    let extra_tags: Vec<(&str, &str)> =
+        tags.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    let target = failure_target(rt, &req);
+
+    let started = Instant::now();
+    let res = rt.client.get().request_stream(req).await;
+    let elapsed = started.elapsed();
+
+    let mut res = match res {
+        Ok(res) => res,
         Err(err) => {
-            let kind = err.transport_error_kind().to_string();
-            rt.request_metrics.record_request(
-                &rt.metrics,
-                wrkr_core::RequestSample {
-                    scenario: rt.metrics_ctx.scenario(),
-                    protocol: wrkr_core::Protocol::Http,
-                    ok: false,
-                    latency: elapsed,
-                    bytes_received: 0,
-                    bytes_sent: 0,
-                    error_kind: Some(kind.as_str()),
-                },
-                &extra_tags,
-            );
-
-            HttpLuaResponse::err(err).into_lua_table(lua)
+            let kind = record_transport_error(rt, &extra_tags, &target, elapsed, &err);
+            return HttpLuaStreamResponse::err(err, kind).into_lua_table(lua);
         }
+    };
+
+    let mut chunks: u64 = 0;
+    let mut body_err = None;
+    let mut callback_err = None;
+    loop {
+        match res.next_chunk().await {
+            Ok(Some(chunk)) => {
+                chunks += 1;
+                // Lua errors (here or in the callback) still leave the request recorded below.
+                let called = match lua.create_string(&chunk) {
+                    Ok(chunk) => on_chunk.call_async::<Value>(chunk).await,
+                    Err(err) => Err(err),
+                };
+                match called {
+                    Ok(Value::Boolean(false)) => break,
+                    Ok(_) => {}
+                    Err(err) => {
+                        callback_err = Some(err);
+                        break;
+                    }
+                }
+            }
+            Ok(None) => break,
+            Err(err) => {
+                body_err = Some(err);
+                break;
+            }
+        }
+    }
+
+    let error_kind = body_err
+        .as_ref()
+        .map(|err| err.transport_error_kind().to_string());
+    rt.recorder().record(
+        &extra_tags,
+        elapsed,
+        wrkr_core::HttpBytes {
+            sent: res.bytes_sent,
+            head_received: res.head_bytes(),
+            body_received: res.body_bytes(),
+        },
+        Some(res.connection_reused),
+        error_kind.as_deref(),
+    );
+    if res.status >= 400 || body_err.is_some() {
+        let error = body_err.as_ref().map(ToString::to_string);
+        log_failure(rt, &target, Some(res.status), error.as_deref(), &[]);
+    }
+
+    if let Some(err) = callback_err {
+        return Err(err);
+    }
+
+    HttpLuaStreamResponse {
+        status: res.status,
+        body_bytes: res.body_bytes(),
+        chunks,
+        error: body_err.map(|err| err.to_string()),
+        error_kind,
+        bytes_sent: res.bytes_sent,
+        bytes_received: res.bytes_received(),
+        headers: res.headers,
     }
+    .into_lua_table(lua)
 }
 
 fn create_http_module(
@@ -142,12 +585,35 @@ fn create_http_module(
     metrics_ctx: wrkr_core::MetricsContext,
 ) -> Result<Table> {
     let http_tbl = lua.create_table()?;
+    let client = if run_ctx.no_vu_connection_reuse {
+        let slot = Arc::new(Mutex::new(Arc::new(run_ctx.client.with_new_pool())));
+        lua.set_app_data(IterationConnections(slot.clone()));
+        VuClient::PerIteration(slot)
+    } else {
+        VuClient::Shared(run_ctx.client.clone())
+    };
     let rt = HttpRuntime {
-        client: run_ctx.client.clone(),
+        client,
         env_vars: run_ctx.env.clone(),
         metrics: run_ctx.metrics.clone(),
         request_metrics: run_ctx.request_metrics,
         metrics_ctx: metrics_ctx.clone(),
+        script_path: run_ctx.script_path.clone(),
+        files: run_ctx.files.clone(),
+        failures: run_ctx.failures.clone(),
+        auto_name: Arc::new(AtomicBool::new(false)),
+        request_names: run_ctx.request_names.clone(),
+        url_names: run_ctx.url_names.clone(),
+        discard_bodies: run_ctx.discard_response_bodies,
+        cache: run_ctx.http_cache.map(|scope| ResponseCache {
+            cache: match scope {
+                wrkr_core::HttpCacheScope::Vu => Arc::new(wrkr_http::HttpCache::new()),
+                wrkr_core::HttpCacheScope::Shared => run_ctx.shared_http_cache.clone(),
+            },
+            hits: run_ctx
+                .metrics
+                .register("http_cache_hits", wrkr_metrics::MetricKind::Rate),
+        }),
     };
 
     // http.get(url, opts?) -> res
@@ -241,8 +707,7 @@ fn create_http_module(
             move |lua, (method, url, body, opts): (String, String, Option<Value>, Option<Table>)| {
                 let rt = rt.clone();
                 async move {
-                    let m = wrkr_http::Method::from_bytes(method.as_bytes())
-                        .map_err(mlua::Error::external)?;
+                    let m = parse_method(&method)?;
                     request_impl(&lua, &rt, m, url, body, opts).await
                 }
             },
@@ -250,6 +715,47 @@ fn create_http_module(
         http_tbl.set("request", f)?;
     }
 
+    // http.batch(requests, opts?) -> { res, ... }
+    {
+        let rt = rt.clone();
+        let f =
+            lua.create_async_function(move |lua, (requests, opts): (Table, Option<Table>)| {
+                let rt = rt.clone();
+                async move { batch_impl(&lua, &rt, requests, opts).await }
+            })?;
+        http_tbl.set("batch", f)?;
+    }
+
+    // http.stream(url, opts?, on_chunk) -> res
+    {
+        let rt = rt.clone();
+        let f = lua.create_async_function(
+            move |lua, (url, opts, on_chunk): (String, Option<Table>, Function)| {
+                let rt = rt.clone();
+                async move { stream_impl(&lua, &rt, url, opts, on_chunk).await }
+            },
+        )?;
+        http_tbl.set("stream", f)?;
+    }
+
+    // http.on_request(hook | nil)
+    {
+        let f = lua.create_function(|lua, hook: Option<Function>| {
+            lua.set_named_registry_value(REG_ON_REQUEST, hook)
+        })?;
+        http_tbl.set("on_request", f)?;
+    }
+
+    // http.auto_name(enabled)
+    {
+        let auto_name = rt.auto_name.clone();
+        let f = lua.create_function(move |_lua, enabled: bool| {
+            auto_name.store(enabled, Ordering::Relaxed);
+            Ok(())
+        })?;
+        http_tbl.set("auto_name", f)?;
+    }
+
     Ok(http_tbl)
 }
 
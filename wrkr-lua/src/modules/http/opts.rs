@@ -9,6 +9,29 @@ pub(super) struct HttpRequestOptions {
     pub(super) timeout: Option<Duration>,
     pub(super) tags: Vec<(String, String)>,
     pub(super) name: Option<String>,
+    /// Script-relative path of a file to send as the request body.
+    pub(super) body_file: Option<String>,
+}
+
+/// String-keyed entries of `tbl` whose values are strings or numbers (headers, query params);
+/// other entries are skipped.
+pub(super) fn string_pairs(tbl: &Table) -> mlua::Result<Vec<(String, String)>> {
+    let mut out = Vec::new();
+    for pair in tbl.pairs::<Value, Value>() {
+        let (k, v) = pair?;
+        let k = match k {
+            Value::String(s) => s.to_string_lossy().to_string(),
+            _ => continue,
+        };
+        let v = match v {
+            Value::String(s) => s.to_string_lossy().to_string(),
+            Value::Integer(i) => i.to_string(),
+            Value::Number(n) => n.to_string(),
+            _ => continue,
+        };
+        out.push((k, v));
+    }
+    Ok(out)
 }
 
 pub(super) fn parse_http_opts(opts: Option<Table>) -> crate::Result<HttpRequestOptions> {
@@ -19,44 +42,19 @@ pub(super) fn parse_http_opts(opts: Option<Table>) -> crate::Result<HttpRequestO
             timeout: None,
             tags: Vec::new(),
             name: None,
+            body_file: None,
         });
     };
 
-    let mut headers = Vec::new();
-    if let Ok(hdrs) = opts.get::<Table>("headers") {
-        for pair in hdrs.pairs::<Value, Value>() {
-            let (k, v) = pair?;
-            let k = match k {
-                Value::String(s) => s.to_string_lossy().to_string(),
-                _ => continue,
-            };
-            let v = match v {
-                Value::String(s) => s.to_string_lossy().to_string(),
-                Value::Integer(i) => i.to_string(),
-                Value::Number(n) => n.to_string(),
-                _ => continue,
-            };
-            headers.push((k, v));
-        }
-    }
+    let headers = match opts.get::<Table>("headers") {
+        Ok(hdrs) => string_pairs(&hdrs)?,
+        Err(_) => Vec::new(),
+    };
 
-    let mut params = Vec::new();
-    if let Ok(p) = opts.get::<Table>("params") {
-        for pair in p.pairs::<Value, Value>() {
-            let (k, v) = pair?;
-            let k = match k {
-                Value::String(s) => s.to_string_lossy().to_string(),
-                _ => continue,
-            };
-            let v = match v {
-                Value::String(s) => s.to_string_lossy().to_string(),
-                Value::Integer(i) => i.to_string(),
-                Value::Number(n) => n.to_string(),
-                _ => continue,
-            };
-            params.push((k, v));
-        }
-    }
+    let params = match opts.get::<Table>("params") {
+        Ok(p) => string_pairs(&p)?,
+        Err(_) => Vec::new(),
+    };
 
     let timeout = match opts.get::<Value>("timeout").ok() {
         Some(Value::Nil) | None => None,
@@ -64,7 +62,7 @@ pub(super) fn parse_http_opts(opts: Option<Table>) -> crate::Result<HttpRequestO
         Some(Value::Integer(i)) if i > 0 => Some(Duration::from_secs(i as u64)),
         Some(Value::String(s)) => {
             let s = s.to_string_lossy();
-            Some(humantime::parse_duration(&s).map_err(|_| crate::Error::InvalidDuration)?)
+            Some(wrkr_core::parse_duration(&s).map_err(|_| crate::Error::InvalidDuration)?)
         }
         _ => return Err(crate::Error::InvalidDuration),
     };
@@ -96,12 +94,19 @@ pub(super) fn parse_http_opts(opts: Option<Table>) -> crate::Result<HttpRequestO
         Some(_) => None,
     };
 
+    let body_file = match opts.get::<Value>("body_file").ok() {
+        None | Some(Value::Nil) => None,
+        Some(Value::String(s)) => Some(s.to_string_lossy().to_string()),
+        Some(_) => return Err(crate::Error::InvalidPath("body_file".to_string())),
+    };
+
     Ok(HttpRequestOptions {
         headers,
         params,
         timeout,
         tags,
         name,
+        body_file,
     })
 }
 
@@ -135,4 +140,18 @@ fn parse_http_opts_timeout_string() {
             parse_http_opts(Some(opts)).unwrap_or_else(|err| panic!("parse_http_opts: {err}"));
         assert_eq!(out.timeout, Some(Duration::from_millis(150)));
     }
+
+    #[test]
+    fn parse_http_opts_body_file() {
+        let lua = mlua::Lua::new();
+        let opts = lua
+            .create_table()
+            .unwrap_or_else(|err| panic!("create_table: {err}"));
+        opts.set("body_file", "data/payload.json")
+            .unwrap_or_else(|err| panic!("set body_file: {err}"));
+
+        let out =
+            parse_http_opts(Some(opts)).unwrap_or_else(|err| panic!("parse_http_opts: {err}"));
+        assert_eq!(out.body_file.as_deref(), Some("data/payload.json"));
+    }
 }
@@ -5,6 +5,12 @@ pub(super) struct HttpLuaResponse {
     pub(super) body: String,
     pub(super) headers: Vec<(String, String)>,
     pub(super) error: Option<String>,
+    pub(super) error_kind: Option<String>,
+    /// Wire bytes as recorded in `bytes_sent_total` / `bytes_received_total`.
+    pub(super) bytes_sent: u64,
+    pub(super) bytes_received: u64,
+    /// The body came from `Options.httpCache` instead of the wire.
+    pub(super) cached: bool,
 }
 
 impl HttpLuaResponse {
@@ -14,15 +20,23 @@ pub(super) fn ok(res: wrkr_http::HttpResponse) -> Self {
             body: res.body_utf8().unwrap_or("").to_string(),
             headers: res.headers,
             error: None,
+            error_kind: None,
+            bytes_sent: res.bytes_sent,
+            bytes_received: res.bytes_received,
+            cached: res.cache_hit.is_some(),
         }
     }
 
-    pub(super) fn err(err: wrkr_http::Error) -> Self {
+    pub(super) fn err(err: wrkr_http::Error, kind: String) -> Self {
         Self {
             status: 0,
             body: String::new(),
             headers: Vec::new(),
             error: Some(err.to_string()),
+            error_kind: Some(kind),
+            bytes_sent: 0,
+            bytes_received: 0,
+            cached: false,
         }
     }
 
@@ -30,6 +44,8 @@ pub(super) fn into_lua_table(self, lua: &Lua) -> mlua::Result<Table> {
         let t = lua.create_table()?;
         t.set("status", self.status)?;
         t.set("body", self.body)?;
+        t.set("bytes_sent", self.bytes_sent)?;
+        t.set("bytes_received", self.bytes_received)?;
 
         let headers_tbl = lua.create_table()?;
         for (k, v) in self.headers {
@@ -40,6 +56,64 @@ pub(super) fn into_lua_table(self, lua: &Lua) -> mlua::Result<Table> {
         if let Some(error) = self.error {
             t.set("error", error)?;
         }
+        if let Some(error_kind) = self.error_kind {
+            t.set("error_kind", error_kind)?;
+        }
+        if self.cached {
+            t.set("cached", true)?;
+        }
+        Ok(t)
+    }
+}
+
+/// Result of `http.stream`: like [`HttpLuaResponse`], but the body was handed to the callback
+/// instead of being kept.
+pub(super) struct HttpLuaStreamResponse {
+    pub(super) status: u16,
+    pub(super) headers: Vec<(String, String)>,
+    /// Body bytes read before the stream ended (or was stopped).
+    pub(super) body_bytes: u64,
+    pub(super) chunks: u64,
+    pub(super) error: Option<String>,
+    pub(super) error_kind: Option<String>,
+    pub(super) bytes_sent: u64,
+    pub(super) bytes_received: u64,
+}
+
+impl HttpLuaStreamResponse {
+    pub(super) fn err(err: wrkr_http::Error, kind: String) -> Self {
+        Self {
+            status: 0,
+            headers: Vec::new(),
+            body_bytes: 0,
+            chunks: 0,
+            error: Some(err.to_string()),
+            error_kind: Some(kind),
+            bytes_sent: 0,
+            bytes_received: 0,
+        }
+    }
+
+    pub(super) fn into_lua_table(self, lua: &Lua) -> mlua::Result<Table> {
+        let t = lua.create_table()?;
+        t.set("status", self.status)?;
+        t.set("bytes", self.body_bytes)?;
+        t.set("chunks", self.chunks)?;
+        t.set("bytes_sent", self.bytes_sent)?;
+        t.set("bytes_received", self.bytes_received)?;
+
+        let headers_tbl = lua.create_table()?;
+        for (k, v) in self.headers {
+            headers_tbl.set(k, v)?;
+        }
+        t.set("headers", headers_tbl)?;
+
+        if let Some(error) = self.error {
+            t.set("error", error)?;
+        }
+        if let Some(error_kind) = self.error_kind {
+            t.set("error_kind", error_kind)?;
+        }
         Ok(t)
     }
 }
@@ -41,6 +41,34 @@ pub(super) fn apply_params_owned(url: String, params: &[(String, String)]) -> St
     u.to_string()
 }
 
+/// Path of `url` with numeric and UUID segments replaced by `:id`; query and fragment are
+/// dropped. Used as the default `name` tag when auto-naming is enabled.
+pub(super) fn url_template(url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |i| &rest[i..]),
+        None => url,
+    };
+
+    path.split('/')
+        .map(|seg| if is_id_segment(seg) { ":id" } else { seg })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn is_id_segment(seg: &str) -> bool {
+    if seg.is_empty() {
+        return false;
+    }
+    if seg.bytes().all(|b| b.is_ascii_digit()) {
+        return true;
+    }
+
+    // 8-4-4-4-12 hex digits.
+    let groups = seg.split('-').map(str::len).collect::<Vec<_>>();
+    groups == [8, 4, 4, 4, 12] && seg.bytes().all(|b| b == b'-' || b.is_ascii_hexdigit())
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::unwrap_used)]
@@ -82,4 +110,18 @@ fn apply_params_owned_appends_query_pairs() {
         // order is deterministic with query_pairs_mut
         assert_eq!(out, "https://example.com/path?a=1&b=2");
     }
+
+    #[test]
+    fn url_template_replaces_numeric_and_uuid_segments() {
+        assert_eq!(
+            url_template(
+                "https://example.com:8080/users/42/orders/3f2b8c1e-9d4a-4b7e-8f00-1234567890ab?x=1"
+            ),
+            "/users/:id/orders/:id"
+        );
+        assert_eq!(url_template("/v1/items/007#top"), "/v1/items/:id");
+        assert_eq!(url_template("https://example.com"), "/");
+        assert_eq!(url_template("/users/me/v2"), "/users/me/v2");
+        assert_eq!(url_template("/hex/deadbeef"), "/hex/deadbeef");
+    }
 }
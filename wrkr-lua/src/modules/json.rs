@@ -2,16 +2,41 @@
 
 use crate::Result;
 use crate::json_util;
+use crate::value_util::Int64Repr;
+
+fn int64_repr(opts: Option<&Table>, what: &str) -> mlua::Result<Int64Repr> {
+    opts.map_or(Ok(Int64Repr::Integer), |opts| {
+        Int64Repr::from_opts(opts, what)
+    })
+}
+
+/// Keys whose integer strings `json.encode` writes as numbers; required with `int64 = "string"`,
+/// since other strings that merely look like integers (zip codes, phone numbers) must stay quoted.
+fn int64_fields(opts: Option<&Table>, repr: Int64Repr) -> mlua::Result<Vec<String>> {
+    let fields = match opts {
+        Some(opts) => opts.get::<Option<Vec<String>>>("int64_fields")?,
+        None => None,
+    };
+    match (repr, fields) {
+        (Int64Repr::String, None) => Err(mlua::Error::external(
+            "json.encode opts.int64_fields is required with int64 = 'string'",
+        )),
+        (_, fields) => Ok(fields.unwrap_or_default()),
+    }
+}
 
 pub(super) fn register(lua: &Lua) -> Result<()> {
     let loader = lua.create_function(|lua, ()| {
         let t = lua.create_table()?;
 
-        let encode = lua.create_function(|lua, v: Value| {
-            json_util::encode(lua, v).map_err(mlua::Error::external)
+        let encode = lua.create_function(|lua, (v, opts): (Value, Option<Table>)| {
+            let repr = int64_repr(opts.as_ref(), "json.encode opts")?;
+            let fields = int64_fields(opts.as_ref(), repr)?;
+            json_util::encode(lua, v, repr, &fields).map_err(mlua::Error::external)
         })?;
-        let decode = lua.create_function(|lua, s: String| {
-            json_util::decode(lua, &s).map_err(mlua::Error::external)
+        let decode = lua.create_function(|lua, (s, opts): (String, Option<Table>)| {
+            let repr = int64_repr(opts.as_ref(), "json.decode opts")?;
+            json_util::decode(lua, &s, repr).map_err(mlua::Error::external)
         })?;
 
         t.set("encode", encode)?;
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Instant;
 
 use mlua::{Lua, Table, Value};
 use wrkr_metrics::MetricKind;
@@ -7,7 +8,7 @@
 
 mod opts;
 
-use opts::{MetricAddLuaArgs, resolve_tags};
+use opts::{MetricAddLuaArgs, metric_tags, resolve_tags};
 
 fn make_metric_handle_table(
     lua: &Lua,
@@ -93,6 +94,35 @@ fn make_metric_handle_table(
     Ok(t)
 }
 
+/// A stopwatch started at creation; `observe` records the elapsed microseconds into a Trend.
+fn make_timer_table(
+    lua: &Lua,
+    metrics: Arc<wrkr_metrics::Registry>,
+    metrics_ctx: wrkr_core::MetricsContext,
+) -> Result<Table> {
+    let started = Instant::now();
+    let t = lua.create_table()?;
+
+    let observe = lua.create_function(
+        move |lua, (_this, name, tags): (Table, String, Option<Table>)| {
+            let elapsed_us: u64 = started.elapsed().as_micros().try_into().unwrap_or(u64::MAX);
+            if name.trim().is_empty() {
+                return Err(mlua::Error::external(crate::Error::InvalidMetricName));
+            }
+
+            let metric = metrics.register(&name, MetricKind::Histogram);
+            let tags = metric_tags(lua, &metrics_ctx, tags)?;
+            if let Some(handle) = metrics.get_handle(metric, resolve_tags(&metrics, &tags)) {
+                handle.observe_histogram(elapsed_us);
+            }
+            Ok(elapsed_us)
+        },
+    )?;
+
+    t.set("observe", observe)?;
+    Ok(t)
+}
+
 pub(super) fn register_runtime(
     lua: &Lua,
     run_ctx: Arc<wrkr_core::RunScenariosContext>,
@@ -124,6 +154,16 @@ pub(super) fn register_runtime(
             t.set("Gauge", make(MetricKind::Gauge)?)?;
             t.set("Rate", make(MetricKind::Rate)?)?;
 
+            let timer = {
+                let metrics = metrics.clone();
+                let metrics_ctx = metrics_ctx.clone();
+                lua.create_function(move |lua, ()| {
+                    make_timer_table(lua, metrics.clone(), metrics_ctx.clone())
+                        .map_err(mlua::Error::external)
+                })?
+            };
+            t.set("timer", timer)?;
+
             Ok::<_, mlua::Error>(t)
         })?
     };
@@ -53,6 +53,21 @@ pub(super) struct MetricAddLuaArgs {
     pub(super) tags: Vec<(String, String)>,
 }
 
+/// User tags plus the base tags every custom metric series carries.
+pub(super) fn metric_tags(
+    lua: &Lua,
+    metrics_ctx: &wrkr_core::MetricsContext,
+    tags: Option<Table>,
+) -> mlua::Result<Vec<(String, String)>> {
+    let mut tags = tags_from_lua(tags)?;
+
+    // Base metric tags always include scenario + scenario.tags.
+    metrics_ctx.merge_base_tags_if_missing(&mut tags, &["group"]);
+
+    add_group_tag_if_missing(lua, &mut tags);
+    Ok(tags)
+}
+
 impl MetricAddLuaArgs {
     pub(super) fn parse(
         lua: &Lua,
@@ -60,12 +75,7 @@ pub(super) fn parse(
         value: Value,
         tags: Option<Table>,
     ) -> mlua::Result<Self> {
-        let mut tags = tags_from_lua(tags)?;
-
-        // Base metric tags always include scenario + scenario.tags.
-        metrics_ctx.merge_base_tags_if_missing(&mut tags, &["group"]);
-
-        add_group_tag_if_missing(lua, &mut tags);
+        let tags = metric_tags(lua, metrics_ctx, tags)?;
         Ok(Self { value, tags })
     }
 }
@@ -0,0 +1,143 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use mlua::{Lua, Table, Value};
+use wrkr_core::{ThinkTime, ThinkTimeSpec};
+
+use crate::Result;
+
+const REG_SLEPT_US: &str = "wrkr_slept_us";
+
+fn slept_us(lua: &Lua) -> u64 {
+    lua.named_registry_value::<Option<u64>>(REG_SLEPT_US)
+        .ok()
+        .flatten()
+        .unwrap_or(0)
+}
+
+pub(super) fn add_slept(lua: &Lua, d: Duration) -> mlua::Result<()> {
+    let us: u64 = d.as_micros().try_into().unwrap_or(u64::MAX);
+    lua.set_named_registry_value(REG_SLEPT_US, slept_us(lua).saturating_add(us))
+}
+
+/// Time spent in `wrkr/sleep` since the previous call, so iterations can exclude think time.
+pub(crate) fn take_slept(lua: &Lua) -> Duration {
+    let us = slept_us(lua);
+    if us > 0 {
+        let _ = lua.set_named_registry_value(REG_SLEPT_US, 0u64);
+    }
+    Duration::from_micros(us)
+}
+
+pub(super) fn parse_duration_value(v: Value, field: &'static str) -> Result<Option<Duration>> {
+    match v {
+        Value::Nil => Ok(None),
+        Value::Integer(i) if i >= 0 => Ok(Some(Duration::from_secs(i as u64))),
+        Value::Number(n) if n.is_finite() && n >= 0.0 => Duration::try_from_secs_f64(n)
+            .map(Some)
+            .map_err(|_| crate::Error::InvalidThinkTime(format!("`{field}` is out of range"))),
+        Value::String(s) => wrkr_core::parse_duration(&s.to_string_lossy())
+            .map(Some)
+            .map_err(|_| crate::Error::InvalidThinkTime(format!("`{field}` is not a duration"))),
+        _ => Err(crate::Error::InvalidThinkTime(format!(
+            "`{field}` must be seconds or a duration string"
+        ))),
+    }
+}
+
+pub(super) fn required(v: Option<Duration>, field: &'static str) -> Result<Duration> {
+    v.ok_or_else(|| crate::Error::InvalidThinkTime(format!("`{field}` is required")))
+}
+
+fn parse_think_opts(opts: &Table) -> Result<ThinkTimeSpec> {
+    let distribution: String = opts
+        .get::<Option<String>>("distribution")?
+        .ok_or_else(|| crate::Error::InvalidThinkTime("`distribution` is required".to_string()))?;
+
+    let min = parse_duration_value(opts.get("min")?, "min")?;
+    let max = parse_duration_value(opts.get("max")?, "max")?;
+    let mean = parse_duration_value(opts.get("mean")?, "mean")?;
+    let stddev = parse_duration_value(opts.get("stddev")?, "stddev")?;
+
+    if let (Some(min), Some(max)) = (min, max)
+        && min > max
+    {
+        return Err(crate::Error::InvalidThinkTime(
+            "`min` must not exceed `max`".to_string(),
+        ));
+    }
+
+    let dist = match distribution.as_str() {
+        "uniform" => ThinkTime::Uniform {
+            min: min.unwrap_or(Duration::ZERO),
+            max: required(max, "max")?,
+        },
+        "normal" => ThinkTime::Normal {
+            mean: required(mean, "mean")?,
+            stddev: required(stddev, "stddev")?,
+        },
+        "exponential" => ThinkTime::Exponential {
+            mean: required(mean, "mean")?,
+        },
+        "lognormal" => ThinkTime::LogNormal {
+            mean: required(mean, "mean")?,
+            stddev: required(stddev, "stddev")?,
+        },
+        other => {
+            return Err(crate::Error::InvalidThinkTime(format!(
+                "unknown distribution `{other}` (expected uniform, normal, exponential or lognormal)"
+            )));
+        }
+    };
+
+    Ok(ThinkTimeSpec { dist, min, max })
+}
+
+/// A scenario's `think_time`: a fixed duration, or the same options table as `sleep.think`.
+pub(crate) fn parse_think_time(v: Value) -> Result<Option<ThinkTimeSpec>> {
+    match v {
+        Value::Table(opts) => parse_think_opts(&opts).map(Some),
+        v => Ok(parse_duration_value(v, "think_time")?.map(ThinkTimeSpec::fixed)),
+    }
+}
+
+pub(super) fn register(lua: &Lua) -> Result<()> {
+    let loader = lua.create_function(|lua, ()| {
+        let t = lua.create_table()?;
+
+        // Each VU has its own Lua state, so this PRNG is per-VU.
+        let rng = Mutex::new(fastrand::Rng::new());
+
+        let fixed = lua.create_async_function(|lua, d: Value| {
+            let d = parse_duration_value(d, "duration");
+            async move {
+                let d = d
+                    .and_then(|d| required(d, "duration"))
+                    .map_err(mlua::Error::external)?;
+                let started = Instant::now();
+                tokio::time::sleep(d).await;
+                add_slept(&lua, started.elapsed())
+            }
+        })?;
+
+        let think = lua.create_async_function(move |lua, opts: Table| {
+            let pause = parse_think_opts(&opts).map(|spec| {
+                let mut rng = rng.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                spec.sample(&mut rng)
+            });
+            async move {
+                let pause = pause.map_err(mlua::Error::external)?;
+                let started = Instant::now();
+                tokio::time::sleep(pause).await;
+                add_slept(&lua, started.elapsed())?;
+                Ok(pause.as_secs_f64())
+            }
+        })?;
+
+        t.set("fixed", fixed)?;
+        t.set("think", think)?;
+        Ok::<Table, mlua::Error>(t)
+    })?;
+
+    super::preload_set(lua, "wrkr/sleep", loader)
+}
@@ -1,12 +1,50 @@
-use mlua::{Lua, Table};
+use mlua::{Lua, Table, Value};
 
 use crate::Result;
 
-pub(super) fn register(lua: &Lua, vu_id: u64) -> Result<()> {
+pub(super) fn register(
+    lua: &Lua,
+    vu_id: u64,
+    metrics_ctx: wrkr_core::MetricsContext,
+) -> Result<()> {
     let loader = lua.create_function(move |lua, ()| {
         let t = lua.create_table()?;
         let id = lua.create_function(move |_lua, ()| Ok(vu_id))?;
         t.set("id", id)?;
+        let metrics_ctx = metrics_ctx.clone();
+        let tags = lua.create_function(move |lua, ()| {
+            let tags = lua.create_table()?;
+            for (k, v) in metrics_ctx.scenario_tags() {
+                tags.set(k.as_str(), v.as_str())?;
+            }
+            Ok(tags)
+        })?;
+        t.set("tags", tags)?;
+        let sleep = lua.create_async_function(|lua, d: Value| {
+            let d = super::sleep::parse_duration_value(d, "duration")
+                .and_then(|d| super::sleep::required(d, "duration"));
+            // Only set on VU states; setup and teardown sleep unclamped.
+            let clock = lua
+                .app_data_ref::<wrkr_core::ScenarioClock>()
+                .map(|clock| (*clock).clone());
+            async move {
+                let d = d.map_err(mlua::Error::external)?;
+                let slept = match clock {
+                    Some(clock) => clock.sleep(d).await,
+                    None => {
+                        let started = std::time::Instant::now();
+                        tokio::time::sleep(d).await;
+                        started.elapsed()
+                    }
+                };
+                super::sleep::add_slept(&lua, slept)?;
+                Ok(slept.as_secs_f64())
+            }
+        })?;
+        t.set("sleep", sleep)?;
+        // `require` caches this module per Lua state (one per VU), so the table lives as long
+        // as the VU does.
+        t.set("state", lua.create_table()?)?;
         Ok::<Table, mlua::Error>(t)
     })?;
 
@@ -14,7 +14,7 @@ pub(super) fn register(lua: &Lua) -> Result<()> {
         #[cfg(feature = "grpc")]
         let grpc: Table = require.call("wrkr/grpc")?;
 
-        let check: mlua::Function = require.call("wrkr/check")?;
+        let check: Table = require.call("wrkr/check")?;
         let env: Table = require.call("wrkr/env")?;
         let fs: Table = require.call("wrkr/fs")?;
         let group: Table = require.call("wrkr/group")?;
@@ -22,6 +22,7 @@ pub(super) fn register(lua: &Lua) -> Result<()> {
         let uuid: Table = require.call("wrkr/uuid")?;
         let metrics: Table = require.call("wrkr/metrics")?;
         let shared: Table = require.call("wrkr/shared")?;
+        let sleep: Table = require.call("wrkr/sleep")?;
         let vu: Table = require.call("wrkr/vu")?;
 
         #[cfg(feature = "http")]
@@ -38,6 +39,7 @@ pub(super) fn register(lua: &Lua) -> Result<()> {
         t.set("uuid", uuid)?;
         t.set("metrics", metrics)?;
         t.set("shared", shared)?;
+        t.set("sleep", sleep)?;
         t.set("vu", vu)?;
         Ok::<Table, mlua::Error>(t)
     })?;
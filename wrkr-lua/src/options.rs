@@ -4,6 +4,7 @@
 
 use crate::loader::{chunk_name, configure_module_path};
 use crate::modules;
+use crate::value_util::{Int64Repr, lua_to_value};
 use crate::{Error, Result};
 
 pub fn parse_script_options(
@@ -38,7 +39,21 @@ pub fn parse_script_options(
         out.vus = get_vus(options)?;
         out.iterations = get_iterations(options)?;
         out.duration = get_duration(options)?;
+        out.warmup_iterations = get_warmup_iterations(options)?;
         out.thresholds = get_thresholds(options)?;
+        out.tags = get_tags(options, "tags", Error::InvalidTags)?;
+        out.discard_response_bodies =
+            get_boolean_option(options, "discardResponseBodies", "discard_response_bodies")?;
+        out.no_connection_reuse =
+            get_boolean_option(options, "noConnectionReuse", "no_connection_reuse")?;
+        out.no_vu_connection_reuse =
+            get_boolean_option(options, "noVUConnectionReuse", "no_vu_connection_reuse")?;
+        out.isolate_scenario_connections = get_boolean_option(
+            options,
+            "isolateScenarioConnections",
+            "isolate_scenario_connections",
+        )?;
+        out.http_cache = get_http_cache(options)?;
     }
 
     if let Some(scenarios_tbl) = scenarios_table {
@@ -54,11 +69,23 @@ pub fn parse_script_options(
             };
 
             let exec = t.get::<String>("exec").ok();
-            let tags = get_scenario_tags(&t)?;
+            let tags = get_tags(&t, "tags", Error::InvalidScenarioTags)?;
+            let description = get_description(&t)?;
+            let params = match t.get::<Value>("params")? {
+                Value::Nil => None,
+                v => Some(Arc::new(lua_to_value(&lua, v, Int64Repr::Integer)?)),
+            };
             let executor = get_string_any(&t, &["executor"])?;
             let vus = get_vus(&t)?;
             let iterations = get_iterations(&t)?;
             let duration = get_duration(&t)?;
+            let think_time = match t.get::<Value>("think_time")? {
+                Value::Nil => t.get::<Value>("thinkTime")?,
+                v => v,
+            };
+            let think_time = modules::parse_think_time(think_time)?;
+            let isolate_connections =
+                get_boolean_option(&t, "isolateConnections", "isolate_connections")?;
 
             let start_vus = get_u64_any(&t, &["start_vus", "startVUs"], true)?;
             let start_rate = get_u64_any(&t, &["start_rate", "startRate"], true)?;
@@ -68,15 +95,20 @@ pub fn parse_script_options(
             let max_vus = get_u64_any(&t, &["max_vus", "maxVUs"], false)?;
 
             let stages = get_stages(&t)?;
+            let matrix = get_matrix(&t)?;
 
-            out.scenarios.push(wrkr_core::ScenarioOptions {
+            let scenario = wrkr_core::ScenarioOptions {
                 name,
                 exec,
                 tags,
+                description,
+                params,
                 executor,
                 vus,
                 iterations,
                 duration,
+                think_time,
+                isolate_connections,
 
                 start_vus,
                 stages,
@@ -84,7 +116,8 @@ pub fn parse_script_options(
                 time_unit,
                 pre_allocated_vus,
                 max_vus,
-            });
+            };
+            out.scenarios.extend(scenario.expand_matrix(&matrix));
         }
     }
 
@@ -114,36 +147,84 @@ fn get_thresholds(t: &Table) -> Result<Vec<wrkr_core::ThresholdSet>> {
         let (metric, tags) = wrkr_core::parse_threshold_metric_key(&metric_key)
             .map_err(|_| Error::InvalidThresholds)?;
 
-        let expressions: Vec<String> = match v {
-            Value::String(s) => vec![s.to_string_lossy().to_string()],
-            Value::Table(list) => {
-                let mut exprs = Vec::new();
-                for item in list.sequence_values::<Value>() {
-                    let item = item?;
-                    match item {
-                        Value::String(s) => exprs.push(s.to_string_lossy().to_string()),
-                        _ => return Err(Error::InvalidThresholds),
-                    }
-                }
-                exprs
-            }
-            _ => return Err(Error::InvalidThresholds),
+        // A list of tables declares several sets for the same key, typically with different
+        // `when` conditions.
+        let entries: Vec<Value> = match v {
+            Value::Table(list) if matches!(list.raw_get::<Value>(1)?, Value::Table(_)) => list
+                .sequence_values::<Value>()
+                .collect::<mlua::Result<Vec<_>>>()?,
+            other => vec![other],
         };
 
-        if expressions.is_empty() {
-            return Err(Error::InvalidThresholds);
+        for entry in entries {
+            let (expressions, description, when, no_data) = parse_threshold_entry(entry)?;
+            out.push(wrkr_core::ThresholdSet {
+                metric: metric.clone(),
+                tags: tags.clone(),
+                expressions,
+                description,
+                when,
+                no_data,
+            });
         }
-
-        out.push(wrkr_core::ThresholdSet {
-            metric,
-            tags,
-            expressions,
-        });
     }
 
     Ok(out)
 }
 
+type ThresholdEntry = (
+    Vec<String>,
+    Option<String>,
+    Vec<(String, String)>,
+    wrkr_core::ThresholdNoData,
+);
+
+/// `"expr"` or `{ "expr", ..., description = "...", when = { k = v }, no_data = "pass" }`.
+fn parse_threshold_entry(v: Value) -> Result<ThresholdEntry> {
+    let (expressions, description, when, no_data) = match v {
+        Value::String(s) => (
+            vec![s.to_string_lossy().to_string()],
+            None,
+            Vec::new(),
+            wrkr_core::ThresholdNoData::default(),
+        ),
+        Value::Table(list) => {
+            let mut exprs = Vec::new();
+            for item in list.sequence_values::<Value>() {
+                let item = item?;
+                match item {
+                    Value::String(s) => exprs.push(s.to_string_lossy().to_string()),
+                    _ => return Err(Error::InvalidThresholds),
+                }
+            }
+            let description = get_description(&list).map_err(|_| Error::InvalidThresholds)?;
+            let when = get_tags(&list, "when", Error::InvalidThresholds)?;
+            let no_data = get_threshold_no_data(&list)?;
+            (exprs, description, when, no_data)
+        }
+        _ => return Err(Error::InvalidThresholds),
+    };
+
+    if expressions.is_empty() {
+        return Err(Error::InvalidThresholds);
+    }
+    Ok((expressions, description, when, no_data))
+}
+
+fn get_threshold_no_data(t: &Table) -> Result<wrkr_core::ThresholdNoData> {
+    for key in ["no_data", "noData"] {
+        match t.get::<Value>(key)? {
+            Value::Nil => continue,
+            Value::String(s) => {
+                return wrkr_core::ThresholdNoData::parse(&s.to_string_lossy())
+                    .map_err(|_| Error::InvalidThresholds);
+            }
+            _ => return Err(Error::InvalidThresholds),
+        }
+    }
+    Ok(wrkr_core::ThresholdNoData::default())
+}
+
 fn get_vus(t: &Table) -> Result<Option<u64>> {
     let v = match t.get::<Value>("vus") {
         Ok(v) => v,
@@ -170,6 +251,51 @@ fn get_iterations(t: &Table) -> Result<Option<u64>> {
     }
 }
 
+fn get_warmup_iterations(t: &Table) -> Result<Option<u64>> {
+    for key in ["warmup_iterations", "warmupIterations"] {
+        let v = match t.get::<Value>(key) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        return match v {
+            Value::Nil => continue,
+            Value::Integer(i) if i >= 0 => Ok(Some(i as u64)),
+            Value::Number(n) if n.fract() == 0.0 && n >= 0.0 => Ok(Some(n as u64)),
+            _ => Err(Error::InvalidWarmupIterations),
+        };
+    }
+    Ok(None)
+}
+
+/// A boolean option given as `name` or its snake_case spelling; `false` when unset.
+fn get_boolean_option(t: &Table, name: &'static str, snake_case: &str) -> Result<bool> {
+    for key in [name, snake_case] {
+        match t.get::<Value>(key)? {
+            Value::Nil => continue,
+            Value::Boolean(b) => return Ok(b),
+            _ => return Err(Error::InvalidBooleanOption(name)),
+        }
+    }
+    Ok(false)
+}
+
+fn get_http_cache(t: &Table) -> Result<Option<wrkr_core::HttpCacheScope>> {
+    for key in ["httpCache", "http_cache"] {
+        match t.get::<Value>(key)? {
+            Value::Nil => continue,
+            Value::String(s) => {
+                return s
+                    .to_string_lossy()
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| Error::InvalidHttpCache);
+            }
+            _ => return Err(Error::InvalidHttpCache),
+        }
+    }
+    Ok(None)
+}
+
 fn get_duration(t: &Table) -> Result<Option<Duration>> {
     let v = match t.get::<Value>("duration") {
         Ok(v) => v,
@@ -177,15 +303,13 @@ fn get_duration(t: &Table) -> Result<Option<Duration>> {
     };
     match v {
         Value::Nil => Ok(None),
-        Value::Number(n) if n > 0.0 => Ok(Some(Duration::from_secs_f64(n))),
-        Value::Integer(i) if i > 0 => Ok(Some(Duration::from_secs(i as u64))),
         Value::String(s) => {
             let s = s.to_string_lossy();
-            humantime::parse_duration(&s)
+            wrkr_core::parse_duration(&s)
                 .map(Some)
                 .map_err(|_| Error::InvalidDuration)
         }
-        _ => Err(Error::InvalidDuration),
+        v => seconds(&v).map(Some).ok_or(Error::InvalidDuration),
     }
 }
 
@@ -204,8 +328,16 @@ fn get_string_any(t: &Table, keys: &[&str]) -> Result<Option<String>> {
     Ok(None)
 }
 
-fn get_scenario_tags(t: &Table) -> Result<Vec<(String, String)>> {
-    let v = match t.get::<Value>("tags") {
+fn get_description(t: &Table) -> Result<Option<String>> {
+    match t.get::<Value>("description")? {
+        Value::Nil => Ok(None),
+        Value::String(s) => Ok(Some(s.to_string_lossy().to_string())),
+        _ => Err(Error::InvalidDescription),
+    }
+}
+
+fn get_tags(t: &Table, key: &str, invalid: Error) -> Result<Vec<(String, String)>> {
+    let v = match t.get::<Value>(key) {
         Ok(v) => v,
         Err(_) => return Ok(Vec::new()),
     };
@@ -213,7 +345,7 @@ fn get_scenario_tags(t: &Table) -> Result<Vec<(String, String)>> {
     let tbl = match v {
         Value::Nil => return Ok(Vec::new()),
         Value::Table(t) => t,
-        _ => return Err(Error::InvalidScenarioTags),
+        _ => return Err(invalid),
     };
 
     let mut out = Vec::new();
@@ -238,6 +370,39 @@ fn get_scenario_tags(t: &Table) -> Result<Vec<(String, String)>> {
     Ok(out)
 }
 
+/// `matrix = { size = { "1k", "10k" }, ... }`. Lua tables have no key order, so parameters are
+/// expanded in key order to keep generated scenario names stable.
+fn get_matrix(t: &Table) -> Result<Vec<(String, Vec<String>)>> {
+    let tbl = match t.get::<Value>("matrix")? {
+        Value::Nil => return Ok(Vec::new()),
+        Value::Table(t) => t,
+        _ => return Err(Error::InvalidMatrix),
+    };
+
+    let mut out = Vec::new();
+    for pair in tbl.pairs::<Value, Value>() {
+        let (k, v) = pair?;
+        let (Value::String(k), Value::Table(values)) = (k, v) else {
+            return Err(Error::InvalidMatrix);
+        };
+
+        let values = values
+            .sequence_values::<Value>()
+            .map(|v| match v? {
+                Value::String(s) => Ok(s.to_string_lossy().to_string()),
+                Value::Integer(i) => Ok(i.to_string()),
+                Value::Number(n) if n.is_finite() => Ok(n.to_string()),
+                Value::Boolean(b) => Ok(b.to_string()),
+                _ => Err(Error::InvalidMatrix),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        out.push((k.to_string_lossy().to_string(), values));
+    }
+    out.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(out)
+}
+
 fn get_duration_any(t: &Table, keys: &[&str]) -> Result<Option<Duration>> {
     for key in keys {
         let v = match t.get::<Value>(*key) {
@@ -246,20 +411,28 @@ fn get_duration_any(t: &Table, keys: &[&str]) -> Result<Option<Duration>> {
         };
         match v {
             Value::Nil => continue,
-            Value::Number(n) if n > 0.0 => return Ok(Some(Duration::from_secs_f64(n))),
-            Value::Integer(i) if i > 0 => return Ok(Some(Duration::from_secs(i as u64))),
             Value::String(s) => {
                 let s = s.to_string_lossy();
-                return humantime::parse_duration(&s)
+                return wrkr_core::parse_duration(&s)
                     .map(Some)
                     .map_err(|_| Error::InvalidTimeUnit);
             }
-            _ => return Err(Error::InvalidTimeUnit),
+            v => return seconds(&v).map(Some).ok_or(Error::InvalidTimeUnit),
         }
     }
     Ok(None)
 }
 
+/// A duration given as a number of seconds. Zero is accepted, as in duration strings; the core
+/// rejects it where it has no meaning (a scenario's `duration`, `timeUnit`).
+fn seconds(v: &Value) -> Option<Duration> {
+    match *v {
+        Value::Number(n) => Duration::try_from_secs_f64(n).ok(),
+        Value::Integer(i) => u64::try_from(i).ok().map(Duration::from_secs),
+        _ => None,
+    }
+}
+
 fn get_u64_any(t: &Table, keys: &[&str], allow_zero: bool) -> Result<Option<u64>> {
     for key in keys {
         let v = match t.get::<Value>(*key) {
@@ -329,7 +502,20 @@ fn get_stages(t: &Table) -> Result<Vec<wrkr_core::Stage>> {
             None => return Err(Error::InvalidStages),
         };
 
-        out.push(wrkr_core::Stage { duration, target });
+        let shape = match stage_tbl.get::<Value>("shape")? {
+            Value::Nil => wrkr_core::StageShape::Linear,
+            Value::String(s) => s
+                .to_string_lossy()
+                .parse()
+                .map_err(|_| Error::InvalidStages)?,
+            _ => return Err(Error::InvalidStages),
+        };
+
+        out.push(wrkr_core::Stage {
+            duration,
+            target,
+            shape,
+        });
     }
 
     Ok(out)
@@ -10,6 +10,20 @@ pub enum Int64Repr {
     String,
 }
 
+impl Int64Repr {
+    /// Read the `int64` option (`"integer"` or `"string"`); `what` names the options table in the
+    /// error message.
+    pub fn from_opts(opts: &Table, what: &str) -> mlua::Result<Self> {
+        match opts.get::<Option<String>>("int64")?.as_deref() {
+            None | Some("integer") => Ok(Self::Integer),
+            Some("string") => Ok(Self::String),
+            Some(_) => Err(mlua::Error::external(format!(
+                "{what}.int64 must be 'integer' or 'string'"
+            ))),
+        }
+    }
+}
+
 pub fn lua_to_value(lua: &Lua, value: Value, int64_repr: Int64Repr) -> Result<wrkr_value::Value> {
     fn err(msg: &str) -> mlua::Error {
         mlua::Error::external(msg.to_string())
@@ -0,0 +1,58 @@
+mod support;
+
+use std::collections::BTreeSet;
+
+use wrkr_lua::Result;
+
+fn tag<'a>(tags: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    tags.iter()
+        .find_map(|(k, v)| (k == key).then_some(v.as_str()))
+}
+
+#[tokio::test]
+async fn check_predicates_can_compute_names_with_bounded_cardinality() -> Result<()> {
+    let summary = support::run_script(
+        "check_dynamic_names.lua",
+        &[],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    let names: BTreeSet<&str> = summary
+        .metrics
+        .iter()
+        .filter(|m| m.name == "checks")
+        .filter_map(|m| tag(&m.tags, "name"))
+        .collect();
+
+    assert!(names.contains("status is 2xx"));
+    assert!(!names.contains("status class"));
+
+    // The computed name and the 150 ids share the default limit; overflow falls back to the key.
+    let ids = names.iter().filter(|n| n.starts_with("id ")).count();
+    assert_eq!(ids, wrkr_core::CardinalityGuard::DEFAULT_LIMIT - 1);
+    assert!(names.contains("unique id"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn non_string_computed_names_fail_with_the_check_name() -> Result<()> {
+    let res = support::run_script(
+        "check_predicate_error.lua",
+        &[],
+        wrkr_core::RunConfig::default(),
+    )
+    .await;
+
+    let err = match res {
+        Ok(_) => panic!("expected the table name to fail the run"),
+        Err(err) => err,
+    };
+    let msg = err.to_string();
+    assert!(
+        msg.contains("check `status class`: computed name must be a string, got table"),
+        "{msg}"
+    );
+    Ok(())
+}
@@ -0,0 +1,21 @@
+mod support;
+
+use wrkr_lua::Result;
+
+#[tokio::test]
+async fn check_json_schema_records_conformance() -> Result<()> {
+    let summary = support::run_script(
+        "check_json_schema.lua",
+        &[],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    let s = &summary.scenarios[0];
+    assert_eq!(s.iterations_total, 1);
+    assert_eq!(s.checks_weight_total, 4);
+    assert_eq!(s.checks_failed_total, 1);
+    assert_eq!(s.checks_failed.get("user is invalid"), Some(&1));
+
+    Ok(())
+}
@@ -17,3 +17,124 @@ async fn e2e_grpc_unary_echo() -> Result<()> {
     grpc.shutdown().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn e2e_grpc_exposes_rich_error_details() -> Result<()> {
+    let grpc = GrpcTestServer::start().await?;
+
+    let summary = support::run_script(
+        "grpc_error_details.lua",
+        &[("BASE_URL", grpc.target())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    grpc.shutdown().await;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_grpc_expected_statuses_make_non_ok_calls_succeed() -> Result<()> {
+    let grpc = GrpcTestServer::start().await?;
+
+    let summary = support::run_script(
+        "grpc_expected_statuses.lua",
+        &[("BASE_URL", grpc.target())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    grpc.shutdown().await;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    assert_eq!(scenario.requests_total, 3);
+    // Each check saw the call it inspects: `ok` flips only for the declared statuses.
+    for name in [
+        "non-OK is not ok by default",
+        "expected by name",
+        "expected by code",
+    ] {
+        assert!(
+            summary
+                .find_series("checks", &[("name", name), ("status", "pass")])
+                .is_some(),
+            "check `{name}` did not pass"
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_grpc_server_stream_separates_message_and_call_timeouts() -> Result<()> {
+    let grpc = GrpcTestServer::start().await?;
+
+    let summary = support::run_script(
+        "grpc_server_stream.lua",
+        &[("BASE_URL", grpc.target())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    grpc.shutdown().await;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    assert_eq!(scenario.requests_total, 3);
+    // Only the stalled stream is a failed request; DEADLINE_EXCEEDED is a gRPC status.
+    assert_eq!(scenario.failed_requests_total, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_grpc_names_default_to_method_and_stay_bounded() -> Result<()> {
+    let grpc = GrpcTestServer::start().await?;
+
+    let summary = support::run_script(
+        "grpc_names.lua",
+        &[("BASE_URL", grpc.target())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    grpc.shutdown().await;
+
+    let requests = |name: &str| {
+        summary
+            .find_series("requests_total", &[("protocol", "grpc"), ("name", name)])
+            .map(|s| match s.values {
+                wrkr_core::MetricValue::Counter(v) => v,
+                ref other => panic!("requests_total is not a counter: {other:?}"),
+            })
+    };
+    assert_eq!(requests("Echo"), Some(1));
+    assert_eq!(requests("echo-1"), Some(1));
+    // The default call plus the dynamic and tagged names that did not fit under the limit.
+    let over_limit = 150 - (wrkr_core::CardinalityGuard::DEFAULT_LIMIT as u64 - 1) + 10;
+    assert_eq!(requests("wrkr.test.EchoService/Echo"), Some(1 + over_limit));
+    assert_eq!(requests("tagged-1"), None);
+
+    let names = summary
+        .metrics
+        .iter()
+        .filter(|m| m.name == "requests_total")
+        .filter_map(|m| m.tags.iter().find(|(k, _)| k == "name"))
+        .count();
+    assert_eq!(names, wrkr_core::CardinalityGuard::DEFAULT_LIMIT + 1);
+    Ok(())
+}
@@ -17,3 +17,25 @@ async fn e2e_grpc_aggregate_orders() -> Result<()> {
     grpc.shutdown().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn e2e_grpc_connect_metadata_is_sent_with_every_call() -> Result<()> {
+    let grpc = GrpcTestServer::start().await?;
+
+    let summary = support::run_script(
+        "grpc_default_metadata.lua",
+        &[("BASE_URL", grpc.target())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    grpc.shutdown().await;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    Ok(())
+}
@@ -22,6 +22,25 @@ async fn e2e_http_plaintext_script_sends_requests() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn e2e_http_post_body_file_sends_file_contents() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "post_body_file.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    let saw_body = server.stats().saw_post_body();
+    server.shutdown().await;
+
+    assert_eq!(saw_body, 4, "expected every request to carry the file body");
+    assert!(summary.scenarios.iter().all(|s| s.checks_failed_total == 0));
+    Ok(())
+}
+
 #[tokio::test]
 async fn e2e_http_post_echo_tracks_header_and_body() -> Result<()> {
     let server = TestServer::start().await?;
@@ -42,6 +61,91 @@ async fn e2e_http_post_echo_tracks_header_and_body() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn e2e_http_discard_response_bodies_still_counts_bytes() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "http_discard_bodies.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+    server.shutdown().await;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(scenario.requests_total, 2);
+    assert_eq!(scenario.checks_failed_total, 0);
+    assert!(scenario.bytes_received_total > 2 * 4096);
+    // Discarded bodies still leave the connection reusable, so both requests are counted.
+    assert_eq!(scenario.connection_reuse_samples, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_http_no_vu_connection_reuse_opens_connections_per_iteration() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "http_no_vu_connection_reuse.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+    server.shutdown().await;
+
+    // Only the second request of each iteration finds an open connection.
+    let scenario = &summary.scenarios[0];
+    assert_eq!(scenario.connection_reuse_samples, 6);
+    assert_eq!(scenario.connection_reused_total, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_http_isolated_scenarios_do_not_share_connections() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "http_isolated_scenario_connections.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+    server.shutdown().await;
+
+    let reused = |name: &str| {
+        let scenario = summary
+            .scenario(name)
+            .unwrap_or_else(|| panic!("missing scenario {name}"));
+        (
+            scenario.connection_reused_total,
+            scenario.connection_reuse_samples,
+        )
+    };
+    // `busy` reuses its own connection; `quiet` cannot borrow it while it sits idle.
+    assert_eq!(reused("busy"), (1, 2));
+    assert_eq!(reused("quiet"), (0, 1));
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_http_no_connection_reuse_opens_a_connection_per_request() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "http_no_connection_reuse.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+    server.shutdown().await;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(scenario.connection_reuse_samples, 4);
+    assert_eq!(scenario.connection_reused_total, 0);
+    Ok(())
+}
+
 #[tokio::test]
 async fn e2e_http_post_json_tracks_content_type() -> Result<()> {
     let server = TestServer::start().await?;
@@ -79,3 +183,40 @@ async fn e2e_http_timeout_is_reported_as_error_response_but_iteration_succeeds()
     server.shutdown().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn e2e_http_cache_serves_fresh_responses_without_requests() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "http_cache.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+    let seen = server.stats().requests_total();
+    server.shutdown().await;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    // One GET and three POSTs reached the server; the cache hits are not requests.
+    assert_eq!(seen, 4);
+    assert_eq!(scenario.requests_total, 4);
+
+    let (hits, total) = summary
+        .metrics
+        .iter()
+        .filter(|m| m.name == "http_cache_hits")
+        .fold((0, 0), |(hits, total), m| match m.values {
+            wrkr_core::MetricValue::Rate {
+                hits: h, total: t, ..
+            } => (hits + h, total + t),
+            _ => (hits, total),
+        });
+    assert_eq!((hits, total), (2, 3));
+    Ok(())
+}
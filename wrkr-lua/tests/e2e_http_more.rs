@@ -42,6 +42,59 @@ async fn e2e_http_more_scripts_run() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn e2e_http_error_statuses_reach_the_script_but_are_not_transport_failures() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "http_status_codes.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+    server.shutdown().await;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(scenario.requests_total, 3);
+    // `request_errors_total` only counts transport errors; status codes are judged by checks.
+    assert_eq!(scenario.failed_requests_total, 0);
+    assert_eq!(scenario.checks_failed_total, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_http_latency_reflects_server_delay() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "http_delay.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+    server.shutdown().await;
+
+    // Latencies are in microseconds; every request sleeps 20..=30ms on the server. The upper
+    // bound leaves room for loopback and scheduling overhead on a busy CI machine.
+    let latency = summary.scenarios[0]
+        .latency
+        .as_ref()
+        .unwrap_or_else(|| panic!("missing latency summary"));
+    assert_eq!(latency.count, 10);
+    let min = latency.min.unwrap_or_else(|| panic!("missing min latency"));
+    let p50 = latency.p50.unwrap_or_else(|| panic!("missing p50 latency"));
+    let p95 = latency.p95.unwrap_or_else(|| panic!("missing p95 latency"));
+    let max = latency.max.unwrap_or_else(|| panic!("missing max latency"));
+    assert!(min >= 20_000.0, "min={min}");
+    assert!(max < 150_000.0, "max={max}");
+    assert!(
+        min <= p50 && p50 <= p95 && p95 <= max,
+        "min={min} p50={p50} p95={p95} max={max}"
+    );
+    assert!(p50 < 80_000.0, "p50={p50}");
+    Ok(())
+}
+
 #[tokio::test]
 async fn e2e_http_ramping_scripts_run() -> Result<()> {
     let server = TestServer::start().await?;
@@ -84,3 +137,92 @@ async fn e2e_http_shared_iterations_script_runs() -> Result<()> {
     assert!(seen > 0, "expected server to see requests");
     Ok(())
 }
+
+#[tokio::test]
+async fn e2e_http_batch_respects_max_concurrent() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "http_batch.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    let max_in_flight = server.stats().slow_max_in_flight();
+    server.shutdown().await;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    assert_eq!(scenario.requests_total, 9);
+    assert!(
+        (1..=2).contains(&max_in_flight),
+        "max_concurrent=2 but the server saw {max_in_flight} slow requests at once"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_http_stream_delivers_chunks_and_stops_early() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "http_stream.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    server.shutdown().await;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    assert_eq!(scenario.requests_total, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_group_steps_record_duration_and_pass_rate_per_step() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let summary = support::run_script(
+        "group_steps.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+    server.shutdown().await;
+
+    // Steps outside a group carry no `group` tag.
+    let series = |name: &str, step: &str, group: Option<&str>| {
+        summary
+            .metrics
+            .iter()
+            .find(|m| m.name == name && m.tag("step") == Some(step) && m.tag("group") == group)
+            .map(|m| m.values.clone())
+            .unwrap_or_else(|| panic!("missing {name} series for step {step} in {group:?}"))
+    };
+    let rate = |step: &str, group: Option<&str>| match series("step_checks", step, group) {
+        wrkr_core::MetricValue::Rate { hits, total, .. } => (hits, total),
+        other => panic!("step_checks is not a rate: {other:?}"),
+    };
+
+    assert_eq!(rate("fetch", None), (2, 2));
+    assert_eq!(rate("reject", None), (0, 2));
+    assert_eq!(rate("raise", None), (0, 2));
+    assert_eq!(rate("fetch", Some("flow")), (2, 2));
+
+    match series("step_duration", "fetch", None) {
+        wrkr_core::MetricValue::Histogram(h) => assert_eq!(h.count, 2),
+        other => panic!("step_duration is not a histogram: {other:?}"),
+    }
+    Ok(())
+}
@@ -13,3 +13,195 @@ async fn e2e_shared_store_script_runs() -> Result<()> {
     support::run_script("shared_store.lua", &[], wrkr_core::RunConfig::default()).await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn e2e_warmup_iterations_are_not_measured() -> Result<()> {
+    let summary = support::run_script("warmup.lua", &[], wrkr_core::RunConfig::default()).await?;
+
+    // 2 VUs x 2 warm-up iterations ran, but only the 3 measured iterations remain.
+    let calls: u64 = summary
+        .metrics
+        .iter()
+        .filter(|m| m.name == "warmup_probe_calls")
+        .map(|m| match m.values {
+            wrkr_core::MetricValue::Counter(v) => v,
+            _ => 0,
+        })
+        .sum();
+    assert_eq!(calls, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_http_transport_failure_exposes_error_kind() -> Result<()> {
+    // Reserve a port, then close it so the connection is refused.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let closed_url = format!("http://{}", listener.local_addr()?);
+    drop(listener);
+
+    let summary = support::run_script(
+        "http_error_kind.lua",
+        &[("CLOSED_URL", closed_url)],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(scenario.failed_requests_total, 1);
+    assert_eq!(scenario.checks_failed_total, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_sleep_think_time_distributions() -> Result<()> {
+    let summary =
+        support::run_script("sleep_think.lua", &[], wrkr_core::RunConfig::default()).await?;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    assert_eq!(scenario.iterations_total, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_iteration_active_duration_excludes_sleep() -> Result<()> {
+    let summary = support::run_script(
+        "sleep_active_duration.lua",
+        &[],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    // Histogram values are in microseconds; every iteration sleeps 30ms and does nothing else.
+    let max_of = |name: &str| {
+        let series = summary
+            .find_series(name, &[("scenario", "Default")])
+            .unwrap_or_else(|| panic!("missing {name} series"));
+        match &series.values {
+            wrkr_core::MetricValue::Histogram(h) => h.max.unwrap_or_else(|| panic!("empty {name}")),
+            other => panic!("{name} is not a histogram: {other:?}"),
+        }
+    };
+    assert!(max_of("iteration_duration") >= 30_000.0);
+    assert!(max_of("iteration_active_duration") < 20_000.0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_exec_receives_scenario_params() -> Result<()> {
+    let summary =
+        support::run_script("scenario_params.lua", &[], wrkr_core::RunConfig::default()).await?;
+
+    let counter = |scenario: &str, label: &str| {
+        let series = summary
+            .find_series("upload_bytes", &[("scenario", scenario), ("label", label)])
+            .unwrap_or_else(|| panic!("missing upload_bytes series for {scenario}"));
+        match series.values {
+            wrkr_core::MetricValue::Counter(v) => v,
+            ref other => panic!("upload_bytes is not a counter: {other:?}"),
+        }
+    };
+    assert_eq!(counter("small", "none"), 30);
+    assert_eq!(counter("large", "big"), 2000);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_scenario_summary_records_active_window() -> Result<()> {
+    let before = std::time::SystemTime::now();
+    let summary =
+        support::run_script("sleep_think.lua", &[], wrkr_core::RunConfig::default()).await?;
+    let after = std::time::SystemTime::now();
+
+    let scenario = &summary.scenarios[0];
+    let (Some(started), Some(ended)) = (scenario.started_at, scenario.ended_at) else {
+        panic!("missing scenario timestamps: {scenario:?}");
+    };
+    assert!(before <= started && started <= ended && ended <= after);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_fs_include_runs_fragments_relative_to_script() -> Result<()> {
+    let summary = support::run_script("include.lua", &[], wrkr_core::RunConfig::default()).await?;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    assert_eq!(scenario.iterations_total, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_vu_state_persists_per_vu() -> Result<()> {
+    let summary = support::run_script("vu_state.lua", &[], wrkr_core::RunConfig::default()).await?;
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    assert_eq!(scenario.iterations_total, 6);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_vu_sleep_stops_at_the_scenario_end() -> Result<()> {
+    let started = std::time::Instant::now();
+    let summary = support::run_script("vu_sleep.lua", &[], wrkr_core::RunConfig::default()).await?;
+    let elapsed = started.elapsed();
+
+    let scenario = &summary.scenarios[0];
+    assert_eq!(
+        scenario.checks_failed_total, 0,
+        "{:?}",
+        scenario.checks_failed
+    );
+    assert_eq!(scenario.iterations_total, 1);
+    assert!(
+        elapsed < std::time::Duration::from_secs(5),
+        "a 10s sleep in a 300ms run took {elapsed:?}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_env_url_joins_base_url_and_rejects_a_missing_one() -> Result<()> {
+    let joined = support::run_script(
+        "env_url.lua",
+        &[
+            ("BASE_URL", "http://api.test/v1/".to_string()),
+            ("EXPECTED_URL", "http://api.test/v1/users/1".to_string()),
+        ],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+    assert_eq!(joined.scenarios[0].checks_failed_total, 0);
+
+    let missing = support::run_script(
+        "env_url.lua",
+        &[("BASE_URL", String::new())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+    assert_eq!(missing.scenarios[0].checks_failed_total, 0);
+
+    Ok(())
+}
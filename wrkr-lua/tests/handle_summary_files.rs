@@ -70,3 +70,63 @@ async fn handle_summary_checks_by_name_produces_json_file() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn handle_summary_trend_exposes_arbitrary_percentiles() -> Result<()> {
+    let summary = support::run_script(
+        "handle_summary_percentile.lua",
+        &[],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    let script = support::load_test_script("handle_summary_percentile.lua")?;
+    let env = support::env_with(&[]);
+    let run_ctx = support::run_ctx_for_script(&script, env);
+
+    let out = wrkr_lua::run_handle_summary(&run_ctx, &summary)?;
+    let Some(out) = out else {
+        panic!("expected HandleSummary outputs");
+    };
+
+    let stdout = out.stdout.unwrap_or_default();
+    let p9999: f64 = stdout
+        .trim()
+        .parse()
+        .unwrap_or_else(|err| panic!("expected numeric p99.99, got {stdout:?}: {err}"));
+    assert!((9_990.0..=10_010.0).contains(&p9999), "{p9999}");
+
+    assert!(
+        out.files.iter().any(|(name, _)| name == "summary.json"),
+        "expected summary.json output"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn teardown_and_handle_summary_metrics_reach_the_summary() -> Result<()> {
+    let script = support::load_test_script("teardown_metrics.lua")?;
+    let env = support::env_with(&[]);
+    let run_ctx = support::run_ctx_for_script(&script, env);
+
+    let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    let scenarios = wrkr_core::scenarios_from_options(opts, wrkr_core::RunConfig::default())?;
+    let mut summary =
+        wrkr_core::run_scenarios(scenarios, run_ctx.clone(), wrkr_lua::run_vu, None).await?;
+    assert!(summary.series("orders_per_session").next().is_none());
+
+    wrkr_lua::run_teardown(&run_ctx)?;
+    summary.refresh_metrics(&run_ctx.metrics, &run_ctx.thresholds)?;
+
+    let out = wrkr_lua::run_handle_summary(&run_ctx, &summary)?;
+    let Some(out) = out else {
+        panic!("expected HandleSummary outputs");
+    };
+    assert_eq!(out.stdout.as_deref(), Some("5"));
+
+    summary.refresh_metrics(&run_ctx.metrics, &run_ctx.thresholds)?;
+    assert!(summary.series("summaries_written").next().is_some());
+
+    Ok(())
+}
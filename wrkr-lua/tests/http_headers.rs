@@ -17,3 +17,18 @@ async fn http_response_includes_headers_table() -> Result<()> {
     server.shutdown().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn http_on_request_hook_rewrites_outgoing_requests() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    support::run_script(
+        "http_on_request.lua",
+        &[("BASE_URL", server.base_url().to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await?;
+
+    server.shutdown().await;
+    Ok(())
+}
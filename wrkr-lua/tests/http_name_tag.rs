@@ -50,3 +50,74 @@ async fn http_opts_name_is_recorded_as_name_tag() -> Result<()> {
     server.shutdown().await;
     Ok(())
 }
+
+#[tokio::test]
+async fn http_auto_name_templates_ids_in_the_url_path() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let script = support::load_test_script("http_auto_name.lua")?;
+    let env = support::env_with(&[("BASE_URL", server.base_url().to_string())]);
+    let run_ctx = support::run_ctx_for_script(&script, env);
+
+    let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    let scenarios = wrkr_core::scenarios_from_options(opts, wrkr_core::RunConfig::default())?;
+
+    let run_ctx_after = run_ctx.clone();
+    let _summary = wrkr_core::run_scenarios(scenarios, run_ctx, wrkr_lua::run_vu, None).await?;
+
+    let mut names = run_ctx_after
+        .metrics
+        .summarize()
+        .into_iter()
+        .filter(|m| m.name == "requests_total")
+        .filter_map(|m| tags_get(&m.tags, "name").map(str::to_string))
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    assert_eq!(names, vec!["/plaintext/:id", "explicit"]);
+
+    server.shutdown().await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn http_explicit_and_auto_names_have_separate_limits() -> Result<()> {
+    let server = TestServer::start().await?;
+
+    let script = support::load_test_script("http_name_budgets.lua")?;
+    let env = support::env_with(&[("BASE_URL", server.base_url().to_string())]);
+    let run_ctx = support::run_ctx_for_script(&script, env);
+
+    let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    let scenarios = wrkr_core::scenarios_from_options(opts, wrkr_core::RunConfig::default())?;
+
+    let run_ctx_after = run_ctx.clone();
+    let _summary = wrkr_core::run_scenarios(scenarios, run_ctx, wrkr_lua::run_vu, None).await?;
+
+    let requests = run_ctx_after
+        .metrics
+        .summarize()
+        .into_iter()
+        .filter(|m| m.name == "requests_total")
+        .filter_map(|m| {
+            let name = tags_get(&m.tags, "name")?.to_string();
+            match m.values {
+                wrkr_core::MetricValue::Counter(v) => Some((name, v)),
+                _ => None,
+            }
+        })
+        .collect::<std::collections::BTreeMap<_, _>>();
+
+    let limit = wrkr_core::CardinalityGuard::DEFAULT_LIMIT;
+    assert!(requests.contains_key(&format!("page-{limit}")));
+    assert!(!requests.contains_key(&format!("page-{}", limit + 1)));
+    // Tagged names past the limit fall back to the URL template, as does the unnamed request.
+    assert_eq!(
+        requests.get("/plaintext/:id").copied(),
+        Some((150 - limit) as u64 + 1)
+    );
+    assert_eq!(requests.len(), limit + 1);
+
+    server.shutdown().await;
+    Ok(())
+}
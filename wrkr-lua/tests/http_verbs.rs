@@ -33,6 +33,8 @@ async fn http_verbs_record_method_and_respect_name_tags_and_group() -> Result<()
         ("PATCH", "PATCH /echo"),
         ("DELETE", "DELETE /echo"),
         ("HEAD", "HEAD /echo"),
+        ("OPTIONS", "OPTIONS /echo"),
+        ("PROPFIND", "PROPFIND /echo"),
     ] {
         series
             .iter()
@@ -0,0 +1,15 @@
+mod support;
+
+use wrkr_lua::Result;
+
+#[tokio::test]
+async fn json_int64_string_repr_round_trips_large_integers() -> Result<()> {
+    let summary =
+        support::run_script("json_int64.lua", &[], wrkr_core::RunConfig::default()).await?;
+
+    let s = &summary.scenarios[0];
+    assert_eq!(s.iterations_total, 1);
+    assert_eq!(s.checks_failed_total, 0, "{:?}", s.checks_failed);
+
+    Ok(())
+}
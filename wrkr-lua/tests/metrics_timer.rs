@@ -0,0 +1,34 @@
+mod support;
+
+use wrkr_lua::Result;
+
+#[tokio::test]
+async fn timer_observations_are_recorded_as_trends() -> Result<()> {
+    let script = support::load_test_script("metrics_timer.lua")?;
+    let env = support::env_with(&[]);
+    let run_ctx = support::run_ctx_for_script(&script, env);
+
+    let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    let scenarios = wrkr_core::scenarios_from_options(opts, wrkr_core::RunConfig::default())?;
+
+    let run_ctx_after = run_ctx.clone();
+    let summary = wrkr_core::run_scenarios(scenarios, run_ctx, wrkr_lua::run_vu, None).await?;
+    assert_eq!(summary.scenarios[0].iterations_total, 3);
+
+    let series = run_ctx_after.metrics.summarize();
+    let timed = series
+        .iter()
+        .find(|m| {
+            m.name == "sign_duration" && m.tags.iter().any(|(k, v)| k == "step" && v == "sign")
+        })
+        .unwrap_or_else(|| panic!("missing sign_duration series"));
+
+    assert_eq!(timed.kind, wrkr_core::MetricKind::Histogram);
+    let wrkr_core::MetricValue::Histogram(h) = &timed.values else {
+        panic!("expected histogram values, got {:?}", timed.values);
+    };
+    assert_eq!(h.count, 3);
+    assert!(h.min.is_some_and(|min| min >= 10_000.0), "{h:?}");
+
+    Ok(())
+}
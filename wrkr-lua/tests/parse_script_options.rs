@@ -59,6 +59,58 @@ fn parse_script_options_thresholds_selector_key() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn parse_script_options_thresholds_when_conditions() -> Result<()> {
+    let script = support::load_test_script("thresholds_when.lua")?;
+    let env = support::env_with(&[]);
+    let run_ctx = support::run_ctx_for_script(&script, env);
+
+    let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    let mut sets = opts
+        .thresholds
+        .iter()
+        .map(|t| (t.metric.as_str(), t.expressions[0].as_str(), t.when.clone()))
+        .collect::<Vec<_>>();
+    sets.sort();
+
+    let when = |k: &str, v: &str| vec![(k.to_string(), v.to_string())];
+    assert_eq!(
+        sets,
+        vec![
+            ("checks", "rate>0.99", when("scenario", "main")),
+            ("request_latency", "p(95)<200000", when("env", "prod")),
+            ("request_latency", "p(95)<500000", when("env", "staging")),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_script_options_thresholds_no_data_policy() -> Result<()> {
+    let script = support::load_test_script("thresholds_no_data.lua")?;
+    let env = support::env_with(&[]);
+    let run_ctx = support::run_ctx_for_script(&script, env);
+
+    let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    let mut sets = opts
+        .thresholds
+        .iter()
+        .map(|t| (t.metric.as_str(), t.no_data))
+        .collect::<Vec<_>>();
+    sets.sort_by_key(|(metric, _)| *metric);
+
+    assert_eq!(
+        sets,
+        vec![
+            ("grpc_req_duration", wrkr_core::ThresholdNoData::Pass),
+            ("request_latency", wrkr_core::ThresholdNoData::Fail),
+        ]
+    );
+
+    Ok(())
+}
+
 #[test]
 fn parse_script_options_accepts_camel_and_snake_case_aliases() -> Result<()> {
     let script = support::load_test_script("options_aliases_ramping_vus.lua")?;
@@ -93,6 +145,62 @@ fn parse_script_options_accepts_camel_and_snake_case_aliases() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn parse_script_options_reads_stage_shapes() -> Result<()> {
+    let script = support::load_test_script("options_stage_shapes.lua")?;
+    let run_ctx = support::run_ctx_for_script(&script, support::env_with(&[]));
+
+    let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    let shapes = opts.scenarios[0]
+        .stages
+        .iter()
+        .map(|s| s.shape)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        shapes,
+        [
+            wrkr_core::StageShape::Step,
+            wrkr_core::StageShape::EaseInOut,
+            wrkr_core::StageShape::Linear,
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn parse_script_options_scenario_think_time() -> Result<()> {
+    let script = support::load_test_script("options_think_time.lua")?;
+    let run_ctx = support::run_ctx_for_script(&script, support::env_with(&[]));
+
+    let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    let think_time = |name: &str| {
+        opts.scenarios
+            .iter()
+            .find(|s| s.name == name)
+            .and_then(|s| s.think_time)
+    };
+
+    assert_eq!(
+        think_time("browse"),
+        Some(wrkr_core::ThinkTimeSpec {
+            dist: wrkr_core::ThinkTime::LogNormal {
+                mean: Duration::from_secs(3),
+                stddev: Duration::from_secs(1),
+            },
+            min: None,
+            max: Some(Duration::from_secs(10)),
+        })
+    );
+    assert_eq!(
+        think_time("api"),
+        Some(wrkr_core::ThinkTimeSpec::fixed(Duration::from_millis(250)))
+    );
+    assert_eq!(think_time("firehose"), None);
+
+    Ok(())
+}
+
 #[test]
 fn parse_script_options_arrival_rate_aliases() -> Result<()> {
     let script = support::load_test_script("options_aliases_arrival_rate.lua")?;
@@ -160,3 +268,29 @@ fn parse_script_options_scenario_tags() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn scenario_matrix_expands_into_tagged_scenarios() -> Result<()> {
+    let script = support::load_test_script("options_matrix.lua")?;
+    let env = support::env_with(&[]);
+    let run_ctx = support::run_ctx_for_script(&script, env);
+
+    let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    let names: Vec<&str> = opts.scenarios.iter().map(|s| s.name.as_str()).collect();
+    // Parameters expand in key order: `parallel`, then `size`.
+    assert_eq!(
+        names,
+        ["upload-1-1k", "upload-1-10k", "upload-8-1k", "upload-8-10k"]
+    );
+    assert!(
+        opts.scenarios[3]
+            .tags
+            .contains(&("size".to_string(), "10k".to_string()))
+    );
+
+    let scenarios = wrkr_core::scenarios_from_options(opts, wrkr_core::RunConfig::default())?;
+    let summary = wrkr_core::run_scenarios(scenarios, run_ctx, wrkr_lua::run_vu, None).await?;
+    assert!(summary.scenarios.iter().all(|s| s.iterations_total == 1));
+
+    Ok(())
+}
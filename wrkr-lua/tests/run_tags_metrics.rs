@@ -0,0 +1,38 @@
+mod support;
+
+use wrkr_lua::Result;
+
+fn tags_get<'a>(tags: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    tags.iter()
+        .find_map(|(k, v)| (k == key).then_some(v.as_str()))
+}
+
+#[tokio::test]
+async fn run_tags_are_attached_to_every_series() -> Result<()> {
+    let script = support::load_test_script("run_tags_metrics.lua")?;
+    let env = support::env_with(&[]);
+    let run_ctx = support::run_ctx_for_script(&script, env);
+
+    let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    let cfg = wrkr_core::RunConfig {
+        tags: vec![("region".to_string(), "us-east".to_string())],
+        ..Default::default()
+    };
+    let scenarios = wrkr_core::scenarios_from_options(opts, cfg)?;
+
+    let run_ctx_after = run_ctx.clone();
+    let _summary = wrkr_core::run_scenarios(scenarios, run_ctx, wrkr_lua::run_vu, None).await?;
+
+    let series = run_ctx_after.metrics.summarize();
+    for name in ["checks", "custom_counter_run_tags", "iterations_total"] {
+        let m = series
+            .iter()
+            .find(|m| m.name == name)
+            .unwrap_or_else(|| panic!("missing {name} series"));
+        // `--tag` overrides `Options.tags` with the same key.
+        assert_eq!(tags_get(&m.tags, "region"), Some("us-east"), "{name}");
+        assert_eq!(tags_get(&m.tags, "team"), Some("checkout"), "{name}");
+    }
+
+    Ok(())
+}
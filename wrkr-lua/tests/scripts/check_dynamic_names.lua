@@ -0,0 +1,20 @@
+Options = { vus = 1, iterations = 1 }
+
+function Default()
+  local check = require("wrkr/check")
+
+  check({ status = 204 }, {
+    ["status class"] = function(r)
+      return r.status < 300, ("status is %dxx"):format(math.floor(r.status / 100))
+    end,
+  })
+
+  -- Unbounded names: only the first ones get their own series, the rest use the static key.
+  for i = 1, 150 do
+    check(i, {
+      ["unique id"] = function(v)
+        return true, "id " .. v
+      end,
+    })
+  end
+end
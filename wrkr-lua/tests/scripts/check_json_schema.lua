@@ -0,0 +1,24 @@
+local check = require("wrkr/check")
+
+Options = { vus = 1, iterations = 1 }
+
+local schema = check.compileSchema({
+  type = "object",
+  required = { "id", "name" },
+  properties = {
+    id = { type = "integer", minimum = 1 },
+    name = { type = "string" },
+  },
+})
+
+function Default()
+  local ok, errors = check.jsonSchema('{"id": 7, "name": "bob"}', schema, "user conforms")
+  check(errors, {
+    ["conforming body has no errors"] = function(e) return ok and #e == 0 end,
+  })
+
+  ok, errors = check.jsonSchema({ id = 0 }, schema, "user is invalid")
+  check(errors, {
+    ["invalid user has two errors"] = function(e) return not ok and #e == 2 end,
+  })
+end
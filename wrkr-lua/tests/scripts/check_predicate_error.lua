@@ -0,0 +1,18 @@
+Options = { vus = 1, iterations = 1 }
+
+function Default()
+  local check = require("wrkr/check")
+
+  -- Raising inside a predicate only fails that check.
+  check({ status = 500 }, {
+    ["body has id"] = function(r)
+      return r.body.id ~= nil
+    end,
+  })
+
+  check({ status = 200 }, {
+    ["status class"] = function(r)
+      return true, { r.status }
+    end,
+  })
+end
@@ -0,0 +1,17 @@
+Options = { iterations = 1 }
+
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+
+function Default()
+  local ok, res = pcall(env.url, "/users/1")
+  check(res, {
+    ["joined or rejected"] = function(r)
+      if env.BASE_URL ~= "" then
+        return ok and r == env.EXPECTED_URL
+      end
+      return not ok and string.find(tostring(r), "BASE_URL", 1, true) ~= nil
+    end,
+    ["url is not an env var"] = function() return rawget(env, "url") == nil end,
+  })
+end
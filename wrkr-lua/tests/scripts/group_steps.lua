@@ -0,0 +1,31 @@
+Options = { vus = 1, iterations = 2 }
+
+local group = require("wrkr/group")
+local http = require("wrkr/http")
+local env = require("wrkr/env")
+
+function Default()
+  local res = group.step("fetch", function()
+    return http.get(env.BASE_URL .. "/plaintext")
+  end)
+  if res.status ~= 200 then
+    error("step did not pass its return value through: " .. tostring(res.status))
+  end
+
+  group.step("reject", function()
+    return false
+  end)
+
+  local ok = pcall(group.step, "raise", function()
+    error("boom")
+  end)
+  if ok then
+    error("expected the step error to be re-raised")
+  end
+
+  group.group("flow", function()
+    group.step("fetch", function()
+      return true
+    end)
+  end)
+end
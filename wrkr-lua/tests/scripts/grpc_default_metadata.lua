@@ -0,0 +1,39 @@
+Options = { iterations = 1 }
+
+local grpc = require("wrkr/grpc")
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+
+local client = grpc.Client.new()
+client:load({ "protos" }, "protos/analytics.proto")
+
+local connected = false
+
+function Default()
+  if not connected then
+    local ok, err = client:connect(env.BASE_URL, {
+      timeout = "2s",
+      metadata = { ["x-client-id"] = "from-connect" },
+    })
+    if not ok then error(err) end
+    connected = true
+  end
+
+  local defaulted = client:invoke("AnalyticsService/AggregateOrders", { orders = {} })
+  check(defaulted, {
+    ["default metadata is sent"] = function(r)
+      return r.ok and r.response.echoed_client_id == "from-connect"
+    end,
+  })
+
+  local overridden = client:invoke(
+    "AnalyticsService/AggregateOrders",
+    { orders = {} },
+    { metadata = { ["x-client-id"] = "per-call" } }
+  )
+  check(overridden, {
+    ["per-call metadata wins"] = function(r)
+      return r.ok and r.response.echoed_client_id == "per-call"
+    end,
+  })
+end
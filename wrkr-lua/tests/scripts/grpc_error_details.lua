@@ -0,0 +1,35 @@
+Options = { iterations = 1 }
+
+local grpc = require("wrkr/grpc")
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+
+local client = grpc.Client.new()
+client:load({ "protos" }, "protos/echo.proto")
+
+local connected = false
+
+function Default()
+  if not connected then
+    local ok, err = client:connect(env.BASE_URL, { timeout = "2s" })
+    if not ok then error(err) end
+    connected = true
+  end
+
+  local failed = client:invoke("wrkr.test.EchoService/Echo", { message = "fail-with-details" })
+  local plain = client:invoke("wrkr.test.EchoService/Echo", { message = "ping" })
+
+  check(failed, {
+    ["status is INVALID_ARGUMENT"] = function(r) return r.ok == false and r.status == 3 end,
+    ["details decoded"] = function(r)
+      local d = r.error_details
+      if d == nil or d.code ~= 3 or #d.details ~= 1 then return false end
+      local bad = d.details[1]
+      return bad.type == "google.rpc.BadRequest"
+        and bad.field_violations[1].field == "message"
+    end,
+  })
+  check(plain, {
+    ["no details on success"] = function(r) return r.ok and r.error_details == nil end,
+  })
+end
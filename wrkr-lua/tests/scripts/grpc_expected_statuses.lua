@@ -0,0 +1,29 @@
+Options = { iterations = 1 }
+
+local grpc = require("wrkr/grpc")
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+
+local client = grpc.Client.new()
+client:load({ "protos" }, "protos/echo.proto")
+
+function Default()
+  local ok, err = client:connect(env.BASE_URL, { timeout = "2s" })
+  if not ok then error(err) end
+
+  local method = "wrkr.test.EchoService/Echo"
+  local req = { message = "fail-with-details" }
+  local unexpected = client:invoke(method, req)
+  local by_name = client:invoke(method, req, { expected_statuses = { "INVALID_ARGUMENT" } })
+  local by_code = client:invoke(method, req, { expected_statuses = { 5, 3 } })
+
+  check(unexpected, {
+    ["non-OK is not ok by default"] = function(r) return r.ok == false and r.error ~= nil end,
+  })
+  check(by_name, {
+    ["expected by name"] = function(r) return r.ok and r.status == 3 and r.error == nil end,
+  })
+  check(by_code, {
+    ["expected by code"] = function(r) return r.ok and r.message == "invalid echo request" end,
+  })
+end
@@ -0,0 +1,27 @@
+Options = { iterations = 1 }
+
+local grpc = require("wrkr/grpc")
+local env = require("wrkr/env")
+
+local client = grpc.Client.new()
+client:load({ "protos" }, "protos/echo.proto")
+
+function Default()
+  local ok, err = client:connect(env.BASE_URL, { timeout = "2s" })
+  if not ok then error(err) end
+
+  local method = "wrkr.test.EchoService/Echo"
+
+  -- No name: the method name is used.
+  client:invoke(method, { message = "default" })
+  -- Static name.
+  client:invoke(method, { message = "static" }, { name = "Echo" })
+  -- Dynamic names: past the run-wide limit they fall back to the method name.
+  for i = 1, 150 do
+    client:invoke(method, { message = "dynamic" }, { name = "echo-" .. i })
+  end
+  -- `tags.name` counts against the same limit.
+  for i = 1, 10 do
+    client:invoke(method, { message = "tagged" }, { tags = { name = "tagged-" .. i } })
+  end
+end
@@ -0,0 +1,42 @@
+Options = { iterations = 1 }
+
+local grpc = require("wrkr/grpc")
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+
+local client = grpc.Client.new()
+client:load({ "protos" }, "protos/echo.proto")
+
+function Default()
+  local ok, err = client:connect(env.BASE_URL, { timeout = "2s" })
+  if not ok then error(err) end
+
+  local method = "wrkr.test.EchoService/EchoStream"
+  local complete = client:server_stream(method, { message = "hi", count = 3, interval_ms = 5 })
+  local stalled = client:serverStream(
+    method,
+    { message = "hi", count = 2, interval_ms = 5, stall = true },
+    { timeout = "30s", message_timeout = "100ms" }
+  )
+  local too_long = client:server_stream(
+    method,
+    { message = "hi", count = 100, interval_ms = 20 },
+    { timeout = "150ms", message_timeout = "10s" }
+  )
+
+  check(complete, {
+    ["every message"] = function(r)
+      return r.ok and #r.response == 3 and r.response[3].message == "hi"
+    end,
+  })
+  check(stalled, {
+    ["stall hits the message timeout"] = function(r)
+      return not r.ok and r.error_kind == "message_timeout" and #r.response == 2
+    end,
+  })
+  check(too_long, {
+    ["long stream hits the call timeout"] = function(r)
+      return not r.ok and r.status == 4 and r.error_kind == nil
+    end,
+  })
+end
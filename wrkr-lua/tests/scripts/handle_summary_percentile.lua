@@ -0,0 +1,30 @@
+Options = {
+  vus = 1,
+  iterations = 1,
+}
+
+local json = require("wrkr/json")
+local metrics = require("wrkr/metrics")
+
+local trend = metrics.Trend("custom_trend_percentile")
+
+function Default()
+  for i = 1, 10000 do
+    trend:add(i)
+  end
+end
+
+function HandleSummary(summary)
+  local p9999 = nil
+  for _, m in ipairs(summary.metrics) do
+    if m.name == "custom_trend_percentile" and m.type == "trend" then
+      p9999 = m.values:percentile(99.99)
+    end
+  end
+
+  return {
+    stdout = tostring(p9999),
+    -- Methods live on metatables, so the summary must still be JSON-encodable.
+    ["summary.json"] = json.encode(summary),
+  }
+end
@@ -0,0 +1,15 @@
+local env = require("wrkr/env")
+local http = require("wrkr/http")
+
+http.auto_name(true)
+
+Options = {
+  vus = 1,
+  iterations = 1,
+}
+
+function Default()
+  http.get(env.BASE_URL .. "/plaintext/42?page=1")
+  http.get(env.BASE_URL .. "/plaintext/3f2b8c1e-9d4a-4b7e-8f00-1234567890ab")
+  http.get(env.BASE_URL .. "/plaintext", { name = "explicit" })
+end
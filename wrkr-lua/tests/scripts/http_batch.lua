@@ -0,0 +1,42 @@
+Options = { iterations = 1 }
+
+local env = require("wrkr/env")
+local http = require("wrkr/http")
+local check = require("wrkr/check")
+
+function Default()
+  local base = env.BASE_URL
+
+  local slow = {}
+  for i = 1, 6 do
+    slow[i] = { url = base .. "/slow" }
+  end
+  local capped = http.batch(slow, { max_concurrent = 2 })
+
+  local mixed = http.batch({
+    { method = "POST", url = base .. "/echo", body = "first" },
+    { url = base .. "/hello" },
+    { method = "PUT", url = base .. "/echo", body = "third", opts = { name = "batch put" } },
+  })
+
+  local bad_ok = pcall(http.batch, slow, { max_concurrent = 0 })
+  local missing_url_ok = pcall(http.batch, { { method = "GET" } })
+
+  check(true, {
+    ["capped batch returns every response"] = function()
+      if #capped ~= 6 then return false end
+      for _, res in ipairs(capped) do
+        if res.status ~= 200 then return false end
+      end
+      return true
+    end,
+    ["responses keep request order"] = function()
+      return #mixed == 3
+        and mixed[1].body == "first"
+        and mixed[2].status == 200
+        and mixed[3].body == "third"
+    end,
+    ["max_concurrent must be positive"] = function() return not bad_ok end,
+    ["url is required"] = function() return not missing_url_ok end,
+  })
+end
@@ -0,0 +1,21 @@
+Options = { vus = 1, iterations = 3, httpCache = "vu" }
+
+local http = require("wrkr/http")
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+
+local calls = 0
+
+function Default()
+  calls = calls + 1
+  -- `/headers` sets its query parameters as response headers.
+  local res = http.get(env.BASE_URL .. "/headers?cache-control=max-age%3D60")
+  check(res, {
+    ["status is 200"] = function(r) return r.status == 200 end,
+    ["only the first response comes from the network"] = function(r)
+      return (r.cached == true) == (calls > 1)
+    end,
+  })
+  -- Other methods always reach the server.
+  http.post(env.BASE_URL .. "/echo", "ping")
+end
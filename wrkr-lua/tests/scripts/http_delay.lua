@@ -0,0 +1,8 @@
+Options = { iterations = 10 }
+
+local http = require("wrkr/http")
+local env = require("wrkr/env")
+
+function Default()
+  http.get(env.BASE_URL .. "/delay?ms=20&jitter=10")
+end
@@ -0,0 +1,16 @@
+Options = { iterations = 2, discardResponseBodies = true }
+
+local http = require("wrkr/http")
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+
+local payload = string.rep("x", 4096)
+
+function Default()
+  local res = http.post(env.BASE_URL .. "/echo", payload)
+  check(res, {
+    ["status is 200"] = function(r) return r.status == 200 end,
+    ["body discarded"] = function(r) return r.body == "" end,
+    ["body bytes counted"] = function(r) return r.bytes_received > #payload end,
+  })
+end
@@ -0,0 +1,14 @@
+Options = { iterations = 1 }
+
+local http = require("wrkr/http")
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+
+function Default()
+  local res = http.get(env.CLOSED_URL .. "/")
+  check(res, {
+    ["error_kind is connect"] = function(r)
+      return r.status == 0 and r.error ~= nil and r.error_kind == "connect"
+    end,
+  })
+end
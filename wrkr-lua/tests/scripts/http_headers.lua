@@ -20,4 +20,24 @@ function Default()
   if ct == nil then
     error("expected content-type header")
   end
+
+  if res.bytes_sent <= 0 or res.bytes_received <= #res.body then
+    error("unexpected byte counts: " .. res.bytes_sent .. "/" .. res.bytes_received)
+  end
+
+  local json = require("wrkr/json")
+  local echoed = http.get(env.BASE_URL .. "/headers?x-reply=pong", {
+    headers = { ["x-ping"] = "ping" },
+  })
+  if echoed.status ~= 200 then
+    error("unexpected /headers status: " .. tostring(echoed.status))
+  end
+
+  local received = json.decode(echoed.body)
+  if received["x-ping"] ~= "ping" then
+    error("request header was not echoed: " .. echoed.body)
+  end
+  if echoed.headers["x-reply"] ~= "pong" then
+    error("expected x-reply response header")
+  end
 end
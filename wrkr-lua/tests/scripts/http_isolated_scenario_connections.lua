@@ -0,0 +1,23 @@
+Options = {
+  isolateScenarioConnections = true,
+  scenarios = {
+    busy = { vus = 1, iterations = 1, exec = "Busy" },
+    quiet = { vus = 1, iterations = 1, exec = "Quiet" },
+  },
+}
+
+local http = require("wrkr/http")
+local sleep = require("wrkr/sleep")
+local env = require("wrkr/env")
+
+function Busy()
+  http.get(env.BASE_URL .. "/hello")
+  -- The connection sits idle in `busy`'s pool while `quiet` sends its request.
+  sleep.fixed("150ms")
+  http.get(env.BASE_URL .. "/hello")
+end
+
+function Quiet()
+  sleep.fixed("50ms")
+  http.get(env.BASE_URL .. "/hello")
+end
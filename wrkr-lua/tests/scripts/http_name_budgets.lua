@@ -0,0 +1,18 @@
+local env = require("wrkr/env")
+local http = require("wrkr/http")
+
+http.auto_name(true)
+
+Options = {
+  vus = 1,
+  iterations = 1,
+}
+
+function Default()
+  -- Explicit names (here via `tags.name`) fill their own limit...
+  for i = 1, 150 do
+    http.get(env.BASE_URL .. "/plaintext/" .. i, { tags = { name = "page-" .. i } })
+  end
+  -- ...and URL templates still get theirs.
+  http.get(env.BASE_URL .. "/plaintext/42")
+end
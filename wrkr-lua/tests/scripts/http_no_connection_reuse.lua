@@ -0,0 +1,11 @@
+Options = { vus = 1, iterations = 2, noConnectionReuse = true }
+
+local http = require("wrkr/http")
+local sleep = require("wrkr/sleep")
+local env = require("wrkr/env")
+
+function Default()
+  http.get(env.BASE_URL .. "/hello")
+  sleep.fixed("50ms")
+  http.get(env.BASE_URL .. "/hello")
+end
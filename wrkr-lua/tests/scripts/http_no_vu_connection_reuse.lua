@@ -0,0 +1,12 @@
+Options = { vus = 1, iterations = 3, noVUConnectionReuse = true }
+
+local http = require("wrkr/http")
+local sleep = require("wrkr/sleep")
+local env = require("wrkr/env")
+
+function Default()
+  http.get(env.BASE_URL .. "/hello")
+  -- Let the pool take the connection back before the second request.
+  sleep.fixed("50ms")
+  http.get(env.BASE_URL .. "/hello")
+end
@@ -0,0 +1,44 @@
+Options = {
+  vus = 1,
+  iterations = 1,
+}
+
+local env = require("wrkr/env")
+local http = require("wrkr/http")
+local json = require("wrkr/json")
+
+-- Sign every request: the signature covers the method, URL and body actually sent.
+http.on_request(function(req)
+  req.headers["x-signature"] = req.method .. " " .. req.url .. " " .. #req.body
+  -- Headers are lists of values, so a hook can add one without replacing the others.
+  local accept = req.headers["accept"] or {}
+  table.insert(accept, "application/json")
+  req.headers["accept"] = accept
+end)
+
+function Default()
+  local echoed = http.get(env.BASE_URL .. "/headers", { headers = { accept = "text/plain" } })
+  local received = json.decode(echoed.body)
+  local want = "GET " .. env.BASE_URL .. "/headers 0"
+  if received["x-signature"] ~= want then
+    error("expected x-signature " .. want .. ", got " .. tostring(received["x-signature"]))
+  end
+  if received["accept"] ~= "text/plain, application/json" then
+    error("expected both accept values, got " .. tostring(received["accept"]))
+  end
+
+  -- A hook may return a new table instead, e.g. to rewrite the body.
+  http.on_request(function(req)
+    return { method = req.method, url = req.url, headers = req.headers, body = req.body .. "!" }
+  end)
+  local res = http.post(env.BASE_URL .. "/echo", "signed")
+  if res.body ~= "signed!" then
+    error("expected the rewritten body, got " .. res.body)
+  end
+
+  http.on_request(nil)
+  res = http.post(env.BASE_URL .. "/echo", "plain")
+  if res.body ~= "plain" then
+    error("expected the hook to be removed, got " .. res.body)
+  end
+end
@@ -0,0 +1,15 @@
+Options = { iterations = 1 }
+
+local http = require("wrkr/http")
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+
+function Default()
+  local ok = http.get(env.BASE_URL .. "/status/200")
+  local unavailable = http.get(env.BASE_URL .. "/status/503")
+  local not_found = http.get(env.BASE_URL .. "/status/404")
+
+  check(ok, { ["status is 200"] = function(r) return r.status == 200 end })
+  check(unavailable, { ["status is 503"] = function(r) return r.status == 503 end })
+  check(not_found, { ["status is 404"] = function(r) return r.status == 404 end })
+end
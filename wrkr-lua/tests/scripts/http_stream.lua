@@ -0,0 +1,47 @@
+Options = { iterations = 1 }
+
+local env = require("wrkr/env")
+local http = require("wrkr/http")
+local check = require("wrkr/check")
+
+local function count_events(s)
+  local n = 0
+  for _ in s:gmatch("data: %d+\n\n") do
+    n = n + 1
+  end
+  return n
+end
+
+function Default()
+  local base = env.BASE_URL
+
+  local body = ""
+  local finite = http.stream(base .. "/events?n=3&interval_ms=1", nil, function(chunk)
+    body = body .. chunk
+  end)
+
+  -- The endpoint never ends without `n`; stop from the callback after two events.
+  local seen = ""
+  local stopped = http.stream(base .. "/events", { tags = { kind = "sse" } }, function(chunk)
+    seen = seen .. chunk
+    return count_events(seen) < 2
+  end)
+
+  check(true, {
+    ["finite stream delivers every event"] = function()
+      return finite.status == 200 and count_events(body) == 3
+    end,
+    ["byte accounting matches delivered chunks"] = function()
+      return finite.bytes == #body and finite.chunks >= 1
+    end,
+    ["callback can stop an endless stream"] = function()
+      return stopped.status == 200 and count_events(seen) >= 2 and stopped.bytes == #seen
+    end,
+    ["wire bytes include the request and response heads"] = function()
+      return finite.bytes_sent > 0 and finite.bytes_received > finite.bytes
+    end,
+    ["sse content type"] = function()
+      return finite.headers["content-type"] == "text/event-stream"
+    end,
+  })
+end
@@ -50,5 +50,30 @@ function Default()
     if res_head.status ~= 200 then
       error("unexpected HEAD status: " .. tostring(res_head.status))
     end
+
+    local res_options = http.options(base .. "/echo", {
+      name = "OPTIONS /echo",
+      tags = opts.tags,
+    })
+    if res_options.headers["x-echo-method"] ~= "OPTIONS" then
+      error("unexpected OPTIONS method seen by server: " .. tostring(res_options.headers["x-echo-method"]))
+    end
+
+    local res_propfind = http.request("PROPFIND", base .. "/echo", nil, {
+      name = "PROPFIND /echo",
+      tags = opts.tags,
+      headers = { Depth = "1" },
+    })
+    if res_propfind.status ~= 200 then
+      error("unexpected PROPFIND status: " .. tostring(res_propfind.status))
+    end
+    if res_propfind.headers["x-echo-method"] ~= "PROPFIND" then
+      error("unexpected PROPFIND method seen by server: " .. tostring(res_propfind.headers["x-echo-method"]))
+    end
+
+    local ok, err = pcall(http.request, "BAD VERB", base .. "/echo")
+    if ok or not tostring(err):find("invalid method", 1, true) then
+      error("expected invalid method error, got: " .. tostring(err))
+    end
   end)
 end
@@ -0,0 +1,19 @@
+Options = { iterations = 1 }
+
+local check = require("wrkr/check")
+local fs = require("wrkr/fs")
+
+local fragment = fs.include("lib/fragment.lua", "hello, ")
+
+function Default()
+  local ok, err = pcall(fragment.fail)
+  local escaped = pcall(fs.include, "../e2e_http.rs")
+
+  check(true, {
+    ["globals from include are visible"] = function() return FragmentGreeting("wrkr") == "hello, wrkr" end,
+    ["errors point at the included file"] = function()
+      return not ok and tostring(err):find("fragment.lua:11:", 1, true) ~= nil
+    end,
+    ["paths outside the script dir are rejected"] = function() return not escaped end,
+  })
+end
@@ -0,0 +1,35 @@
+local check = require("wrkr/check")
+local json = require("wrkr/json")
+
+Options = { vus = 1, iterations = 1 }
+
+local body = '{"id":9007199254740993,"owner":-42,"ratio":0.5,"tags":["007",12],"zip":"90210"}'
+
+function Default()
+  local v = json.decode(body, { int64 = "string" })
+  local encoded = json.encode(v, { int64 = "string", int64_fields = { "id", "owner", "tags" } })
+
+  check(v, {
+    ["id keeps every digit"] = function(r) return r.id == "9007199254740993" end,
+    ["negative integers are strings"] = function(r) return r.owner == "-42" end,
+    ["floats stay numbers"] = function(r) return r.ratio == 0.5 end,
+    ["array integers are strings"] = function(r) return r.tags[2] == "12" end,
+  })
+  check(json.decode(encoded), {
+    ["id is encoded unquoted"] = function(_) return encoded:find('"id":9007199254740993', 1, true) ~= nil end,
+    ["non-canonical strings stay quoted"] = function(r) return r.tags[1] == "007" end,
+    ["other integer-like strings stay quoted"] = function(r) return r.zip == "90210" end,
+  })
+  check(json.decode(body), {
+    ["default decodes numbers"] = function(r) return type(r.id) == "number" end,
+  })
+
+  local ok = pcall(json.decode, body, { int64 = "bigint" })
+  check(ok, {
+    ["unknown int64 repr is rejected"] = function(v) return v == false end,
+  })
+  local encoded_blind = pcall(json.encode, v, { int64 = "string" })
+  check(encoded_blind, {
+    ["string encoding needs the integer fields"] = function(v) return v == false end,
+  })
+end
@@ -0,0 +1,14 @@
+-- Included via fs.include(); defines a global and returns a helper table.
+local prefix = ...
+
+function FragmentGreeting(name)
+  return prefix .. name
+end
+
+local M = {}
+
+function M.fail()
+  error("boom from fragment")
+end
+
+return M
@@ -0,0 +1,7 @@
+local M = {}
+
+function M.broken(
+  return M
+end
+
+return M
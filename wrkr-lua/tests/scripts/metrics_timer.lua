@@ -0,0 +1,18 @@
+Options = {
+  iterations = 3,
+}
+
+local metrics = require("wrkr/metrics")
+local sleep = require("wrkr/sleep")
+
+function Default()
+  local timer = metrics.timer()
+  sleep.fixed(0.01)
+
+  local elapsed_us = timer:observe("sign_duration", { step = "sign" })
+  assert(elapsed_us >= 10000, "expected at least 10ms, got " .. tostring(elapsed_us))
+
+  -- Each observation measures from the same start.
+  local total_us = timer:observe("sign_total_duration")
+  assert(total_us >= elapsed_us)
+end
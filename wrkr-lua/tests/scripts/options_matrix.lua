@@ -0,0 +1,18 @@
+local vu = require("wrkr/vu")
+
+Options = {
+  scenarios = {
+    upload = {
+      exec = "Upload",
+      iterations = 1,
+      tags = { team = "storage" },
+      matrix = { size = { "1k", "10k" }, parallel = { 1, 8 } },
+    },
+  },
+}
+
+function Upload()
+  local tags = vu.tags()
+  assert(tags.size == "1k" or tags.size == "10k", "unexpected size " .. tostring(tags.size))
+  assert(tags.team == "storage")
+end
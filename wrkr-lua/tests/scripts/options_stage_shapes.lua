@@ -0,0 +1,16 @@
+Options = {
+  scenarios = {
+    steps = {
+      executor = "ramping-vus",
+      exec = "Default",
+
+      stages = {
+        { duration = "10s", target = 10, shape = "step" },
+        { duration = "10s", target = 20, shape = "ease-in-out" },
+        { duration = "10s", target = 0 },
+      },
+    },
+  },
+}
+
+function Default() end
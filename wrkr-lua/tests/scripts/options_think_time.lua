@@ -0,0 +1,20 @@
+Options = {
+  scenarios = {
+    browse = {
+      vus = 2,
+      duration = "10s",
+      think_time = { distribution = "lognormal", mean = "3s", stddev = "1s", max = "10s" },
+    },
+    api = {
+      vus = 1,
+      iterations = 10,
+      thinkTime = "250ms",
+    },
+    firehose = {
+      vus = 1,
+      iterations = 10,
+    },
+  },
+}
+
+function Default() end
@@ -0,0 +1,16 @@
+Options = { vus = 2, iterations = 4 }
+
+local http = require("wrkr/http")
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+local fs = require("wrkr/fs")
+
+local expected = fs.read_file("data/payload.txt")
+
+function Default()
+  local res = http.post(env.BASE_URL .. "/echo", nil, { body_file = "data/payload.txt" })
+  check(res, {
+    ["status is 200"] = function(r) return r.status == 200 end,
+    ["body is file contents"] = function(r) return r.body == expected end,
+  })
+end
@@ -0,0 +1,20 @@
+Options = {
+  iterations = 1,
+  tags = {
+    region = "us-west",
+    team = "checkout",
+  },
+}
+
+function Default()
+  local check = require("wrkr/check")
+  local metrics = require("wrkr/metrics")
+
+  local counter = metrics.Counter("custom_counter_run_tags")
+
+  check({}, {
+    ["ok"] = function(_) return true end,
+  })
+
+  counter:add(1)
+end
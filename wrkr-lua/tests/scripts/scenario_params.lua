@@ -0,0 +1,25 @@
+Options = {
+  scenarios = {
+    small = {
+      exec = "Upload",
+      executor = "constant-vus",
+      vus = 1,
+      iterations = 3,
+      params = { size = 10 },
+    },
+    large = {
+      exec = "Upload",
+      executor = "constant-vus",
+      vus = 1,
+      iterations = 2,
+      params = { size = 1000, label = "big" },
+    },
+  },
+}
+
+local metrics = require("wrkr/metrics")
+local bytes = metrics.Counter("upload_bytes")
+
+function Upload(params)
+  bytes:add(params.size, { label = params.label or "none" })
+end
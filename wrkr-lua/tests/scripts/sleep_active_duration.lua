@@ -0,0 +1,7 @@
+Options = { iterations = 3 }
+
+local sleep = require("wrkr/sleep")
+
+function Default()
+  sleep.fixed("30ms")
+end
@@ -0,0 +1,31 @@
+Options = { iterations = 1 }
+
+local sleep = require("wrkr/sleep")
+local check = require("wrkr/check")
+
+function Default()
+  sleep.fixed("1ms")
+  sleep.fixed(0.001)
+
+  local uniform = sleep.think({ distribution = "uniform", min = "1ms", max = "3ms" })
+  local capped = sleep.think({ distribution = "exponential", mean = "1s", max = "5ms" })
+  local floored = sleep.think({ distribution = "normal", mean = 0, stddev = "1ms", min = "2ms", max = "2ms" })
+  local unknown_ok, unknown_err = pcall(sleep.think, { distribution = "pareto", mean = "1ms" })
+  local missing_ok = pcall(sleep.think, { distribution = "lognormal", mean = "1ms" })
+  local huge_ok, huge_err = pcall(sleep.fixed, 1e300)
+  local huge_max_ok = pcall(sleep.think, { distribution = "uniform", max = 1e300 })
+
+  check(true, {
+    ["uniform within range"] = function() return uniform >= 0.001 and uniform <= 0.003 end,
+    ["max clamps the tail"] = function() return capped <= 0.005 end,
+    ["min and max clamp"] = function() return math.abs(floored - 0.002) < 1e-9 end,
+    ["unknown distribution rejected"] = function()
+      return not unknown_ok and tostring(unknown_err):find("unknown distribution", 1, true) ~= nil
+    end,
+    ["missing stddev rejected"] = function() return not missing_ok end,
+    ["out of range duration rejected"] = function()
+      return not huge_ok and tostring(huge_err):find("out of range", 1, true) ~= nil
+    end,
+    ["out of range max rejected"] = function() return not huge_max_ok end,
+  })
+end
@@ -0,0 +1,24 @@
+Options = { vus = 1, iterations = 2 }
+
+local metrics = require("wrkr/metrics")
+
+local orders_per_session = metrics.Gauge("orders_per_session")
+local summaries = metrics.Counter("summaries_written")
+
+function Default() end
+
+function Teardown()
+  orders_per_session:add(5)
+end
+
+function HandleSummary(summary)
+  local kpi = nil
+  for _, m in ipairs(summary.metrics) do
+    if m.name == "orders_per_session" then
+      kpi = m.values.value
+    end
+  end
+  summaries:add(1)
+
+  return { stdout = tostring(kpi) }
+end
@@ -0,0 +1,12 @@
+Options = {
+  vus = 1,
+  iterations = 1,
+  thresholds = {
+    grpc_req_duration = { "p(95)<200000", no_data = "pass" },
+    request_latency = "p(95)<200000",
+  },
+}
+
+function Default(_data)
+  -- No-op; this script only validates options parsing.
+end
@@ -0,0 +1,15 @@
+Options = {
+  vus = 1,
+  iterations = 1,
+  thresholds = {
+    request_latency = {
+      { "p(95)<200000", when = { env = "prod" } },
+      { "p(95)<500000", when = { env = "staging" } },
+    },
+    checks = { "rate>0.99", when = { scenario = "main" } },
+  },
+}
+
+function Default(_data)
+  -- No-op; this script only validates options parsing.
+end
@@ -0,0 +1,15 @@
+-- Loads while the options are read (outside any VU), then fails to load in VU 2.
+local env = require("wrkr/env")
+local vu = require("wrkr/vu")
+
+Options = {
+  scenarios = {
+    checkout = { vus = 2, iterations = 2 },
+  },
+}
+
+if vu.id() == 2 then
+  require(env.FAILING_MODULE)
+end
+
+function Default() end
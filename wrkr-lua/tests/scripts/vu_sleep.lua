@@ -0,0 +1,16 @@
+Options = { vus = 1, duration = "300ms" }
+
+local vu = require("wrkr/vu")
+local check = require("wrkr/check")
+
+function Default()
+  local huge_ok, huge_err = pcall(vu.sleep, 1e300)
+  local slept = vu.sleep("10s")
+
+  check(slept, {
+    ["clamped to the scenario end"] = function(s) return s <= 0.3 end,
+    ["out of range duration rejected"] = function()
+      return not huge_ok and tostring(huge_err):find("out of range", 1, true) ~= nil
+    end,
+  })
+end
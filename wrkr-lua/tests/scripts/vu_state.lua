@@ -0,0 +1,21 @@
+Options = { vus = 2, iterations = 6 }
+
+local vu = require("wrkr/vu")
+local wrkr = require("wrkr")
+local check = require("wrkr/check")
+
+local seen = 0
+
+function Default()
+  seen = seen + 1
+
+  local state = vu.state
+  state.owner = state.owner or vu.id()
+  state.count = (state.count or 0) + 1
+
+  check(state, {
+    ["persists across iterations"] = function(s) return s.count == seen end,
+    ["owned by this vu"] = function(s) return s.owner == vu.id() end,
+    ["same table via wrkr.vu"] = function(s) return wrkr.vu.state == s end,
+  })
+end
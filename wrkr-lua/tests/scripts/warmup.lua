@@ -0,0 +1,12 @@
+Options = {
+  vus = 2,
+  iterations = 3,
+  warmup_iterations = 2,
+}
+
+function Default()
+  local metrics = require("wrkr/metrics")
+  local calls = metrics.Counter("warmup_probe_calls")
+
+  calls:add(1)
+end
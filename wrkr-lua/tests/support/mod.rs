@@ -51,9 +51,16 @@ pub async fn run_script(
 ) -> Result<wrkr_core::RunSummary> {
     let script = load_test_script(script_name)?;
     let env = env_with(env_overrides);
-    let run_ctx = run_ctx_for_script(&script, env);
+    let mut run_ctx = run_ctx_for_script(&script, env);
 
     let opts = wrkr_lua::parse_script_options(&run_ctx)?;
+    run_ctx.discard_response_bodies = opts.discard_response_bodies;
+    run_ctx.no_vu_connection_reuse = opts.no_vu_connection_reuse;
+    run_ctx.isolate_scenario_connections = opts.isolate_scenario_connections;
+    run_ctx.http_cache = opts.http_cache;
+    if opts.no_connection_reuse {
+        run_ctx.client = std::sync::Arc::new(run_ctx.client.as_ref().clone().keep_alive(false));
+    }
     let scenarios = wrkr_core::scenarios_from_options(opts, cfg)?;
 
     let summary = wrkr_core::run_scenarios(scenarios, run_ctx, wrkr_lua::run_vu, None).await?;
@@ -0,0 +1,38 @@
+mod support;
+
+/// The error a run fails with when VU 2 cannot `require` `module`.
+async fn init_error(module: &str) -> String {
+    match support::run_script(
+        "vu_init_failure.lua",
+        &[("FAILING_MODULE", module.to_string())],
+        wrkr_core::RunConfig::default(),
+    )
+    .await
+    {
+        Ok(_) => panic!("expected VU 2 to fail to load the script"),
+        Err(err) => err.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn a_failing_require_names_the_vu_the_location_and_the_traceback() {
+    let msg = init_error("lib.does_not_exist").await;
+
+    assert!(msg.contains("VU 2 (scenario `checkout`)"), "{msg}");
+    assert!(
+        msg.contains("module 'lib.does_not_exist' not found"),
+        "{msg}"
+    );
+    assert!(msg.contains("vu_init_failure.lua:12:"), "{msg}");
+    assert!(msg.contains("stack traceback"), "{msg}");
+}
+
+#[tokio::test]
+async fn a_syntax_error_in_a_required_module_names_its_file_and_line() {
+    let msg = init_error("lib.syntax_error").await;
+
+    assert!(msg.contains("VU 2 (scenario `checkout`)"), "{msg}");
+    assert!(msg.contains("syntax_error.lua:4:"), "{msg}");
+    assert!(msg.contains("vu_init_failure.lua:12:"), "{msg}");
+    assert!(msg.contains("stack traceback"), "{msg}");
+}
@@ -1,11 +1,10 @@
 use std::collections::HashMap;
 use std::sync::atomic::Ordering;
 
-use hdrhistogram::Histogram;
 use smallvec::SmallVec;
 
 use crate::key::KeyId;
-use crate::metrics::{HistogramSummary, MetricStorage, new_default_histogram, summarize_histogram};
+use crate::metrics::{HistogramAccumulator, HistogramSummary, MetricStorage, summarize_histogram};
 use crate::registry::{MetricId, Registry};
 use crate::tags::TagSet;
 
@@ -197,8 +196,23 @@ pub fn sum_counter_total(self) -> u64 {
         self.sum_counter().values().copied().sum()
     }
 
+    /// Sum of the current values of the matching gauge series; `None` when none match.
+    pub fn sum_gauge_total(self) -> Option<i64> {
+        let mut total: Option<i64> = None;
+        self.registry.visit_series(self.metric, |tags, storage| {
+            if !self.matches(tags) {
+                return;
+            }
+            if let MetricStorage::Gauge(g) = storage {
+                let v = g.load(Ordering::Relaxed);
+                total = Some(total.map_or(v, |cur| cur.saturating_add(v)));
+            }
+        });
+        total
+    }
+
     pub fn merge_histogram_summary(self) -> HashMap<TagSet, HistogramSummary> {
-        let mut acc: HashMap<TagSet, Histogram<u64>> = HashMap::new();
+        let mut acc: HashMap<TagSet, HistogramAccumulator> = HashMap::new();
 
         self.registry.visit_series(self.metric, |tags, storage| {
             if !self.matches(tags) {
@@ -209,14 +223,14 @@ pub fn merge_histogram_summary(self) -> HashMap<TagSet, HistogramSummary> {
             };
 
             let k = self.group_key(tags);
-            let entry = acc.entry(k).or_insert_with(new_default_histogram);
+            let entry = acc.entry(k).or_default();
 
             let h = h.lock();
-            let _ = entry.add(&*h);
+            let _ = entry.add(&h);
         });
 
         acc.into_iter()
-            .map(|(k, h)| (k, summarize_histogram(&h)))
+            .map(|(k, h)| (k, summarize_histogram(h.finish())))
             .collect()
     }
 
@@ -5,6 +5,9 @@
 pub mod tags;
 
 pub use key::KeyId;
-pub use metrics::{HistogramSummary, MetricHandle, MetricKind, MetricSeriesSummary, MetricValue};
-pub use registry::{MetricId, Registry};
+pub use metrics::{
+    DEFAULT_HISTOGRAM_SIG_FIGS, HISTOGRAM_MAX_VALUE, HISTOGRAM_SIG_FIGS_RANGE, HistogramMergeError,
+    HistogramSummary, MetricHandle, MetricKind, MetricSeriesSummary, MetricValue,
+};
+pub use registry::{MetricId, Registry, RegistrySnapshot};
 pub use tags::TagSet;
@@ -1,4 +1,6 @@
+use base64::Engine as _;
 use hdrhistogram::Histogram;
+use hdrhistogram::serialization::{Deserializer, Serializer as _, V2DeflateSerializer};
 use parking_lot::Mutex;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
@@ -19,6 +21,21 @@ pub struct MetricSeriesSummary {
     pub values: MetricValue,
 }
 
+impl MetricSeriesSummary {
+    #[must_use]
+    pub fn tag(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find_map(|(k, v)| (k == key).then_some(v.as_str()))
+    }
+
+    /// Whether every `(key, value)` pair in `tags` is set on this series.
+    #[must_use]
+    pub fn has_tags(&self, tags: &[(&str, &str)]) -> bool {
+        tags.iter().all(|(k, v)| self.tag(k) == Some(*v))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum MetricValue {
     Counter(u64),
@@ -43,18 +60,173 @@ pub struct HistogramSummary {
     pub mean: Option<f64>,
     pub stdev: Option<f64>,
     pub count: u64,
+    histogram: HistogramState,
 }
 
+/// Histograms that could not be added together.
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[error("failed to merge histograms: {0}")]
+pub struct HistogramMergeError(#[from] hdrhistogram::AdditionError);
+
+impl HistogramSummary {
+    /// Combine summaries (e.g. the same metric from several scenarios) by merging their
+    /// histograms, so percentiles are those of the pooled samples rather than an average of
+    /// per-summary percentiles.
+    ///
+    /// Returns `Ok(None)` when `summaries` is empty.
+    pub fn merge<'a>(
+        summaries: impl IntoIterator<Item = &'a HistogramSummary>,
+    ) -> Result<Option<Self>, HistogramMergeError> {
+        let mut summaries = summaries.into_iter().peekable();
+        if summaries.peek().is_none() {
+            return Ok(None);
+        }
+
+        let mut merged = HistogramAccumulator::default();
+        for s in summaries {
+            merged.add(&s.histogram.0)?;
+        }
+        Ok(Some(summarize_histogram(merged.finish())))
+    }
+
+    /// The samples recorded since `earlier`, an older summary of the same histogram (e.g. the
+    /// previous progress tick), so percentiles describe that interval alone.
+    ///
+    /// Returns `None` when `earlier` holds samples this summary does not.
+    #[must_use]
+    pub fn since(&self, earlier: &HistogramSummary) -> Option<Self> {
+        let mut h = (*self.histogram.0).clone();
+        h.subtract(&*earlier.histogram.0).ok()?;
+        Some(summarize_histogram(h))
+    }
+
+    /// Value at an arbitrary percentile (`0.0..=100.0`), computed from the merged histogram.
+    ///
+    /// Returns `None` when the histogram is empty or `p` is out of range.
+    #[must_use]
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.count == 0 || !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        Some(self.histogram.0.value_at_percentile(p) as f64)
+    }
+
+    /// Fraction (0..=1) of recorded values strictly greater than `target`.
+    ///
+    /// Values are compared at the histogram's bucket resolution. Returns `None` when the
+    /// histogram is empty or `target` is negative/NaN.
+    #[must_use]
+    pub fn rate_above(&self, target: f64) -> Option<f64> {
+        if self.count == 0 || target.is_nan() || target < 0.0 {
+            return None;
+        }
+        let h = &self.histogram.0;
+        let at_or_below = if target >= h.max() as f64 {
+            h.len()
+        } else {
+            h.count_between(0, target.floor() as u64)
+        };
+        Some(h.len().saturating_sub(at_or_below) as f64 / h.len() as f64)
+    }
+
+    /// The histogram in HdrHistogram's compressed V2 encoding, base64-encoded: the form other
+    /// HdrHistogram libraries decode, so histograms from separate runs can be added together
+    /// without losing precision.
+    ///
+    /// Returns `None` if the histogram cannot be encoded.
+    #[must_use]
+    pub fn to_compressed_base64(&self) -> Option<String> {
+        let mut bytes = Vec::new();
+        V2DeflateSerializer::new()
+            .serialize(&self.histogram.0, &mut bytes)
+            .ok()?;
+        Some(base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    /// Decode [`Self::to_compressed_base64`] output (or any V2 encoded histogram) into a
+    /// summary. Returns `None` for anything that is not a valid encoding.
+    #[must_use]
+    pub fn from_compressed_base64(encoded: &str) -> Option<Self> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .ok()?;
+        let h: Histogram<u64> = Deserializer::new()
+            .deserialize(&mut bytes.as_slice())
+            .ok()?;
+        Some(summarize_histogram(h))
+    }
+}
+
+/// Shared, immutable copy of the histogram a summary was computed from.
+///
+/// Kept behind an `Arc` so cloning summaries stays cheap; `Debug` only prints the sample count
+/// (the raw bucket array is large).
+#[derive(Clone)]
+struct HistogramState(Arc<Histogram<u64>>);
+
+impl std::fmt::Debug for HistogramState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HistogramState")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+/// Upper bound of every histogram (built-in latency and custom trends alike): 1 hour in
+/// microseconds.
+pub const HISTOGRAM_MAX_VALUE: u64 = 3_600_000_000;
+
+/// Significant figures a histogram keeps unless configured otherwise, see
+/// [`crate::Registry::set_histogram_sig_figs`].
+pub const DEFAULT_HISTOGRAM_SIG_FIGS: u8 = 3;
+
+/// Supported significant figures; each extra figure costs roughly 10x the bucket memory.
+pub const HISTOGRAM_SIG_FIGS_RANGE: std::ops::RangeInclusive<u8> = 1..=5;
+
 pub(crate) fn new_default_histogram() -> Histogram<u64> {
-    // Defaults compatible with typical latency in microseconds.
-    // Upper bound: 1 hour in microseconds.
-    match Histogram::<u64>::new_with_bounds(1, 3_600_000_000, 3) {
+    new_histogram(DEFAULT_HISTOGRAM_SIG_FIGS)
+}
+
+pub(crate) fn new_histogram(sig_figs: u8) -> Histogram<u64> {
+    // Bounds compatible with typical latency in microseconds.
+    let sig_figs = sig_figs.clamp(
+        *HISTOGRAM_SIG_FIGS_RANGE.start(),
+        *HISTOGRAM_SIG_FIGS_RANGE.end(),
+    );
+    match Histogram::<u64>::new_with_bounds(1, HISTOGRAM_MAX_VALUE, sig_figs) {
         Ok(h) => h,
         Err(err) => panic!("failed to create histogram: {err}"),
     }
 }
 
-pub(crate) fn summarize_histogram(h: &Histogram<u64>) -> HistogramSummary {
+/// Histograms added together at the precision of the most precise one, so a metric configured
+/// with more significant figures than the default keeps them in every merged summary.
+#[derive(Default)]
+pub(crate) struct HistogramAccumulator(Option<Histogram<u64>>);
+
+impl HistogramAccumulator {
+    pub(crate) fn add(&mut self, h: &Histogram<u64>) -> Result<(), hdrhistogram::AdditionError> {
+        if let Some(acc) = &mut self.0
+            && acc.sigfig() >= h.sigfig()
+        {
+            return acc.add(h);
+        }
+        let mut wider = new_histogram(h.sigfig());
+        if let Some(acc) = &self.0 {
+            wider.add(acc)?;
+        }
+        wider.add(h)?;
+        self.0 = Some(wider);
+        Ok(())
+    }
+
+    /// The sum, or an empty default histogram when nothing was added.
+    pub(crate) fn finish(self) -> Histogram<u64> {
+        self.0.unwrap_or_else(new_default_histogram)
+    }
+}
+
+pub(crate) fn summarize_histogram(h: Histogram<u64>) -> HistogramSummary {
     let count = h.len();
     let map_val = |v| v as f64;
 
@@ -69,6 +241,7 @@ pub(crate) fn summarize_histogram(h: &Histogram<u64>) -> HistogramSummary {
         mean: (count > 0).then(|| h.mean()),
         stdev: (count > 0).then(|| h.stdev()),
         count,
+        histogram: HistogramState(Arc::new(h)),
     }
 }
 
@@ -100,6 +273,68 @@ pub fn new(kind: MetricKind) -> Self {
             }
         }
     }
+
+    /// Histogram storage keeping `sig_figs` significant figures (clamped to
+    /// [`HISTOGRAM_SIG_FIGS_RANGE`]).
+    pub fn histogram(sig_figs: u8) -> Self {
+        MetricStorage::Histogram(Arc::new(Mutex::new(new_histogram(sig_figs))))
+    }
+
+    /// Zero the stored values in place, so handles that were already handed out stay valid.
+    pub fn reset(&self) {
+        match self {
+            MetricStorage::Counter(c) => c.store(0, Ordering::Relaxed),
+            MetricStorage::Gauge(g) => g.store(0, Ordering::Relaxed),
+            MetricStorage::Rate(r) => {
+                r.total.store(0, Ordering::Relaxed);
+                r.hits.store(0, Ordering::Relaxed);
+            }
+            MetricStorage::Histogram(h) => h.lock().reset(),
+        }
+    }
+
+    /// A copy of the stored values that later writes don't reach.
+    pub fn snapshot(&self) -> Self {
+        match self {
+            MetricStorage::Counter(c) => {
+                MetricStorage::Counter(Arc::new(AtomicU64::new(c.load(Ordering::Relaxed))))
+            }
+            MetricStorage::Gauge(g) => {
+                MetricStorage::Gauge(Arc::new(AtomicI64::new(g.load(Ordering::Relaxed))))
+            }
+            MetricStorage::Rate(r) => MetricStorage::Rate(Arc::new(Rate {
+                total: AtomicU64::new(r.total.load(Ordering::Relaxed)),
+                hits: AtomicU64::new(r.hits.load(Ordering::Relaxed)),
+            })),
+            MetricStorage::Histogram(h) => {
+                MetricStorage::Histogram(Arc::new(Mutex::new(h.lock().clone())))
+            }
+        }
+    }
+
+    /// Put back the values of `snapshot` (see [`Self::snapshot`]) in place, like [`Self::reset`]
+    /// does with zeros.
+    pub fn restore(&self, snapshot: &Self) {
+        match (self, snapshot) {
+            (MetricStorage::Counter(c), MetricStorage::Counter(s)) => {
+                c.store(s.load(Ordering::Relaxed), Ordering::Relaxed);
+            }
+            (MetricStorage::Gauge(g), MetricStorage::Gauge(s)) => {
+                g.store(s.load(Ordering::Relaxed), Ordering::Relaxed);
+            }
+            (MetricStorage::Rate(r), MetricStorage::Rate(s)) => {
+                r.total
+                    .store(s.total.load(Ordering::Relaxed), Ordering::Relaxed);
+                r.hits
+                    .store(s.hits.load(Ordering::Relaxed), Ordering::Relaxed);
+            }
+            (MetricStorage::Histogram(h), MetricStorage::Histogram(s)) => {
+                let values = s.lock().clone();
+                *h.lock() = values;
+            }
+            _ => self.reset(),
+        }
+    }
 }
 
 // Public handle for writing metrics
@@ -154,7 +389,9 @@ pub fn observe_histogram(&self, value: u64) {
             // Locking is unavoidable with shared histogram unless we use a window or thread-local buffer
             // For now, simple mutex
             let mut h = h.lock();
-            let _ = h.record(value);
+            // Custom trends can carry arbitrary values; clamp instead of silently dropping
+            // anything above the histogram's upper bound.
+            h.saturating_record(value);
         }
     }
 }
@@ -195,7 +432,7 @@ mod tests {
     #[test]
     fn summarize_histogram_empty_has_no_stats() {
         let h = new_default_histogram();
-        let s = summarize_histogram(&h);
+        let s = summarize_histogram(h);
         assert_eq!(s.count, 0);
         assert!(s.p50.is_none());
         assert!(s.min.is_none());
@@ -211,7 +448,7 @@ fn summarize_histogram_non_empty_has_stats() {
         let _ = h.record(20);
         let _ = h.record(30);
 
-        let s = summarize_histogram(&h);
+        let s = summarize_histogram(h);
         assert_eq!(s.count, 3);
         assert_eq!(s.min, Some(10.0));
         assert_eq!(s.max, Some(30.0));
@@ -221,6 +458,93 @@ fn summarize_histogram_non_empty_has_stats() {
         assert!(s.stdev.is_some());
     }
 
+    #[test]
+    fn compressed_base64_round_trips_the_histogram() {
+        let mut h = new_histogram(4);
+        for v in [150, 1_200, 1_250, 48_000] {
+            let _ = h.record(v);
+        }
+        let summary = summarize_histogram(h);
+
+        let encoded = summary
+            .to_compressed_base64()
+            .unwrap_or_else(|| panic!("failed to encode"));
+        let decoded = HistogramSummary::from_compressed_base64(&encoded)
+            .unwrap_or_else(|| panic!("failed to decode {encoded}"));
+        assert_eq!(decoded.count, 4);
+        assert_eq!(decoded.min, summary.min);
+        assert_eq!(decoded.max, summary.max);
+        assert_eq!(decoded.p50, summary.p50);
+        assert_eq!(*decoded.histogram.0, *summary.histogram.0);
+
+        assert!(HistogramSummary::from_compressed_base64("not a histogram").is_none());
+    }
+
+    #[test]
+    fn merge_pools_samples_instead_of_averaging_percentiles() {
+        let summary = |values: &[u64]| {
+            let mut h = new_default_histogram();
+            for v in values {
+                let _ = h.record(*v);
+            }
+            summarize_histogram(h)
+        };
+        // 99 fast requests in one scenario, a single slow one in the other.
+        let fast = summary(&[1_000; 99]);
+        let slow = summary(&[500_000]);
+
+        let merged = HistogramSummary::merge([&fast, &slow])
+            .unwrap_or_else(|e| panic!("{e}"))
+            .unwrap_or_else(|| panic!("expected a merged summary"));
+        assert_eq!(merged.count, 100);
+        assert_eq!(merged.min, Some(1_000.0));
+        assert!(merged.max.is_some_and(|v| v >= 500_000.0));
+        // The pooled p99 is the 99th sample: a fast one, not the mean of both p99s.
+        assert!(merged.p99.is_some_and(|v| v < 1_100.0), "{merged:?}");
+
+        assert!(matches!(HistogramSummary::merge([]), Ok(None)));
+    }
+
+    #[test]
+    fn summary_percentile_queries_arbitrary_quantiles() {
+        let mut h = new_default_histogram();
+        for v in 1..=10_000u64 {
+            let _ = h.record(v);
+        }
+
+        let s = summarize_histogram(h);
+        let p9999 = s
+            .percentile(99.99)
+            .unwrap_or_else(|| panic!("expected p99.99"));
+        assert!((9_990.0..=10_010.0).contains(&p9999), "{p9999}");
+        assert_eq!(s.percentile(100.0), s.max);
+        assert!(s.percentile(-1.0).is_none());
+        assert!(s.percentile(100.5).is_none());
+
+        let empty = summarize_histogram(new_default_histogram());
+        assert!(empty.percentile(50.0).is_none());
+    }
+
+    #[test]
+    fn summary_rate_above_counts_values_over_target() {
+        let mut h = new_default_histogram();
+        for v in 1..=1_000u64 {
+            let _ = h.record(v);
+        }
+
+        let s = summarize_histogram(h);
+        let over = s
+            .rate_above(900.0)
+            .unwrap_or_else(|| panic!("expected rate"));
+        assert!((over - 0.1).abs() < 0.005, "{over}");
+        assert_eq!(s.rate_above(0.0), Some(1.0));
+        assert_eq!(s.rate_above(1_000_000.0), Some(0.0));
+        assert!(s.rate_above(-1.0).is_none());
+
+        let empty = summarize_histogram(new_default_histogram());
+        assert!(empty.rate_above(1.0).is_none());
+    }
+
     #[test]
     fn metric_storage_new_initializes_defaults() {
         match MetricStorage::new(MetricKind::Counter) {
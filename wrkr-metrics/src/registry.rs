@@ -1,4 +1,6 @@
 // use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+
 use dashmap::DashMap;
 use parking_lot::RwLock;
 use smallvec::SmallVec;
@@ -17,11 +19,34 @@ pub struct MetricDef {
     pub kind: MetricKind,
 }
 
+/// The values of every series of a [`Registry`] at one point in time.
+#[derive(Debug, Default)]
+pub struct RegistrySnapshot(HashMap<(MetricId, TagSet), MetricStorage>);
+
 #[derive(Debug, Default)]
 pub struct Registry {
     interner: Interner,
     defs: RwLock<Vec<MetricDef>>,
     storage: DashMap<MetricId, DashMap<TagSet, MetricStorage>>,
+    disabled: RwLock<HashSet<KeyId>>,
+    sig_figs: RwLock<SigFigs>,
+}
+
+/// Histogram precision: a registry-wide default and per-metric overrides, by metric name.
+#[derive(Debug, Default)]
+struct SigFigs {
+    default: Option<u8>,
+    by_name: HashMap<KeyId, u8>,
+}
+
+impl SigFigs {
+    fn for_metric(&self, name: KeyId) -> u8 {
+        self.by_name
+            .get(&name)
+            .copied()
+            .or(self.default)
+            .unwrap_or(crate::metrics::DEFAULT_HISTOGRAM_SIG_FIGS)
+    }
 }
 
 impl Registry {
@@ -49,10 +74,42 @@ pub fn register(&self, name: &str, kind: MetricKind) -> MetricId {
             name: name_id,
             kind,
         });
-        self.storage.insert(id, DashMap::new());
+        if !self.disabled.read().contains(&name_id) {
+            self.storage.insert(id, DashMap::new());
+        }
         id
     }
 
+    /// Stop recording the metric `name`, whether or not it is registered yet.
+    ///
+    /// A disabled metric keeps its `MetricId` but has no series storage, so `get_handle` returns
+    /// `None` and recording sites skip it without allocating anything.
+    pub fn disable(&self, name: &str) {
+        let name_id = self.interner.get_or_intern(name);
+        self.disabled.write().insert(name_id);
+
+        let defs = self.defs.read();
+        if let Some(idx) = defs.iter().position(|d| d.name == name_id) {
+            self.storage.remove(&MetricId(idx as u32));
+        }
+    }
+
+    /// Significant figures of every histogram series without a per-metric setting (default 3).
+    ///
+    /// Fewer figures shrink each series' bucket array about tenfold per figure, at the cost of
+    /// coarser percentiles. Only series created afterwards are affected, so call this before
+    /// recording.
+    pub fn set_histogram_sig_figs(&self, sig_figs: u8) {
+        self.sig_figs.write().default = Some(sig_figs);
+    }
+
+    /// Like [`Self::set_histogram_sig_figs`], for the histogram metric `name` only, whether or not
+    /// it is registered yet.
+    pub fn set_metric_sig_figs(&self, name: &str, sig_figs: u8) {
+        let name_id = self.interner.get_or_intern(name);
+        self.sig_figs.write().by_name.insert(name_id, sig_figs);
+    }
+
     pub fn resolve_key(&self, key: &str) -> KeyId {
         self.interner.get_or_intern(key)
     }
@@ -81,12 +138,18 @@ pub fn get_handle(&self, metric: MetricId, tags: TagSet) -> Option<MetricHandle>
             return Some(self.storage_to_handle(storage.value()));
         }
 
-        let kind = {
+        let (name, kind) = {
             let defs = self.defs.read();
-            defs.get(metric.0 as usize)?.kind
+            let def = defs.get(metric.0 as usize)?;
+            (def.name, def.kind)
         };
 
-        let new_storage = MetricStorage::new(kind);
+        let new_storage = match kind {
+            MetricKind::Histogram => {
+                MetricStorage::histogram(self.sig_figs.read().for_metric(name))
+            }
+            kind => MetricStorage::new(kind),
+        };
         let handle = self.storage_to_handle(&new_storage);
         series_map.insert(tags, new_storage);
 
@@ -139,7 +202,7 @@ pub fn fold_histogram_summary<P>(
     where
         P: FnMut(&TagSet) -> bool,
     {
-        let mut acc = crate::metrics::new_default_histogram();
+        let mut acc = crate::metrics::HistogramAccumulator::default();
         let mut any = false;
 
         self.visit_series(metric, |tags, storage| {
@@ -152,10 +215,10 @@ pub fn fold_histogram_summary<P>(
 
             any = true;
             let h = h.lock();
-            let _ = acc.add(&*h);
+            let _ = acc.add(&h);
         });
 
-        any.then(|| crate::metrics::summarize_histogram(&acc))
+        any.then(|| crate::metrics::summarize_histogram(acc.finish()))
     }
 
     pub fn fold_rate_sum<P>(&self, metric: MetricId, mut predicate: P) -> (u64, u64, Option<f64>)
@@ -182,6 +245,41 @@ pub fn fold_rate_sum<P>(&self, metric: MetricId, mut predicate: P) -> (u64, u64,
         (total, hits, rate)
     }
 
+    /// Zero every recorded series while keeping metric definitions and series handles intact.
+    pub fn reset(&self) {
+        for entry in self.storage.iter() {
+            for series in entry.value().iter() {
+                series.value().reset();
+            }
+        }
+    }
+
+    /// The current values of every series, to go back to with [`Self::reset_to`].
+    pub fn snapshot(&self) -> RegistrySnapshot {
+        let mut out = HashMap::new();
+        for entry in self.storage.iter() {
+            for series in entry.value().iter() {
+                out.insert(
+                    (*entry.key(), series.key().clone()),
+                    series.value().snapshot(),
+                );
+            }
+        }
+        RegistrySnapshot(out)
+    }
+
+    /// Like [`Self::reset`], except that series in `snapshot` go back to their values there.
+    pub fn reset_to(&self, snapshot: &RegistrySnapshot) {
+        for entry in self.storage.iter() {
+            for series in entry.value().iter() {
+                match snapshot.0.get(&(*entry.key(), series.key().clone())) {
+                    Some(values) => series.value().restore(values),
+                    None => series.value().reset(),
+                }
+            }
+        }
+    }
+
     pub fn summarize(&self) -> Vec<MetricSeriesSummary> {
         let mut out = Vec::new();
         let defs = self.defs.read();
@@ -237,7 +335,7 @@ pub fn summarize(&self) -> Vec<MetricSeriesSummary> {
                     }
                     MetricStorage::Histogram(h) => {
                         let h = h.lock();
-                        MetricValue::Histogram(crate::metrics::summarize_histogram(&h))
+                        MetricValue::Histogram(crate::metrics::summarize_histogram((*h).clone()))
                     }
                 };
 
@@ -258,7 +356,7 @@ pub fn summarize(&self) -> Vec<MetricSeriesSummary> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::metrics::MetricKind;
+    use crate::metrics::{HistogramSummary, MetricKind, MetricValue};
     use std::sync::atomic::Ordering;
 
     #[test]
@@ -287,6 +385,82 @@ fn fold_counter_sum_filters_by_tags() {
         assert_eq!(sum_all, 13);
     }
 
+    #[test]
+    fn merged_histograms_keep_the_precision_of_their_inputs() {
+        let reg = Registry::default();
+        reg.set_metric_sig_figs("request_latency", 5);
+        let latency = reg.register("request_latency", MetricKind::Histogram);
+        let scenario = reg.resolve_key("scenario");
+        // Exact at 5 significant figures; 3 would round it to a 128us bucket.
+        let value = 123_457;
+        for name in ["a", "b"] {
+            let tags = TagSet::from_sorted_iter([(scenario, reg.resolve_key(name))]);
+            if let Some(MetricHandle::Histogram(h)) = reg.get_handle(latency, tags) {
+                let _ = h.lock().record(value);
+            }
+        }
+        let exact = Some(value as f64);
+
+        let folded = reg.fold_histogram_summary(latency, |_| true);
+        assert_eq!(folded.as_ref().and_then(|s| s.max), exact);
+
+        let queried = reg.query(latency).merge_histogram_summary_single();
+        assert_eq!(queried.and_then(|s| s.max), exact);
+
+        let per_series: Vec<_> = reg
+            .summarize()
+            .into_iter()
+            .filter_map(|series| match series.values {
+                MetricValue::Histogram(h) => Some(h),
+                _ => None,
+            })
+            .collect();
+        let merged = HistogramSummary::merge(&per_series).unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(merged.as_ref().map(|s| s.count), Some(2));
+        assert_eq!(merged.and_then(|s| s.max), exact);
+    }
+
+    #[test]
+    fn histogram_sig_figs_apply_per_metric_over_the_default() {
+        let reg = Registry::default();
+        reg.set_histogram_sig_figs(2);
+        reg.set_metric_sig_figs("request_latency", 4);
+        let latency = reg.register("request_latency", MetricKind::Histogram);
+        let custom = reg.register("cart_size", MetricKind::Histogram);
+
+        let sig_figs =
+            |metric| match reg.get_handle(metric, reg.resolve_tags(&[("scenario", "main")])) {
+                Some(MetricHandle::Histogram(h)) => h.lock().sigfig(),
+                other => panic!("expected a histogram, got {other:?}"),
+            };
+        assert_eq!(sig_figs(latency), 4);
+        assert_eq!(sig_figs(custom), 2);
+
+        let fresh = Registry::default();
+        let trend = fresh.register("cart_size", MetricKind::Histogram);
+        match fresh.get_handle(trend, TagSet::default()) {
+            Some(MetricHandle::Histogram(h)) => assert_eq!(h.lock().sigfig(), 3),
+            other => panic!("expected a histogram, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn disabled_metrics_do_not_record() {
+        let reg = Registry::default();
+        reg.disable("request_latency");
+        let latency = reg.register("request_latency", MetricKind::Histogram);
+        let requests = reg.register("requests_total", MetricKind::Counter);
+
+        let tags = reg.resolve_tags(&[("scenario", "main")]);
+        assert!(reg.get_handle(latency, tags.clone()).is_none());
+        assert!(reg.get_handle(requests, tags.clone()).is_some());
+
+        // Disabling after registration drops the storage too.
+        reg.disable("requests_total");
+        assert!(reg.get_handle(requests, tags).is_none());
+        assert!(reg.summarize().is_empty());
+    }
+
     #[test]
     fn fold_histogram_summary_merges_series() {
         let reg = Registry::default();
@@ -326,6 +500,57 @@ fn fold_histogram_summary_merges_series() {
         assert_eq!(summary.min, Some(10.0));
     }
 
+    #[test]
+    fn custom_histograms_summarize_like_built_in_latency() {
+        let reg = Registry::default();
+        let built_in = reg.register("request_latency", MetricKind::Histogram);
+        let custom = reg.register("sign_duration", MetricKind::Histogram);
+        let tags = reg.resolve_tags(&[("scenario", "main")]);
+
+        for id in [built_in, custom] {
+            let Some(handle) = reg.get_handle(id, tags.clone()) else {
+                panic!("expected a histogram handle");
+            };
+            for v in 1..=1_000u64 {
+                handle.observe_histogram(v * 10);
+            }
+            // Above the upper bound: clamped, not dropped.
+            handle.observe_histogram(u64::MAX);
+        }
+
+        let summary = |name: &str| {
+            let series = reg.summarize();
+            match series
+                .into_iter()
+                .find(|s| s.name == name)
+                .map(|s| s.values)
+            {
+                Some(MetricValue::Histogram(h)) => h,
+                other => panic!("expected histogram summary for {name}, got {other:?}"),
+            }
+        };
+        let (a, b) = (summary("request_latency"), summary("sign_duration"));
+
+        assert_eq!(b.count, 1_001);
+        assert!(
+            b.max
+                .is_some_and(|max| max >= crate::HISTOGRAM_MAX_VALUE as f64)
+        );
+        for (x, y) in [
+            (a.p50, b.p50),
+            (a.p90, b.p90),
+            (a.p99, b.p99),
+            (a.min, b.min),
+            (a.max, b.max),
+            (a.mean, b.mean),
+            (a.stdev, b.stdev),
+        ] {
+            assert!(y.is_some());
+            assert_eq!(x, y);
+        }
+        assert_eq!(a.percentile(99.9), b.percentile(99.9));
+    }
+
     #[test]
     fn lookup_metric_returns_id_and_kind() {
         let reg = Registry::default();
@@ -346,6 +571,61 @@ fn lookup_metric_returns_id_and_kind() {
         assert_eq!(reg.fold_counter_sum(id, |_| true), 1);
     }
 
+    #[test]
+    fn reset_zeroes_series_but_keeps_handles() {
+        let reg = Registry::default();
+        let c = reg.register("requests_total", MetricKind::Counter);
+        let h = reg.register("request_latency", MetricKind::Histogram);
+        let tags = TagSet::from_sorted_iter([]);
+
+        let Some(MetricHandle::Counter(counter)) = reg.get_handle(c, tags.clone()) else {
+            panic!("expected counter handle");
+        };
+        let Some(MetricHandle::Histogram(hist)) = reg.get_handle(h, tags) else {
+            panic!("expected histogram handle");
+        };
+        counter.fetch_add(5, Ordering::Relaxed);
+        let _ = hist.lock().record(100);
+
+        reg.reset();
+        assert_eq!(reg.fold_counter_sum(c, |_| true), 0);
+        assert!(
+            reg.fold_histogram_summary(h, |_| true)
+                .is_some_and(|s| s.count == 0)
+        );
+
+        // Handles obtained before the reset keep writing into the registry.
+        counter.fetch_add(2, Ordering::Relaxed);
+        assert_eq!(reg.fold_counter_sum(c, |_| true), 2);
+    }
+
+    #[test]
+    fn reset_to_restores_the_series_of_a_snapshot() {
+        let reg = Registry::default();
+        let c = reg.register("setup_calls", MetricKind::Counter);
+        let h = reg.register("request_latency", MetricKind::Histogram);
+        let tags = TagSet::from_sorted_iter([]);
+
+        let Some(MetricHandle::Counter(counter)) = reg.get_handle(c, tags.clone()) else {
+            panic!("expected counter handle");
+        };
+        counter.fetch_add(3, Ordering::Relaxed);
+        let snapshot = reg.snapshot();
+
+        counter.fetch_add(4, Ordering::Relaxed);
+        let Some(MetricHandle::Histogram(hist)) = reg.get_handle(h, tags) else {
+            panic!("expected histogram handle");
+        };
+        let _ = hist.lock().record(100);
+
+        reg.reset_to(&snapshot);
+        assert_eq!(reg.fold_counter_sum(c, |_| true), 3);
+        assert!(
+            reg.fold_histogram_summary(h, |_| true)
+                .is_some_and(|s| s.count == 0)
+        );
+    }
+
     #[test]
     fn fold_rate_sum_aggregates_series() {
         let reg = Registry::default();
@@ -1,8 +1,11 @@
+use std::pin::Pin;
+use std::time::Duration;
 use std::{collections::HashMap, net::SocketAddr};
 
 use tokio::net::TcpListener;
 use tokio::sync::oneshot;
 use tokio_stream::wrappers::TcpListenerStream;
+use tokio_stream::{Stream, StreamExt as _};
 use tonic::{Request, Response, Status};
 
 pub mod echo {
@@ -10,18 +13,112 @@ pub mod echo {
     tonic::include_proto!("_");
 }
 
+/// Echo requests with this message fail with `INVALID_ARGUMENT` and a `google.rpc.BadRequest`
+/// detail in the `grpc-status-details-bin` trailer.
+pub const ECHO_FAIL_WITH_DETAILS: &str = "fail-with-details";
+
+/// Minimal `google.rpc` messages, enough to build a rich error status.
+mod rpc {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Status {
+        #[prost(int32, tag = "1")]
+        pub code: i32,
+        #[prost(string, tag = "2")]
+        pub message: String,
+        #[prost(message, repeated, tag = "3")]
+        pub details: Vec<Any>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Any {
+        #[prost(string, tag = "1")]
+        pub type_url: String,
+        #[prost(bytes = "vec", tag = "2")]
+        pub value: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct BadRequest {
+        #[prost(message, repeated, tag = "1")]
+        pub field_violations: Vec<FieldViolation>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct FieldViolation {
+        #[prost(string, tag = "1")]
+        pub field: String,
+        #[prost(string, tag = "2")]
+        pub description: String,
+    }
+}
+
+fn status_with_details() -> Status {
+    use prost::Message as _;
+
+    let message = "invalid echo request";
+    let bad_request = rpc::BadRequest {
+        field_violations: vec![rpc::FieldViolation {
+            field: "message".to_string(),
+            description: "must not request a failure".to_string(),
+        }],
+    };
+    let status = rpc::Status {
+        code: tonic::Code::InvalidArgument as i32,
+        message: message.to_string(),
+        details: vec![rpc::Any {
+            type_url: "type.googleapis.com/google.rpc.BadRequest".to_string(),
+            value: bad_request.encode_to_vec(),
+        }],
+    };
+
+    Status::with_details(
+        tonic::Code::InvalidArgument,
+        message,
+        status.encode_to_vec().into(),
+    )
+}
+
 #[derive(Debug, Default)]
 struct EchoSvc;
 
+type EchoStream = Pin<Box<dyn Stream<Item = Result<echo::EchoResponse, Status>> + Send>>;
+
 #[tonic::async_trait]
 impl echo::echo_service_server::EchoService for EchoSvc {
+    type EchoStreamStream = EchoStream;
+
     async fn echo(
         &self,
         request: Request<echo::EchoRequest>,
     ) -> std::result::Result<Response<echo::EchoResponse>, Status> {
         let msg = request.into_inner().message;
+        if msg == ECHO_FAIL_WITH_DETAILS {
+            return Err(status_with_details());
+        }
         Ok(Response::new(echo::EchoResponse { message: msg }))
     }
+
+    async fn echo_stream(
+        &self,
+        request: Request<echo::EchoStreamRequest>,
+    ) -> std::result::Result<Response<Self::EchoStreamStream>, Status> {
+        let req = request.into_inner();
+        let interval = Duration::from_millis(u64::try_from(req.interval_ms).unwrap_or(0));
+        let count = usize::try_from(req.count).unwrap_or(0);
+
+        let messages = tokio_stream::iter(std::iter::repeat_n(req.message, count)).then(
+            move |message| async move {
+                tokio::time::sleep(interval).await;
+                Ok(echo::EchoResponse { message })
+            },
+        );
+        let stream: EchoStream = if req.stall {
+            Box::pin(messages.chain(tokio_stream::pending()))
+        } else {
+            Box::pin(messages)
+        };
+        Ok(Response::new(stream))
+    }
 }
 
 #[derive(Debug, Default)]
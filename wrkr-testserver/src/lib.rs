@@ -1,24 +1,39 @@
 use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::hash::{BuildHasher as _, RandomState};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use axum::Router;
 use axum::body::Bytes;
-use axum::extract::{Query, State};
-use axum::http::{HeaderMap, StatusCode};
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use axum::response::sse::{Event, Sse};
 use axum::routing::{any, get, post};
 use serde::{Deserialize, Serialize};
 use tokio::net::TcpListener;
 use tokio::sync::oneshot;
 use tokio::time::{Duration, sleep};
+use tokio_stream::wrappers::IntervalStream;
+use tokio_stream::{Stream, StreamExt as _};
 
 pub const PATH_HELLO: &str = "/hello";
 pub const PATH_PLAINTEXT: &str = "/plaintext";
 pub const PATH_ECHO: &str = "/echo";
+/// Returns the request headers as a JSON object (repeated headers joined with `, `) and sets
+/// every query parameter as a response header, e.g. `/headers?set-cookie=a%3D1`.
+pub const PATH_HEADERS: &str = "/headers";
 pub const PATH_SLOW: &str = "/slow";
+/// Sleeps `?ms=N` milliseconds, plus a uniform `0..=jitter` extra when `?jitter=J` is given.
+pub const PATH_DELAY: &str = "/delay";
 pub const PATH_QP: &str = "/qp";
 pub const PATH_ANALYTICS_AGGREGATE: &str = "/analytics/aggregate";
+/// Responds with the status code given in the next path segment, e.g. `/status/503`.
+pub const PATH_STATUS: &str = "/status";
+/// Server-Sent Events: one `data: <i>` event every `?interval_ms=N` (10ms by default),
+/// `?n=N` events or forever if omitted.
+pub const PATH_EVENTS: &str = "/events";
 
 pub mod grpc;
 pub use grpc::GrpcTestServer;
@@ -29,6 +44,8 @@ pub struct TestServerStats {
     saw_post_header: Arc<AtomicU64>,
     saw_post_body: Arc<AtomicU64>,
     saw_json_content_type: Arc<AtomicU64>,
+    slow_in_flight: Arc<AtomicU64>,
+    slow_max_in_flight: Arc<AtomicU64>,
 }
 
 impl TestServerStats {
@@ -48,6 +65,15 @@ fn inc_saw_json_content_type(&self) {
         self.saw_json_content_type.fetch_add(1, Ordering::Relaxed);
     }
 
+    fn enter_slow(&self) {
+        let now = self.slow_in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        self.slow_max_in_flight.fetch_max(now, Ordering::Relaxed);
+    }
+
+    fn exit_slow(&self) {
+        self.slow_in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+
     pub fn requests_total(&self) -> u64 {
         self.requests_total.load(Ordering::Relaxed)
     }
@@ -63,6 +89,11 @@ pub fn saw_post_body(&self) -> u64 {
     pub fn saw_json_content_type(&self) -> u64 {
         self.saw_json_content_type.load(Ordering::Relaxed)
     }
+
+    /// Highest number of `/slow` requests the server was handling at the same time.
+    pub fn slow_max_in_flight(&self) -> u64 {
+        self.slow_max_in_flight.load(Ordering::Relaxed)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -71,9 +102,13 @@ pub struct TestServerUrls {
     pub hello: String,
     pub plaintext: String,
     pub echo: String,
+    pub headers: String,
     pub slow: String,
+    pub delay: String,
     pub qp: String,
     pub analytics_aggregate: String,
+    pub events: String,
+    pub status: String,
 }
 
 impl TestServerUrls {
@@ -82,12 +117,21 @@ pub fn new(base_url: String) -> Self {
             hello: format!("{base_url}{PATH_HELLO}"),
             plaintext: format!("{base_url}{PATH_PLAINTEXT}"),
             echo: format!("{base_url}{PATH_ECHO}"),
+            headers: format!("{base_url}{PATH_HEADERS}"),
             slow: format!("{base_url}{PATH_SLOW}"),
+            delay: format!("{base_url}{PATH_DELAY}"),
             qp: format!("{base_url}{PATH_QP}"),
             analytics_aggregate: format!("{base_url}{PATH_ANALYTICS_AGGREGATE}"),
+            events: format!("{base_url}{PATH_EVENTS}"),
+            status: format!("{base_url}{PATH_STATUS}"),
             base_url,
         }
     }
+
+    /// URL that makes the server answer with `code`.
+    pub fn status_code(&self, code: u16) -> String {
+        format!("{}/{code}", self.status)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -204,15 +248,41 @@ async fn handle_plaintext(State(stats): State<TestServerStats>) -> &'static str
 
 async fn handle_slow(State(stats): State<TestServerStats>) -> &'static str {
     stats.inc_requests_total();
+    stats.enter_slow();
     sleep(Duration::from_millis(50)).await;
+    stats.exit_slow();
     "slow"
 }
 
+async fn handle_delay(
+    State(stats): State<TestServerStats>,
+    Query(query): Query<HashMap<String, String>>,
+) -> StatusCode {
+    stats.inc_requests_total();
+
+    let param = |key: &str| query.get(key).map(|v| v.parse::<u64>());
+    let (ms, jitter) = match (param("ms"), param("jitter")) {
+        (Some(Ok(ms)), None) => (ms, 0),
+        (Some(Ok(ms)), Some(Ok(jitter))) => (ms, jitter),
+        _ => return StatusCode::BAD_REQUEST,
+    };
+
+    let extra = if jitter > 0 {
+        // A fresh `RandomState` is randomly keyed, which is plenty for spreading delays.
+        RandomState::new().hash_one(stats.requests_total()) % (jitter + 1)
+    } else {
+        0
+    };
+    sleep(Duration::from_millis(ms.saturating_add(extra))).await;
+    StatusCode::OK
+}
+
 async fn handle_echo(
     State(stats): State<TestServerStats>,
+    method: Method,
     headers: HeaderMap,
     body: Bytes,
-) -> (StatusCode, Bytes) {
+) -> (StatusCode, [(&'static str, String); 1], Bytes) {
     stats.inc_requests_total();
 
     if headers.get("x-test").and_then(|v| v.to_str().ok()) == Some("1") {
@@ -229,7 +299,60 @@ async fn handle_echo(
         stats.inc_saw_post_body();
     }
 
-    (StatusCode::OK, body)
+    (
+        StatusCode::OK,
+        [("x-echo-method", method.as_str().to_string())],
+        body,
+    )
+}
+
+async fn handle_headers(
+    State(stats): State<TestServerStats>,
+    Query(query): Query<Vec<(String, String)>>,
+    headers: HeaderMap,
+) -> (StatusCode, HeaderMap, Bytes) {
+    stats.inc_requests_total();
+
+    let mut received: BTreeMap<&str, String> = BTreeMap::new();
+    for (name, value) in &headers {
+        let value = String::from_utf8_lossy(value.as_bytes());
+        received
+            .entry(name.as_str())
+            .and_modify(|v| {
+                v.push_str(", ");
+                v.push_str(&value);
+            })
+            .or_insert_with(|| value.into_owned());
+    }
+
+    let mut reply = HeaderMap::new();
+    for (name, value) in query {
+        let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) else {
+            return (
+                StatusCode::BAD_REQUEST,
+                HeaderMap::new(),
+                Bytes::from_static(b"bad header"),
+            );
+        };
+        reply.append(name, value);
+    }
+
+    match serde_json::to_vec(&received) {
+        Ok(body) => {
+            reply
+                .entry("content-type")
+                .or_insert(HeaderValue::from_static("application/json"));
+            (StatusCode::OK, reply, Bytes::from(body))
+        }
+        Err(_) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            HeaderMap::new(),
+            Bytes::from_static(b"encode error"),
+        ),
+    }
 }
 
 async fn handle_qp(
@@ -245,14 +368,58 @@ async fn handle_qp(
     }
 }
 
+async fn handle_status(
+    State(stats): State<TestServerStats>,
+    Path(code): Path<u16>,
+) -> (StatusCode, Bytes) {
+    stats.inc_requests_total();
+
+    match StatusCode::from_u16(code) {
+        Ok(status) => (status, Bytes::from(code.to_string())),
+        Err(_) => (
+            StatusCode::BAD_REQUEST,
+            Bytes::from_static(b"bad status code"),
+        ),
+    }
+}
+
+async fn handle_events(
+    State(stats): State<TestServerStats>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    stats.inc_requests_total();
+
+    let n = query
+        .get("n")
+        .and_then(|n| n.parse::<usize>().ok())
+        .unwrap_or(usize::MAX);
+    // `tokio::time::interval` panics on a zero period.
+    let interval_ms = query
+        .get("interval_ms")
+        .and_then(|ms| ms.parse::<u64>().ok())
+        .unwrap_or(10)
+        .max(1);
+    let ticks = IntervalStream::new(tokio::time::interval(Duration::from_millis(interval_ms)));
+    let mut next: u64 = 0;
+    Sse::new(ticks.take(n).map(move |_| {
+        let event = Event::default().data(next.to_string());
+        next += 1;
+        Ok(event)
+    }))
+}
+
 pub fn router(stats: TestServerStats) -> Router {
     Router::new()
         .route(PATH_HELLO, get(handle_hello))
         .route(PATH_PLAINTEXT, get(handle_plaintext))
         .route(PATH_SLOW, get(handle_slow))
+        .route(PATH_DELAY, get(handle_delay))
         .route(PATH_ECHO, any(handle_echo))
+        .route(PATH_HEADERS, any(handle_headers))
         .route(PATH_ANALYTICS_AGGREGATE, post(handle_analytics_aggregate))
         .route(PATH_QP, get(handle_qp))
+        .route(PATH_EVENTS, get(handle_events))
+        .route(&format!("{PATH_STATUS}/{{code}}"), any(handle_status))
         .with_state(stats)
 }
 
@@ -0,0 +1,5 @@
+//! TLS pieces shared by the HTTP and gRPC clients.
+
+mod skip_verification;
+
+pub use skip_verification::SkipVerification;
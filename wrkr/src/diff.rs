@@ -0,0 +1,424 @@
+//! `wrkr diff`: compare two JSON run summaries (`wrkr run --output json`).
+
+use std::path::Path;
+use std::time::SystemTime;
+
+use anyhow::Context as _;
+use serde::Deserialize;
+
+use crate::cli::DiffArgs;
+use crate::exit_codes::ExitCode;
+use crate::run_error::RunError;
+
+/// Latency percentiles compared by `wrkr diff`, in the order they are reported.
+const PERCENTILES: [&str; 4] = ["p50", "p90", "p95", "p99"];
+
+/// The parts of a `kind: "summary"` NDJSON line that are compared; other fields are ignored.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SummaryDoc {
+    kind: String,
+    #[serde(default)]
+    scenarios: Vec<ScenarioDoc>,
+    #[serde(default)]
+    totals: TotalsDoc,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScenarioDoc {
+    scenario: String,
+    started_at: Option<String>,
+    ended_at: Option<String>,
+    requests_total: u64,
+    failed_requests_total: u64,
+    latency_seconds: Option<LatencyDoc>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TotalsDoc {
+    requests_total: u64,
+    failed_requests_total: u64,
+    latency_seconds: Option<LatencyDoc>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct LatencyDoc {
+    p50: Option<f64>,
+    p90: Option<f64>,
+    p95: Option<f64>,
+    p99: Option<f64>,
+}
+
+impl LatencyDoc {
+    /// Percentiles in [`PERCENTILES`] order, in microseconds.
+    fn micros(&self) -> [Option<f64>; 4] {
+        [self.p50, self.p90, self.p95, self.p99].map(|v| v.map(|s| s * 1_000_000.0))
+    }
+}
+
+/// Headline numbers of one scenario, or of the whole run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct DiffStats {
+    pub rps: Option<f64>,
+    /// Failed requests as a fraction of all requests.
+    pub error_rate: Option<f64>,
+    /// Latency percentiles in [`PERCENTILES`] order, in microseconds.
+    pub latency: [Option<f64>; 4],
+}
+
+/// Largest changes a candidate may show before it counts as a regression.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DiffLimits {
+    /// Percent.
+    pub latency_increase: f64,
+    /// Percent.
+    pub rps_decrease: f64,
+    /// Percentage points.
+    pub error_rate_increase: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiffMetric {
+    Rps,
+    ErrorRate,
+    Latency(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MetricDelta {
+    pub metric: DiffMetric,
+    pub baseline: f64,
+    /// `None` when the candidate lacks a metric the baseline has, which is a regression.
+    pub candidate: Option<f64>,
+    pub regression: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ScopeDiff {
+    /// Scenario name, or `None` for the run totals.
+    pub scenario: Option<String>,
+    pub deltas: Vec<MetricDelta>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct DiffReport {
+    pub scopes: Vec<ScopeDiff>,
+    /// Scenarios the candidate lost; each counts as a regression.
+    pub only_in_baseline: Vec<String>,
+    pub only_in_candidate: Vec<String>,
+}
+
+impl DiffReport {
+    pub(crate) fn regressions(&self) -> usize {
+        let metrics = self
+            .scopes
+            .iter()
+            .flat_map(|s| &s.deltas)
+            .filter(|d| d.regression)
+            .count();
+        metrics + self.only_in_baseline.len()
+    }
+}
+
+pub async fn diff(args: DiffArgs) -> Result<ExitCode, RunError> {
+    let baseline = load_summary(&args.baseline).await?;
+    let candidate = load_summary(&args.candidate).await?;
+
+    let limits = DiffLimits {
+        latency_increase: args.max_latency_increase,
+        rps_decrease: args.max_rps_decrease,
+        error_rate_increase: args.max_error_rate_increase,
+    };
+    let report = compare(&baseline, &candidate, limits);
+    print!("{}", crate::output::render_diff(&report));
+
+    if report.regressions() > 0 {
+        Ok(ExitCode::Regression)
+    } else {
+        Ok(ExitCode::Success)
+    }
+}
+
+async fn load_summary(path: &Path) -> Result<SummaryDoc, RunError> {
+    let text = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("failed to read summary: {}", path.display()))
+        .map_err(RunError::InvalidInput)?;
+
+    parse_summary(&text)
+        .with_context(|| format!("invalid summary file: {}", path.display()))
+        .map_err(RunError::InvalidInput)
+}
+
+/// Accepts a whole `--output json` stream (the last `summary` line wins) or a single summary
+/// object, pretty-printed or not.
+fn parse_summary(text: &str) -> anyhow::Result<SummaryDoc> {
+    if let Ok(doc) = serde_json::from_str::<SummaryDoc>(text) {
+        anyhow::ensure!(
+            doc.kind == "summary",
+            "expected a `summary` line, got `{}`",
+            doc.kind
+        );
+        return Ok(doc);
+    }
+
+    let mut found = None;
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let value: serde_json::Value = serde_json::from_str(line)
+            .with_context(|| format!("line {}: not valid JSON", idx + 1))?;
+        if value.get("kind").and_then(|k| k.as_str()) == Some("summary") {
+            found = Some(
+                serde_json::from_value::<SummaryDoc>(value)
+                    .with_context(|| format!("line {}: malformed summary", idx + 1))?,
+            );
+        }
+    }
+
+    found.ok_or_else(|| {
+        anyhow::anyhow!("no `summary` line found (save `wrkr run --output json` stdout)")
+    })
+}
+
+fn parse_time(s: Option<&str>) -> Option<SystemTime> {
+    s.and_then(|s| humantime::parse_rfc3339_weak(s).ok())
+}
+
+fn rps(requests: u64, started: Option<SystemTime>, ended: Option<SystemTime>) -> Option<f64> {
+    let secs = ended?.duration_since(started?).ok()?.as_secs_f64();
+    (secs > 0.0).then(|| requests as f64 / secs)
+}
+
+fn error_rate(requests: u64, failed: u64) -> Option<f64> {
+    (requests > 0).then(|| failed as f64 / requests as f64)
+}
+
+fn scenario_stats(s: &ScenarioDoc) -> DiffStats {
+    DiffStats {
+        rps: rps(
+            s.requests_total,
+            parse_time(s.started_at.as_deref()),
+            parse_time(s.ended_at.as_deref()),
+        ),
+        error_rate: error_rate(s.requests_total, s.failed_requests_total),
+        latency: s
+            .latency_seconds
+            .as_ref()
+            .map(LatencyDoc::micros)
+            .unwrap_or_default(),
+    }
+}
+
+/// Run totals; RPS spans from the first scenario start to the last scenario end.
+fn totals_stats(doc: &SummaryDoc) -> DiffStats {
+    let started = doc
+        .scenarios
+        .iter()
+        .filter_map(|s| parse_time(s.started_at.as_deref()))
+        .min();
+    let ended = doc
+        .scenarios
+        .iter()
+        .filter_map(|s| parse_time(s.ended_at.as_deref()))
+        .max();
+
+    DiffStats {
+        rps: rps(doc.totals.requests_total, started, ended),
+        error_rate: error_rate(doc.totals.requests_total, doc.totals.failed_requests_total),
+        latency: doc
+            .totals
+            .latency_seconds
+            .as_ref()
+            .map(LatencyDoc::micros)
+            .unwrap_or_default(),
+    }
+}
+
+/// Deltas between two stats. Metrics missing from the baseline are skipped; a metric the
+/// candidate lost is a regression.
+pub(crate) fn compare_stats(
+    baseline: &DiffStats,
+    candidate: &DiffStats,
+    limits: DiffLimits,
+) -> Vec<MetricDelta> {
+    let mut out = Vec::new();
+
+    out.extend(delta(
+        DiffMetric::Rps,
+        baseline.rps,
+        candidate.rps,
+        |b, c| c < b * (1.0 - limits.rps_decrease / 100.0),
+    ));
+
+    out.extend(delta(
+        DiffMetric::ErrorRate,
+        baseline.error_rate,
+        candidate.error_rate,
+        |b, c| (c - b) * 100.0 > limits.error_rate_increase,
+    ));
+
+    for (idx, name) in PERCENTILES.iter().enumerate() {
+        out.extend(delta(
+            DiffMetric::Latency(name),
+            baseline.latency[idx],
+            candidate.latency[idx],
+            |b, c| c > b * (1.0 + limits.latency_increase / 100.0),
+        ));
+    }
+
+    out
+}
+
+fn delta(
+    metric: DiffMetric,
+    baseline: Option<f64>,
+    candidate: Option<f64>,
+    regressed: impl Fn(f64, f64) -> bool,
+) -> Option<MetricDelta> {
+    let baseline = baseline?;
+    Some(MetricDelta {
+        metric,
+        baseline,
+        candidate,
+        regression: candidate.is_none_or(|c| regressed(baseline, c)),
+    })
+}
+
+fn compare(baseline: &SummaryDoc, candidate: &SummaryDoc, limits: DiffLimits) -> DiffReport {
+    let mut report = DiffReport::default();
+
+    for b in &baseline.scenarios {
+        match candidate
+            .scenarios
+            .iter()
+            .find(|c| c.scenario == b.scenario)
+        {
+            Some(c) => report.scopes.push(ScopeDiff {
+                scenario: Some(b.scenario.clone()),
+                deltas: compare_stats(&scenario_stats(b), &scenario_stats(c), limits),
+            }),
+            None => report.only_in_baseline.push(b.scenario.clone()),
+        }
+    }
+    report.only_in_candidate = candidate
+        .scenarios
+        .iter()
+        .filter(|c| !baseline.scenarios.iter().any(|b| b.scenario == c.scenario))
+        .map(|c| c.scenario.clone())
+        .collect();
+
+    report.scopes.push(ScopeDiff {
+        scenario: None,
+        deltas: compare_stats(&totals_stats(baseline), &totals_stats(candidate), limits),
+    });
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: DiffLimits = DiffLimits {
+        latency_increase: 10.0,
+        rps_decrease: 10.0,
+        error_rate_increase: 1.0,
+    };
+
+    fn summary_line(requests: u64, failed: u64, p95: f64) -> String {
+        format!(
+            r#"{{"schema":"wrkr.ndjson.v1","kind":"summary","scenarios":[{{"scenario":"main","startedAt":"2024-01-01T00:00:00.000Z","endedAt":"2024-01-01T00:00:10.000Z","requestsTotal":{requests},"failedRequestsTotal":{failed},"latencySeconds":{{"p50":0.01,"p95":{p95},"count":{requests}}}}}],"totals":{{"requestsTotal":{requests},"failedRequestsTotal":{failed}}}}}"#
+        )
+    }
+
+    fn parse(text: &str) -> SummaryDoc {
+        parse_summary(text).unwrap_or_else(|e| panic!("{e:#}"))
+    }
+
+    #[test]
+    fn parse_summary_takes_last_summary_line_of_ndjson() {
+        let text = format!(
+            "{{\"schema\":\"wrkr.ndjson.v1\",\"kind\":\"runStart\"}}\n{}\n{}\n",
+            summary_line(100, 0, 0.02),
+            summary_line(200, 0, 0.02)
+        );
+        let doc = parse(&text);
+        assert_eq!(doc.scenarios[0].requests_total, 200);
+
+        assert!(parse_summary("{\"kind\":\"progress\"}\n").is_err());
+        assert!(parse_summary("not json").is_err());
+    }
+
+    #[test]
+    fn compare_flags_only_changes_beyond_limits() {
+        let baseline = parse(&summary_line(1000, 10, 0.020));
+        let same = compare(&baseline, &parse(&summary_line(950, 12, 0.021)), LIMITS);
+        assert_eq!(same.regressions(), 0);
+
+        let worse = compare(&baseline, &parse(&summary_line(800, 40, 0.030)), LIMITS);
+        let main = &worse.scopes[0];
+        assert_eq!(main.scenario.as_deref(), Some("main"));
+        let regressed = main
+            .deltas
+            .iter()
+            .filter(|d| d.regression)
+            .map(|d| d.metric)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            regressed,
+            vec![
+                DiffMetric::Rps,
+                DiffMetric::ErrorRate,
+                DiffMetric::Latency("p95")
+            ]
+        );
+
+        let rps = &main.deltas[0];
+        assert_eq!((rps.baseline, rps.candidate), (100.0, Some(80.0)));
+        // Totals carry no latency here, and their RPS spans the scenarios' window.
+        assert_eq!(worse.scopes[1].scenario, None);
+        assert_eq!(worse.scopes[1].deltas.len(), 2);
+    }
+
+    #[test]
+    fn compare_lists_scenarios_missing_on_either_side() {
+        let baseline = parse(&summary_line(100, 0, 0.02));
+        let candidate = parse(&summary_line(100, 0, 0.02).replace("\"main\"", "\"other\""));
+        let report = compare(&baseline, &candidate, LIMITS);
+
+        assert_eq!(report.only_in_baseline, vec!["main".to_string()]);
+        assert_eq!(report.only_in_candidate, vec!["other".to_string()]);
+        assert_eq!(report.scopes.len(), 1);
+        // Losing a scenario is a regression; gaining one is not.
+        assert_eq!(report.regressions(), 1);
+    }
+
+    #[test]
+    fn compare_flags_metrics_the_candidate_lost() {
+        let baseline = parse(&summary_line(100, 0, 0.02));
+        let candidate = parse(&summary_line(100, 0, 0.02).replace(",\"p95\":0.02", ""));
+        let report = compare(&baseline, &candidate, LIMITS);
+
+        let lost = report.scopes[0]
+            .deltas
+            .iter()
+            .filter(|d| d.regression)
+            .map(|d| (d.metric, d.candidate))
+            .collect::<Vec<_>>();
+        assert_eq!(lost, vec![(DiffMetric::Latency("p95"), None)]);
+        assert_eq!(report.regressions(), 1);
+
+        // Metrics the baseline lacks (here p90) are not compared at all.
+        assert!(
+            report.scopes[0]
+                .deltas
+                .iter()
+                .all(|d| d.metric != DiffMetric::Latency("p90"))
+        );
+    }
+}
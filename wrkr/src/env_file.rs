@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use anyhow::Context as _;
+
+/// Read a dotenv-style file into `KEY=VALUE` pairs (in file order).
+pub(crate) fn load_env_file(path: &Path) -> anyhow::Result<Vec<(String, String)>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read env file: {}", path.display()))?;
+    parse_env_file(&text).with_context(|| format!("invalid env file: {}", path.display()))
+}
+
+/// Parse dotenv-style text.
+///
+/// Supported syntax:
+/// - blank lines and lines starting with `#` are ignored
+/// - an optional `export ` prefix before the key
+/// - unquoted values are trimmed; ` #` starts a trailing comment
+/// - single-quoted values are taken literally
+/// - double-quoted values support `\n`, `\t`, `\"` and `\\` escapes
+fn parse_env_file(text: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let mut out = Vec::new();
+
+    for (idx, raw) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("line {line_no}: expected KEY=VALUE"))?;
+
+        let key = key.trim();
+        if key.is_empty() || key.chars().any(char::is_whitespace) {
+            anyhow::bail!("line {line_no}: invalid key `{key}`");
+        }
+
+        let value = parse_value(value.trim()).with_context(|| format!("line {line_no}"))?;
+        out.push((key.to_string(), value));
+    }
+
+    Ok(out)
+}
+
+fn parse_value(value: &str) -> anyhow::Result<String> {
+    if let Some(rest) = value.strip_prefix('\'') {
+        let (inner, tail) = rest
+            .split_once('\'')
+            .context("unterminated single-quoted value")?;
+        ensure_only_comment(tail)?;
+        return Ok(inner.to_string());
+    }
+
+    if let Some(rest) = value.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    ensure_only_comment(&rest[i + 1..])?;
+                    return Ok(out);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some('r') => out.push('\r'),
+                    Some(other) => out.push(other),
+                    None => break,
+                },
+                c => out.push(c),
+            }
+        }
+        anyhow::bail!("unterminated double-quoted value");
+    }
+
+    let value = match value.find(" #") {
+        Some(pos) => &value[..pos],
+        None => value,
+    };
+    Ok(value.trim_end().to_string())
+}
+
+fn ensure_only_comment(tail: &str) -> anyhow::Result<()> {
+    let tail = tail.trim();
+    if tail.is_empty() || tail.starts_with('#') {
+        Ok(())
+    } else {
+        anyhow::bail!("unexpected characters after quoted value: `{tail}`")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Vec<(String, String)> {
+        parse_env_file(text).unwrap_or_else(|e| panic!("failed to parse env file: {e:#}"))
+    }
+
+    fn pair(k: &str, v: &str) -> (String, String) {
+        (k.to_string(), v.to_string())
+    }
+
+    #[test]
+    fn parses_comments_exports_and_quotes() {
+        let text = r#"
+# staging
+BASE_URL=https://staging.example.com   # trailing comment
+export TOKEN = 'abc#123'
+GREETING="hello \"world\"\nbye" # comment
+EMPTY=
+HASH=a#b
+"#;
+
+        assert_eq!(
+            parse(text),
+            vec![
+                pair("BASE_URL", "https://staging.example.com"),
+                pair("TOKEN", "abc#123"),
+                pair("GREETING", "hello \"world\"\nbye"),
+                pair("EMPTY", ""),
+                pair("HASH", "a#b"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(parse_env_file("NO_EQUALS").is_err());
+        assert!(parse_env_file("=value").is_err());
+        assert!(parse_env_file("KEY=\"unterminated").is_err());
+        assert!(parse_env_file("KEY='a' b").is_err());
+    }
+}
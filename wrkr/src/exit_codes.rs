@@ -12,6 +12,16 @@ pub enum ExitCode {
     /// Checks and thresholds failed.
     ChecksAndThresholdsFailed = 12,
 
+    /// The run was stopped early by `--abort-on-error-rate` or `--max-duration`. Takes precedence
+    /// over failed checks and thresholds, whose metrics are partial.
+    Aborted = 13,
+
+    /// `--fail-on-no-requests` was set and a run made no requests at all.
+    NoRequests = 14,
+
+    /// `wrkr diff` found a regression beyond the configured limits.
+    Regression = 15,
+
     /// Script execution error (runtime raised an error while executing the user script).
     ScriptError = 20,
 
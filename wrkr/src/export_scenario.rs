@@ -6,14 +6,17 @@
 use crate::scenario_yaml;
 
 pub async fn export_scenario(args: ExportScenarioArgs) -> Result<ExitCode, RunError> {
-    let env = merged_env(&args.env).map_err(RunError::InvalidInput)?;
+    let env = merged_env(&args.env_file, &args.env).map_err(RunError::InvalidInput)?;
     let cfg = wrkr_core::RunConfig {
         iterations: args.iterations,
         vus: args.vus,
         duration: args.duration,
+        warmup_iterations: None,
+        tags: Vec::new(),
     };
 
-    let runtime = runtime::create_runtime(&args.script).map_err(classify_runtime_create_error)?;
+    let runtime = runtime::create_runtime(&args.script, &runtime::RuntimeOptions::default())
+        .map_err(classify_runtime_create_error)?;
     let run_ctx = runtime.create_run_context(&env);
 
     let opts = runtime
@@ -0,0 +1,74 @@
+//! wrkr's own diagnostics (`--log-level`, `--log-format`).
+//!
+//! Events go to stderr so they never interleave with the NDJSON stream that `--output json`
+//! writes to stdout.
+
+use std::io::IsTerminal as _;
+
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::SubscriberExt as _;
+use tracing_subscriber::util::SubscriberInitExt as _;
+
+use crate::cli::{LogFormat, LogLevel};
+
+pub(crate) fn init(level: LogLevel, format: LogFormat) {
+    let fmt = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+    let registry = tracing_subscriber::registry().with(filter(level));
+
+    // Fails only if a subscriber is already installed, which leaves that one in charge.
+    let _ = match format {
+        LogFormat::Text => registry
+            .with(fmt.with_ansi(std::io::stderr().is_terminal()))
+            .try_init(),
+        LogFormat::Json => registry.with(fmt.json().flatten_event(true)).try_init(),
+    };
+}
+
+/// Report the error a command failed with. With `--log-format json` it becomes an `error` event
+/// so that every stderr line stays parseable; otherwise it is printed as is.
+pub(crate) fn report_failure(format: LogFormat, err: impl std::fmt::Display) {
+    match format {
+        LogFormat::Text => eprintln!("{err}"),
+        LogFormat::Json => tracing::error!(error = %err, "command failed"),
+    }
+}
+
+/// `level` for wrkr's crates (`wrkr` matches every `wrkr_*` target by prefix). Dependencies such
+/// as hyper and h2 trace connection internals at debug and below, so only their warnings and
+/// errors are let through.
+fn filter(level: LogLevel) -> Targets {
+    let level = match level {
+        LogLevel::Off => LevelFilter::OFF,
+        LogLevel::Error => LevelFilter::ERROR,
+        LogLevel::Warn => LevelFilter::WARN,
+        LogLevel::Info => LevelFilter::INFO,
+        LogLevel::Debug => LevelFilter::DEBUG,
+        LogLevel::Trace => LevelFilter::TRACE,
+    };
+    Targets::new()
+        .with_default(level.min(LevelFilter::WARN))
+        .with_target("wrkr", level)
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::Level;
+
+    use super::*;
+
+    #[test]
+    fn filter_keeps_dependencies_at_warn_or_quieter() {
+        let debug = filter(LogLevel::Debug);
+        assert!(debug.would_enable("wrkr_core::run", &Level::DEBUG));
+        assert!(debug.would_enable("wrkr", &Level::INFO));
+        assert!(!debug.would_enable("h2::codec", &Level::DEBUG));
+        assert!(debug.would_enable("h2::codec", &Level::WARN));
+
+        let error = filter(LogLevel::Error);
+        assert!(!error.would_enable("wrkr_core::abort", &Level::WARN));
+        assert!(!error.would_enable("hyper", &Level::WARN));
+
+        let off = filter(LogLevel::Off);
+        assert!(!off.would_enable("wrkr_core::vu", &Level::ERROR));
+    }
+}
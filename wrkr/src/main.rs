@@ -1,9 +1,15 @@
 mod cli;
+mod diff;
+mod env_file;
 mod exit_codes;
 mod export_scenario;
 mod init;
+mod logging;
 mod output;
+mod preflight;
+mod repeat;
 mod run;
+mod run_config;
 mod run_error;
 mod run_support;
 mod runtime;
@@ -33,11 +39,14 @@ async fn main() {
         }
     };
 
+    logging::init(cli.log_level, cli.log_format);
+    let log_format = cli.log_format;
+
     let code = match cli.command {
-        cli::Command::Run(args) => match run::run(args).await {
+        cli::Command::Run(args) => match run::run(*args).await {
             Ok(code) => code.as_i32(),
             Err(err) => {
-                eprintln!("{err}");
+                logging::report_failure(log_format, &err);
                 err.exit_code().as_i32()
             }
         },
@@ -46,16 +55,23 @@ async fn main() {
                 match export_scenario::export_scenario(args).await {
                     Ok(code) => code.as_i32(),
                     Err(err) => {
-                        eprintln!("{err}");
+                        logging::report_failure(log_format, &err);
                         err.exit_code().as_i32()
                     }
                 }
             }
         },
+        cli::Command::Diff(args) => match diff::diff(args).await {
+            Ok(code) => code.as_i32(),
+            Err(err) => {
+                logging::report_failure(log_format, &err);
+                err.exit_code().as_i32()
+            }
+        },
         cli::Command::Init(args) => match init::init(args).await {
             Ok(()) => exit_codes::ExitCode::Success.as_i32(),
             Err(err) => {
-                eprintln!("{err:#}");
+                logging::report_failure(log_format, format_args!("{err:#}"));
                 exit_codes::ExitCode::RuntimeError.as_i32()
             }
         },
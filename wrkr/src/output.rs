@@ -1,18 +1,314 @@
-use crate::cli::OutputFormat;
+use crate::cli::{OutputFormat, OutputTarget, TimeUnit};
+use crate::repeat::ScenarioRepeatStats;
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 
+mod hdr;
 mod human;
 mod json;
 
+pub(crate) use human::render_diff;
+
 pub(crate) trait OutputFormatter: Send + Sync {
-    fn print_header(&self, script_path: &Path, scenarios: &[wrkr_core::ScenarioConfig]);
+    /// Echo the resolved run configuration (after CLI overrides) before VUs start.
+    fn print_header(
+        &self,
+        script_path: &Path,
+        scenarios: &[wrkr_core::ScenarioConfig],
+        thresholds: &[wrkr_core::ThresholdSet],
+    );
     fn progress(&self) -> Option<wrkr_core::ProgressFn>;
     fn print_summary(&self, summary: &wrkr_core::RunSummary) -> anyhow::Result<()>;
+    /// Run-to-run spread printed after the last of several `--repeat` runs.
+    fn print_repeat_summary(&self, stats: &[ScenarioRepeatStats]) -> anyhow::Result<()>;
+}
+
+pub(crate) fn formatter(
+    targets: &[OutputTarget],
+    no_buffer: bool,
+    quiet: bool,
+    summary_time_unit: Option<TimeUnit>,
+    renames: &[(String, String)],
+) -> anyhow::Result<Box<dyn OutputFormatter>> {
+    if targets.iter().filter(|t| t.path.is_none()).count() > 1 {
+        anyhow::bail!(
+            "at most one --output can write to stdout (use json=PATH or hdr=PATH for the others)"
+        );
+    }
+
+    let mut outs = targets
+        .iter()
+        .map(|target| -> anyhow::Result<Box<dyn OutputFormatter>> {
+            Ok(match (target.format, &target.path) {
+                (OutputFormat::HumanReadable, _) => {
+                    Box::new(human::HumanReadableOutput::new(summary_time_unit))
+                }
+                (OutputFormat::Json, None) => Box::new(json::JsonOutput::new(no_buffer)),
+                (OutputFormat::Json, Some(path)) => {
+                    Box::new(json::JsonOutput::to_file(path, no_buffer)?)
+                }
+                (OutputFormat::Hdr, None) => Box::new(hdr::HdrOutput::new()),
+                (OutputFormat::Hdr, Some(path)) => Box::new(hdr::HdrOutput::to_file(path)?),
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let out = match outs.len() {
+        1 => outs.remove(0),
+        _ => Box::new(FanOut(outs)),
+    };
+    let out: Box<dyn OutputFormatter> = match renames {
+        [] => out,
+        _ => Box::new(RenameMetrics {
+            out,
+            names: Arc::new(renames.iter().cloned().collect()),
+        }),
+    };
+
+    Ok(if quiet { Box::new(Quiet(out)) } else { out })
+}
+
+/// Several `--output` formatters active at once; each sees every event.
+struct FanOut(Vec<Box<dyn OutputFormatter>>);
+
+impl OutputFormatter for FanOut {
+    fn print_header(
+        &self,
+        script_path: &Path,
+        scenarios: &[wrkr_core::ScenarioConfig],
+        thresholds: &[wrkr_core::ThresholdSet],
+    ) {
+        for out in &self.0 {
+            out.print_header(script_path, scenarios, thresholds);
+        }
+    }
+
+    fn progress(&self) -> Option<wrkr_core::ProgressFn> {
+        let sinks: Vec<_> = self.0.iter().filter_map(|out| out.progress()).collect();
+        if sinks.is_empty() {
+            return None;
+        }
+        Some(Arc::new(move |update: wrkr_core::ProgressUpdate| {
+            for sink in &sinks {
+                sink(update.clone());
+            }
+        }))
+    }
+
+    fn print_summary(&self, summary: &wrkr_core::RunSummary) -> anyhow::Result<()> {
+        // One failing output must not keep the summary from the others.
+        let results: Vec<_> = self
+            .0
+            .iter()
+            .map(|out| out.print_summary(summary))
+            .collect();
+        results.into_iter().collect()
+    }
+
+    fn print_repeat_summary(&self, stats: &[ScenarioRepeatStats]) -> anyhow::Result<()> {
+        let results: Vec<_> = self
+            .0
+            .iter()
+            .map(|out| out.print_repeat_summary(stats))
+            .collect();
+        results.into_iter().collect()
+    }
+}
+
+/// Drops live progress output but keeps the header and final summary (`--quiet`).
+struct Quiet(Box<dyn OutputFormatter>);
+
+impl OutputFormatter for Quiet {
+    fn print_header(
+        &self,
+        script_path: &Path,
+        scenarios: &[wrkr_core::ScenarioConfig],
+        thresholds: &[wrkr_core::ThresholdSet],
+    ) {
+        self.0.print_header(script_path, scenarios, thresholds);
+    }
+
+    fn progress(&self) -> Option<wrkr_core::ProgressFn> {
+        None
+    }
+
+    fn print_summary(&self, summary: &wrkr_core::RunSummary) -> anyhow::Result<()> {
+        self.0.print_summary(summary)
+    }
+
+    fn print_repeat_summary(&self, stats: &[ScenarioRepeatStats]) -> anyhow::Result<()> {
+        self.0.print_repeat_summary(stats)
+    }
+}
+
+/// Reports metrics under other names (`--rename-metric`), e.g. the ones a dashboard expects.
+///
+/// Only what the formatters print is renamed; thresholds are evaluated before this point, so
+/// they keep matching the original names.
+struct RenameMetrics {
+    out: Box<dyn OutputFormatter>,
+    names: Arc<HashMap<String, String>>,
+}
+
+fn rename_metric(names: &HashMap<String, String>, name: &mut String) {
+    if let Some(alias) = names.get(name.as_str()) {
+        name.clone_from(alias);
+    }
+}
+
+impl OutputFormatter for RenameMetrics {
+    fn print_header(
+        &self,
+        script_path: &Path,
+        scenarios: &[wrkr_core::ScenarioConfig],
+        thresholds: &[wrkr_core::ThresholdSet],
+    ) {
+        let mut thresholds = thresholds.to_vec();
+        for set in &mut thresholds {
+            rename_metric(&self.names, &mut set.metric);
+        }
+        self.out.print_header(script_path, scenarios, &thresholds);
+    }
+
+    fn progress(&self) -> Option<wrkr_core::ProgressFn> {
+        let sink = self.out.progress()?;
+        let names = self.names.clone();
+        Some(Arc::new(move |mut update: wrkr_core::ProgressUpdate| {
+            for (name, _) in &mut update.metrics.gauges {
+                rename_metric(&names, name);
+            }
+            sink(update);
+        }))
+    }
+
+    fn print_summary(&self, summary: &wrkr_core::RunSummary) -> anyhow::Result<()> {
+        let mut summary = summary.clone();
+        for series in &mut summary.metrics {
+            rename_metric(&self.names, &mut series.name);
+        }
+        for violation in &mut summary.threshold_violations {
+            rename_metric(&self.names, &mut violation.metric);
+        }
+        for result in &mut summary.threshold_results {
+            rename_metric(&self.names, &mut result.metric);
+        }
+        self.out.print_summary(&summary)
+    }
+
+    fn print_repeat_summary(&self, stats: &[ScenarioRepeatStats]) -> anyhow::Result<()> {
+        self.out.print_repeat_summary(stats)
+    }
 }
 
-pub(crate) fn formatter(format: OutputFormat) -> Box<dyn OutputFormatter> {
-    match format {
-        OutputFormat::HumanReadable => Box::new(human::HumanReadableOutput::new()),
-        OutputFormat::Json => Box::new(json::JsonOutput::new()),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stdout(format: OutputFormat) -> OutputTarget {
+        OutputTarget { format, path: None }
+    }
+
+    fn build(targets: &[OutputTarget], quiet: bool) -> Box<dyn OutputFormatter> {
+        formatter(targets, false, quiet, None, &[]).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    #[test]
+    fn quiet_formatter_has_no_progress() {
+        for format in [OutputFormat::HumanReadable, OutputFormat::Json] {
+            assert!(build(&[stdout(format)], true).progress().is_none());
+        }
+        assert!(
+            build(&[stdout(OutputFormat::Json)], false)
+                .progress()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn several_outputs_all_receive_the_run() {
+        let path = std::env::temp_dir().join(format!("wrkr-fanout-{}.ndjson", std::process::id()));
+        let out = build(
+            &[
+                stdout(OutputFormat::HumanReadable),
+                OutputTarget {
+                    format: OutputFormat::Json,
+                    path: Some(path.clone()),
+                },
+            ],
+            false,
+        );
+        out.print_header(Path::new("bench.lua"), &[], &[]);
+        assert!(out.progress().is_some());
+        out.print_summary(&wrkr_core::RunSummary::default())
+            .unwrap_or_else(|e| panic!("{e}"));
+
+        let written = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{e}"));
+        let _ = std::fs::remove_file(&path);
+        let kinds: Vec<String> = written
+            .lines()
+            .map(|line| {
+                let v: serde_json::Value =
+                    serde_json::from_str(line).unwrap_or_else(|e| panic!("{e}: {line}"));
+                v["kind"].as_str().unwrap_or_default().to_string()
+            })
+            .collect();
+        assert_eq!(kinds, ["runStart", "summary"]);
+    }
+
+    #[test]
+    fn only_one_output_may_use_stdout() {
+        let targets = [
+            stdout(OutputFormat::HumanReadable),
+            stdout(OutputFormat::Json),
+        ];
+        assert!(formatter(&targets, false, false, None, &[]).is_err());
+    }
+
+    #[test]
+    fn renamed_metrics_reach_the_output_under_their_alias() {
+        let path = std::env::temp_dir().join(format!("wrkr-rename-{}.ndjson", std::process::id()));
+        let out = formatter(
+            &[OutputTarget {
+                format: OutputFormat::Json,
+                path: Some(path.clone()),
+            }],
+            false,
+            false,
+            None,
+            &[(
+                "request_latency".to_string(),
+                "http_req_duration".to_string(),
+            )],
+        )
+        .unwrap_or_else(|e| panic!("{e}"));
+
+        let result = |metric: &str| wrkr_core::ThresholdResult {
+            metric: metric.to_string(),
+            tags: Vec::new(),
+            expression: "p(95) < 300".to_string(),
+            observed: Some(120.0),
+            limit: Some(300.0),
+            passed: true,
+            no_data: false,
+            description: None,
+        };
+        let summary = wrkr_core::RunSummary {
+            threshold_results: vec![result("request_latency"), result("iteration_duration")],
+            ..Default::default()
+        };
+        out.print_summary(&summary)
+            .unwrap_or_else(|e| panic!("{e}"));
+
+        let written = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{e}"));
+        let _ = std::fs::remove_file(&path);
+        let line: serde_json::Value =
+            serde_json::from_str(written.trim()).unwrap_or_else(|e| panic!("{e}: {written}"));
+        let metrics: Vec<&str> = line["thresholds"]["results"]
+            .as_array()
+            .unwrap_or_else(|| panic!("no threshold results: {line}"))
+            .iter()
+            .filter_map(|r| r["metric"].as_str())
+            .collect();
+        assert_eq!(metrics, ["http_req_duration", "iteration_duration"]);
     }
 }
@@ -0,0 +1,169 @@
+use anyhow::Context as _;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::OutputFormatter;
+use crate::repeat::ScenarioRepeatStats;
+
+/// `--output hdr`: one NDJSON line per histogram series of the final summary, carrying the
+/// histogram itself so runs from several hosts can be merged exactly. Prints nothing else.
+pub(crate) struct HdrOutput {
+    out: Mutex<Box<dyn Write + Send>>,
+    /// Summaries printed so far; with `--repeat`, every run prints one.
+    runs: AtomicU64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HdrHistogramLine<'a> {
+    schema: &'static str,
+    kind: &'static str,
+    /// Which `--repeat` run the histogram is from, starting at 1.
+    run: u64,
+    metric: &'a str,
+    tags: BTreeMap<&'a str, &'a str>,
+    count: u64,
+    /// HdrHistogram V2 compressed encoding, base64.
+    histogram: String,
+}
+
+impl HdrOutput {
+    pub(crate) fn new() -> Self {
+        Self::with_writer(Box::new(std::io::stdout()))
+    }
+
+    /// Lines written to `path` (created or truncated) instead of stdout.
+    pub(crate) fn to_file(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("failed to create output file: {}", path.display()))?;
+        Ok(Self::with_writer(Box::new(file)))
+    }
+
+    fn with_writer(writer: Box<dyn Write + Send>) -> Self {
+        Self {
+            out: Mutex::new(writer),
+            runs: AtomicU64::new(0),
+        }
+    }
+}
+
+impl OutputFormatter for HdrOutput {
+    fn print_header(
+        &self,
+        _script_path: &Path,
+        _scenarios: &[wrkr_core::ScenarioConfig],
+        _thresholds: &[wrkr_core::ThresholdSet],
+    ) {
+    }
+
+    fn progress(&self) -> Option<wrkr_core::ProgressFn> {
+        None
+    }
+
+    fn print_summary(&self, summary: &wrkr_core::RunSummary) -> anyhow::Result<()> {
+        let run = self.runs.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut text = String::new();
+        for line in histogram_lines(summary, run) {
+            text.push_str(&serde_json::to_string(&line)?);
+            text.push('\n');
+        }
+
+        let mut out = self
+            .out
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        out.write_all(text.as_bytes())
+            .and_then(|()| out.flush())
+            .context("failed to write HDR histograms")
+    }
+
+    fn print_repeat_summary(&self, _stats: &[ScenarioRepeatStats]) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Series without samples are left out; there is nothing to merge.
+fn histogram_lines(summary: &wrkr_core::RunSummary, run: u64) -> Vec<HdrHistogramLine<'_>> {
+    summary
+        .metrics
+        .iter()
+        .filter_map(|series| match &series.values {
+            wrkr_core::MetricValue::Histogram(h) if h.count > 0 => Some(HdrHistogramLine {
+                schema: super::json::NDJSON_SCHEMA,
+                kind: "histogram",
+                run,
+                metric: &series.name,
+                tags: series
+                    .tags
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect(),
+                count: h.count,
+                histogram: h.to_compressed_base64()?,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_non_empty_histogram_series_gets_a_line() {
+        let run_ctx = wrkr_core::RunScenariosContext::new(
+            std::sync::Arc::from([]),
+            String::new(),
+            std::path::PathBuf::new(),
+        );
+        let metrics = &run_ctx.metrics;
+        let latency = metrics.register("request_latency", wrkr_core::MetricKind::Histogram);
+        let unused = metrics.register("login_duration", wrkr_core::MetricKind::Histogram);
+        let requests = metrics.register("requests_total", wrkr_core::MetricKind::Counter);
+        for (scenario, values) in [("browse", &[1_200, 1_500][..]), ("checkout", &[9_000])] {
+            let tags = metrics.resolve_tags(&[("scenario", scenario)]);
+            for v in values {
+                if let Some(h) = metrics.get_handle(latency, tags.clone()) {
+                    h.observe_histogram(*v);
+                }
+            }
+            if let Some(h) = metrics.get_handle(requests, tags.clone()) {
+                h.increment(1);
+            }
+            let _ = metrics.get_handle(unused, tags);
+        }
+        let summary = wrkr_core::RunSummary {
+            metrics: metrics.summarize(),
+            ..Default::default()
+        };
+
+        let mut lines = histogram_lines(&summary, 2);
+        lines.sort_by_key(|l| l.tags.get("scenario").copied());
+        let counts: Vec<(&str, Option<&str>, u64)> = lines
+            .iter()
+            .map(|l| (l.metric, l.tags.get("scenario").copied(), l.count))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                ("request_latency", Some("browse"), 2),
+                ("request_latency", Some("checkout"), 1),
+            ]
+        );
+
+        let decoded = wrkr_core::HistogramSummary::from_compressed_base64(&lines[0].histogram)
+            .unwrap_or_else(|| panic!("undecodable histogram: {}", lines[0].histogram));
+        assert_eq!(decoded.count, 2);
+        assert_eq!(decoded.min, Some(1_200.0));
+
+        let json = serde_json::to_value(&lines[1]).unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(json["kind"], "histogram");
+        assert_eq!(json["run"], 2);
+        assert_eq!(json["tags"]["scenario"], "checkout");
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -7,40 +7,85 @@
 mod progress;
 mod summary;
 
-use format::{format_bytes, format_rate};
+use format::{format_bytes, format_rate, format_sparkline};
 use progress::HumanProgress;
 use summary::render;
+pub(crate) use summary::render_diff;
 
 use crate::output::human::format::*;
 
 use super::OutputFormatter;
 
+/// Number of recent progress intervals shown in the RPS sparkline.
+const SPARKLINE_WIDTH: usize = 12;
+
+/// Counters of one scenario as of its previous progress update.
+#[derive(Debug, Default, Clone, Copy)]
+struct ScenarioTotals {
+    iterations: u64,
+    errors: u64,
+    requests: u64,
+    failed_requests: u64,
+}
+
+/// What the progress line of one scenario remembers between updates.
+#[derive(Default)]
+struct ScenarioTrend {
+    totals: ScenarioTotals,
+    rps: VecDeque<f64>,
+}
+
 pub(crate) struct HumanReadableOutput {
     progress: Arc<HumanProgress>,
     max_elapsed_ms: Arc<AtomicU64>,
+    /// `--summary-time-unit`; `None` picks a unit per value.
+    time_unit: Option<crate::cli::TimeUnit>,
 }
 
 impl HumanReadableOutput {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(time_unit: Option<crate::cli::TimeUnit>) -> Self {
         Self {
             progress: Arc::new(HumanProgress::new()),
             max_elapsed_ms: Arc::new(AtomicU64::new(0)),
+            time_unit,
         }
     }
 }
 
 impl OutputFormatter for HumanReadableOutput {
-    fn print_header(&self, script_path: &std::path::Path, scenarios: &[wrkr_core::ScenarioConfig]) {
+    fn print_header(
+        &self,
+        script_path: &std::path::Path,
+        scenarios: &[wrkr_core::ScenarioConfig],
+        thresholds: &[wrkr_core::ThresholdSet],
+    ) {
+        // The formatter is reused across `--repeat` runs; elapsed time is per run.
+        self.max_elapsed_ms.store(0, Ordering::Relaxed);
         println!("script: {}", script_path.display());
         for s in scenarios {
+            // In a run of several scripts, say which one each scenario comes from.
+            let script = s
+                .script
+                .as_ref()
+                .map(|script| format!(" script={}", script.path.display()))
+                .unwrap_or_default();
             println!(
-                "scenario: {} exec={} iterations={:?} duration={:?}",
+                "scenario: {}{script} exec={} executor={} iterations={:?} duration={:?}",
                 s.metrics_ctx.scenario(),
                 s.exec,
+                format_executor(&s.executor),
                 s.iterations,
                 s.duration
             );
         }
+        for t in thresholds {
+            println!(
+                "threshold: {}{} {}",
+                t.metric,
+                format_selector(&t.tags),
+                t.expressions.join(", ")
+            );
+        }
         if !scenarios.is_empty() {
             println!();
         }
@@ -49,8 +94,8 @@ fn print_header(&self, script_path: &std::path::Path, scenarios: &[wrkr_core::Sc
     fn progress(&self) -> Option<wrkr_core::ProgressFn> {
         let progress = self.progress.clone();
         let max_elapsed_ms = self.max_elapsed_ms.clone();
-        let prev_iters: Arc<Mutex<HashMap<String, u64>>> = Arc::new(Mutex::new(HashMap::new()));
-        let prev_errors: Arc<Mutex<HashMap<String, u64>>> = Arc::new(Mutex::new(HashMap::new()));
+        let trends: Arc<Mutex<HashMap<String, ScenarioTrend>>> =
+            Arc::new(Mutex::new(HashMap::new()));
 
         Some(Arc::new(move |u| {
             let elapsed_ms = u.elapsed.as_millis() as u64;
@@ -73,53 +118,65 @@ fn progress(&self) -> Option<wrkr_core::ProgressFn> {
                 .metrics
                 .failed_requests_total
                 .saturating_add(u.metrics.checks_failed_total);
+            let rps = u.metrics.rps_now;
 
-            let prev = {
-                let mut inner = prev_iters
-                    .lock()
-                    .unwrap_or_else(|poisoned| poisoned.into_inner());
-                let prev = inner.get(&u.scenario).copied();
-                inner.insert(u.scenario.clone(), iters_total);
-                prev
-            };
-
-            let prev_err = {
-                let mut inner = prev_errors
+            let (prev, sparkline) = {
+                let mut inner = trends
                     .lock()
                     .unwrap_or_else(|poisoned| poisoned.into_inner());
-                let prev = inner.get(&u.scenario).copied();
-                inner.insert(u.scenario.clone(), errors_total);
-                prev
+                let trend = inner.entry(u.scenario.clone()).or_default();
+                let prev = trend.totals;
+                trend.totals = ScenarioTotals {
+                    iterations: iters_total,
+                    errors: errors_total,
+                    requests: u.metrics.requests_total,
+                    failed_requests: u.metrics.failed_requests_total,
+                };
+                if trend.rps.len() == SPARKLINE_WIDTH {
+                    trend.rps.pop_front();
+                }
+                trend.rps.push_back(rps);
+                (prev, format_sparkline(trend.rps.make_contiguous()))
             };
 
-            let iters_delta = iters_total.saturating_sub(prev.unwrap_or_default());
+            let iters_delta = iters_total.saturating_sub(prev.iterations);
             let iters_per_sec = (iters_delta as f64) / dt;
 
-            let errors_delta = errors_total.saturating_sub(prev_err.unwrap_or_default());
+            let errors_delta = errors_total.saturating_sub(prev.errors);
+
+            // Error rate of the last interval, like `rps`, so a recovering run shows it.
+            let requests_delta = u.metrics.requests_total.saturating_sub(prev.requests);
+            let failed_delta = u
+                .metrics
+                .failed_requests_total
+                .saturating_sub(prev.failed_requests);
+            let error_pct = if requests_delta == 0 {
+                0.0
+            } else {
+                (failed_delta as f64) * 100.0 / (requests_delta as f64)
+            };
 
-            let rps = u.metrics.rps_now;
             let throughput_per_sec = u
                 .metrics
                 .bytes_received_per_sec_now
                 .saturating_add(u.metrics.bytes_sent_per_sec_now);
 
-            let rates = format!(
-                " iters/s={} rps={} tps={}/s errors={errors_delta}/{errors_total}",
+            let mut rates = format!(" rps={} {sparkline} err={error_pct:.1}%", format_rate(rps));
+            if let Some((p, us)) = u.metrics.latency_selected {
+                rates.push_str(&format!(" p{p}={}", format_duration_from_micros(us as f64)));
+            }
+            rates.push_str(&format!(
+                " iters/s={} tps={}/s errors={errors_delta}/{errors_total}",
                 format_rate(iters_per_sec),
-                format_rate(rps),
                 format_bytes(throughput_per_sec)
-            );
+            ));
 
-            let (total_duration_opt, message) = match &u.progress {
-                wrkr_core::ScenarioProgress::ConstantVus { vus, duration } => (
-                    *duration,
-                    format!("vus={vus} elapsed={}{}", format_duration(u.elapsed), rates),
-                ),
-                wrkr_core::ScenarioProgress::RampingVus {
-                    total_duration,
-                    stage,
-                } => {
-                    let msg = if let Some(stage) = stage {
+            let message = match &u.progress {
+                wrkr_core::ScenarioProgress::ConstantVus { vus, .. } => {
+                    format!("vus={vus} elapsed={}{}", format_duration(u.elapsed), rates)
+                }
+                wrkr_core::ScenarioProgress::RampingVus { stage, .. } => {
+                    if let Some(stage) = stage {
                         format!(
                             "stage={}/{} target={} elapsed={} stage_remaining={}{}",
                             stage.stage,
@@ -131,11 +188,9 @@ fn progress(&self) -> Option<wrkr_core::ProgressFn> {
                         )
                     } else {
                         format!("elapsed={}{}", format_duration(u.elapsed), rates)
-                    };
-                    (Some(*total_duration), msg)
+                    }
                 }
                 wrkr_core::ScenarioProgress::RampingArrivalRate {
-                    total_duration,
                     stage,
                     active_vus,
                     max_vus,
@@ -153,41 +208,47 @@ fn progress(&self) -> Option<wrkr_core::ProgressFn> {
                             stage.stage, stage.stages, stage.current_target
                         ));
                     }
-                    (Some(*total_duration), msg)
+                    msg
                 }
             };
 
-            progress.update(&u.scenario, total_duration_opt, u.elapsed, message);
+            progress.update(&u.scenario, u.percent_complete, u.elapsed, message);
         }))
     }
 
     fn print_summary(&self, summary: &wrkr_core::RunSummary) -> anyhow::Result<()> {
         self.progress.finish();
         let elapsed_ms = self.max_elapsed_ms.load(Ordering::Relaxed);
-        let run_elapsed = (elapsed_ms > 0).then(|| std::time::Duration::from_millis(elapsed_ms));
-        print!("{}", render(summary, run_elapsed));
+        // Without live progress (`--quiet`) fall back to the scenarios' recorded active window.
+        let run_elapsed = (elapsed_ms > 0)
+            .then(|| std::time::Duration::from_millis(elapsed_ms))
+            .or_else(|| summary.active_duration());
+        print!("{}", render(summary, run_elapsed, self.time_unit));
+
+        if let Some(reason) = &summary.aborted {
+            eprintln!("run aborted: {reason}");
+        }
 
         if !summary.threshold_violations.is_empty() {
             eprintln!("thresholds failed:");
             for v in &summary.threshold_violations {
-                let key = if v.tags.is_empty() {
-                    v.metric.clone()
-                } else {
-                    let selector = v
-                        .tags
-                        .iter()
-                        .map(|(k, val)| format!("{k}={val}"))
-                        .collect::<Vec<_>>()
-                        .join(",");
-                    format!("{}{{{selector}}}", v.metric)
-                };
+                let key = format!("{}{}", v.metric, format_selector(&v.tags));
                 match v.observed {
                     Some(obs) => eprintln!("  {key}: {} (observed {obs})", v.expression),
-                    None => eprintln!("  {key}: {} (missing series)", v.expression),
+                    None if v.no_data => eprintln!("  {key}: {} (no data)", v.expression),
+                    None => eprintln!("  {key}: {} (not applicable)", v.expression),
                 }
             }
         }
 
         Ok(())
     }
+
+    fn print_repeat_summary(
+        &self,
+        stats: &[crate::repeat::ScenarioRepeatStats],
+    ) -> anyhow::Result<()> {
+        print!("{}", summary::render_repeat(stats, self.time_unit));
+        Ok(())
+    }
 }
@@ -1,3 +1,5 @@
+use crate::cli::TimeUnit;
+
 pub(crate) fn format_bytes(b: u64) -> String {
     const KIB: u64 = 1024;
     const MIB: u64 = 1024 * 1024;
@@ -46,11 +48,40 @@ pub(crate) fn format_rate(v: f64) -> String {
     }
 }
 
-pub(crate) fn format_duration_from_micros_opt(us: Option<f64>) -> String {
-    let Some(us) = us else {
+/// Block-character sparkline of `values`, scaled to the largest one (e.g. `▁▃▅█`).
+pub(crate) fn format_sparkline(values: &[f64]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let max = values
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .fold(0.0_f64, f64::max);
+    values
+        .iter()
+        .map(|&v| {
+            if max <= 0.0 || !v.is_finite() {
+                return BLOCKS[0];
+            }
+            let level = ((v.max(0.0) / max) * (BLOCKS.len() - 1) as f64).round() as usize;
+            BLOCKS[level.min(BLOCKS.len() - 1)]
+        })
+        .collect()
+}
+
+/// A summary latency: in `unit` when one is fixed (`--summary-time-unit`), else in whichever
+/// unit suits the value.
+pub(crate) fn format_latency(us: Option<f64>, unit: Option<TimeUnit>) -> String {
+    let Some(us) = us.filter(|us| us.is_finite()) else {
         return "-".to_string();
     };
-    format_duration_from_micros(us)
+    let us = us.max(0.0);
+    match unit {
+        None => format_duration_from_micros(us),
+        Some(TimeUnit::Us) => format!("{us:.2}µs"),
+        Some(TimeUnit::Ms) => format!("{:.2}ms", us / 1_000.0),
+        Some(TimeUnit::S) => format!("{:.3}s", us / 1_000_000.0),
+    }
 }
 
 pub(crate) fn format_duration(d: std::time::Duration) -> String {
@@ -81,3 +112,77 @@ pub(crate) fn format_duration_from_micros(us: f64) -> String {
     let m = s / 60.0;
     format!("{m:.2}m")
 }
+
+/// Threshold tag selector as written in scripts (`{k=v,...}`), or empty when untagged.
+pub(crate) fn format_selector(tags: &[(String, String)]) -> String {
+    if tags.is_empty() {
+        return String::new();
+    }
+    let pairs = tags
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{{{pairs}}}")
+}
+
+/// One-line description of a resolved executor, e.g. `ramping-vus start_vus=0 stages=[30.00s:10]`.
+pub(crate) fn format_executor(executor: &wrkr_core::ScenarioExecutor) -> String {
+    let stages = |stages: &[wrkr_core::Stage]| {
+        stages
+            .iter()
+            .map(|st| format!("{}:{}", format_duration(st.duration), st.target))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match executor {
+        wrkr_core::ScenarioExecutor::ConstantVus { vus } => format!("constant-vus vus={vus}"),
+        wrkr_core::ScenarioExecutor::RampingVus {
+            start_vus,
+            stages: st,
+        } => format!("ramping-vus start_vus={start_vus} stages=[{}]", stages(st)),
+        wrkr_core::ScenarioExecutor::RampingArrivalRate {
+            start_rate,
+            time_unit,
+            pre_allocated_vus,
+            max_vus,
+            stages: st,
+        } => format!(
+            "ramping-arrival-rate start_rate={start_rate}/{} pre_allocated_vus={pre_allocated_vus} max_vus={max_vus} stages=[{}]",
+            format_duration(*time_unit),
+            stages(st)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_scales_to_the_largest_value() {
+        assert_eq!(format_sparkline(&[0.0, 50.0, 100.0]), "▁▅█");
+        assert_eq!(format_sparkline(&[0.0, 0.0]), "▁▁");
+        assert_eq!(format_sparkline(&[f64::NAN, 10.0]), "▁█");
+        assert_eq!(format_sparkline(&[]), "");
+    }
+
+    #[test]
+    fn latency_keeps_a_fixed_unit_when_one_is_given() {
+        let values = [Some(850.0), Some(12_300.0), Some(2_500_000.0)];
+        let auto: Vec<_> = values.iter().map(|us| format_latency(*us, None)).collect();
+        assert_eq!(auto, ["850.00µs", "12.30ms", "2.50s"]);
+        let ms: Vec<_> = values
+            .iter()
+            .map(|us| format_latency(*us, Some(TimeUnit::Ms)))
+            .collect();
+        assert_eq!(ms, ["0.85ms", "12.30ms", "2500.00ms"]);
+        assert_eq!(
+            format_latency(Some(1_500.0), Some(TimeUnit::Us)),
+            "1500.00µs"
+        );
+        assert_eq!(format_latency(Some(1_500.0), Some(TimeUnit::S)), "0.002s");
+        assert_eq!(format_latency(None, Some(TimeUnit::Ms)), "-");
+    }
+}
@@ -1,11 +1,18 @@
 use std::collections::HashMap;
+use std::io::IsTerminal as _;
 use std::sync::Mutex;
 use std::time::Duration;
 
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 
+/// Without a terminal, progress is appended as plain lines at most this often per scenario.
+const LINE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Live per-scenario status: bars redrawn in place on a terminal, periodic lines otherwise
+/// (e.g. CI logs, where redraws would be lost).
 pub(crate) struct HumanProgress {
     inner: Mutex<Inner>,
+    lines: Option<Mutex<HashMap<String, Duration>>>,
 }
 
 impl HumanProgress {
@@ -18,35 +25,41 @@ pub(crate) fn new() -> Self {
                 multi,
                 bars: HashMap::new(),
             }),
+            lines: (!std::io::stderr().is_terminal()).then(|| Mutex::new(HashMap::new())),
         }
     }
 
     pub(crate) fn update(
         &self,
         scenario: &str,
-        total_duration_opt: Option<Duration>,
+        percent_complete: f64,
         elapsed: Duration,
         message: String,
     ) {
+        if let Some(lines) = &self.lines {
+            let mut last = lines
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            if last
+                .get(scenario)
+                .is_some_and(|at| elapsed < at.saturating_add(LINE_INTERVAL))
+            {
+                return;
+            }
+            last.insert(scenario.to_string(), elapsed);
+            eprintln!("{scenario} {percent_complete:>3.0}% {message}");
+            return;
+        }
+
         let mut inner = self
             .inner
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
 
-        let pb = inner.get_or_create_bar(scenario, total_duration_opt);
+        let pb = inner.get_or_create_bar(scenario);
         pb.set_message(message);
-
-        match total_duration_opt {
-            Some(total_d) => {
-                let total_ms = total_d.as_millis() as u64;
-                let elapsed_ms = elapsed.as_millis() as u64;
-                pb.set_length(total_ms);
-                pb.set_position(elapsed_ms.min(total_ms));
-            }
-            None => {
-                pb.tick();
-            }
-        }
+        // Tenths of a percent, so slow scenarios still move the bar.
+        pb.set_position((percent_complete * 10.0).round() as u64);
     }
 
     pub(crate) fn finish(&self) {
@@ -55,8 +68,8 @@ pub(crate) fn finish(&self) {
             .lock()
             .unwrap_or_else(|poisoned| poisoned.into_inner());
 
-        for (_, b) in inner.bars.drain() {
-            b.pb.finish_and_clear();
+        for (_, pb) in inner.bars.drain() {
+            pb.finish_and_clear();
         }
 
         let _ = inner.multi.clear();
@@ -65,77 +78,22 @@ pub(crate) fn finish(&self) {
 
 struct Inner {
     multi: MultiProgress,
-    bars: HashMap<String, ScenarioProgressBar>,
+    bars: HashMap<String, ProgressBar>,
 }
 
 impl Inner {
-    fn get_or_create_bar(
-        &mut self,
-        scenario: &str,
-        total_duration_opt: Option<Duration>,
-    ) -> &ProgressBar {
-        let desired_kind = if total_duration_opt.is_some() {
-            ProgressBarKind::Bar
-        } else {
-            ProgressBarKind::Spinner
-        };
-
-        let needs_recreate = self
-            .bars
-            .get(scenario)
-            .is_some_and(|b| b.kind != desired_kind);
-
-        if needs_recreate && let Some(old) = self.bars.remove(scenario) {
-            old.pb.finish_and_clear();
-        }
-
-        let entry = self
-            .bars
-            .entry(scenario.to_string())
-            .or_insert_with(|| match desired_kind {
-                ProgressBarKind::Bar => {
-                    let pb = self.multi.add(ProgressBar::new(0));
-                    pb.set_style(bar_style());
-                    pb.set_prefix(scenario.to_string());
-                    ScenarioProgressBar {
-                        kind: ProgressBarKind::Bar,
-                        pb,
-                    }
-                }
-                ProgressBarKind::Spinner => {
-                    let pb = self.multi.add(ProgressBar::new_spinner());
-                    pb.set_style(spinner_style());
-                    pb.set_prefix(scenario.to_string());
-                    pb.enable_steady_tick(Duration::from_millis(120));
-                    ScenarioProgressBar {
-                        kind: ProgressBarKind::Spinner,
-                        pb,
-                    }
-                }
-            });
-
-        &entry.pb
+    fn get_or_create_bar(&mut self, scenario: &str) -> &ProgressBar {
+        self.bars.entry(scenario.to_string()).or_insert_with(|| {
+            let pb = self.multi.add(ProgressBar::new(1000));
+            pb.set_style(bar_style());
+            pb.set_prefix(scenario.to_string());
+            pb
+        })
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ProgressBarKind {
-    Spinner,
-    Bar,
-}
-
-struct ScenarioProgressBar {
-    kind: ProgressBarKind,
-    pb: ProgressBar,
-}
-
 fn bar_style() -> ProgressStyle {
     ProgressStyle::with_template("{prefix} [ {bar:20.cyan/blue} ] {percent:>3}% {msg}")
         .unwrap_or_else(|_| ProgressStyle::default_bar())
         .progress_chars("█░")
 }
-
-fn spinner_style() -> ProgressStyle {
-    ProgressStyle::with_template("{prefix} {spinner} {msg}")
-        .unwrap_or_else(|_| ProgressStyle::default_spinner())
-}
@@ -2,20 +2,25 @@
 use std::fmt::Write as _;
 use std::time::Duration;
 
-use crate::output::human::format::format_duration_from_micros_opt;
-
 use super::format::*;
-
-pub(crate) fn render(summary: &wrkr_core::RunSummary, run_elapsed: Option<Duration>) -> String {
+use crate::cli::TimeUnit;
+use crate::repeat::Spread;
+
+pub(crate) fn render(
+    summary: &wrkr_core::RunSummary,
+    run_elapsed: Option<Duration>,
+    time_unit: Option<TimeUnit>,
+) -> String {
     let mut out = String::new();
 
     let metric_series = (!summary.metrics.is_empty()).then_some(summary.metrics.as_slice());
 
     if summary.scenarios.is_empty() {
         out.push_str("summary: no scenarios\n");
+        render_thresholds(&summary.threshold_results, &mut out);
         if let Some(series) = metric_series {
             render_checks(series, &mut out);
-            render_metrics(series, &mut out);
+            render_metrics(series, time_unit, &mut out);
         }
         return out;
     }
@@ -28,13 +33,37 @@ pub(crate) fn render(summary: &wrkr_core::RunSummary, run_elapsed: Option<Durati
         totals.add(s);
 
         writeln!(&mut out, "scenario: {}", s.scenario).ok();
+        if let (Some(started), Some(ended)) = (s.started_at, s.ended_at) {
+            let active = s.active_duration().unwrap_or_default();
+            writeln!(
+                &mut out,
+                "  active: {} .. {} ({})",
+                humantime::format_rfc3339_millis(started),
+                humantime::format_rfc3339_millis(ended),
+                format_duration(active)
+            )
+            .ok();
+        }
         writeln!(
             &mut out,
             "  requests: {} (failed {})",
             s.requests_total, s.failed_requests_total
         )
         .ok();
+        render_error_kinds(&s.errors_by_kind, &mut out);
         writeln!(&mut out, "  iterations: {}", s.iterations_total).ok();
+        if s.saturated() {
+            let due = s
+                .iterations_total
+                .saturating_add(s.dropped_iterations_total);
+            let pct = (s.dropped_iterations_total as f64 / due.max(1) as f64) * 100.0;
+            writeln!(
+                &mut out,
+                "  dropped_iterations: {} ({pct:.1}% of due) SATURATED: target arrival rate not met",
+                s.dropped_iterations_total
+            )
+            .ok();
+        }
         writeln!(
             &mut out,
             "  bytes: recv {} sent {}",
@@ -42,6 +71,16 @@ pub(crate) fn render(summary: &wrkr_core::RunSummary, run_elapsed: Option<Durati
             format_bytes(s.bytes_sent_total)
         )
         .ok();
+        if let Some(rate) = s.connection_reuse_rate() {
+            writeln!(
+                &mut out,
+                "  connections: reused {:.1}% ({}/{} requests)",
+                rate * 100.0,
+                s.connection_reused_total,
+                s.connection_reuse_samples
+            )
+            .ok();
+        }
 
         if s.checks_failed_total > 0 {
             writeln!(&mut out, "  checks_failed_total: {}", s.checks_failed_total).ok();
@@ -57,19 +96,10 @@ pub(crate) fn render(summary: &wrkr_core::RunSummary, run_elapsed: Option<Durati
                 writeln!(&mut out, "    {name}: {count}").ok();
             }
         }
+        render_checks_score(s.checks_weight_passed, s.checks_weight_total, &mut out);
 
         if let Some(h) = &s.latency {
-            writeln!(
-                out,
-                "  latency = p50={} p90={} p99={} mean={} max={} (n={})",
-                format_duration_from_micros_opt(h.p50),
-                format_duration_from_micros_opt(h.p90),
-                format_duration_from_micros_opt(h.p99),
-                format_duration_from_micros_opt(h.mean),
-                format_duration_from_micros_opt(h.max),
-                h.count
-            )
-            .ok();
+            render_latency(h, time_unit, &mut out);
         } else {
             out.push_str("  latency: n/a\n");
         }
@@ -84,6 +114,10 @@ pub(crate) fn render(summary: &wrkr_core::RunSummary, run_elapsed: Option<Durati
         totals.requests_total, totals.failed_requests_total
     )
     .ok();
+    // Pooled across scenarios; with a single scenario it would repeat the table above.
+    if summary.scenarios.len() > 1 {
+        render_error_kinds(&totals.errors_by_kind, &mut out);
+    }
     writeln!(&mut out, "  iterations: {}", totals.iterations_total).ok();
     writeln!(
         &mut out,
@@ -109,29 +143,124 @@ pub(crate) fn render(summary: &wrkr_core::RunSummary, run_elapsed: Option<Durati
         )
         .ok();
     }
+    // Pooled across scenarios; with a single scenario it would repeat the line above.
+    if summary.scenarios.len() > 1
+        && let Some(h) = &summary.latency
+    {
+        render_latency(h, time_unit, &mut out);
+    }
     writeln!(
         &mut out,
         "  checks_failed_total: {}",
         totals.checks_failed_total
     )
     .ok();
+    if summary.scenarios.len() > 1 {
+        render_checks_score(
+            totals.checks_weight_passed,
+            totals.checks_weight_total,
+            &mut out,
+        );
+    }
+
+    render_thresholds(&summary.threshold_results, &mut out);
 
     if let Some(series) = metric_series {
         render_checks(series, &mut out);
-        render_metrics(series, &mut out);
+        render_metrics(series, time_unit, &mut out);
     }
+    render_pooled_metrics(&summary.pooled_metrics, time_unit, &mut out);
 
     out
 }
 
+/// Every threshold expression with its verdict; failures are repeated on stderr.
+fn render_thresholds(results: &[wrkr_core::ThresholdResult], out: &mut String) {
+    if results.is_empty() {
+        return;
+    }
+    out.push_str("thresholds\n");
+    for r in results {
+        let verdict = if r.passed { "pass" } else { "FAIL" };
+        let observed = match r.observed {
+            Some(obs) => format!("observed {obs}"),
+            None if r.no_data => "no data".to_string(),
+            None => "not applicable".to_string(),
+        };
+        writeln!(
+            out,
+            "  {verdict}  {}{}: {} ({observed})",
+            r.metric,
+            format_selector(&r.tags),
+            r.expression
+        )
+        .ok();
+    }
+}
+
+/// Failed requests per transport error kind, most frequent first, with their share of failures.
+fn render_error_kinds(by_kind: &HashMap<String, u64>, out: &mut String) {
+    if by_kind.is_empty() {
+        return;
+    }
+
+    let mut rows: Vec<(&str, u64)> = by_kind.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+    rows.sort_by(|(a_kind, a), (b_kind, b)| b.cmp(a).then_with(|| a_kind.cmp(b_kind)));
+
+    let failed: u64 = rows.iter().map(|(_, v)| v).sum();
+    let width = rows
+        .iter()
+        .map(|(k, _)| k.len())
+        .max()
+        .unwrap_or(0)
+        .max("kind".len());
+    out.push_str("  errors by kind:\n");
+    writeln!(
+        out,
+        "    {:<width$}  {:>8}  {:>6}",
+        "kind", "count", "share"
+    )
+    .ok();
+    for (kind, count) in rows {
+        let pct = count as f64 / failed.max(1) as f64 * 100.0;
+        writeln!(out, "    {kind:<width$}  {count:>8}  {pct:>5.1}%").ok();
+    }
+}
+
+/// Weighted share of passed checks; nothing without checks.
+fn render_checks_score(passed: u64, total: u64, out: &mut String) {
+    if total > 0 {
+        let pct = passed as f64 / total as f64 * 100.0;
+        writeln!(out, "  checks_score: {pct:.1}% ({passed}/{total} weighted)").ok();
+    }
+}
+
+fn render_latency(h: &wrkr_core::HistogramSummary, unit: Option<TimeUnit>, out: &mut String) {
+    writeln!(
+        out,
+        "  latency = p50={} p90={} p99={} mean={} min={} max={} (n={})",
+        format_latency(h.p50, unit),
+        format_latency(h.p90, unit),
+        format_latency(h.p99, unit),
+        format_latency(h.mean, unit),
+        format_latency(h.min, unit),
+        format_latency(h.max, unit),
+        h.count
+    )
+    .ok();
+}
+
 #[derive(Default)]
 struct Totals {
     requests_total: u64,
     failed_requests_total: u64,
+    errors_by_kind: HashMap<String, u64>,
     bytes_received_total: u64,
     bytes_sent_total: u64,
     iterations_total: u64,
     checks_failed_total: u64,
+    checks_weight_total: u64,
+    checks_weight_passed: u64,
 }
 
 impl Totals {
@@ -140,6 +269,10 @@ fn add(&mut self, s: &wrkr_core::ScenarioSummary) {
         self.failed_requests_total = self
             .failed_requests_total
             .saturating_add(s.failed_requests_total);
+        for (kind, count) in &s.errors_by_kind {
+            let cur = self.errors_by_kind.entry(kind.clone()).or_default();
+            *cur = cur.saturating_add(*count);
+        }
         self.bytes_received_total = self
             .bytes_received_total
             .saturating_add(s.bytes_received_total);
@@ -148,9 +281,125 @@ fn add(&mut self, s: &wrkr_core::ScenarioSummary) {
         self.checks_failed_total = self
             .checks_failed_total
             .saturating_add(s.checks_failed_total);
+        self.checks_weight_total = self
+            .checks_weight_total
+            .saturating_add(s.checks_weight_total);
+        self.checks_weight_passed = self
+            .checks_weight_passed
+            .saturating_add(s.checks_weight_passed);
     }
 }
 
+/// Run-to-run spread after `--repeat` runs: mean ± stdev% and the observed range.
+pub(crate) fn render_repeat(
+    stats: &[crate::repeat::ScenarioRepeatStats],
+    time_unit: Option<TimeUnit>,
+) -> String {
+    let mut out = String::new();
+    let runs = stats.iter().map(|s| s.runs).max().unwrap_or(0);
+    writeln!(&mut out, "repeat summary ({runs} runs)").ok();
+
+    let line =
+        |out: &mut String, label: &str, spread: Option<Spread>, fmt: &dyn Fn(f64) -> String| {
+            if let Some(s) = spread {
+                writeln!(
+                    out,
+                    "  {label}: {} ±{:.1}% (min {} max {})",
+                    fmt(s.mean),
+                    s.stdev_pct(),
+                    fmt(s.min),
+                    fmt(s.max)
+                )
+                .ok();
+            }
+        };
+    let count = |v: f64| format!("{v:.0}");
+    let latency = |us: f64| format_latency(Some(us), time_unit);
+
+    for s in stats {
+        writeln!(&mut out, "scenario: {} ({} runs)", s.scenario, s.runs).ok();
+        line(&mut out, "rps", s.rps, &format_rate);
+        line(&mut out, "requests", s.requests_total, &count);
+        line(&mut out, "failed", s.failed_requests_total, &count);
+        line(&mut out, "latency mean", s.latency_mean, &latency);
+        line(&mut out, "latency p50", s.latency_p50, &latency);
+        line(&mut out, "latency p90", s.latency_p90, &latency);
+        line(&mut out, "latency p99", s.latency_p99, &latency);
+    }
+
+    out
+}
+
+/// `wrkr diff` report: `baseline -> candidate (change)` per metric, regressions marked.
+pub(crate) fn render_diff(report: &crate::diff::DiffReport) -> String {
+    use crate::diff::DiffMetric;
+
+    let mut out = String::new();
+    let pct = |b: f64, c: f64| {
+        if b > 0.0 {
+            format!("{:+.1}%", (c - b) / b * 100.0)
+        } else {
+            "n/a".to_string()
+        }
+    };
+
+    for scope in &report.scopes {
+        match &scope.scenario {
+            Some(name) => writeln!(&mut out, "scenario: {name}").ok(),
+            None => writeln!(&mut out, "totals").ok(),
+        };
+        if scope.deltas.is_empty() {
+            out.push_str("  no comparable metrics\n");
+        }
+        for d in &scope.deltas {
+            let (label, format): (String, fn(f64) -> String) = match d.metric {
+                DiffMetric::Rps => ("rps".to_string(), format_rate),
+                DiffMetric::ErrorRate => {
+                    ("error rate".to_string(), |v| format!("{:.2}%", v * 100.0))
+                }
+                DiffMetric::Latency(p) => (format!("latency {p}"), format_duration_from_micros),
+            };
+            let b = format(d.baseline);
+            let (c, change) = match (d.metric, d.candidate) {
+                (_, None) => ("missing".to_string(), "n/a".to_string()),
+                (DiffMetric::ErrorRate, Some(c)) => {
+                    (format(c), format!("{:+.2}pp", (c - d.baseline) * 100.0))
+                }
+                (_, Some(c)) => (format(c), pct(d.baseline, c)),
+            };
+            let mark = if d.regression { " REGRESSION" } else { "" };
+            writeln!(&mut out, "  {label}: {b} -> {c} ({change}){mark}").ok();
+        }
+    }
+
+    if !report.only_in_baseline.is_empty() {
+        writeln!(
+            &mut out,
+            "only in baseline: {} REGRESSION",
+            report.only_in_baseline.join(", ")
+        )
+        .ok();
+    }
+    if !report.only_in_candidate.is_empty() {
+        writeln!(
+            &mut out,
+            "only in candidate: {}",
+            report.only_in_candidate.join(", ")
+        )
+        .ok();
+    }
+
+    match report.regressions() {
+        0 => out.push_str("no regressions\n"),
+        1 => out.push_str("1 regression\n"),
+        n => {
+            writeln!(&mut out, "{n} regressions").ok();
+        }
+    }
+
+    out
+}
+
 fn render_checks(series: &[wrkr_core::MetricSeriesSummary], out: &mut String) {
     #[derive(Debug, Default, Clone, Copy)]
     struct Counts {
@@ -268,7 +517,11 @@ struct CheckKey {
     }
 }
 
-fn render_metrics(series: &[wrkr_core::MetricSeriesSummary], out: &mut String) {
+fn render_metrics(
+    series: &[wrkr_core::MetricSeriesSummary],
+    unit: Option<TimeUnit>,
+    out: &mut String,
+) {
     let mut by_scenario_group: BTreeMap<
         (String, Option<String>),
         Vec<&wrkr_core::MetricSeriesSummary>,
@@ -363,19 +616,7 @@ fn render_metrics(series: &[wrkr_core::MetricSeriesSummary], out: &mut String) {
                     }
                 }
                 wrkr_core::MetricValue::Histogram(h) => {
-                    writeln!(
-                        out,
-                        "    {}{} = p50={} p90={} p99={} mean={} max={} (n={})",
-                        s.name,
-                        tags_s,
-                        format_duration_from_micros_opt(h.p50),
-                        format_duration_from_micros_opt(h.p90),
-                        format_duration_from_micros_opt(h.p99),
-                        format_duration_from_micros_opt(h.mean),
-                        format_duration_from_micros_opt(h.max),
-                        h.count
-                    )
-                    .ok();
+                    render_histogram_row(&s.name, &tags_s, h, unit, out);
                 }
             }
         }
@@ -384,6 +625,46 @@ fn render_metrics(series: &[wrkr_core::MetricSeriesSummary], out: &mut String) {
     }
 }
 
+/// Histogram metrics recorded by several scenarios, merged across them.
+fn render_pooled_metrics(
+    series: &[wrkr_core::MetricSeriesSummary],
+    unit: Option<TimeUnit>,
+    out: &mut String,
+) {
+    if series.is_empty() {
+        return;
+    }
+
+    out.push_str("\nmetrics across scenarios\n");
+    for s in series {
+        if let wrkr_core::MetricValue::Histogram(h) = &s.values {
+            let tags_s = format_tags_inline(&s.tags, &[]);
+            render_histogram_row(&s.name, &tags_s, h, unit, out);
+        }
+    }
+}
+
+fn render_histogram_row(
+    name: &str,
+    tags_s: &str,
+    h: &wrkr_core::HistogramSummary,
+    unit: Option<TimeUnit>,
+    out: &mut String,
+) {
+    writeln!(
+        out,
+        "    {name}{tags_s} = p50={} p90={} p99={} mean={} min={} max={} (n={})",
+        format_latency(h.p50, unit),
+        format_latency(h.p90, unit),
+        format_latency(h.p99, unit),
+        format_latency(h.mean, unit),
+        format_latency(h.min, unit),
+        format_latency(h.max, unit),
+        h.count
+    )
+    .ok();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -393,29 +674,207 @@ fn render_includes_scenario_and_totals() {
         let summary = wrkr_core::RunSummary {
             scenarios: vec![wrkr_core::ScenarioSummary {
                 scenario: "default".to_string(),
+                started_at: Some(std::time::UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+                ended_at: Some(std::time::UNIX_EPOCH + Duration::from_millis(1_700_000_010_500)),
                 requests_total: 10,
                 failed_requests_total: 2,
+                errors_by_kind: [("timeout".to_string(), 2)].into_iter().collect(),
                 bytes_received_total: 2048,
                 bytes_sent_total: 1024,
                 iterations_total: 10,
+                dropped_iterations_total: 0,
+                connection_reuse_samples: 10,
+                connection_reused_total: 9,
                 checks_failed_total: 1,
                 checks_failed: [("status_is_200".to_string(), 1)].into_iter().collect(),
+                checks_weight_total: 12,
+                checks_weight_passed: 9,
                 latency: None,
             }],
             ..Default::default()
         };
 
-        let text = render(&summary, Some(Duration::from_secs(10)));
+        let text = render(&summary, Some(Duration::from_secs(10)), None);
         assert!(text.contains("scenario: default"));
+        assert!(
+            text.contains("active: 2023-11-14T22:13:20.000Z .. 2023-11-14T22:13:30.500Z"),
+            "{text}"
+        );
         assert!(text.contains("requests: 10"));
         assert!(text.contains("failed 2"));
+        assert!(text.contains("errors by kind:"), "{text}");
         assert!(text.contains("bytes: recv 2.00KiB sent 1.00KiB"));
+        assert!(text.contains("connections: reused 90.0% (9/10 requests)"));
         assert!(text.contains("checks_failed_total: 1"));
         assert!(text.contains("status_is_200: 1"));
+        assert!(
+            text.contains("checks_score: 75.0% (9/12 weighted)"),
+            "{text}"
+        );
         assert!(text.contains("latency: n/a"));
         assert!(text.contains("totals"));
         assert!(text.contains("rates: rps="));
         assert!(text.contains("tps="));
+        assert!(!text.contains("SATURATED"));
+    }
+
+    #[test]
+    fn render_lists_every_threshold_result() {
+        let result = |expression: &str, observed, passed, no_data| wrkr_core::ThresholdResult {
+            metric: "http_req_duration".to_string(),
+            tags: vec![("name".to_string(), "login".to_string())],
+            expression: expression.to_string(),
+            observed,
+            limit: None,
+            passed,
+            no_data,
+            description: None,
+        };
+        let summary = wrkr_core::RunSummary {
+            threshold_results: vec![
+                result("p(95)<500", Some(120.0), true, false),
+                result("max<100", Some(640.0), false, false),
+                result("avg<50", None, true, true),
+            ],
+            ..Default::default()
+        };
+
+        let text = render(&summary, None, None);
+        assert!(text.contains("thresholds\n"), "{text}");
+        assert!(
+            text.contains("  pass  http_req_duration{name=login}: p(95)<500 (observed 120)"),
+            "{text}"
+        );
+        assert!(
+            text.contains("  FAIL  http_req_duration{name=login}: max<100 (observed 640)"),
+            "{text}"
+        );
+        assert!(
+            text.contains("  pass  http_req_duration{name=login}: avg<50 (no data)"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn render_diff_marks_regressions() {
+        use crate::diff::{DiffMetric, DiffReport, MetricDelta, ScopeDiff};
+
+        let report = DiffReport {
+            scopes: vec![ScopeDiff {
+                scenario: Some("main".to_string()),
+                deltas: vec![
+                    MetricDelta {
+                        metric: DiffMetric::Rps,
+                        baseline: 100.0,
+                        candidate: Some(95.0),
+                        regression: false,
+                    },
+                    MetricDelta {
+                        metric: DiffMetric::Latency("p95"),
+                        baseline: 20_000.0,
+                        candidate: Some(30_000.0),
+                        regression: true,
+                    },
+                    MetricDelta {
+                        metric: DiffMetric::Latency("p99"),
+                        baseline: 40_000.0,
+                        candidate: None,
+                        regression: true,
+                    },
+                ],
+            }],
+            only_in_baseline: vec!["gone".to_string()],
+            only_in_candidate: vec!["new".to_string()],
+        };
+
+        let text = render_diff(&report);
+        assert!(text.contains("scenario: main"));
+        assert!(text.contains("  rps: 100 -> 95 (-5.0%)\n"));
+        assert!(text.contains("  latency p95: 20.00ms -> 30.00ms (+50.0%) REGRESSION"));
+        assert!(text.contains("  latency p99: 40.00ms -> missing (n/a) REGRESSION"));
+        assert!(text.contains("only in baseline: gone REGRESSION"));
+        assert!(text.contains("only in candidate: new"));
+        assert!(text.ends_with("3 regressions\n"));
+    }
+
+    #[test]
+    fn render_repeat_shows_mean_spread_and_range() {
+        let spread = |mean, min, max| crate::repeat::Spread {
+            mean,
+            stdev: mean * 0.05,
+            min,
+            max,
+        };
+        let stats = vec![crate::repeat::ScenarioRepeatStats {
+            scenario: "main".to_string(),
+            runs: 3,
+            requests_total: Some(spread(1_000.0, 950.0, 1_050.0)),
+            failed_requests_total: None,
+            rps: Some(spread(100.0, 95.0, 105.0)),
+            latency_mean: None,
+            latency_p50: None,
+            latency_p90: None,
+            latency_p99: Some(spread(2_000.0, 1_500.0, 2_500.0)),
+        }];
+
+        let text = render_repeat(&stats, None);
+        assert!(text.starts_with("repeat summary (3 runs)\n"), "{text}");
+        assert!(text.contains("scenario: main (3 runs)"), "{text}");
+        assert!(text.contains("  rps: 100 ±5.0% (min 95 max 105)"), "{text}");
+        assert!(
+            text.contains("  latency p99: 2.00ms ±5.0% (min 1.50ms max 2.50ms)"),
+            "{text}"
+        );
+        assert!(!text.contains("failed"), "{text}");
+    }
+
+    #[test]
+    fn render_flags_saturated_scenarios() {
+        let summary = wrkr_core::RunSummary {
+            scenarios: vec![wrkr_core::ScenarioSummary {
+                scenario: "rate".to_string(),
+                iterations_total: 70,
+                dropped_iterations_total: 30,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let text = render(&summary, None, None);
+        assert!(
+            text.contains("dropped_iterations: 30 (30.0% of due) SATURATED"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn render_error_kinds_sorts_by_count_and_pools_totals() {
+        let scenario = |name: &str, kinds: &[(&str, u64)]| wrkr_core::ScenarioSummary {
+            scenario: name.to_string(),
+            requests_total: 100,
+            failed_requests_total: kinds.iter().map(|(_, v)| v).sum(),
+            errors_by_kind: kinds.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+            ..Default::default()
+        };
+        let summary = wrkr_core::RunSummary {
+            scenarios: vec![
+                scenario("a", &[("timeout", 1), ("connect", 3)]),
+                scenario("b", &[("timeout", 4)]),
+            ],
+            ..Default::default()
+        };
+
+        let text = render(&summary, None, None);
+        let refused = text.find("    connect         3   75.0%");
+        let timeout = text.find("    timeout         1   25.0%");
+        assert!(refused.is_some() && refused < timeout, "{text}");
+
+        let (_, totals) = text
+            .split_once("totals\n")
+            .unwrap_or_else(|| panic!("{text}"));
+        let timeout = totals.find("    timeout         5   62.5%");
+        let refused = totals.find("    connect         3   37.5%");
+        assert!(timeout.is_some() && timeout < refused, "{totals}");
     }
 
     #[test]
@@ -453,7 +912,7 @@ fn render_checks_includes_pass_fail_and_tags() {
             ..Default::default()
         };
 
-        let text = render(&summary, None);
+        let text = render(&summary, None, None);
         assert!(text.contains("checks"));
         assert!(text.contains("scenario: Default"));
         assert!(text.contains("group: g1"));
@@ -492,8 +951,45 @@ fn render_metrics_combines_vu_active_end_and_peak() {
             ..Default::default()
         };
 
-        let text = render(&summary, None);
+        let text = render(&summary, None, None);
         assert!(text.contains("vu_active = end=0 peak=10"));
         assert!(!text.contains("vu_active_max"));
     }
+
+    #[test]
+    fn render_lists_histograms_pooled_across_scenarios() {
+        let run_ctx = wrkr_core::RunScenariosContext::new(
+            std::sync::Arc::from([]),
+            String::new(),
+            std::path::PathBuf::new(),
+        );
+        let metrics = &run_ctx.metrics;
+        let cart = metrics.register("cart_size", wrkr_core::MetricKind::Histogram);
+        let tags = metrics.resolve_tags(&[("shop", "eu")]);
+        for v in [3, 7] {
+            if let Some(h) = metrics.get_handle(cart, tags.clone()) {
+                h.observe_histogram(v);
+            }
+        }
+
+        let summary = wrkr_core::RunSummary {
+            scenarios: vec![wrkr_core::ScenarioSummary {
+                scenario: "browse".to_string(),
+                ..Default::default()
+            }],
+            pooled_metrics: metrics.summarize(),
+            ..Default::default()
+        };
+
+        let text = render(&summary, None, None);
+        let pooled = text
+            .split_once("metrics across scenarios\n")
+            .map(|(_, rest)| rest)
+            .unwrap_or_else(|| panic!("{text}"));
+        assert!(
+            pooled.starts_with("    cart_size{shop=eu} = p50="),
+            "{text}"
+        );
+        assert!(pooled.contains("(n=2)"), "{text}");
+    }
 }
@@ -1,45 +1,195 @@
+use anyhow::Context as _;
 use serde::Serialize;
 use std::collections::BTreeMap;
-use std::io::Write as _;
+use std::io::{BufWriter, Write};
 use std::path::Path;
-use std::sync::Arc;
 use std::sync::OnceLock;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use super::OutputFormatter;
 
 pub(crate) struct JsonOutput {
     scenarios: OnceLock<Vec<wrkr_core::ScenarioConfig>>,
+    sink: Arc<JsonSink>,
 }
 
-const NDJSON_SCHEMA: &str = "wrkr.ndjson.v1";
+pub(super) const NDJSON_SCHEMA: &str = "wrkr.ndjson.v1";
+
+/// How long buffered progress lines may sit before being flushed to stdout.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
 
 impl JsonOutput {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(no_buffer: bool) -> Self {
+        Self::with_writer(Box::new(std::io::stdout()), no_buffer)
+    }
+
+    /// NDJSON written to `path` (created or truncated) instead of stdout.
+    pub(crate) fn to_file(path: &Path, no_buffer: bool) -> anyhow::Result<Self> {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("failed to create output file: {}", path.display()))?;
+        Ok(Self::with_writer(Box::new(file), no_buffer))
+    }
+
+    fn with_writer(writer: Box<dyn Write + Send>, no_buffer: bool) -> Self {
         Self {
             scenarios: OnceLock::new(),
+            sink: Arc::new(JsonSink::new(writer, !no_buffer)),
+        }
+    }
+}
+
+/// Shared writer for NDJSON lines (stdout or a `json=PATH` file).
+///
+/// In buffered mode lines are batched and flushed at most every `FLUSH_INTERVAL` (or when the
+/// buffer fills); otherwise every line is flushed as soon as it is written.
+struct JsonSink {
+    buffered: bool,
+    state: Mutex<JsonSinkState>,
+}
+
+struct JsonSinkState {
+    out: BufWriter<Box<dyn Write + Send>>,
+    last_flush: Instant,
+}
+
+impl JsonSink {
+    fn new(writer: Box<dyn Write + Send>, buffered: bool) -> Self {
+        Self {
+            buffered,
+            state: Mutex::new(JsonSinkState {
+                out: BufWriter::new(writer),
+                last_flush: Instant::now(),
+            }),
+        }
+    }
+
+    fn emit<T: Serialize>(&self, line: &T, force_flush: bool) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        match serde_json::to_writer(&mut state.out, line) {
+            Ok(()) => {
+                if let Err(err) = writeln!(state.out) {
+                    tracing::warn!(error = %err, "failed to write NDJSON line");
+                }
+            }
+            Err(err) => tracing::warn!(error = %err, "failed to write NDJSON line"),
+        }
+
+        if force_flush || !self.buffered || state.last_flush.elapsed() >= FLUSH_INTERVAL {
+            if let Err(err) = state.out.flush() {
+                tracing::warn!(error = %err, "failed to flush NDJSON output");
+            }
+            state.last_flush = Instant::now();
         }
     }
 }
 
 impl OutputFormatter for JsonOutput {
-    fn print_header(&self, _script_path: &Path, scenarios: &[wrkr_core::ScenarioConfig]) {
+    fn print_header(
+        &self,
+        script_path: &Path,
+        scenarios: &[wrkr_core::ScenarioConfig],
+        thresholds: &[wrkr_core::ThresholdSet],
+    ) {
         let _ = self.scenarios.set(scenarios.to_vec());
+        let line = build_run_start_line(script_path, scenarios, thresholds);
+        self.sink.emit(&line, true);
     }
 
     fn progress(&self) -> Option<wrkr_core::ProgressFn> {
+        let sink = self.sink.clone();
         Some(Arc::new(move |u| {
             let line = build_progress_line(&u);
-            emit_json_line(&line);
+            sink.emit(&line, false);
         }))
     }
 
     fn print_summary(&self, summary: &wrkr_core::RunSummary) -> anyhow::Result<()> {
         let line = build_summary_line(summary, self.scenarios.get().map(Vec::as_slice));
-        emit_json_line(&line);
+        // The process exits right after the summary, so it must never sit in the buffer.
+        self.sink.emit(&line, true);
+        Ok(())
+    }
+
+    fn print_repeat_summary(
+        &self,
+        stats: &[crate::repeat::ScenarioRepeatStats],
+    ) -> anyhow::Result<()> {
+        self.sink.emit(&build_repeat_summary_line(stats), true);
         Ok(())
     }
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JsonRunStartLine {
+    pub schema: &'static str,
+    pub kind: &'static str,
+    pub script: String,
+    pub scenarios: Vec<JsonRunStartScenario>,
+    pub thresholds: Vec<JsonThresholdConfig>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JsonRunStartScenario {
+    pub scenario: String,
+    /// Script defining the scenario, when several scripts run together.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+    pub exec: String,
+    pub description: Option<String>,
+    pub executor: JsonScenarioExecutorConfig,
+    pub iterations: Option<u64>,
+    pub duration_seconds: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JsonThresholdConfig {
+    pub metric: String,
+    pub tags: BTreeMap<String, String>,
+    pub expressions: Vec<String>,
+    pub description: Option<String>,
+}
+
+fn build_run_start_line(
+    script_path: &Path,
+    scenarios: &[wrkr_core::ScenarioConfig],
+    thresholds: &[wrkr_core::ThresholdSet],
+) -> JsonRunStartLine {
+    JsonRunStartLine {
+        schema: NDJSON_SCHEMA,
+        kind: "runStart",
+        script: script_path.display().to_string(),
+        scenarios: scenarios
+            .iter()
+            .map(|s| JsonRunStartScenario {
+                scenario: s.metrics_ctx.scenario().to_string(),
+                script: s.script.as_ref().map(|s| s.path.display().to_string()),
+                exec: s.exec.clone(),
+                description: s.description.clone(),
+                executor: executor_config(s),
+                iterations: s.iterations,
+                duration_seconds: s.duration.map(|d| d.as_secs_f64()),
+            })
+            .collect(),
+        thresholds: thresholds
+            .iter()
+            .map(|t| JsonThresholdConfig {
+                metric: t.metric.clone(),
+                tags: t.tags.iter().cloned().collect(),
+                expressions: t.expressions.clone(),
+                description: t.description.clone(),
+            })
+            .collect(),
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct JsonProgressLine {
@@ -52,6 +202,8 @@ pub(crate) struct JsonProgressLine {
 
     pub scenario: String,
     pub exec: String,
+    /// Scenario completion, `0..=100`.
+    pub percent_complete: f64,
 
     pub executor: JsonProgressExecutor,
     pub metrics: JsonProgressMetrics,
@@ -88,6 +240,10 @@ pub(crate) struct JsonProgressMetrics {
     pub req_per_sec_stdev: f64,
     pub req_per_sec_max: f64,
     pub req_per_sec_stdev_pct: f64,
+
+    /// Current values of the `--gauge-series` gauges.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub gauges: BTreeMap<String, i64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -95,12 +251,22 @@ pub(crate) struct JsonProgressMetrics {
 pub(crate) struct JsonProgressLatencySeconds {
     pub mean: f64,
     pub stdev: f64,
+    pub min: f64,
     pub max: f64,
     pub p50: f64,
     pub p75: f64,
     pub p90: f64,
     pub p99: f64,
     pub stdev_pct: f64,
+    /// Latency at `--progress-percentile`.
+    pub selected: Option<JsonProgressSelectedPercentile>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JsonProgressSelectedPercentile {
+    pub percentile: f64,
+    pub seconds: f64,
 }
 
 fn build_progress_line(u: &wrkr_core::ProgressUpdate) -> JsonProgressLine {
@@ -121,6 +287,7 @@ fn build_progress_line(u: &wrkr_core::ProgressUpdate) -> JsonProgressLine {
 
         scenario: u.scenario.clone(),
         exec: u.exec.clone(),
+        percent_complete: u.percent_complete,
 
         executor: JsonProgressExecutor {
             kind: executor_kind,
@@ -145,18 +312,26 @@ fn build_progress_line(u: &wrkr_core::ProgressUpdate) -> JsonProgressLine {
             latency_seconds: JsonProgressLatencySeconds {
                 mean: us_to_secs(u.metrics.latency_mean),
                 stdev: us_to_secs(u.metrics.latency_stdev),
+                min: u64_us_to_secs(u.metrics.latency_min),
                 max: u64_us_to_secs(u.metrics.latency_max),
                 p50: u64_us_to_secs(u.metrics.latency_p50),
                 p75: u64_us_to_secs(u.metrics.latency_p75),
                 p90: u64_us_to_secs(u.metrics.latency_p90),
                 p99: u64_us_to_secs(u.metrics.latency_p99),
                 stdev_pct: u.metrics.latency_stdev_pct,
+                selected: u.metrics.latency_selected.map(|(percentile, us)| {
+                    JsonProgressSelectedPercentile {
+                        percentile,
+                        seconds: u64_us_to_secs(us),
+                    }
+                }),
             },
 
             req_per_sec_avg: u.metrics.req_per_sec_avg,
             req_per_sec_stdev: u.metrics.req_per_sec_stdev,
             req_per_sec_max: u.metrics.req_per_sec_max,
             req_per_sec_stdev_pct: u.metrics.req_per_sec_stdev_pct,
+            gauges: u.metrics.gauges.iter().cloned().collect(),
         },
     }
 }
@@ -169,12 +344,78 @@ pub(crate) struct JsonSummaryLine {
     pub scenarios: Vec<JsonScenarioSummary>,
     pub totals: JsonTotals,
     pub thresholds: JsonThresholdsSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aborted: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JsonRepeatSummaryLine {
+    pub schema: &'static str,
+    pub kind: &'static str,
+    pub runs: usize,
+    pub scenarios: Vec<JsonScenarioRepeatSummary>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JsonScenarioRepeatSummary {
+    pub scenario: String,
+    pub runs: usize,
+    pub requests_total: Option<JsonSpread>,
+    pub failed_requests_total: Option<JsonSpread>,
+    pub rps: Option<JsonSpread>,
+    pub latency_seconds: JsonRepeatLatencySeconds,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JsonRepeatLatencySeconds {
+    pub mean: Option<JsonSpread>,
+    pub p50: Option<JsonSpread>,
+    pub p90: Option<JsonSpread>,
+    pub p99: Option<JsonSpread>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JsonSpread {
+    pub mean: f64,
+    pub stdev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl JsonSpread {
+    fn scaled(s: crate::repeat::Spread, factor: f64) -> Self {
+        Self {
+            mean: s.mean * factor,
+            stdev: s.stdev * factor,
+            min: s.min * factor,
+            max: s.max * factor,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct JsonThresholdsSummary {
     pub violations: Vec<JsonThresholdViolation>,
+    /// Every expression, passed or failed.
+    pub results: Vec<JsonThresholdResult>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JsonThresholdResult {
+    pub metric: String,
+    pub tags: BTreeMap<String, String>,
+    pub expression: String,
+    pub observed: Option<f64>,
+    pub limit: Option<f64>,
+    pub passed: bool,
+    pub no_data: bool,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -184,6 +425,8 @@ pub(crate) struct JsonThresholdViolation {
     pub tags: BTreeMap<String, String>,
     pub expression: String,
     pub observed: Option<f64>,
+    pub no_data: bool,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -192,15 +435,30 @@ pub(crate) struct JsonScenarioSummary {
     pub scenario: String,
 
     pub exec: Option<String>,
+    pub description: Option<String>,
     pub executor: Option<JsonScenarioExecutorConfig>,
 
+    /// RFC 3339 wall-clock time the scenario started.
+    pub started_at: Option<String>,
+    /// RFC 3339 wall-clock time the scenario's last VU finished.
+    pub ended_at: Option<String>,
+
     pub requests_total: u64,
     pub failed_requests_total: u64,
+    /// Failed requests per transport error kind (`connect`, `dns`, `tls`, `reset`, `timeout`, ...).
+    pub errors_by_kind: BTreeMap<String, u64>,
     pub bytes_received_total: u64,
     pub bytes_sent_total: u64,
     pub iterations_total: u64,
+    pub dropped_iterations_total: u64,
+    /// `true` when the executor dropped iterations (the target arrival rate was not met).
+    pub saturated: bool,
+    /// Share of HTTP requests sent on a reused keep-alive connection; `null` without any.
+    pub connection_reuse_rate: Option<f64>,
 
     pub checks: Option<JsonChecksSummary>,
+    /// Weighted share of passed checks (see `check` weights); `null` without checks.
+    pub checks_score: Option<f64>,
 
     pub latency_seconds: Option<JsonLatencySummarySeconds>,
 }
@@ -226,6 +484,7 @@ pub(crate) struct JsonScenarioExecutorConfig {
 pub(crate) struct JsonStage {
     pub duration_seconds: f64,
     pub target: u64,
+    pub shape: String,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -267,10 +526,38 @@ pub(crate) struct JsonLatencySummarySeconds {
 pub(crate) struct JsonTotals {
     pub requests_total: u64,
     pub failed_requests_total: u64,
+    pub errors_by_kind: BTreeMap<String, u64>,
     pub bytes_received_total: u64,
     pub bytes_sent_total: u64,
     pub iterations_total: u64,
+    pub dropped_iterations_total: u64,
     pub checks_failed_total: u64,
+    /// Weighted share of passed checks over all scenarios; `null` without checks.
+    pub checks_score: Option<f64>,
+    /// Latency over all scenarios' pooled samples.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_seconds: Option<JsonLatencySummarySeconds>,
+}
+
+fn rfc3339(t: std::time::SystemTime) -> String {
+    humantime::format_rfc3339_millis(t).to_string()
+}
+
+fn latency_seconds(l: &wrkr_core::HistogramSummary) -> JsonLatencySummarySeconds {
+    let us_to_secs_opt = |v: Option<f64>| v.map(|x| x / 1_000_000.0);
+
+    JsonLatencySummarySeconds {
+        p50: us_to_secs_opt(l.p50),
+        p75: us_to_secs_opt(l.p75),
+        p90: us_to_secs_opt(l.p90),
+        p95: us_to_secs_opt(l.p95),
+        p99: us_to_secs_opt(l.p99),
+        min: us_to_secs_opt(l.min),
+        max: us_to_secs_opt(l.max),
+        mean: us_to_secs_opt(l.mean),
+        stdev: us_to_secs_opt(l.stdev),
+        count: l.count,
+    }
 }
 
 fn build_summary_line(
@@ -280,6 +567,7 @@ fn build_summary_line(
     let mut totals = JsonTotals::default();
 
     let checks_by_scenario = parse_checks_from_metric_series(&summary.metrics);
+    let (mut checks_weight_passed, mut checks_weight_total) = (0u64, 0u64);
 
     let scenarios = summary
         .scenarios
@@ -289,11 +577,21 @@ fn build_summary_line(
             totals.failed_requests_total = totals
                 .failed_requests_total
                 .saturating_add(s.failed_requests_total);
+            for (kind, count) in &s.errors_by_kind {
+                let total = totals.errors_by_kind.entry(kind.clone()).or_default();
+                *total = total.saturating_add(*count);
+            }
             totals.bytes_received_total = totals
                 .bytes_received_total
                 .saturating_add(s.bytes_received_total);
             totals.bytes_sent_total = totals.bytes_sent_total.saturating_add(s.bytes_sent_total);
             totals.iterations_total = totals.iterations_total.saturating_add(s.iterations_total);
+            totals.dropped_iterations_total = totals
+                .dropped_iterations_total
+                .saturating_add(s.dropped_iterations_total);
+
+            checks_weight_passed = checks_weight_passed.saturating_add(s.checks_weight_passed);
+            checks_weight_total = checks_weight_total.saturating_add(s.checks_weight_total);
 
             let checks = checks_by_scenario.get(s.scenario.as_str()).cloned();
             totals.checks_failed_total = totals.checks_failed_total.saturating_add(
@@ -303,44 +601,51 @@ fn build_summary_line(
                     .unwrap_or(s.checks_failed_total),
             );
 
-            let (exec, executor) = scenarios
-                .and_then(|cfgs| cfgs.iter().find(|c| c.metrics_ctx.scenario() == s.scenario))
-                .map(|cfg| (Some(cfg.exec.clone()), Some(executor_config(cfg))))
-                .unwrap_or((None, None));
-
-            let us_to_secs_opt = |v: Option<f64>| v.map(|x| x / 1_000_000.0);
-
-            let latency_seconds = s.latency.as_ref().map(|l| JsonLatencySummarySeconds {
-                p50: us_to_secs_opt(l.p50),
-                p75: us_to_secs_opt(l.p75),
-                p90: us_to_secs_opt(l.p90),
-                p95: us_to_secs_opt(l.p95),
-                p99: us_to_secs_opt(l.p99),
-                min: us_to_secs_opt(l.min),
-                max: us_to_secs_opt(l.max),
-                mean: us_to_secs_opt(l.mean),
-                stdev: us_to_secs_opt(l.stdev),
-                count: l.count,
-            });
+            // Scenario names are unique (`scenarios_from_options` rejects duplicates), so the
+            // name alone identifies the executor config for this summary entry.
+            let cfg = scenarios
+                .and_then(|cfgs| cfgs.iter().find(|c| c.metrics_ctx.scenario() == s.scenario));
+            let exec = cfg.map(|cfg| cfg.exec.clone());
+            let description = cfg.and_then(|cfg| cfg.description.clone());
+            let executor = cfg.map(executor_config);
+
+            let latency_seconds = s.latency.as_ref().map(latency_seconds);
 
             JsonScenarioSummary {
                 scenario: s.scenario.clone(),
 
                 exec,
+                description,
                 executor,
 
+                started_at: s.started_at.map(rfc3339),
+                ended_at: s.ended_at.map(rfc3339),
+
                 requests_total: s.requests_total,
                 failed_requests_total: s.failed_requests_total,
+                errors_by_kind: s
+                    .errors_by_kind
+                    .iter()
+                    .map(|(k, v)| (k.clone(), *v))
+                    .collect(),
                 bytes_received_total: s.bytes_received_total,
                 bytes_sent_total: s.bytes_sent_total,
                 iterations_total: s.iterations_total,
+                dropped_iterations_total: s.dropped_iterations_total,
+                saturated: s.saturated(),
+                connection_reuse_rate: s.connection_reuse_rate(),
 
                 checks,
+                checks_score: s.checks_score(),
                 latency_seconds,
             }
         })
         .collect::<Vec<_>>();
 
+    totals.checks_score =
+        (checks_weight_total > 0).then(|| checks_weight_passed as f64 / checks_weight_total as f64);
+    totals.latency_seconds = summary.latency.as_ref().map(latency_seconds);
+
     let thresholds = JsonThresholdsSummary {
         violations: summary
             .threshold_violations
@@ -350,6 +655,22 @@ fn build_summary_line(
                 tags: v.tags.iter().cloned().collect::<BTreeMap<_, _>>(),
                 expression: v.expression.clone(),
                 observed: v.observed,
+                no_data: v.no_data,
+                description: v.description.clone(),
+            })
+            .collect(),
+        results: summary
+            .threshold_results
+            .iter()
+            .map(|r| JsonThresholdResult {
+                metric: r.metric.clone(),
+                tags: r.tags.iter().cloned().collect::<BTreeMap<_, _>>(),
+                expression: r.expression.clone(),
+                observed: r.observed,
+                limit: r.limit,
+                passed: r.passed,
+                no_data: r.no_data,
+                description: r.description.clone(),
             })
             .collect(),
     };
@@ -360,6 +681,36 @@ fn build_summary_line(
         scenarios,
         totals,
         thresholds,
+        aborted: summary.aborted.clone(),
+    }
+}
+
+fn build_repeat_summary_line(
+    stats: &[crate::repeat::ScenarioRepeatStats],
+) -> JsonRepeatSummaryLine {
+    let plain = |s: Option<crate::repeat::Spread>| s.map(|s| JsonSpread::scaled(s, 1.0));
+    let secs = |s: Option<crate::repeat::Spread>| s.map(|s| JsonSpread::scaled(s, 1e-6));
+
+    JsonRepeatSummaryLine {
+        schema: NDJSON_SCHEMA,
+        kind: "repeatSummary",
+        runs: stats.iter().map(|s| s.runs).max().unwrap_or(0),
+        scenarios: stats
+            .iter()
+            .map(|s| JsonScenarioRepeatSummary {
+                scenario: s.scenario.clone(),
+                runs: s.runs,
+                requests_total: plain(s.requests_total),
+                failed_requests_total: plain(s.failed_requests_total),
+                rps: plain(s.rps),
+                latency_seconds: JsonRepeatLatencySeconds {
+                    mean: secs(s.latency_mean),
+                    p50: secs(s.latency_p50),
+                    p90: secs(s.latency_p90),
+                    p99: secs(s.latency_p99),
+                },
+            })
+            .collect(),
     }
 }
 
@@ -386,6 +737,7 @@ fn executor_config(cfg: &wrkr_core::ScenarioConfig) -> JsonScenarioExecutorConfi
                         .map(|s| JsonStage {
                             duration_seconds: s.duration.as_secs_f64(),
                             target: s.target,
+                            shape: s.shape.to_string(),
                         })
                         .collect(),
                 ),
@@ -411,6 +763,7 @@ fn executor_config(cfg: &wrkr_core::ScenarioConfig) -> JsonScenarioExecutorConfi
                     .map(|s| JsonStage {
                         duration_seconds: s.duration.as_secs_f64(),
                         target: s.target,
+                        shape: s.shape.to_string(),
                     })
                     .collect(),
             ),
@@ -564,13 +917,6 @@ struct Acc {
     ChecksByScenario { by_scenario }
 }
 
-fn emit_json_line<T: Serialize>(line: &T) {
-    let mut out = std::io::stdout().lock();
-    if serde_json::to_writer(&mut out, line).is_ok() {
-        let _ = writeln!(out);
-    }
-}
-
 fn scenario_progress_vus(
     progress: &wrkr_core::ScenarioProgress,
 ) -> (u64, Option<u64>, Option<u64>) {
@@ -602,6 +948,64 @@ mod tests {
     use super::*;
     use serde_json::Value;
 
+    /// A `Write` whose bytes stay readable after it is boxed into a sink.
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedBuf {
+        fn lines(&self) -> usize {
+            let bytes = self.0.lock().unwrap_or_else(|p| p.into_inner());
+            bytes.iter().filter(|b| **b == b'\n').count()
+        }
+    }
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let mut bytes = self.0.lock().unwrap_or_else(|p| p.into_inner());
+            bytes.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn buffered_sink_holds_lines_until_forced_or_the_interval_passes() {
+        let buf = SharedBuf::default();
+        let sink = JsonSink::new(Box::new(buf.clone()), true);
+
+        sink.emit(&serde_json::json!({ "kind": "progress" }), false);
+        sink.emit(&serde_json::json!({ "kind": "progress" }), false);
+        assert_eq!(buf.lines(), 0);
+
+        sink.emit(&serde_json::json!({ "kind": "summary" }), true);
+        assert_eq!(buf.lines(), 3);
+
+        sink.emit(&serde_json::json!({ "kind": "progress" }), false);
+        assert_eq!(buf.lines(), 3);
+        {
+            let mut state = sink.state.lock().unwrap_or_else(|p| p.into_inner());
+            state.last_flush = Instant::now()
+                .checked_sub(FLUSH_INTERVAL)
+                .unwrap_or_else(|| panic!("clock too close to its origin"));
+        }
+        sink.emit(&serde_json::json!({ "kind": "progress" }), false);
+        assert_eq!(buf.lines(), 5);
+    }
+
+    #[test]
+    fn unbuffered_sink_writes_every_line_immediately() {
+        let buf = SharedBuf::default();
+        let sink = JsonSink::new(Box::new(buf.clone()), false);
+
+        sink.emit(&serde_json::json!({ "kind": "progress" }), false);
+        assert_eq!(buf.lines(), 1);
+        sink.emit(&serde_json::json!({ "kind": "progress" }), false);
+        assert_eq!(buf.lines(), 2);
+    }
+
     #[test]
     fn progress_line_has_kind() {
         let line = JsonProgressLine {
@@ -612,6 +1016,7 @@ fn progress_line_has_kind() {
             interval_seconds: 1.0,
             scenario: "s1".to_string(),
             exec: "Default".to_string(),
+            percent_complete: 40.0,
             executor: JsonProgressExecutor {
                 kind: "constant-vus",
                 vus_active: 2,
@@ -631,17 +1036,23 @@ fn progress_line_has_kind() {
                 latency_seconds: JsonProgressLatencySeconds {
                     mean: 0.01,
                     stdev: 0.02,
+                    min: 0.005,
                     max: 0.03,
                     p50: 0.04,
                     p75: 0.05,
                     p90: 0.06,
                     p99: 0.07,
                     stdev_pct: 7.0,
+                    selected: Some(JsonProgressSelectedPercentile {
+                        percentile: 99.9,
+                        seconds: 0.08,
+                    }),
                 },
                 req_per_sec_avg: 18.0,
                 req_per_sec_stdev: 19.0,
                 req_per_sec_max: 20.0,
                 req_per_sec_stdev_pct: 21.0,
+                gauges: BTreeMap::from([("queue_depth".to_string(), 12)]),
             },
         };
 
@@ -651,6 +1062,81 @@ fn progress_line_has_kind() {
         };
         assert_eq!(v.get("schema").and_then(Value::as_str), Some(NDJSON_SCHEMA));
         assert_eq!(v.get("kind").and_then(Value::as_str), Some("progress"));
+        assert_eq!(v.get("percentComplete").and_then(Value::as_f64), Some(40.0));
+        assert_eq!(
+            v.pointer("/metrics/latencySeconds/selected/percentile")
+                .and_then(Value::as_f64),
+            Some(99.9)
+        );
+        assert_eq!(
+            v.pointer("/metrics/gauges/queue_depth")
+                .and_then(Value::as_i64),
+            Some(12)
+        );
+    }
+
+    #[test]
+    fn run_start_line_echoes_cli_overridden_executor() {
+        let opts = wrkr_core::ScriptOptions {
+            scenarios: vec![wrkr_core::ScenarioOptions {
+                name: "ramp".to_string(),
+                exec: None,
+                tags: Vec::new(),
+                description: None,
+                params: None,
+                executor: Some("ramping-vus".to_string()),
+                vus: None,
+                iterations: None,
+                duration: None,
+                think_time: None,
+                isolate_connections: false,
+                start_vus: None,
+                stages: vec![wrkr_core::Stage {
+                    duration: Duration::from_secs(10),
+                    target: 20,
+                    shape: wrkr_core::StageShape::Linear,
+                }],
+                start_rate: None,
+                time_unit: None,
+                pre_allocated_vus: None,
+                max_vus: None,
+            }],
+            ..Default::default()
+        };
+        let cfg = wrkr_core::RunConfig {
+            vus: Some(3),
+            ..Default::default()
+        };
+        let scenarios =
+            wrkr_core::scenarios_from_options(opts, cfg).unwrap_or_else(|e| panic!("{e}"));
+        let thresholds = [wrkr_core::ThresholdSet {
+            metric: "request_latency".to_string(),
+            tags: vec![("scenario".to_string(), "ramp".to_string())],
+            expressions: vec!["p(95)<200000".to_string()],
+            description: None,
+            when: Vec::new(),
+            no_data: wrkr_core::ThresholdNoData::Fail,
+        }];
+
+        let line = build_run_start_line(Path::new("bench.lua"), &scenarios, &thresholds);
+        let v = serde_json::to_value(&line).unwrap_or_else(|e| panic!("to_value failed: {e}"));
+
+        assert_eq!(v.get("kind").and_then(Value::as_str), Some("runStart"));
+        assert_eq!(
+            v.pointer("/scenarios/0/executor/kind")
+                .and_then(Value::as_str),
+            Some("constant-vus")
+        );
+        assert_eq!(
+            v.pointer("/scenarios/0/executor/vus")
+                .and_then(Value::as_u64),
+            Some(3)
+        );
+        assert_eq!(
+            v.pointer("/thresholds/0/tags/scenario")
+                .and_then(Value::as_str),
+            Some("ramp")
+        );
     }
 
     #[test]
@@ -658,13 +1144,21 @@ fn summary_line_has_totals() {
         let summary = wrkr_core::RunSummary {
             scenarios: vec![wrkr_core::ScenarioSummary {
                 scenario: "s1".to_string(),
+                started_at: Some(std::time::UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+                ended_at: None,
                 requests_total: 10,
                 failed_requests_total: 2,
+                errors_by_kind: [("connect".to_string(), 2)].into_iter().collect(),
                 bytes_received_total: 3,
                 bytes_sent_total: 4,
                 iterations_total: 5,
+                dropped_iterations_total: 7,
+                connection_reuse_samples: 4,
+                connection_reused_total: 3,
                 checks_failed_total: 6,
                 checks_failed: [("c1".to_string(), 6)].into_iter().collect(),
+                checks_weight_total: 20,
+                checks_weight_passed: 15,
                 latency: None,
             }],
             ..Default::default()
@@ -691,9 +1185,80 @@ fn summary_line_has_totals() {
             v.pointer("/scenarios/0/scenario").and_then(Value::as_str),
             Some("s1")
         );
+        assert_eq!(
+            v.pointer("/scenarios/0/startedAt").and_then(Value::as_str),
+            Some("2023-11-14T22:13:20.000Z")
+        );
+        assert_eq!(v.pointer("/scenarios/0/endedAt"), Some(&Value::Null));
+        assert_eq!(
+            v.pointer("/scenarios/0/droppedIterationsTotal")
+                .and_then(Value::as_u64),
+            Some(7)
+        );
+        assert_eq!(
+            v.pointer("/scenarios/0/saturated").and_then(Value::as_bool),
+            Some(true)
+        );
+        assert_eq!(
+            v.pointer("/scenarios/0/connectionReuseRate")
+                .and_then(Value::as_f64),
+            Some(0.75)
+        );
+        for scope in ["/scenarios/0", "/totals"] {
+            assert_eq!(
+                v.pointer(&format!("{scope}/errorsByKind/connect"))
+                    .and_then(Value::as_u64),
+                Some(2),
+                "{scope}"
+            );
+            assert_eq!(
+                v.pointer(&format!("{scope}/checksScore"))
+                    .and_then(Value::as_f64),
+                Some(0.75),
+                "{scope}"
+            );
+        }
         assert!(
             v.get("thresholds").is_some(),
             "expected summary json to include `thresholds`"
         );
     }
+
+    #[test]
+    fn repeat_summary_line_reports_latency_in_seconds() {
+        let spread = |mean| crate::repeat::Spread {
+            mean,
+            stdev: 0.0,
+            min: mean,
+            max: mean,
+        };
+        let stats = vec![crate::repeat::ScenarioRepeatStats {
+            scenario: "main".to_string(),
+            runs: 3,
+            requests_total: Some(spread(100.0)),
+            failed_requests_total: Some(spread(0.0)),
+            rps: Some(spread(10.0)),
+            latency_mean: None,
+            latency_p50: Some(spread(2_500.0)),
+            latency_p90: None,
+            latency_p99: None,
+        }];
+
+        let v: Value = match serde_json::to_value(build_repeat_summary_line(&stats)) {
+            Ok(v) => v,
+            Err(err) => panic!("to_value failed: {err}"),
+        };
+
+        assert_eq!(v.get("kind").and_then(Value::as_str), Some("repeatSummary"));
+        assert_eq!(v.get("runs").and_then(Value::as_u64), Some(3));
+        assert_eq!(
+            v.pointer("/scenarios/0/rps/mean").and_then(Value::as_f64),
+            Some(10.0)
+        );
+        assert_eq!(
+            v.pointer("/scenarios/0/latencySeconds/p50/mean")
+                .and_then(Value::as_f64),
+            Some(0.0025)
+        );
+    }
 }
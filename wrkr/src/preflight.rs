@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+
+/// Upper bound for checking one `--preflight` target, connection and response included.
+const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Check every target once before the run starts; the first unreachable one fails the run.
+///
+/// `http://` and `https://` URLs get a single GET through the run's HTTP client (so `--proxy`
+/// and `--insecure` apply) and pass on any response status. Anything else, including
+/// `tcp://host:port`, only has to accept a TCP connection.
+#[cfg_attr(not(feature = "http"), allow(unused_variables))]
+pub(crate) async fn check(
+    targets: &[String],
+    run_ctx: &wrkr_core::RunScenariosContext,
+) -> anyhow::Result<()> {
+    for target in targets {
+        let attempt = async {
+            #[cfg(feature = "http")]
+            if is_http_url(target) {
+                return run_ctx
+                    .client
+                    .get(target)
+                    .await
+                    .map(|_| ())
+                    .map_err(anyhow::Error::new);
+            }
+            let addr = tcp_address(target)?;
+            tokio::net::TcpStream::connect(addr.as_str())
+                .await
+                .map(|_| ())
+                .with_context(|| format!("no TCP connection to {addr}"))
+        };
+        match tokio::time::timeout(PREFLIGHT_TIMEOUT, attempt).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                return Err(err.context(format!("preflight: {target} is unreachable")));
+            }
+            Err(_) => anyhow::bail!(
+                "preflight: {target} did not respond within {}",
+                humantime::format_duration(PREFLIGHT_TIMEOUT)
+            ),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "http")]
+fn is_http_url(target: &str) -> bool {
+    let lower = target.to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// The `host:port` to connect to: a URL's authority (with the scheme's default port when it
+/// has none), or the target itself.
+fn tcp_address(target: &str) -> anyhow::Result<String> {
+    let (scheme, rest) = match target.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme.to_ascii_lowercase()), rest),
+        None => (None, target),
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    // Credentials are not part of the address.
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, a)| a);
+    if authority.is_empty() {
+        anyhow::bail!("invalid --preflight target `{target}` (expected a URL or host:port)");
+    }
+
+    let has_port = match authority.rsplit_once(':') {
+        // An IPv6 literal without a port ends in `]`.
+        Some((_, port)) => !port.ends_with(']'),
+        None => false,
+    };
+    if has_port {
+        return Ok(authority.to_string());
+    }
+    let default_port = match scheme.as_deref() {
+        Some("http") => 80,
+        Some("https") => 443,
+        _ => anyhow::bail!("invalid --preflight target `{target}` (missing port)"),
+    };
+    Ok(format!("{authority}:{default_port}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_ctx() -> wrkr_core::RunScenariosContext {
+        wrkr_core::RunScenariosContext::new(
+            std::sync::Arc::from([]),
+            String::new(),
+            std::path::PathBuf::new(),
+        )
+    }
+
+    #[test]
+    fn tcp_address_uses_the_authority_and_default_ports() {
+        let addr = |s: &str| tcp_address(s).unwrap_or_else(|e| panic!("{s}: {e}"));
+        assert_eq!(addr("localhost:50051"), "localhost:50051");
+        assert_eq!(addr("tcp://10.0.0.1:9000"), "10.0.0.1:9000");
+        assert_eq!(
+            addr("https://api.example.com/health"),
+            "api.example.com:443"
+        );
+        assert_eq!(
+            addr("http://user:pw@example.com:8080/x"),
+            "example.com:8080"
+        );
+        assert_eq!(addr("http://[::1]/"), "[::1]:80");
+        assert_eq!(addr("http://[::1]:8080"), "[::1]:8080");
+        assert!(tcp_address("localhost").is_err());
+        assert!(tcp_address("tcp://").is_err());
+    }
+
+    #[tokio::test]
+    async fn unreachable_targets_fail_with_the_target_in_the_message() {
+        // Bind and drop a listener to get a local port nothing listens on.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|l| l.local_addr())
+            .map(|a| a.port())
+            .unwrap_or_else(|e| panic!("bind: {e}"));
+        let target = format!("127.0.0.1:{port}");
+
+        let res = check(std::slice::from_ref(&target), &run_ctx()).await;
+        let err = res
+            .err()
+            .unwrap_or_else(|| panic!("expected {target} to fail"));
+        assert!(
+            format!("{err:#}").contains(&format!("preflight: {target} is unreachable")),
+            "{err:#}"
+        );
+    }
+
+    #[tokio::test]
+    async fn reachable_tcp_targets_pass() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap_or_else(|e| panic!("bind: {e}"));
+        let addr = listener
+            .local_addr()
+            .unwrap_or_else(|e| panic!("local_addr: {e}"));
+
+        let res = check(&[format!("tcp://{addr}")], &run_ctx()).await;
+        assert!(res.is_ok(), "{res:?}");
+    }
+}
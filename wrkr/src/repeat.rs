@@ -0,0 +1,143 @@
+//! Run-to-run spread for `wrkr run --repeat N`.
+
+/// Spread of one statistic across repeated runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Spread {
+    pub mean: f64,
+    /// Sample standard deviation (`n - 1`); zero for a single run.
+    pub stdev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Spread {
+    pub(crate) fn of(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let stdev = if values.len() > 1 {
+            let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            var.sqrt()
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            mean,
+            stdev,
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+
+    /// Standard deviation as a percentage of the mean.
+    pub(crate) fn stdev_pct(&self) -> f64 {
+        if self.mean > 0.0 {
+            (self.stdev / self.mean) * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Per-scenario spread of the headline numbers; latencies are in microseconds.
+#[derive(Debug, Clone)]
+pub(crate) struct ScenarioRepeatStats {
+    pub scenario: String,
+    pub runs: usize,
+    pub requests_total: Option<Spread>,
+    pub failed_requests_total: Option<Spread>,
+    pub rps: Option<Spread>,
+    pub latency_mean: Option<Spread>,
+    pub latency_p50: Option<Spread>,
+    pub latency_p90: Option<Spread>,
+    pub latency_p99: Option<Spread>,
+}
+
+pub(crate) fn scenario_stats(runs: &[wrkr_core::RunSummary]) -> Vec<ScenarioRepeatStats> {
+    let mut names: Vec<&str> = Vec::new();
+    for s in runs.iter().flat_map(|r| &r.scenarios) {
+        if !names.contains(&s.scenario.as_str()) {
+            names.push(&s.scenario);
+        }
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            let scenarios: Vec<&wrkr_core::ScenarioSummary> = runs
+                .iter()
+                .filter_map(|r| r.scenarios.iter().find(|s| s.scenario == name))
+                .collect();
+
+            let spread = |f: &dyn Fn(&wrkr_core::ScenarioSummary) -> Option<f64>| {
+                let values: Vec<f64> = scenarios.iter().filter_map(|s| f(s)).collect();
+                Spread::of(&values)
+            };
+            let latency = |f: fn(&wrkr_core::HistogramSummary) -> Option<f64>| {
+                spread(&|s| s.latency.as_ref().and_then(f))
+            };
+
+            ScenarioRepeatStats {
+                scenario: name.to_string(),
+                runs: scenarios.len(),
+                requests_total: spread(&|s| Some(s.requests_total as f64)),
+                failed_requests_total: spread(&|s| Some(s.failed_requests_total as f64)),
+                rps: spread(&wrkr_core::ScenarioSummary::rps),
+                latency_mean: latency(|h| h.mean),
+                latency_p50: latency(|h| h.p50),
+                latency_p90: latency(|h| h.p90),
+                latency_p99: latency(|h| h.p99),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    fn run(requests_total: u64, active_secs: u64) -> wrkr_core::RunSummary {
+        let started = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        wrkr_core::RunSummary {
+            scenarios: vec![wrkr_core::ScenarioSummary {
+                scenario: "main".to_string(),
+                started_at: Some(started),
+                ended_at: Some(started + Duration::from_secs(active_secs)),
+                requests_total,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn spread_uses_sample_stdev() {
+        let s = Spread::of(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0])
+            .unwrap_or_else(|| panic!("expected spread"));
+        assert_eq!(s.mean, 5.0);
+        assert!((s.stdev - 2.138).abs() < 1e-3, "{}", s.stdev);
+        assert_eq!((s.min, s.max), (2.0, 9.0));
+
+        assert_eq!(Spread::of(&[3.0]).map(|s| s.stdev), Some(0.0));
+        assert!(Spread::of(&[]).is_none());
+    }
+
+    #[test]
+    fn scenario_stats_compare_runs_per_scenario() {
+        let stats = scenario_stats(&[run(1_000, 10), run(1_200, 10), run(800, 10)]);
+        assert_eq!(stats.len(), 1);
+
+        let main = &stats[0];
+        assert_eq!(main.runs, 3);
+        let rps = main.rps.unwrap_or_else(|| panic!("expected rps spread"));
+        assert_eq!((rps.mean, rps.min, rps.max), (100.0, 80.0, 120.0));
+        assert!((rps.stdev_pct() - 20.0).abs() < 1e-9);
+        assert!(main.latency_p99.is_none());
+    }
+}
@@ -1,86 +1,366 @@
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use crate::cli::OutputFormat;
 use crate::cli::RunArgs;
 use crate::exit_codes::ExitCode;
 use crate::output;
+use crate::output::OutputFormatter;
+use crate::repeat;
+use crate::run_config;
 use crate::run_error::RunError;
 use crate::run_support::{classify_runtime_create_error, classify_runtime_error, merged_env};
 use crate::runtime;
+use crate::runtime::ScriptRuntime;
 use crate::scenario_yaml;
 
-pub async fn run(args: RunArgs) -> Result<ExitCode, RunError> {
-    let out = output::formatter(args.output);
+pub async fn run(mut args: RunArgs) -> Result<ExitCode, RunError> {
+    let config_thresholds = run_config::apply(&mut args)
+        .await
+        .map_err(RunError::InvalidInput)?;
+    let out = output::formatter(
+        &args.output,
+        args.no_buffer,
+        args.quiet,
+        args.summary_time_unit,
+        &args.rename_metric,
+    )
+    .map_err(RunError::InvalidInput)?;
+
+    let env = merged_env(&args.env_file, &args.env).map_err(RunError::InvalidInput)?;
+    let runtime_opts = runtime::RuntimeOptions {
+        replay_speed: args.replay_speed,
+        replay_rate: args.replay_rate,
+    };
+    let scripts = args
+        .scripts
+        .iter()
+        .map(|path| {
+            runtime::create_runtime(path, &runtime_opts).map_err(classify_runtime_create_error)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let scripts = RunScripts::new(scripts);
+
+    let mut summaries = Vec::new();
+    for i in 1..=args.repeat {
+        if args.repeat > 1 && human_output(&args) {
+            println!("run {i}/{}", args.repeat);
+        }
+
+        let summary = run_once(&args, &config_thresholds, out.as_ref(), &scripts, &env).await?;
+        // The target is already failing; further repetitions would only repeat that.
+        let aborted = summary.aborted.is_some();
+        summaries.push(summary);
+        if aborted {
+            break;
+        }
+    }
+
+    if summaries.len() > 1 {
+        out.print_repeat_summary(&repeat::scenario_stats(&summaries))
+            .map_err(RunError::RuntimeError)?;
+    }
+
+    let code = exit_code(&summaries, args.fail_on_no_requests);
+    if code == ExitCode::NoRequests {
+        tracing::error!("run failed: no requests were made (--fail-on-no-requests)");
+    }
+    Ok(code)
+}
+
+/// One script of the run; `source` is only set when several scripts run together.
+struct RunScript {
+    runtime: Arc<dyn ScriptRuntime>,
+    source: Option<Arc<wrkr_core::ScriptSource>>,
+}
+
+/// The scripts of a run, in command-line order.
+#[derive(Clone)]
+struct RunScripts(Arc<[RunScript]>);
+
+impl RunScripts {
+    fn new(runtimes: Vec<Arc<dyn ScriptRuntime>>) -> Self {
+        // A single script keeps using the run context's own script, exactly as before.
+        let composite = runtimes.len() > 1;
+        Self(
+            runtimes
+                .into_iter()
+                .map(|runtime| RunScript {
+                    source: composite.then(|| Arc::new(runtime.script_source())),
+                    runtime,
+                })
+                .collect(),
+        )
+    }
+
+    fn primary(&self) -> &Arc<dyn ScriptRuntime> {
+        &self.0[0].runtime
+    }
+
+    fn is_composite(&self) -> bool {
+        self.0.len() > 1
+    }
+
+    /// Each script with the run context it sees (its own source, the run's shared state).
+    fn with_contexts<'a>(
+        &'a self,
+        run_ctx: &'a wrkr_core::RunScenariosContext,
+    ) -> impl Iterator<Item = (&'a RunScript, Cow<'a, wrkr_core::RunScenariosContext>)> {
+        self.0.iter().map(move |script| {
+            let ctx = match &script.source {
+                Some(source) => Cow::Owned(run_ctx.with_script(source)),
+                None => Cow::Borrowed(run_ctx),
+            };
+            (script, ctx)
+        })
+    }
+
+    /// Runtime of the script that defined the VU's scenario.
+    fn for_vu(&self, ctx: &wrkr_core::VuContext) -> Arc<dyn ScriptRuntime> {
+        self.0
+            .iter()
+            .find(|script| match (&script.source, &ctx.script) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            })
+            .map_or_else(|| self.primary().clone(), |script| script.runtime.clone())
+    }
+}
 
-    let env = merged_env(&args.env).map_err(RunError::InvalidInput)?;
+/// What `--scenario` asks for: at most one scenario YAML file, plus the names of the scenarios
+/// to run (all of them when none are given).
+struct ScenarioSelection<'a> {
+    yaml: Option<&'a str>,
+    names: Vec<&'a str>,
+}
+
+impl<'a> ScenarioSelection<'a> {
+    fn parse(values: &'a [String]) -> Result<Self, RunError> {
+        let mut selection = Self {
+            yaml: None,
+            names: Vec::new(),
+        };
+        for value in values {
+            if !scenario_yaml::looks_like_yaml_path(value) {
+                selection.names.push(value);
+            } else if let Some(first) = selection.yaml {
+                return Err(RunError::InvalidInput(anyhow::anyhow!(
+                    "--scenario {value}: only one scenario YAML file can be used (already got {first})"
+                )));
+            } else {
+                selection.yaml = Some(value);
+            }
+        }
+        Ok(selection)
+    }
+
+    fn retain(&self, scenarios: &mut Vec<wrkr_core::ScenarioConfig>) {
+        if !self.names.is_empty() {
+            scenarios.retain(|s| self.names.contains(&s.metrics_ctx.scenario()));
+        }
+    }
+
+    /// Every selected name must match a scenario, so a typo in a CI job fails loudly instead of
+    /// silently running less.
+    fn ensure_matched(&self, scenarios: &[wrkr_core::ScenarioConfig]) -> Result<(), RunError> {
+        match self
+            .names
+            .iter()
+            .find(|name| !scenarios.iter().any(|s| s.metrics_ctx.scenario() == **name))
+        {
+            Some(name) => Err(RunError::InvalidInput(anyhow::anyhow!(
+                "unknown scenario: {name}"
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// One full run (Setup, scenarios, Teardown, HandleSummary) with a fresh run context.
+async fn run_once(
+    args: &RunArgs,
+    config_thresholds: &[wrkr_core::ThresholdSet],
+    out: &dyn OutputFormatter,
+    scripts: &RunScripts,
+    env: &wrkr_core::EnvVars,
+) -> Result<wrkr_core::RunSummary, RunError> {
     let cfg = wrkr_core::RunConfig {
         iterations: args.iterations,
         vus: args.vus,
         duration: args.duration,
+        warmup_iterations: args.warmup_iterations,
+        tags: args.tag.clone(),
     };
 
-    let runtime = runtime::create_runtime(&args.script).map_err(classify_runtime_create_error)?;
-    let mut run_ctx = runtime.create_run_context(&env);
-
-    let (opts, scenarios) = match args.scenario.as_deref() {
-        None => {
-            let opts = runtime
-                .parse_script_options(&run_ctx)
-                .map_err(|e| classify_runtime_error("failed to parse script options", e))?;
+    let mut run_ctx = scripts.primary().create_run_context(env);
+    run_ctx.progress_percentile = args.progress_percentile;
+    run_ctx.gauge_series = Arc::from(args.gauge_series.clone());
+    for name in &args.disable_metric {
+        run_ctx.metrics.disable(name);
+    }
+    for precision in &args.histogram_sig_figs {
+        match &precision.metric {
+            Some(name) => run_ctx
+                .metrics
+                .set_metric_sig_figs(name, precision.sig_figs),
+            None => run_ctx.metrics.set_histogram_sig_figs(precision.sig_figs),
+        }
+    }
+    if let Some(limit) = args.log_failures {
+        run_ctx.failures = Some(Arc::new(wrkr_core::FailureLog::new(
+            limit,
+            args.log_failures_window,
+        )));
+    }
+    run_ctx.max_duration = args.max_duration;
+    run_ctx.abort_on_error_rate =
+        args.abort_on_error_rate
+            .map(|max_rate| wrkr_core::ErrorRateBreaker {
+                max_rate,
+                window: args.abort_window,
+            });
+    run_ctx.insecure_skip_verify = args.insecure;
+    run_ctx.disable_tls_resumption = args.no_tls_resumption;
+    #[cfg(feature = "http")]
+    if args.proxy.is_some()
+        || args.insecure
+        || args.connect_timeout != wrkr_core::HttpClient::DEFAULT_CONNECT_TIMEOUT
+        || args.no_keep_alive
+        || args.no_tls_resumption
+    {
+        let proxy = match args.proxy.as_deref() {
+            Some(url) => wrkr_core::ProxyConfig::all(url).map_err(|e| {
+                RunError::InvalidInput(anyhow::Error::new(e).context("invalid --proxy"))
+            })?,
+            None => wrkr_core::ProxyConfig::from_env(),
+        };
+        let client = wrkr_core::HttpClient::with_config(
+            Some(args.connect_timeout),
+            proxy,
+            wrkr_core::HttpTlsConfig {
+                insecure_skip_verify: args.insecure,
+                disable_session_resumption: args.no_tls_resumption,
+            },
+        )
+        .map_err(|e| {
+            RunError::InvalidInput(anyhow::Error::new(e).context("invalid TLS settings"))
+        })?;
+        run_ctx.client = Arc::new(client.keep_alive(!args.no_keep_alive));
+    }
 
-            let scenarios = wrkr_core::scenarios_from_options(opts.clone(), cfg).map_err(|e| {
-                RunError::InvalidInput(anyhow::Error::new(e).context("invalid scenario config"))
-            })?;
+    let invalid_scenarios =
+        |e| RunError::InvalidInput(anyhow::Error::new(e).context("invalid scenario config"));
 
-            (opts, scenarios)
-        }
-        Some(sel) if scenario_yaml::looks_like_yaml_path(sel) => {
+    let selection = ScenarioSelection::parse(&args.scenario)?;
+    let (scenarios, mut thresholds) = match selection.yaml {
+        Some(sel) => {
+            if scripts.is_composite() {
+                return Err(RunError::InvalidInput(anyhow::anyhow!(
+                    "--scenario {sel}: a scenario YAML file can only be used with a single script"
+                )));
+            }
             let scenario_path = std::path::PathBuf::from(sel);
             let opts = scenario_yaml::load_script_options_from_yaml(&scenario_path)
                 .await
                 .map_err(|e| RunError::InvalidInput(e.context("failed to load scenario YAML")))?;
 
-            let scenarios = wrkr_core::scenarios_from_options(opts.clone(), cfg).map_err(|e| {
-                RunError::InvalidInput(anyhow::Error::new(e).context("invalid scenario config"))
-            })?;
+            let mut scenarios =
+                wrkr_core::scenarios_from_options(opts.clone(), cfg).map_err(invalid_scenarios)?;
+            selection.retain(&mut scenarios);
+            selection.ensure_matched(&scenarios)?;
 
-            (opts, scenarios)
+            // Sets whose `when` condition does not match this run are dropped up front, so they
+            // are neither echoed in the header nor evaluated.
+            let thresholds = wrkr_core::active_thresholds(&opts.thresholds, &scenarios);
+            (scenarios, thresholds)
         }
-        Some(name) => {
-            let opts = runtime
-                .parse_script_options(&run_ctx)
-                .map_err(|e| classify_runtime_error("failed to parse script options", e))?;
+        None => {
+            let mut scenarios = Vec::new();
+            let mut thresholds = Vec::new();
+            let mut discard_response_bodies = false;
+            let mut no_vu_connection_reuse = false;
+            let mut isolate_scenario_connections = false;
+            let mut http_cache = None;
+            #[cfg(feature = "http")]
+            let mut no_connection_reuse = false;
+            for (script, script_ctx) in scripts.with_contexts(&run_ctx) {
+                let opts = script
+                    .runtime
+                    .parse_script_options(&script_ctx)
+                    .map_err(|e| classify_runtime_error("failed to parse script options", e))?;
 
-            let mut scenarios =
-                wrkr_core::scenarios_from_options(opts.clone(), cfg).map_err(|e| {
-                    RunError::InvalidInput(anyhow::Error::new(e).context("invalid scenario config"))
-                })?;
+                let mut script_scenarios =
+                    wrkr_core::scenarios_from_options(opts.clone(), cfg.clone())
+                        .map_err(invalid_scenarios)?;
+                if let Some(source) = &script.source {
+                    wrkr_core::attach_script(&mut script_scenarios, source);
+                }
+                selection.retain(&mut script_scenarios);
 
-            scenarios.retain(|s| s.metrics_ctx.scenario() == name);
-            if scenarios.is_empty() {
-                return Err(RunError::InvalidInput(anyhow::anyhow!(
-                    "unknown scenario: {name}"
-                )));
+                // As above, thresholds are matched against this script's scenarios only.
+                let mut sets = wrkr_core::active_thresholds(&opts.thresholds, &script_scenarios);
+                if let Some(source) = &script.source {
+                    wrkr_core::scope_thresholds(&mut sets, source);
+                }
+
+                scenarios.extend(script_scenarios);
+                thresholds.extend(sets);
+                discard_response_bodies |= opts.discard_response_bodies;
+                no_vu_connection_reuse |= opts.no_vu_connection_reuse;
+                isolate_scenario_connections |= opts.isolate_scenario_connections;
+                http_cache = http_cache.max(opts.http_cache);
+                #[cfg(feature = "http")]
+                {
+                    no_connection_reuse |= opts.no_connection_reuse;
+                }
             }
+            // The HTTP client is shared by the whole run, so one script opting in is enough.
+            run_ctx.discard_response_bodies = discard_response_bodies;
+            run_ctx.no_vu_connection_reuse = no_vu_connection_reuse;
+            run_ctx.isolate_scenario_connections = isolate_scenario_connections;
+            run_ctx.http_cache = http_cache;
+            #[cfg(feature = "http")]
+            if no_connection_reuse {
+                run_ctx.client = Arc::new(run_ctx.client.as_ref().clone().keep_alive(false));
+            }
+
+            selection.ensure_matched(&scenarios)?;
+            wrkr_core::ensure_unique_scenarios(&scenarios).map_err(invalid_scenarios)?;
 
-            (opts, scenarios)
+            (scenarios, thresholds)
         }
     };
 
-    run_ctx.thresholds = Arc::from(opts.thresholds.clone().into_boxed_slice());
+    // `--config` and `--thresholds` files apply to the whole run, on top of the script's own sets.
+    thresholds.extend(wrkr_core::active_thresholds(config_thresholds, &scenarios));
+    for path in &args.thresholds {
+        let sets = scenario_yaml::load_thresholds_file(path)
+            .await
+            .map_err(|e| RunError::InvalidInput(e.context("failed to load --thresholds file")))?;
+        thresholds.extend(wrkr_core::active_thresholds(&sets, &scenarios));
+    }
+    run_ctx.thresholds = Arc::from(thresholds.clone().into_boxed_slice());
+
+    // Before Setup, which may already talk to the targets.
+    crate::preflight::check(&args.preflight, &run_ctx)
+        .await
+        .map_err(RunError::InvalidInput)?;
 
-    runtime
-        .run_setup(&run_ctx)
-        .map_err(|e| classify_runtime_error("script Setup failed", e))?;
+    for (script, script_ctx) in scripts.with_contexts(&run_ctx) {
+        script
+            .runtime
+            .run_setup(&script_ctx)
+            .map_err(|e| classify_runtime_error("script Setup failed", e))?;
+    }
 
-    out.print_header(args.script.as_path(), &scenarios);
+    out.print_header(args.scripts[0].as_path(), &scenarios, &thresholds);
     let progress = out.progress();
 
-    let runtime_for_vu = runtime.clone();
-    let summary = wrkr_core::run_scenarios(
+    let vu_scripts = scripts.clone();
+    let mut summary = wrkr_core::run_scenarios(
         scenarios,
         run_ctx.clone(),
-        move |ctx| runtime_for_vu.run_vu(ctx),
+        move |ctx| vu_scripts.for_vu(&ctx).run_vu(ctx),
         progress,
     )
     .await
@@ -91,43 +371,170 @@ pub async fn run(args: RunArgs) -> Result<ExitCode, RunError> {
         _ => RunError::ScriptError(anyhow::Error::new(e).context("script run failed")),
     })?;
 
-    runtime
-        .run_teardown(&run_ctx)
-        .map_err(|e| classify_runtime_error("script Teardown failed", e))?;
-
-    let outputs = runtime
-        .run_handle_summary(&run_ctx, &summary)
-        .map_err(|e| classify_runtime_error("script HandleSummary failed", e))?;
-    if let Some(outputs) = outputs {
-        let cwd = std::env::current_dir().map_err(|e| {
-            RunError::RuntimeError(
-                anyhow::Error::new(e).context("failed to resolve current working directory"),
-            )
-        })?;
-        wrkr_core::write_output_files(&cwd, &outputs.files).map_err(|e| {
-            RunError::RuntimeError(
-                anyhow::Error::new(e).context("failed to write HandleSummary output files"),
-            )
-        })?;
+    for (script, script_ctx) in scripts.with_contexts(&run_ctx) {
+        script
+            .runtime
+            .run_teardown(&script_ctx)
+            .map_err(|e| classify_runtime_error("script Teardown failed", e))?;
+    }
 
-        if matches!(args.output, OutputFormat::HumanReadable) {
-            if let Some(s) = outputs.stdout {
-                print!("{s}");
-            }
-            if let Some(s) = outputs.stderr {
-                eprint!("{s}");
-            }
+    // `Teardown` and `HandleSummary` may record final values (e.g. a derived KPI); they belong
+    // in the summary that `HandleSummary` and the outputs see.
+    let refresh = |summary: &mut wrkr_core::RunSummary| {
+        summary
+            .refresh_metrics(&run_ctx.metrics, &run_ctx.thresholds)
+            .map_err(|e| {
+                RunError::InvalidInput(anyhow::Error::new(e).context("invalid thresholds"))
+            })
+    };
+    refresh(&mut summary)?;
+
+    for (script, script_ctx) in scripts.with_contexts(&run_ctx) {
+        let outputs = script
+            .runtime
+            .run_handle_summary(&script_ctx, &summary)
+            .map_err(|e| classify_runtime_error("script HandleSummary failed", e))?;
+        if let Some(outputs) = outputs {
+            write_script_outputs(args, outputs)?;
         }
     }
+    refresh(&mut summary)?;
 
+    if let Some(failures) = &run_ctx.failures {
+        failures.finish();
+    }
     out.print_summary(&summary)
         .map_err(RunError::RuntimeError)?;
 
-    let checks_failed = summary.scenarios.iter().any(|s| s.checks_failed_total > 0);
-    let thresholds_failed = !summary.threshold_violations.is_empty();
+    Ok(summary)
+}
+
+/// Files, stdout and stderr returned by a script's `HandleSummary`.
+fn write_script_outputs(args: &RunArgs, outputs: runtime::ScriptOutputs) -> Result<(), RunError> {
+    let cwd = std::env::current_dir().map_err(|e| {
+        RunError::RuntimeError(
+            anyhow::Error::new(e).context("failed to resolve current working directory"),
+        )
+    })?;
+    wrkr_core::write_output_files(&cwd, &outputs.files).map_err(|e| {
+        RunError::RuntimeError(
+            anyhow::Error::new(e).context("failed to write HandleSummary output files"),
+        )
+    })?;
+
+    if human_output(args) {
+        if let Some(s) = outputs.stdout {
+            print!("{s}");
+        }
+        if let Some(s) = outputs.stderr {
+            eprint!("{s}");
+        }
+    }
+    Ok(())
+}
+
+fn human_output(args: &RunArgs) -> bool {
+    args.output
+        .iter()
+        .any(|target| target.format == OutputFormat::HumanReadable)
+}
+
+/// Exit code over all runs: any abort wins, then failed checks/thresholds in any run.
+///
+/// Thresholds are only evaluated once a run has ended, so `ThresholdsFailed` always means a
+/// completed run, while `Aborted` marks partial metrics whatever the thresholds say.
+fn exit_code(summaries: &[wrkr_core::RunSummary], fail_on_no_requests: bool) -> ExitCode {
+    if summaries.iter().any(|s| s.aborted.is_some()) {
+        return ExitCode::Aborted;
+    }
+
+    let no_requests = |s: &wrkr_core::RunSummary| s.scenarios.iter().all(|s| s.requests_total == 0);
+    if fail_on_no_requests && summaries.iter().any(no_requests) {
+        return ExitCode::NoRequests;
+    }
+
+    let checks_failed = summaries
+        .iter()
+        .flat_map(|s| &s.scenarios)
+        .any(|s| s.checks_failed_total > 0);
+    let thresholds_failed = summaries.iter().any(|s| !s.threshold_violations.is_empty());
+
+    ExitCode::from_quality_gates(checks_failed, thresholds_failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(ExitCode::from_quality_gates(
-        checks_failed,
-        thresholds_failed,
-    ))
+    fn summary(requests_total: u64, checks_failed_total: u64) -> wrkr_core::RunSummary {
+        wrkr_core::RunSummary {
+            scenarios: vec![wrkr_core::ScenarioSummary {
+                scenario: "main".to_string(),
+                requests_total,
+                checks_failed_total,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn zero_request_runs_fail_only_when_requested() {
+        let runs = [summary(100, 0), summary(0, 1)];
+
+        assert_eq!(exit_code(&runs, false), ExitCode::ChecksFailed);
+        assert_eq!(exit_code(&runs, true), ExitCode::NoRequests);
+        assert_eq!(exit_code(&runs[..1], true), ExitCode::Success);
+    }
+
+    #[test]
+    fn an_aborted_run_is_not_reported_as_a_threshold_failure() {
+        let mut failed = summary(100, 0);
+        failed
+            .threshold_violations
+            .push(wrkr_core::ThresholdViolation {
+                metric: "request_latency".to_string(),
+                tags: Vec::new(),
+                expression: "p(95)<200".to_string(),
+                observed: Some(250.0),
+                no_data: false,
+                description: None,
+            });
+        assert_eq!(
+            exit_code(std::slice::from_ref(&failed), false),
+            ExitCode::ThresholdsFailed
+        );
+
+        // Thresholds of a cut-short run were evaluated on partial metrics.
+        failed.aborted = Some("error rate above 50%".to_string());
+        assert_eq!(exit_code(&[failed], false), ExitCode::Aborted);
+    }
+
+    #[test]
+    fn scenario_selection_keeps_named_scenarios_from_a_yaml_file() {
+        let values = ["ci.yaml", "smoke", "stress"].map(String::from);
+        let selection = ScenarioSelection::parse(&values).unwrap_or_else(|e| panic!("{e:?}"));
+        assert_eq!(selection.yaml, Some("ci.yaml"));
+
+        let opts = wrkr_core::ScriptOptions {
+            scenarios: ["smoke", "load", "stress"]
+                .map(wrkr_core::ScenarioOptions::new)
+                .to_vec(),
+            ..Default::default()
+        };
+        let mut scenarios = wrkr_core::scenarios_from_options(opts, Default::default())
+            .unwrap_or_else(|e| panic!("{e}"));
+        selection.retain(&mut scenarios);
+        let names: Vec<_> = scenarios.iter().map(|s| s.metrics_ctx.scenario()).collect();
+        assert_eq!(names, ["smoke", "stress"]);
+        assert!(selection.ensure_matched(&scenarios).is_ok());
+
+        let typo = ["smoek".to_string()];
+        let selection = ScenarioSelection::parse(&typo).unwrap_or_else(|e| panic!("{e:?}"));
+        selection.retain(&mut scenarios);
+        assert!(selection.ensure_matched(&scenarios).is_err());
+
+        let two_files = ["a.yaml", "b.yml"].map(String::from);
+        assert!(ScenarioSelection::parse(&two_files).is_err());
+    }
 }
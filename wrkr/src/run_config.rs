@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Context as _;
+use serde::Deserialize;
+
+use crate::cli::{OutputFormat, OutputTarget, RunArgs};
+use crate::scenario_yaml::{self, ThresholdExprYaml, YamlDuration};
+
+/// Run options from a `--config` file. Every key is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct RunConfigFile {
+    vus: Option<u64>,
+    iterations: Option<u64>,
+    duration: Option<YamlDuration>,
+    #[serde(default, deserialize_with = "scenario_yaml::deserialize_tags")]
+    tags: BTreeMap<String, String>,
+    #[serde(default)]
+    thresholds: BTreeMap<String, ThresholdExprYaml>,
+    /// Same syntax as `--output`: `human-readable`, `json`, `hdr`, `json=PATH` or `hdr=PATH`.
+    #[serde(default)]
+    output: Vec<String>,
+}
+
+/// Fill in `args` from its `--config` file, if any, and settle the default output.
+///
+/// Values from the file only apply where the command line left a gap: `--vus`, `--iterations`,
+/// `--duration` and `--output` replace the file's value, `--tag` overrides the file's tag of the
+/// same key. The file's thresholds are returned to be evaluated with the `--thresholds` files.
+pub(crate) async fn apply(args: &mut RunArgs) -> anyhow::Result<Vec<wrkr_core::ThresholdSet>> {
+    let thresholds = match args.config.clone() {
+        Some(path) => {
+            let config: RunConfigFile = scenario_yaml::read_yaml_or_json(&path, "config").await?;
+            merge(args, config)
+                .with_context(|| format!("invalid config file: {}", path.display()))?
+        }
+        None => Vec::new(),
+    };
+
+    if args.output.is_empty() {
+        args.output.push(OutputTarget {
+            format: OutputFormat::HumanReadable,
+            path: None,
+        });
+    }
+    Ok(thresholds)
+}
+
+fn merge(
+    args: &mut RunArgs,
+    config: RunConfigFile,
+) -> anyhow::Result<Vec<wrkr_core::ThresholdSet>> {
+    args.vus = args.vus.or(config.vus);
+    args.iterations = args.iterations.or(config.iterations);
+    args.duration = args
+        .duration
+        .or(config.duration.map(YamlDuration::into_inner));
+
+    let mut tags: Vec<(String, String)> = config
+        .tags
+        .into_iter()
+        .filter(|(k, _)| !args.tag.iter().any(|(cli, _)| cli == k))
+        .collect();
+    tags.append(&mut args.tag);
+    args.tag = tags;
+
+    if args.output.is_empty() {
+        args.output = config
+            .output
+            .iter()
+            .map(|raw| crate::cli::parse_output_target(raw).map_err(anyhow::Error::msg))
+            .collect::<anyhow::Result<_>>()?;
+    }
+
+    scenario_yaml::parse_thresholds_map(config.thresholds)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::cli::tests::run_args;
+
+    fn config(yaml: &str) -> RunConfigFile {
+        serde_yaml::from_str(yaml).unwrap_or_else(|err| panic!("failed to parse config: {err}"))
+    }
+
+    #[test]
+    fn command_line_flags_take_precedence_over_the_config_file() {
+        let mut args = run_args(&["bench.lua", "--vus", "4", "--tag", "env=ci"]);
+        let thresholds = merge(
+            &mut args,
+            config(
+                "vus: 10\n\
+                 duration: 30s\n\
+                 tags: { env: staging, team: checkout }\n\
+                 thresholds: { http_req_duration: 'p(95) < 300' }\n\
+                 output: [json=out/run.ndjson]\n",
+            ),
+        )
+        .unwrap_or_else(|err| panic!("merge failed: {err:#}"));
+
+        assert_eq!(args.vus, Some(4));
+        assert_eq!(args.duration, Some(Duration::from_secs(30)));
+        assert_eq!(args.iterations, None);
+        assert_eq!(
+            args.tag,
+            vec![
+                ("team".to_string(), "checkout".to_string()),
+                ("env".to_string(), "ci".to_string()),
+            ]
+        );
+        assert_eq!(
+            args.output,
+            vec![OutputTarget {
+                format: OutputFormat::Json,
+                path: Some(PathBuf::from("out/run.ndjson")),
+            }]
+        );
+        assert_eq!(thresholds.len(), 1);
+        assert_eq!(thresholds[0].metric, "http_req_duration");
+    }
+
+    #[test]
+    fn output_flags_replace_the_config_outputs() {
+        let mut args = run_args(&["bench.lua", "--output", "json"]);
+        merge(&mut args, config("output: [human-readable]"))
+            .unwrap_or_else(|err| panic!("merge failed: {err:#}"));
+        assert_eq!(
+            args.output,
+            vec![OutputTarget {
+                format: OutputFormat::Json,
+                path: None,
+            }]
+        );
+
+        let mut args = run_args(&["bench.lua"]);
+        assert!(merge(&mut args, config("output: [yaml]")).is_err());
+    }
+
+    #[test]
+    fn unknown_config_keys_are_rejected() {
+        assert!(serde_yaml::from_str::<RunConfigFile>("vu: 10").is_err());
+    }
+}
@@ -1,14 +1,23 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Context as _;
 
+use crate::env_file::load_env_file;
 use crate::run_error::RunError;
 
-pub(crate) fn merged_env(overrides: &[String]) -> anyhow::Result<wrkr_core::EnvVars> {
-    let mut map: BTreeMap<String, String> = std::env::vars()
-        .map(|(k, v)| (k.to_string(), v.to_string()))
-        .collect();
+pub(crate) fn merged_env(
+    env_files: &[PathBuf],
+    overrides: &[String],
+) -> anyhow::Result<wrkr_core::EnvVars> {
+    // Precedence (lowest to highest): env files (later files win), process env, `--env`.
+    let mut map: BTreeMap<String, String> = BTreeMap::new();
+    for path in env_files {
+        map.extend(load_env_file(path)?);
+    }
+
+    map.extend(std::env::vars());
 
     for raw in overrides {
         let (k, v) = parse_env_override(raw)?;
@@ -47,47 +56,54 @@ pub(crate) fn classify_runtime_error(
     context: &'static str,
     err: crate::runtime::RuntimeError,
 ) -> RunError {
-    #[cfg(feature = "lua")]
-    {
-        match err {
-            crate::runtime::RuntimeError::Lua(lua_err) => {
-                use wrkr_lua::Error as LuaError;
+    match err {
+        #[cfg(feature = "lua")]
+        crate::runtime::RuntimeError::Lua(lua_err) => {
+            use wrkr_lua::Error as LuaError;
 
-                let kind = match &lua_err {
-                    // Invalid options/config input.
-                    LuaError::InvalidIterations
-                    | LuaError::InvalidVus
-                    | LuaError::InvalidExecutor
-                    | LuaError::InvalidStages
-                    | LuaError::InvalidDuration
-                    | LuaError::InvalidTimeUnit
-                    | LuaError::InvalidScenarioTags
-                    | LuaError::InvalidThresholds => RunError::InvalidInput,
+            let kind = match &lua_err {
+                // Invalid options/config input.
+                LuaError::InvalidIterations
+                | LuaError::InvalidVus
+                | LuaError::InvalidExecutor
+                | LuaError::InvalidStages
+                | LuaError::InvalidDuration
+                | LuaError::InvalidTimeUnit
+                | LuaError::InvalidScenarioTags
+                | LuaError::InvalidMatrix
+                | LuaError::InvalidTags
+                | LuaError::InvalidDescription
+                | LuaError::InvalidWarmupIterations
+                | LuaError::InvalidThinkTime(_)
+                | LuaError::InvalidBooleanOption(_)
+                | LuaError::InvalidHttpCache
+                | LuaError::InvalidThresholds => RunError::InvalidInput,
 
-                    // User script error (runtime error, missing entrypoints, bad API use).
-                    LuaError::Lua(_)
-                    | LuaError::MissingDefault
-                    | LuaError::MissingExec(_)
-                    | LuaError::MissingScriptPath(_)
-                    | LuaError::InvalidPath(_)
-                    | LuaError::InvalidMetricName
-                    | LuaError::InvalidMetricValue => RunError::ScriptError,
+                // User script error (runtime error, missing entrypoints, bad API use).
+                LuaError::Lua(_)
+                | LuaError::MissingDefault
+                | LuaError::MissingExec(_)
+                | LuaError::MissingScriptPath(_)
+                | LuaError::InvalidPath(_)
+                | LuaError::InvalidMetricName
+                | LuaError::InvalidMetricValue => RunError::ScriptError,
 
-                    // Core errors surfaced through the Lua layer.
-                    LuaError::Core(_) => RunError::InvalidInput,
+                // Core errors surfaced through the Lua layer.
+                LuaError::Core(_) => RunError::InvalidInput,
 
-                    // IO while executing script hooks/modules.
-                    LuaError::Io(_) => RunError::RuntimeError,
-                };
+                // IO while executing script hooks/modules.
+                LuaError::Io(_) => RunError::RuntimeError,
+            };
 
-                kind(anyhow::Error::new(lua_err).context(context))
-            }
+            kind(anyhow::Error::new(lua_err).context(context))
+        }
+        #[cfg(feature = "http")]
+        crate::runtime::RuntimeError::Replay(core_err) => {
+            RunError::RuntimeError(anyhow::Error::new(core_err).context(context))
+        }
+        #[cfg(not(feature = "lua"))]
+        err @ crate::runtime::RuntimeError::Unavailable(_) => {
+            RunError::RuntimeError(anyhow::Error::new(err).context(context))
         }
-    }
-
-    #[cfg(not(feature = "lua"))]
-    {
-        let _ = context;
-        RunError::RuntimeError(anyhow::Error::new(err))
     }
 }
@@ -5,6 +5,8 @@
 
 #[cfg(feature = "lua")]
 mod lua;
+#[cfg(feature = "http")]
+mod replay;
 
 pub use error::*;
 pub use factory::*;
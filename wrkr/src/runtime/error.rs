@@ -3,6 +3,9 @@ pub enum RuntimeError {
     #[cfg(feature = "lua")]
     Lua(wrkr_lua::Error),
 
+    #[cfg(feature = "http")]
+    Replay(wrkr_core::Error),
+
     #[cfg(not(feature = "lua"))]
     Unavailable(&'static str),
 }
@@ -12,6 +15,8 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             #[cfg(feature = "lua")]
             Self::Lua(e) => write!(f, "{e}"),
+            #[cfg(feature = "http")]
+            Self::Replay(e) => write!(f, "{e}"),
             #[cfg(not(feature = "lua"))]
             Self::Unavailable(msg) => write!(f, "{msg}"),
         }
@@ -23,6 +28,8 @@ fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             #[cfg(feature = "lua")]
             Self::Lua(e) => Some(e),
+            #[cfg(feature = "http")]
+            Self::Replay(e) => Some(e),
             #[cfg(not(feature = "lua"))]
             Self::Unavailable(_) => None,
         }
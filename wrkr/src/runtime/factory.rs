@@ -1,16 +1,22 @@
 use std::path::Path;
 use std::sync::Arc;
 
-use super::ScriptRuntime;
+use super::{RuntimeOptions, ScriptRuntime};
 
-pub fn create_runtime(path: &Path) -> anyhow::Result<Arc<dyn ScriptRuntime>> {
+#[cfg_attr(not(feature = "http"), allow(unused_variables))]
+pub fn create_runtime(
+    path: &Path,
+    opts: &RuntimeOptions,
+) -> anyhow::Result<Arc<dyn ScriptRuntime>> {
     let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
     let script = std::fs::read_to_string(path)?;
     match ext {
         #[cfg(feature = "lua")]
         "lua" => Ok(Arc::new(super::lua::LuaRuntime::new(path, script)?)),
-        _ => {
-            anyhow::bail!("unsupported script extension `{ext}`: {}", path.display());
-        }
+        #[cfg(feature = "http")]
+        "har" | "ndjson" | "jsonl" => Ok(Arc::new(super::replay::ReplayRuntime::new(
+            path, script, opts,
+        )?)),
+        _ => anyhow::bail!("unsupported script extension `{ext}`: {}", path.display()),
     }
 }
@@ -26,6 +26,11 @@ fn create_run_context(&self, env: &wrkr_core::EnvVars) -> wrkr_core::RunScenario
             self.script_path.clone(),
         )
     }
+
+    fn script_source(&self) -> wrkr_core::ScriptSource {
+        wrkr_core::ScriptSource::new(self.script.clone(), self.script_path.clone())
+    }
+
     fn parse_script_options(
         &self,
         run_ctx: &wrkr_core::RunScenariosContext,
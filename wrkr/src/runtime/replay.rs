@@ -0,0 +1,99 @@
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use super::{RuntimeError, RuntimeOptions, ScriptOutputs, ScriptRuntime};
+
+/// Replays a recorded trace (`.har`, or `.ndjson` / `.jsonl`) instead of running a script.
+pub struct ReplayRuntime {
+    text: String,
+    path: PathBuf,
+    replay: Arc<wrkr_core::Replay>,
+}
+
+impl ReplayRuntime {
+    pub fn new(path: &Path, text: String, opts: &RuntimeOptions) -> anyhow::Result<Self> {
+        let ext = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+        let trace = if ext.eq_ignore_ascii_case("har") {
+            wrkr_core::Trace::from_har(&text)?
+        } else {
+            wrkr_core::Trace::from_ndjson(&text)?
+        };
+        if trace.is_empty() {
+            anyhow::bail!("trace has no requests: {}", path.display());
+        }
+
+        let pacing = match opts.replay_rate {
+            Some(per_second) => wrkr_core::ReplayPacing::Rate { per_second },
+            None => wrkr_core::ReplayPacing::Recorded {
+                speed: opts.replay_speed,
+            },
+        };
+        Ok(Self {
+            text,
+            path: path.to_path_buf(),
+            replay: Arc::new(wrkr_core::Replay::new(trace, pacing)),
+        })
+    }
+
+    fn scenario_name(&self) -> String {
+        self.path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("replay")
+            .to_string()
+    }
+}
+
+impl ScriptRuntime for ReplayRuntime {
+    fn create_run_context(&self, env: &wrkr_core::EnvVars) -> wrkr_core::RunScenariosContext {
+        wrkr_core::RunScenariosContext::new(env.clone(), self.text.clone(), self.path.clone())
+    }
+
+    fn script_source(&self) -> wrkr_core::ScriptSource {
+        wrkr_core::ScriptSource::new(self.text.clone(), self.path.clone())
+    }
+
+    fn parse_script_options(
+        &self,
+        _run_ctx: &wrkr_core::RunScenariosContext,
+    ) -> std::result::Result<wrkr_core::ScriptOptions, RuntimeError> {
+        Ok(wrkr_core::ScriptOptions {
+            scenarios: vec![self.replay.scenario_options(&self.scenario_name())],
+            ..Default::default()
+        })
+    }
+
+    fn run_setup(
+        &self,
+        _run_ctx: &wrkr_core::RunScenariosContext,
+    ) -> std::result::Result<(), RuntimeError> {
+        // Every `--repeat` run replays the trace from the top.
+        self.replay.rewind();
+        Ok(())
+    }
+
+    fn run_teardown(
+        &self,
+        _run_ctx: &wrkr_core::RunScenariosContext,
+    ) -> std::result::Result<(), RuntimeError> {
+        Ok(())
+    }
+
+    fn run_handle_summary(
+        &self,
+        _run_ctx: &wrkr_core::RunScenariosContext,
+        _summary: &wrkr_core::RunSummary,
+    ) -> std::result::Result<Option<ScriptOutputs>, RuntimeError> {
+        Ok(None)
+    }
+
+    fn run_vu(
+        &self,
+        ctx: wrkr_core::VuContext,
+    ) -> Pin<Box<dyn Future<Output = std::result::Result<(), RuntimeError>> + Send>> {
+        let replay = self.replay.clone();
+        Box::pin(async move { replay.run_vu(&ctx).await.map_err(RuntimeError::Replay) })
+    }
+}
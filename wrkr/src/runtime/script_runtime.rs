@@ -6,6 +6,9 @@
 pub trait ScriptRuntime: Send + Sync {
     fn create_run_context(&self, env: &wrkr_core::EnvVars) -> wrkr_core::RunScenariosContext;
 
+    /// The script this runtime executes, for runs where several scripts share one run context.
+    fn script_source(&self) -> wrkr_core::ScriptSource;
+
     fn parse_script_options(
         &self,
         run_ctx: &wrkr_core::RunScenariosContext,
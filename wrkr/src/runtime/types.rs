@@ -3,3 +3,23 @@ pub struct ScriptOutputs {
     pub stderr: Option<String>,
     pub files: Vec<(String, String)>,
 }
+
+/// Settings of `wrkr run` that only apply to some runtimes; today only the replay runtime (HAR and
+/// NDJSON traces, `http` feature) reads them.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(not(feature = "http"), allow(dead_code))]
+pub struct RuntimeOptions {
+    /// Divides the recorded gaps of a replayed trace (`--replay-speed`).
+    pub replay_speed: f64,
+    /// Replay a trace at this many requests per second instead (`--replay-rate`).
+    pub replay_rate: Option<f64>,
+}
+
+impl Default for RuntimeOptions {
+    fn default() -> Self {
+        Self {
+            replay_speed: 1.0,
+            replay_rate: None,
+        }
+    }
+}
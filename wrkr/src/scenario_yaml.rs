@@ -1,8 +1,10 @@
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Context as _;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +26,19 @@ pub(crate) struct ScenarioYaml {
     )]
     pub tags: BTreeMap<String, String>,
 
+    /// Free-form, human-readable description (metadata only).
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub description: Option<String>,
+
+    /// Value passed to the scenario's exec function on every iteration.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub params: Option<serde_yaml::Value>,
+
+    /// Parameter matrix (`key: [value, ...]`); the scenario is expanded into one scenario per
+    /// combination, tagged with its values.
+    #[serde(skip_serializing_if = "serde_yaml::Mapping::is_empty", default)]
+    pub matrix: serde_yaml::Mapping,
+
     /// Executor kind: constant-vus | ramping-vus | ramping-arrival-rate
     #[serde(skip_serializing_if = "Option::is_none")]
     pub executor: Option<String>,
@@ -36,6 +51,14 @@ pub(crate) struct ScenarioYaml {
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub duration: Option<YamlDuration>,
 
+    /// Pause after every iteration: a duration, or `{ distribution, min, max, mean, stddev }`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub think_time: Option<YamlThinkTime>,
+
+    /// Give the scenario an HTTP connection pool of its own.
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub isolate_connections: bool,
+
     // ramping-vus
     #[serde(rename = "startVUs")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -67,13 +90,170 @@ pub(crate) struct StageYaml {
 
     #[serde(default)]
     pub duration: YamlDuration,
+
+    /// Ramp curve: linear (default) | step | ease-in | ease-out | ease-in-out
+    #[serde(skip_serializing_if = "YamlStageShape::is_linear", default)]
+    pub shape: YamlStageShape,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct YamlStageShape(wrkr_core::StageShape);
+
+impl YamlStageShape {
+    fn is_linear(&self) -> bool {
+        self.0 == wrkr_core::StageShape::Linear
+    }
+}
+
+impl Serialize for YamlStageShape {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for YamlStageShape {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse().map(YamlStageShape).map_err(|_| {
+            serde::de::Error::custom(format!(
+                "invalid stage shape `{raw}` (expected linear, step, ease-in, ease-out or ease-in-out)"
+            ))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct YamlThinkTime(wrkr_core::ThinkTimeSpec);
+
+/// Wire form of [`YamlThinkTime`].
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ThinkTimeRepr {
+    Fixed(YamlDuration),
+    Distribution {
+        distribution: String,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        min: Option<YamlDuration>,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        max: Option<YamlDuration>,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        mean: Option<YamlDuration>,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        stddev: Option<YamlDuration>,
+    },
+}
+
+impl Serialize for YamlThinkTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use wrkr_core::ThinkTime;
+
+        let spec = self.0;
+        let d = |d: Duration| Some(YamlDuration(d));
+        let (distribution, mut min, mut max, mean, stddev) = match spec.dist {
+            ThinkTime::Uniform { min, max }
+                if min == max && spec.min.is_none() && spec.max.is_none() =>
+            {
+                return ThinkTimeRepr::Fixed(YamlDuration(min)).serialize(serializer);
+            }
+            ThinkTime::Uniform { min, max } => ("uniform", d(min), d(max), None, None),
+            ThinkTime::Normal { mean, stddev } => ("normal", None, None, d(mean), d(stddev)),
+            ThinkTime::Exponential { mean } => ("exponential", None, None, d(mean), None),
+            ThinkTime::LogNormal { mean, stddev } => ("lognormal", None, None, d(mean), d(stddev)),
+        };
+        // Bounds of a uniform distribution are its own range.
+        min = min.or(spec.min.map(YamlDuration));
+        max = max.or(spec.max.map(YamlDuration));
+
+        ThinkTimeRepr::Distribution {
+            distribution: distribution.to_string(),
+            min,
+            max,
+            mean,
+            stddev,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for YamlThinkTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error as _;
+        use wrkr_core::ThinkTime;
+
+        let raw = ThinkTimeRepr::deserialize(deserializer).map_err(|_| {
+            D::Error::custom(
+                "invalid thinkTime (expected a duration or { distribution, min, max, mean, stddev })",
+            )
+        })?;
+        let (distribution, min, max, mean, stddev) = match raw {
+            ThinkTimeRepr::Fixed(d) => return Ok(Self(wrkr_core::ThinkTimeSpec::fixed(d.0))),
+            ThinkTimeRepr::Distribution {
+                distribution,
+                min,
+                max,
+                mean,
+                stddev,
+            } => (
+                distribution,
+                min.map(YamlDuration::into_inner),
+                max.map(YamlDuration::into_inner),
+                mean.map(YamlDuration::into_inner),
+                stddev.map(YamlDuration::into_inner),
+            ),
+        };
+
+        let required = |v: Option<Duration>, field: &str| {
+            v.ok_or_else(|| D::Error::custom(format!("thinkTime `{field}` is required")))
+        };
+        if let (Some(min), Some(max)) = (min, max)
+            && min > max
+        {
+            return Err(D::Error::custom("thinkTime `min` must not exceed `max`"));
+        }
+
+        let dist = match distribution.as_str() {
+            "uniform" => ThinkTime::Uniform {
+                min: min.unwrap_or(Duration::ZERO),
+                max: required(max, "max")?,
+            },
+            "normal" => ThinkTime::Normal {
+                mean: required(mean, "mean")?,
+                stddev: required(stddev, "stddev")?,
+            },
+            "exponential" => ThinkTime::Exponential {
+                mean: required(mean, "mean")?,
+            },
+            "lognormal" => ThinkTime::LogNormal {
+                mean: required(mean, "mean")?,
+                stddev: required(stddev, "stddev")?,
+            },
+            other => {
+                return Err(D::Error::custom(format!(
+                    "unknown thinkTime distribution `{other}` (expected uniform, normal, exponential or lognormal)"
+                )));
+            }
+        };
+        Ok(Self(wrkr_core::ThinkTimeSpec { dist, min, max }))
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
 pub(crate) struct YamlDuration(Duration);
 
 impl YamlDuration {
-    fn into_inner(self) -> Duration {
+    pub(crate) fn into_inner(self) -> Duration {
         self.0
     }
 }
@@ -118,27 +298,25 @@ fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
             where
                 E: serde::de::Error,
             {
-                if v <= 0 {
-                    return Err(E::custom("duration must be positive"));
-                }
-                Ok(YamlDuration(Duration::from_secs(v as u64)))
+                u64::try_from(v)
+                    .map(|v| YamlDuration(Duration::from_secs(v)))
+                    .map_err(|_| E::custom("duration must not be negative"))
             }
 
             fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
             where
                 E: serde::de::Error,
             {
-                if !v.is_finite() || v <= 0.0 {
-                    return Err(E::custom("duration must be a positive, finite number"));
-                }
-                Ok(YamlDuration(Duration::from_secs_f64(v)))
+                Duration::try_from_secs_f64(v)
+                    .map(YamlDuration)
+                    .map_err(|_| E::custom("duration must be a non-negative, finite number"))
             }
 
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
             where
                 E: serde::de::Error,
             {
-                let d = humantime::parse_duration(v).map_err(E::custom)?;
+                let d = wrkr_core::parse_duration(v).map_err(E::custom)?;
                 Ok(YamlDuration(d))
             }
 
@@ -159,6 +337,14 @@ fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
 struct ScenarioDocYamlNested {
     pub scenario: ScenarioYaml,
 
+    /// Run-level tags (`Options.tags`), added to every scenario's tags.
+    #[serde(
+        skip_serializing_if = "BTreeMap::is_empty",
+        default,
+        deserialize_with = "deserialize_tags"
+    )]
+    pub tags: BTreeMap<String, String>,
+
     #[serde(default)]
     pub thresholds: BTreeMap<String, ThresholdExprYaml>,
 }
@@ -178,6 +364,14 @@ pub(crate) struct ScenarioDocYamlFlat {
 pub(crate) struct ScenarioDocYamlMulti {
     pub scenarios: Vec<ScenarioYaml>,
 
+    /// Run-level tags (`Options.tags`), added to every scenario's tags.
+    #[serde(
+        skip_serializing_if = "BTreeMap::is_empty",
+        default,
+        deserialize_with = "deserialize_tags"
+    )]
+    pub tags: BTreeMap<String, String>,
+
     #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
     pub thresholds: BTreeMap<String, ThresholdExprYaml>,
 }
@@ -195,9 +389,35 @@ enum ScenarioDocYaml {
 pub(crate) enum ThresholdExprYaml {
     One(String),
     Many(Vec<String>),
+    Detailed(ThresholdDetailedYaml),
+    /// Several sets for the same key, e.g. one per `when` condition.
+    Variants(Vec<ThresholdDetailedYaml>),
 }
 
-fn deserialize_tags<'de, D>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error>
+/// Long form: `metric: { expressions: [...], description: "...", when: { env: prod }, noData: pass }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThresholdDetailedYaml {
+    pub expressions: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub description: Option<String>,
+
+    #[serde(
+        skip_serializing_if = "BTreeMap::is_empty",
+        default,
+        deserialize_with = "deserialize_tags"
+    )]
+    pub when: BTreeMap<String, String>,
+
+    /// `fail` (default) or `pass`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub no_data: Option<String>,
+}
+
+pub(crate) fn deserialize_tags<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, String>, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
@@ -236,35 +456,34 @@ pub async fn load_script_options_from_yaml(
     let doc: ScenarioDocYaml = serde_yaml::from_slice(&bytes)
         .with_context(|| format!("failed to parse YAML: {}", path.display()))?;
 
-    let (scenarios_yaml, thresholds) = match doc {
-        ScenarioDocYaml::Multi(d) => (d.scenarios, d.thresholds),
-        ScenarioDocYaml::Nested(d) => (vec![d.scenario], d.thresholds),
-        ScenarioDocYaml::Flat(d) => (vec![d.scenario], d.thresholds),
+    // A flat document's top-level `tags` belong to its only scenario, which has the same effect.
+    let (scenarios_yaml, tags, thresholds) = match doc {
+        ScenarioDocYaml::Multi(d) => (d.scenarios, d.tags, d.thresholds),
+        ScenarioDocYaml::Nested(d) => (vec![d.scenario], d.tags, d.thresholds),
+        ScenarioDocYaml::Flat(d) => (vec![d.scenario], BTreeMap::new(), d.thresholds),
     };
 
     let total = scenarios_yaml.len();
-    let scenarios = scenarios_yaml
-        .into_iter()
-        .enumerate()
-        .map(|(idx, scenario)| {
-            let name_opt = scenario.name.clone();
-
-            let default_name = if total <= 1 {
-                name_opt
-                    .clone()
-                    .or_else(|| {
-                        path.file_stem()
-                            .and_then(|s| s.to_str())
-                            .map(|s| s.to_string())
-                    })
-                    .unwrap_or_else(|| "main".to_string())
-            } else {
-                name_opt.unwrap_or_else(|| format!("scenario_{}", idx + 1))
-            };
+    let mut scenarios = Vec::with_capacity(total);
+    for (idx, mut scenario) in scenarios_yaml.into_iter().enumerate() {
+        let matrix = parse_matrix(std::mem::take(&mut scenario.matrix))?;
+        let name_opt = scenario.name.clone();
+
+        let default_name = if total <= 1 {
+            name_opt
+                .clone()
+                .or_else(|| {
+                    path.file_stem()
+                        .and_then(|s| s.to_str())
+                        .map(|s| s.to_string())
+                })
+                .unwrap_or_else(|| "main".to_string())
+        } else {
+            name_opt.unwrap_or_else(|| format!("scenario_{}", idx + 1))
+        };
 
-            scenario_yaml_into_options(scenario, default_name)
-        })
-        .collect::<Vec<_>>();
+        scenarios.extend(scenario_yaml_into_options(scenario, default_name).expand_matrix(&matrix));
+    }
 
     let thresholds = parse_thresholds_map(thresholds)?;
 
@@ -272,11 +491,124 @@ pub async fn load_script_options_from_yaml(
         vus: None,
         iterations: None,
         duration: None,
+        warmup_iterations: None,
         scenarios,
+        tags: tags.into_iter().collect(),
+        discard_response_bodies: false,
+        no_connection_reuse: false,
+        no_vu_connection_reuse: false,
+        isolate_scenario_connections: false,
+        http_cache: None,
         thresholds,
     })
 }
 
+/// `params:` as a script value; mapping keys are read as strings (like tags).
+fn yaml_to_value(v: serde_yaml::Value) -> wrkr_value::Value {
+    match v {
+        serde_yaml::Value::Null => wrkr_value::Value::Null,
+        serde_yaml::Value::Bool(b) => wrkr_value::Value::Bool(b),
+        serde_yaml::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                wrkr_value::Value::I64(i)
+            } else if let Some(u) = n.as_u64() {
+                wrkr_value::Value::U64(u)
+            } else {
+                wrkr_value::Value::F64(n.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        serde_yaml::Value::String(s) => wrkr_value::Value::String(Arc::from(s)),
+        serde_yaml::Value::Sequence(items) => {
+            wrkr_value::Value::Array(items.into_iter().map(yaml_to_value).collect())
+        }
+        serde_yaml::Value::Mapping(map) => wrkr_value::Value::Object(
+            map.into_iter()
+                .map(|(k, v)| {
+                    let key = match k {
+                        serde_yaml::Value::String(s) => s,
+                        serde_yaml::Value::Bool(b) => b.to_string(),
+                        serde_yaml::Value::Number(n) => n.to_string(),
+                        other => serde_yaml::to_string(&other)
+                            .unwrap_or_default()
+                            .trim_end()
+                            .to_string(),
+                    };
+                    (Arc::from(key), yaml_to_value(v))
+                })
+                .collect(),
+        ),
+        serde_yaml::Value::Tagged(tagged) => yaml_to_value(tagged.value),
+    }
+}
+
+fn value_to_yaml(v: &wrkr_value::Value) -> serde_yaml::Value {
+    fn key(k: &wrkr_value::MapKey) -> serde_yaml::Value {
+        match k {
+            wrkr_value::MapKey::Bool(b) => serde_yaml::Value::Bool(*b),
+            wrkr_value::MapKey::I64(i) => serde_yaml::Value::Number((*i).into()),
+            wrkr_value::MapKey::U64(u) => serde_yaml::Value::Number((*u).into()),
+            wrkr_value::MapKey::String(s) => serde_yaml::Value::String(s.to_string()),
+        }
+    }
+
+    match v {
+        wrkr_value::Value::Null => serde_yaml::Value::Null,
+        wrkr_value::Value::Bool(b) => serde_yaml::Value::Bool(*b),
+        wrkr_value::Value::I64(i) => serde_yaml::Value::Number((*i).into()),
+        wrkr_value::Value::U64(u) => serde_yaml::Value::Number((*u).into()),
+        wrkr_value::Value::F64(f) => serde_yaml::Value::Number((*f).into()),
+        wrkr_value::Value::String(s) => serde_yaml::Value::String(s.to_string()),
+        wrkr_value::Value::Bytes(b) => {
+            serde_yaml::Value::String(String::from_utf8_lossy(b).into_owned())
+        }
+        wrkr_value::Value::Array(items) => {
+            serde_yaml::Value::Sequence(items.iter().map(value_to_yaml).collect())
+        }
+        wrkr_value::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            serde_yaml::Value::Mapping(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (serde_yaml::Value::String(k.to_string()), value_to_yaml(v)))
+                    .collect(),
+            )
+        }
+        wrkr_value::Value::Map(map) => serde_yaml::Value::Mapping(
+            map.iter()
+                .map(|(k, v)| (key(k), value_to_yaml(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// `matrix:` entries in document order; values are scalars, read as strings like tags.
+fn parse_matrix(raw: serde_yaml::Mapping) -> anyhow::Result<Vec<(String, Vec<String>)>> {
+    let scalar = |v: serde_yaml::Value| match v {
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::String(s) => Some(s),
+        _ => None,
+    };
+
+    raw.into_iter()
+        .map(|(k, v)| {
+            let key = scalar(k).ok_or_else(|| anyhow::anyhow!("matrix keys must be scalars"))?;
+            let serde_yaml::Value::Sequence(values) = v else {
+                anyhow::bail!("matrix `{key}` must be a list of values");
+            };
+            let values = values
+                .into_iter()
+                .map(|v| {
+                    scalar(v)
+                        .ok_or_else(|| anyhow::anyhow!("matrix `{key}` values must be scalars"))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            Ok((key, values))
+        })
+        .collect()
+}
+
 fn scenario_yaml_into_options(
     scenario: ScenarioYaml,
     default_name: String,
@@ -285,10 +617,15 @@ fn scenario_yaml_into_options(
         name,
         exec,
         tags,
+        description,
+        params,
+        matrix: _,
         executor,
         vus,
         iterations,
         duration,
+        think_time,
+        isolate_connections,
         start_vus,
         stages,
         start_rate,
@@ -304,10 +641,14 @@ fn scenario_yaml_into_options(
         name,
         exec,
         tags,
+        description,
+        params: params.map(|p| Arc::new(yaml_to_value(p))),
         executor,
         vus,
         iterations,
         duration: duration.map(|d| d.into_inner()),
+        think_time: think_time.map(|t| t.0),
+        isolate_connections,
 
         start_vus,
         stages: stages
@@ -315,6 +656,7 @@ fn scenario_yaml_into_options(
             .map(|s| wrkr_core::Stage {
                 duration: s.duration.into_inner(),
                 target: s.target,
+                shape: s.shape.0,
             })
             .collect(),
 
@@ -334,15 +676,22 @@ pub(crate) fn build_doc_from_resolved_scenario(
         tags.insert(k.clone(), v.clone());
     }
 
+    let params = s.params.as_deref().map(value_to_yaml);
+
     let scenario = match &s.executor {
         wrkr_core::ScenarioExecutor::ConstantVus { vus } => ScenarioYaml {
             name: Some(s.metrics_ctx.scenario().to_string()),
             exec: Some(s.exec.clone()),
             tags,
+            description: s.description.clone(),
+            params: params.clone(),
+            matrix: serde_yaml::Mapping::new(),
             executor: Some("constant-vus".to_string()),
             vus: Some(*vus),
             iterations: s.iterations,
             duration: s.duration.map(YamlDuration::from),
+            think_time: s.think_time.map(YamlThinkTime),
+            isolate_connections: s.isolate_connections,
             start_vus: None,
             stages: Vec::new(),
             start_rate: None,
@@ -354,16 +703,22 @@ pub(crate) fn build_doc_from_resolved_scenario(
             name: Some(s.metrics_ctx.scenario().to_string()),
             exec: Some(s.exec.clone()),
             tags,
+            description: s.description.clone(),
+            params: params.clone(),
+            matrix: serde_yaml::Mapping::new(),
             executor: Some("ramping-vus".to_string()),
             vus: None,
             iterations: None,
             duration: None,
+            think_time: s.think_time.map(YamlThinkTime),
+            isolate_connections: s.isolate_connections,
             start_vus: Some(*start_vus),
             stages: stages
                 .iter()
                 .map(|st| StageYaml {
                     duration: YamlDuration::from(st.duration),
                     target: st.target,
+                    shape: YamlStageShape(st.shape),
                 })
                 .collect(),
             start_rate: None,
@@ -381,16 +736,22 @@ pub(crate) fn build_doc_from_resolved_scenario(
             name: Some(s.metrics_ctx.scenario().to_string()),
             exec: Some(s.exec.clone()),
             tags,
+            description: s.description.clone(),
+            params: params.clone(),
+            matrix: serde_yaml::Mapping::new(),
             executor: Some("ramping-arrival-rate".to_string()),
             vus: None,
             iterations: None,
             duration: None,
+            think_time: s.think_time.map(YamlThinkTime),
+            isolate_connections: s.isolate_connections,
             start_vus: None,
             stages: stages
                 .iter()
                 .map(|st| StageYaml {
                     duration: YamlDuration::from(st.duration),
                     target: st.target,
+                    shape: YamlStageShape(st.shape),
                 })
                 .collect(),
             start_rate: Some(*start_rate),
@@ -415,8 +776,10 @@ pub(crate) fn build_doc_from_resolved_scenarios(
         .map(|s| build_doc_from_resolved_scenario(s, &[]).scenario)
         .collect::<Vec<_>>();
 
+    // Run-level tags are already part of every resolved scenario's tags.
     ScenarioDocYamlMulti {
         scenarios,
+        tags: BTreeMap::new(),
         thresholds: render_thresholds(thresholds),
     }
 }
@@ -440,14 +803,34 @@ pub(crate) async fn write_yaml_file<T: Serialize>(path: &Path, doc: &T) -> anyho
 }
 
 fn render_thresholds(sets: &[wrkr_core::ThresholdSet]) -> BTreeMap<String, ThresholdExprYaml> {
-    let mut out = BTreeMap::new();
-
+    let mut grouped: BTreeMap<String, Vec<&wrkr_core::ThresholdSet>> = BTreeMap::new();
     for s in sets {
-        let key = render_metric_key(&s.metric, &s.tags);
-        let v = if s.expressions.len() == 1 {
-            ThresholdExprYaml::One(s.expressions[0].clone())
-        } else {
-            ThresholdExprYaml::Many(s.expressions.clone())
+        grouped
+            .entry(render_metric_key(&s.metric, &s.tags))
+            .or_default()
+            .push(s);
+    }
+
+    let detailed = |s: &wrkr_core::ThresholdSet| ThresholdDetailedYaml {
+        expressions: s.expressions.clone(),
+        description: s.description.clone(),
+        when: s.when.iter().cloned().collect(),
+        no_data: (s.no_data != wrkr_core::ThresholdNoData::default())
+            .then(|| s.no_data.as_str().to_string()),
+    };
+
+    let mut out = BTreeMap::new();
+    for (key, group) in grouped {
+        let v = match group.as_slice() {
+            [s] if s.description.is_some()
+                || !s.when.is_empty()
+                || s.no_data != wrkr_core::ThresholdNoData::default() =>
+            {
+                ThresholdExprYaml::Detailed(detailed(s))
+            }
+            [s] if s.expressions.len() == 1 => ThresholdExprYaml::One(s.expressions[0].clone()),
+            [s] => ThresholdExprYaml::Many(s.expressions.clone()),
+            many => ThresholdExprYaml::Variants(many.iter().map(|s| detailed(s)).collect()),
         };
         out.insert(key, v);
     }
@@ -469,7 +852,27 @@ fn render_metric_key(metric: &str, tags: &[(String, String)]) -> String {
     format!("{metric}{{{selector}}}")
 }
 
-fn parse_thresholds_map(
+/// Loads a `--thresholds` file: a `metric_key -> expr | [expr] | {...}` map, as YAML or JSON.
+pub async fn load_thresholds_file(path: &Path) -> anyhow::Result<Vec<wrkr_core::ThresholdSet>> {
+    let raw: BTreeMap<String, ThresholdExprYaml> = read_yaml_or_json(path, "thresholds").await?;
+    parse_thresholds_map(raw)
+}
+
+/// Reads and parses a YAML or JSON file; `what` names the kind of file in errors.
+pub(crate) async fn read_yaml_or_json<T: DeserializeOwned>(
+    path: &Path,
+    what: &str,
+) -> anyhow::Result<T> {
+    let bytes = tokio::fs::read(path)
+        .await
+        .with_context(|| format!("failed to read {what} file: {}", path.display()))?;
+
+    // JSON is valid YAML, so one parser covers both formats.
+    serde_yaml::from_slice(&bytes)
+        .with_context(|| format!("failed to parse {what} file: {}", path.display()))
+}
+
+pub(crate) fn parse_thresholds_map(
     raw: BTreeMap<String, ThresholdExprYaml>,
 ) -> anyhow::Result<Vec<wrkr_core::ThresholdSet>> {
     let mut out = Vec::new();
@@ -478,20 +881,42 @@ fn parse_thresholds_map(
         let (metric, tags) = wrkr_core::parse_threshold_metric_key(&metric_key)
             .map_err(|e| anyhow::anyhow!("invalid threshold metric key `{metric_key}`: {e}"))?;
 
-        let expressions: Vec<String> = match v {
-            ThresholdExprYaml::One(s) => vec![s],
-            ThresholdExprYaml::Many(v) => v,
+        let entries = match v {
+            ThresholdExprYaml::One(s) => vec![(vec![s], None, BTreeMap::new(), None)],
+            ThresholdExprYaml::Many(v) => vec![(v, None, BTreeMap::new(), None)],
+            ThresholdExprYaml::Detailed(d) => {
+                vec![(d.expressions, d.description, d.when, d.no_data)]
+            }
+            ThresholdExprYaml::Variants(list) => list
+                .into_iter()
+                .map(|d| (d.expressions, d.description, d.when, d.no_data))
+                .collect(),
         };
 
-        if expressions.is_empty() {
+        if entries.is_empty() {
             anyhow::bail!("invalid thresholds for `{metric_key}`: empty list");
         }
 
-        out.push(wrkr_core::ThresholdSet {
-            metric,
-            tags,
-            expressions,
-        });
+        for (expressions, description, when, no_data) in entries {
+            if expressions.is_empty() {
+                anyhow::bail!("invalid thresholds for `{metric_key}`: empty list");
+            }
+            let no_data = no_data
+                .as_deref()
+                .map(wrkr_core::ThresholdNoData::parse)
+                .transpose()
+                .map_err(|e| anyhow::anyhow!("invalid `noData` for `{metric_key}`: {e}"))?
+                .unwrap_or_default();
+
+            out.push(wrkr_core::ThresholdSet {
+                metric: metric.clone(),
+                tags: tags.clone(),
+                expressions,
+                description,
+                when: when.into_iter().collect(),
+                no_data,
+            });
+        }
     }
 
     Ok(out)
@@ -551,6 +976,98 @@ fn metric_key_renders_selector() {
         assert_eq!(key, "http_req_duration{group=login,method=GET}");
     }
 
+    #[test]
+    fn conditional_threshold_variants_round_trip() {
+        let doc: ScenarioDocYamlFlat = serde_yaml::from_str(
+            r#"
+name: main
+thresholds:
+  request_latency:
+    - expressions: ["p(95) < 200000"]
+      when: { env: prod }
+    - expressions: ["p(95) < 500000"]
+      when: { env: staging }
+  checks: "rate > 0.99"
+"#,
+        )
+        .unwrap_or_else(|e| panic!("{e:#}"));
+
+        let sets = parse_thresholds_map(doc.thresholds).unwrap_or_else(|e| panic!("{e:#}"));
+        assert_eq!(sets.len(), 3);
+        let latency = sets
+            .iter()
+            .filter(|s| s.metric == "request_latency")
+            .map(|s| (s.when.clone(), s.expressions[0].as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            latency,
+            vec![
+                (
+                    vec![("env".to_string(), "prod".to_string())],
+                    "p(95) < 200000"
+                ),
+                (
+                    vec![("env".to_string(), "staging".to_string())],
+                    "p(95) < 500000"
+                ),
+            ]
+        );
+
+        let rendered = render_thresholds(&sets);
+        assert!(matches!(
+            rendered.get("request_latency"),
+            Some(ThresholdExprYaml::Variants(v)) if v.len() == 2
+        ));
+        assert!(matches!(
+            rendered.get("checks"),
+            Some(ThresholdExprYaml::One(_))
+        ));
+    }
+
+    #[test]
+    fn threshold_no_data_policy_round_trips() {
+        let doc: ScenarioDocYamlFlat = serde_yaml::from_str(
+            r#"
+name: main
+thresholds:
+  grpc_req_duration:
+    expressions: ["p(95) < 200000"]
+    noData: pass
+  request_latency: "p(95) < 200000"
+"#,
+        )
+        .unwrap_or_else(|e| panic!("{e:#}"));
+
+        let sets = parse_thresholds_map(doc.thresholds).unwrap_or_else(|e| panic!("{e:#}"));
+        let policy = |metric: &str| {
+            sets.iter()
+                .find(|s| s.metric == metric)
+                .map(|s| s.no_data)
+                .unwrap_or_else(|| panic!("missing {metric}"))
+        };
+        assert_eq!(
+            policy("grpc_req_duration"),
+            wrkr_core::ThresholdNoData::Pass
+        );
+        assert_eq!(policy("request_latency"), wrkr_core::ThresholdNoData::Fail);
+
+        let rendered = render_thresholds(&sets);
+        assert!(matches!(
+            rendered.get("grpc_req_duration"),
+            Some(ThresholdExprYaml::Detailed(d)) if d.no_data.as_deref() == Some("pass")
+        ));
+
+        let bad: ScenarioDocYamlFlat = serde_yaml::from_str(
+            r#"
+name: main
+thresholds:
+  request_latency: { expressions: ["count > 0"], noData: maybe }
+"#,
+        )
+        .unwrap_or_else(|e| panic!("{e:#}"));
+        assert!(parse_thresholds_map(bad.thresholds).is_err());
+    }
+
     #[tokio::test]
     async fn loads_flat_yaml() {
         let path = fixture_path("flat.yaml");
@@ -582,6 +1099,97 @@ async fn loads_nested_yaml() {
         assert_eq!(s.stages.len(), 2);
     }
 
+    #[tokio::test]
+    async fn loads_matrix_yaml() {
+        let path = fixture_path("matrix.yaml");
+        let opts = load_script_options_from_yaml(&path)
+            .await
+            .unwrap_or_else(|e| panic!("{e:#}"));
+
+        let names: Vec<&str> = opts.scenarios.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "upload-1k-1",
+                "upload-1k-8",
+                "upload-100k-1",
+                "upload-100k-8"
+            ]
+        );
+        let s = &opts.scenarios[3];
+        assert_eq!(
+            s.tags,
+            [
+                ("team".to_string(), "storage".to_string()),
+                ("size".to_string(), "100k".to_string()),
+                ("parallel".to_string(), "8".to_string()),
+            ]
+        );
+        assert_eq!(s.exec.as_deref(), Some("Upload"));
+    }
+
+    #[test]
+    fn matrix_values_must_be_lists() {
+        let doc: ScenarioDocYamlFlat = serde_yaml::from_str("name: main\nmatrix:\n  size: 1k\n")
+            .unwrap_or_else(|e| panic!("{e:#}"));
+        let err = parse_matrix(doc.scenario.matrix)
+            .err()
+            .unwrap_or_else(|| panic!("expected an error"));
+        assert!(err.to_string().contains("`size` must be a list"), "{err}");
+    }
+
+    #[test]
+    fn think_time_accepts_a_duration_or_a_distribution() {
+        let parse = |yaml: &str| {
+            serde_yaml::from_str::<ScenarioDocYamlFlat>(yaml)
+                .map(|doc| doc.scenario.think_time.map(|t| t.0))
+        };
+
+        assert_eq!(
+            parse("name: main\nthinkTime: 2s\n").unwrap_or_else(|e| panic!("{e:#}")),
+            Some(wrkr_core::ThinkTimeSpec::fixed(Duration::from_secs(2)))
+        );
+        assert_eq!(
+            parse("name: main\nthinkTime: { distribution: uniform, min: 1s, max: 3s }\n")
+                .unwrap_or_else(|e| panic!("{e:#}")),
+            Some(wrkr_core::ThinkTimeSpec {
+                dist: wrkr_core::ThinkTime::Uniform {
+                    min: Duration::from_secs(1),
+                    max: Duration::from_secs(3),
+                },
+                min: Some(Duration::from_secs(1)),
+                max: Some(Duration::from_secs(3)),
+            })
+        );
+
+        let err = parse("name: main\nthinkTime: { distribution: normal, mean: 1s }\n")
+            .err()
+            .unwrap_or_else(|| panic!("expected an error"));
+        assert!(err.to_string().contains("`stddev` is required"), "{err}");
+    }
+
+    #[test]
+    fn durations_may_be_zero_whatever_their_spelling() {
+        let parse = |yaml: &str| {
+            serde_yaml::from_str::<ScenarioDocYamlFlat>(yaml)
+                .map(|doc| doc.scenario.duration.map(YamlDuration::into_inner))
+        };
+
+        for zero in ["0", "0.0", "0s", "\"0\""] {
+            assert_eq!(
+                parse(&format!("name: main\nduration: {zero}\n"))
+                    .unwrap_or_else(|e| panic!("{zero}: {e:#}")),
+                Some(Duration::ZERO)
+            );
+        }
+        for invalid in ["-1", "-0.5", ".inf", ".nan", "1e300"] {
+            assert!(
+                parse(&format!("name: main\nduration: {invalid}\n")).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn loads_multi_yaml() {
         let path = fixture_path("multi.yaml");
@@ -593,6 +1201,42 @@ async fn loads_multi_yaml() {
         assert_eq!(opts.scenarios[0].name, "main");
         assert_eq!(opts.scenarios[1].name, "alt");
         assert_eq!(opts.thresholds.len(), 2);
+        assert_eq!(opts.tags, [("region".to_string(), "us-east".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn loads_thresholds_files_in_yaml_and_json() {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/thresholds");
+
+        let yaml = load_thresholds_file(&dir.join("slo.yaml"))
+            .await
+            .unwrap_or_else(|e| panic!("{e:#}"));
+        assert_eq!(yaml.len(), 4);
+        let prod_checks = yaml
+            .iter()
+            .find(|s| s.metric == "checks" && !s.when.is_empty())
+            .unwrap_or_else(|| panic!("missing conditional checks threshold"));
+        assert_eq!(
+            prod_checks.when,
+            vec![("env".to_string(), "prod".to_string())]
+        );
+
+        let json = load_thresholds_file(&dir.join("slo.json"))
+            .await
+            .unwrap_or_else(|e| panic!("{e:#}"));
+        assert_eq!(json.len(), 2);
+        let login = json
+            .iter()
+            .find(|s| s.metric == "iteration_duration")
+            .unwrap_or_else(|| panic!("missing iteration_duration threshold"));
+        assert_eq!(login.tags, vec![("group".to_string(), "login".to_string())]);
+        assert_eq!(login.expressions, vec!["avg<1000".to_string()]);
+
+        assert!(
+            load_thresholds_file(&dir.join("missing.yaml"))
+                .await
+                .is_err()
+        );
     }
 
     #[tokio::test]
@@ -611,19 +1255,37 @@ fn arc_tags(tags: Vec<(String, String)>) -> Arc<[(String, String)]> {
                 const_name.clone(),
                 arc_tags(vec![("tier".to_string(), "core".to_string())]),
             ),
+            description: Some("steady baseline load".to_string()),
             executor: wrkr_core::ScenarioExecutor::ConstantVus { vus: 5 },
             iterations: Some(10),
             duration: Some(Duration::from_secs(2)),
+            warmup_iterations: 0,
+            script: None,
+            params: Some(Arc::new(wrkr_value::Value::Object(
+                [
+                    (Arc::from("size"), wrkr_value::Value::I64(10)),
+                    (
+                        Arc::from("paths"),
+                        wrkr_value::Value::Array(vec![wrkr_value::Value::String(Arc::from("/a"))]),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            ))),
+            think_time: Some(wrkr_core::ThinkTimeSpec::fixed(Duration::from_millis(500))),
+            isolate_connections: true,
         };
 
         let ramp_stages = vec![
             wrkr_core::Stage {
                 duration: Duration::from_secs(1),
                 target: 3,
+                shape: wrkr_core::StageShape::Step,
             },
             wrkr_core::Stage {
                 duration: Duration::from_secs(2),
                 target: 1,
+                shape: wrkr_core::StageShape::Linear,
             },
         ];
         let ramp_total = ramp_stages
@@ -633,22 +1295,37 @@ fn arc_tags(tags: Vec<(String, String)>) -> Arc<[(String, String)]> {
         let ramp_cfg = wrkr_core::ScenarioConfig {
             exec: "Ramp".to_string(),
             metrics_ctx: wrkr_core::MetricsContext::new(ramp_name.clone(), arc_tags(vec![])),
+            description: None,
             executor: wrkr_core::ScenarioExecutor::RampingVus {
                 start_vus: 0,
                 stages: ramp_stages,
             },
             iterations: None,
             duration: Some(ramp_total),
+            warmup_iterations: 0,
+            script: None,
+            params: None,
+            think_time: Some(wrkr_core::ThinkTimeSpec {
+                dist: wrkr_core::ThinkTime::LogNormal {
+                    mean: Duration::from_secs(3),
+                    stddev: Duration::from_secs(1),
+                },
+                min: None,
+                max: Some(Duration::from_secs(10)),
+            }),
+            isolate_connections: false,
         };
 
         let rate_stages = vec![
             wrkr_core::Stage {
                 duration: Duration::from_secs(1),
                 target: 10,
+                shape: wrkr_core::StageShape::Linear,
             },
             wrkr_core::Stage {
                 duration: Duration::from_secs(3),
                 target: 20,
+                shape: wrkr_core::StageShape::EaseInOut,
             },
         ];
         let rate_total = rate_stages
@@ -658,6 +1335,7 @@ fn arc_tags(tags: Vec<(String, String)>) -> Arc<[(String, String)]> {
         let rate_cfg = wrkr_core::ScenarioConfig {
             exec: "Rate".to_string(),
             metrics_ctx: wrkr_core::MetricsContext::new(rate_name.clone(), arc_tags(vec![])),
+            description: None,
             executor: wrkr_core::ScenarioExecutor::RampingArrivalRate {
                 start_rate: 5,
                 time_unit: Duration::from_secs(1),
@@ -667,12 +1345,20 @@ fn arc_tags(tags: Vec<(String, String)>) -> Arc<[(String, String)]> {
             },
             iterations: None,
             duration: Some(rate_total),
+            warmup_iterations: 0,
+            script: None,
+            params: None,
+            think_time: None,
+            isolate_connections: false,
         };
 
         let thresholds = vec![wrkr_core::ThresholdSet {
             metric: "http_req_duration".to_string(),
             tags: vec![("scenario".to_string(), "const".to_string())],
             expressions: vec!["p(95)<200".to_string()],
+            description: Some("p95 latency SLO".to_string()),
+            when: Vec::new(),
+            no_data: wrkr_core::ThresholdNoData::Fail,
         }];
 
         let resolved = vec![const_cfg.clone(), ramp_cfg.clone(), rate_cfg.clone()];
@@ -722,6 +1408,7 @@ fn arc_tags(tags: Vec<(String, String)>) -> Arc<[(String, String)]> {
         assert_eq!(got_thr[0].metric, exp_thr[0].metric);
         assert_eq!(got_thr[0].tags, exp_thr[0].tags);
         assert_eq!(got_thr[0].expressions, exp_thr[0].expressions);
+        assert_eq!(got_thr[0].description, exp_thr[0].description);
 
         // Resolve imported options into ScenarioConfig and compare executors.
         let imported_cfgs = wrkr_core::scenarios_from_options(
@@ -730,6 +1417,8 @@ fn arc_tags(tags: Vec<(String, String)>) -> Arc<[(String, String)]> {
                 iterations: None,
                 vus: None,
                 duration: None,
+                warmup_iterations: None,
+                tags: Vec::new(),
             },
         )
         .unwrap_or_else(|e| panic!("{e:#}"));
@@ -742,8 +1431,12 @@ fn arc_tags(tags: Vec<(String, String)>) -> Arc<[(String, String)]> {
                 .unwrap_or_else(|| panic!("missing scenario: {}", got.metrics_ctx.scenario()));
 
             assert_eq!(got.exec, expected.exec);
+            assert_eq!(got.description, expected.description);
+            assert_eq!(got.params, expected.params);
             assert_eq!(got.iterations, expected.iterations);
             assert_eq!(got.duration, expected.duration);
+            assert_eq!(got.think_time, expected.think_time);
+            assert_eq!(got.isolate_connections, expected.isolate_connections);
             assert_eq!(
                 got.metrics_ctx.scenario_tags(),
                 expected.metrics_ctx.scenario_tags()
@@ -769,6 +1462,7 @@ fn arc_tags(tags: Vec<(String, String)>) -> Arc<[(String, String)]> {
                     for (a, b) in a_st.iter().zip(b_st.iter()) {
                         assert_eq!(a.target, b.target);
                         assert_eq!(a.duration, b.duration);
+                        assert_eq!(a.shape, b.shape);
                     }
                 }
                 (
@@ -795,6 +1489,7 @@ fn arc_tags(tags: Vec<(String, String)>) -> Arc<[(String, String)]> {
                     for (a, b) in a_st.iter().zip(b_st.iter()) {
                         assert_eq!(a.target, b.target);
                         assert_eq!(a.duration, b.duration);
+                        assert_eq!(a.shape, b.shape);
                     }
                 }
                 _ => panic!("executor mismatch for {}", got.metrics_ctx.scenario()),
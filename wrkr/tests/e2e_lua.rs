@@ -121,3 +121,64 @@ async fn e2e_lua_runs_for_2s_and_sends_requests() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn e2e_lua_runs_several_scripts_as_one_test() -> anyhow::Result<()> {
+    let server = TestServer::start().await.context("start test server")?;
+    let base_url = server.base_url().to_string();
+
+    let scripts = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scripts");
+    let exe = env!("CARGO_BIN_EXE_wrkr");
+
+    let output = tokio::task::spawn_blocking(move || {
+        Command::new(exe)
+            .arg("run")
+            .arg(scripts.join("hello_world.lua"))
+            .arg(scripts.join("echo_post.lua"))
+            .arg("--duration")
+            .arg("1s")
+            .arg("--output")
+            .arg("json")
+            .env("BASE_URL", &base_url)
+            .output()
+    })
+    .await
+    .context("spawn_blocking join")?
+    .context("run wrkr binary")?;
+    server.shutdown().await;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    anyhow::ensure!(
+        output.status.success(),
+        "wrkr exited with {}\nstdout:\n{}\nstderr:\n{}",
+        output.status,
+        stdout,
+        stderr
+    );
+
+    let summary = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|v| v.get("kind").and_then(serde_json::Value::as_str) == Some("summary"))
+        .with_context(|| format!("missing summary line\nstdout:\n{stdout}"))?;
+    let scenarios = summary
+        .get("scenarios")
+        .and_then(serde_json::Value::as_array)
+        .context("summary without scenarios")?;
+
+    // Each script's implicit scenario is named after its file and sends its own requests.
+    for name in ["hello_world", "echo_post"] {
+        let scenario = scenarios
+            .iter()
+            .find(|s| s.get("scenario").and_then(serde_json::Value::as_str) == Some(name))
+            .with_context(|| format!("missing scenario {name}\nstdout:\n{stdout}"))?;
+        let requests = scenario
+            .get("requestsTotal")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+        anyhow::ensure!(requests > 0, "scenario {name} sent no requests\n{stdout}");
+    }
+
+    Ok(())
+}
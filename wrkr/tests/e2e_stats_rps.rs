@@ -47,6 +47,9 @@ struct SummaryLine {
 #[derive(Debug, Deserialize)]
 #[serde(tag = "kind")]
 enum JsonLine {
+    #[serde(rename = "runStart")]
+    RunStart,
+
     #[serde(rename = "progress")]
     Progress(ProgressLine),
 
@@ -108,6 +111,7 @@ async fn e2e_stats_rps_matches_server_observed_rps() -> anyhow::Result<()> {
             .with_context(|| format!("failed to parse json line: {line}"))?;
 
         match parsed {
+            JsonLine::RunStart => {}
             JsonLine::Progress(p) => {
                 anyhow::ensure!(
                     p.schema == "wrkr.ndjson.v1",
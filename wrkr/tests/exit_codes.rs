@@ -97,3 +97,39 @@ async fn thresholds_failed_exit_11() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn diff_regression_exit_15() -> anyhow::Result<()> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/diff");
+    let exe = env!("CARGO_BIN_EXE_wrkr");
+
+    let diff = |candidate: &str, extra: &[&str]| {
+        Command::new(exe)
+            .arg("diff")
+            .arg(fixtures.join("baseline.ndjson"))
+            .arg(fixtures.join(candidate))
+            .args(extra)
+            .output()
+            .context("run wrkr binary")
+    };
+
+    let out = diff("slower.ndjson", &[])?;
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    anyhow::ensure!(
+        status_code(out.status) == 15 && stdout.contains("latency p95: 10.00ms -> 16.00ms"),
+        "expected exit code 15, got {}\nstdout:\n{stdout}\nstderr:\n{}",
+        status_code(out.status),
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    // RPS dropped 1% and p95/p99 rose 60%: within these limits.
+    let out = diff("slower.ndjson", &["--max-latency-increase", "75%"])?;
+    anyhow::ensure!(
+        status_code(out.status) == 0,
+        "expected exit code 0, got {}\nstdout:\n{}",
+        status_code(out.status),
+        String::from_utf8_lossy(&out.stdout)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Context as _;
+
+#[test]
+fn json_log_format_keeps_stderr_parseable_and_stdout_ndjson() -> anyhow::Result<()> {
+    let script_path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scripts/iteration_error.lua");
+    let exe = env!("CARGO_BIN_EXE_wrkr");
+
+    let out = Command::new(exe)
+        .arg("run")
+        .arg(&script_path)
+        .arg("--iterations")
+        .arg("1")
+        .arg("--output")
+        .arg("json")
+        .arg("--log-format")
+        .arg("json")
+        .output()
+        .context("run wrkr binary")?;
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    anyhow::ensure!(
+        !out.status.success(),
+        "expected a failing run\nstdout:\n{stdout}\nstderr:\n{stderr}"
+    );
+
+    let events = stderr
+        .lines()
+        .map(serde_json::from_str::<serde_json::Value>)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("stderr is not JSON lines:\n{stderr}"))?;
+    let vu_stopped = events.iter().find(|e| e["message"] == "VU stopped");
+    anyhow::ensure!(
+        vu_stopped.is_some_and(|e| e["level"] == "ERROR"
+            && e["scenario"] == "Default"
+            && e["error"].as_str().is_some_and(|err| err.contains("boom"))),
+        "expected a `VU stopped` error event\nstderr:\n{stderr}"
+    );
+    anyhow::ensure!(
+        events.iter().any(|e| e["message"] == "command failed"),
+        "expected the run failure as an event\nstderr:\n{stderr}"
+    );
+
+    for line in stdout.lines() {
+        serde_json::from_str::<serde_json::Value>(line)
+            .with_context(|| format!("stdout line is not JSON: {line}"))?;
+    }
+
+    Ok(())
+}
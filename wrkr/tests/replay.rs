@@ -0,0 +1,70 @@
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::Context as _;
+use wrkr_testserver::TestServer;
+
+#[tokio::test]
+async fn ndjson_trace_is_replayed_with_its_recorded_timing() -> anyhow::Result<()> {
+    let server = TestServer::start().await.context("start test server")?;
+    let urls = server.urls();
+    let trace = format!(
+        "{{\"offsetMs\": 0, \"url\": \"{}\"}}\n\
+         {{\"offsetMs\": 150, \"method\": \"POST\", \"url\": \"{}\", \
+           \"headers\": {{\"content-type\": \"application/json\"}}, \"body\": \"{{}}\"}}\n\
+         {{\"offsetMs\": 300, \"url\": \"{}\"}}\n",
+        urls.hello,
+        urls.echo,
+        urls.status_code(503),
+    );
+    let trace_path =
+        std::env::temp_dir().join(format!("wrkr-replay-{}.ndjson", std::process::id()));
+    std::fs::write(&trace_path, trace).context("write trace")?;
+
+    let exe = env!("CARGO_BIN_EXE_wrkr");
+    let run_path = trace_path.clone();
+    let started = Instant::now();
+    let output = tokio::task::spawn_blocking(move || {
+        Command::new(exe)
+            .arg("run")
+            .arg(&run_path)
+            .arg("--output")
+            .arg("json")
+            .output()
+    })
+    .await
+    .context("spawn_blocking join")?
+    .context("run wrkr binary")?;
+    let elapsed = started.elapsed();
+
+    let server_seen = server.stats().requests_total();
+    server.shutdown().await;
+    let _ = std::fs::remove_file(&trace_path);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    anyhow::ensure!(
+        output.status.success(),
+        "wrkr exited with {}\nstdout:\n{stdout}\nstderr:\n{stderr}",
+        output.status
+    );
+
+    let summary = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|v| v["kind"] == "summary")
+        .with_context(|| format!("no summary line\nstdout:\n{stdout}"))?;
+    let scenario = &summary["scenarios"][0];
+    anyhow::ensure!(
+        scenario["scenario"] == format!("wrkr-replay-{}", std::process::id())
+            && scenario["requestsTotal"] == 3,
+        "expected one replay scenario with 3 requests\nsummary: {summary}"
+    );
+    anyhow::ensure!(server_seen == 3, "server saw {server_seen} requests");
+    anyhow::ensure!(
+        elapsed >= Duration::from_millis(300),
+        "the trace spans 300ms, but the run took {elapsed:?}"
+    );
+
+    Ok(())
+}
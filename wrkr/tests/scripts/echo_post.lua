@@ -0,0 +1,12 @@
+Options = { iterations = 3 }
+
+local http = require("wrkr/http")
+local check = require("wrkr/check")
+local env = require("wrkr/env")
+
+function Default()
+  local res = http.post(env.BASE_URL .. "/echo", "ping")
+  check(res, {
+    ["echoes the body"] = function(r) return r.body == "ping" end,
+  })
+end
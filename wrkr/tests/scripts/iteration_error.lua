@@ -0,0 +1,3 @@
+function Default()
+  error("boom")
+end